@@ -0,0 +1,270 @@
+use std::collections::BTreeMap;
+
+use bollard::service::{ChangeType, FilesystemChange};
+
+use super::{ContainerId, Tree, TreeNode};
+
+/// The kind of a change in the writable layer of a container, relative to its image
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    Added,
+    Changed,
+    Deleted,
+}
+
+impl ChangeKind {
+    /// Single letter, as shown by `docker diff`
+    pub const fn symbol(self) -> &'static str {
+        match self {
+            Self::Added => "A",
+            Self::Changed => "C",
+            Self::Deleted => "D",
+        }
+    }
+
+    /// Convert from the symbol, stored as the value of a tree item
+    pub fn from_symbol(symbol: &str) -> Option<Self> {
+        match symbol {
+            "A" => Some(Self::Added),
+            "C" => Some(Self::Changed),
+            "D" => Some(Self::Deleted),
+            _ => None,
+        }
+    }
+
+    /// Cycle the kind filter, all -> added -> changed -> deleted -> all
+    pub const fn next_filter(filter: Option<Self>) -> Option<Self> {
+        match filter {
+            None => Some(Self::Added),
+            Some(Self::Added) => Some(Self::Changed),
+            Some(Self::Changed) => Some(Self::Deleted),
+            Some(Self::Deleted) => None,
+        }
+    }
+}
+
+impl From<ChangeType> for ChangeKind {
+    fn from(value: ChangeType) -> Self {
+        match value {
+            ChangeType::_0 => Self::Changed,
+            ChangeType::_1 => Self::Added,
+            ChangeType::_2 => Self::Deleted,
+        }
+    }
+}
+
+/// A directory in the changes tree, before it's converted into TreeNodes
+#[derive(Debug, Default)]
+struct PathNode {
+    kind: Option<ChangeKind>,
+    children: BTreeMap<String, Self>,
+}
+
+impl PathNode {
+    fn insert(&mut self, path: &str, kind: ChangeKind) {
+        let node = path
+            .split('/')
+            .filter(|i| !i.is_empty())
+            .fold(self, |node, part| {
+                node.children.entry(part.to_owned()).or_default()
+            });
+        node.kind = Some(kind);
+    }
+
+    fn into_nodes(self) -> Vec<TreeNode> {
+        self.children
+            .into_iter()
+            .map(|(label, node)| {
+                let kind = node.kind.map(ChangeKind::symbol);
+                let children = node.into_nodes();
+                match (kind, children.is_empty()) {
+                    (Some(kind), true) => TreeNode::leaf(label, kind),
+                    (Some(kind), false) => TreeNode::branch_value(label, kind, children),
+                    (None, _) => TreeNode::branch(label, children),
+                }
+            })
+            .collect()
+    }
+}
+
+/// The filesystem changes of a single container, displayed as a tree of paths
+/// The tree can be filtered by path, and by the kind of change
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangesData {
+    changes: Vec<(ChangeKind, String)>,
+    pub editing: bool,
+    pub filter: String,
+    pub id: ContainerId,
+    pub kind_filter: Option<ChangeKind>,
+    pub name: String,
+    pub size_rw: Option<i64>,
+    pub tree: Tree,
+}
+
+impl ChangesData {
+    pub fn new(
+        id: ContainerId,
+        name: &str,
+        changes: Vec<FilesystemChange>,
+        size_rw: Option<i64>,
+    ) -> Self {
+        let mut changes = changes
+            .into_iter()
+            .map(|i| (ChangeKind::from(i.kind), i.path))
+            .collect::<Vec<_>>();
+        changes.sort_by(|a, b| a.1.cmp(&b.1));
+        let mut output = Self {
+            changes,
+            editing: false,
+            filter: String::new(),
+            id,
+            kind_filter: None,
+            name: name.to_owned(),
+            size_rw,
+            tree: Tree::default(),
+        };
+        output.rebuild();
+        output
+    }
+
+    /// Total number of changed paths, ignoring any filters
+    pub const fn len(&self) -> usize {
+        self.changes.len()
+    }
+
+    /// Number of changed paths that match the current filters
+    pub fn matching(&self) -> usize {
+        self.changes.iter().filter(|i| self.matches(i)).count()
+    }
+
+    fn matches(&self, (kind, path): &(ChangeKind, String)) -> bool {
+        self.kind_filter.is_none_or(|i| i == *kind)
+            && (self.filter.is_empty() || path.to_lowercase().contains(&self.filter.to_lowercase()))
+    }
+
+    /// Rebuild the tree from the paths that match the current filters, when filtered every branch is expanded
+    fn rebuild(&mut self) {
+        let mut root = PathNode::default();
+        for (kind, path) in self.changes.iter().filter(|i| self.matches(i)) {
+            root.insert(path, *kind);
+        }
+        self.tree = Tree::new(root.into_nodes());
+        if !self.filter.is_empty() || self.kind_filter.is_some() {
+            self.tree.expand_all();
+        }
+    }
+
+    /// Add a char to the path filter
+    pub fn filter_push(&mut self, c: char) {
+        self.filter.push(c);
+        self.rebuild();
+    }
+
+    /// Remove the last char from the path filter
+    pub fn filter_pop(&mut self) {
+        if self.filter.pop().is_some() {
+            self.rebuild();
+        }
+    }
+
+    /// Remove the path filter
+    pub fn filter_clear(&mut self) {
+        if !self.filter.is_empty() {
+            self.filter.clear();
+            self.rebuild();
+        }
+    }
+
+    /// Cycle the kind filter
+    pub fn next_kind_filter(&mut self) {
+        self.kind_filter = ChangeKind::next_filter(self.kind_filter);
+        self.rebuild();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bollard::service::{ChangeType, FilesystemChange};
+
+    use crate::app_data::ContainerId;
+
+    use super::{ChangeKind, ChangesData};
+
+    fn gen_changes() -> ChangesData {
+        let change = |path: &str, kind| FilesystemChange {
+            path: path.to_owned(),
+            kind,
+        };
+        ChangesData::new(
+            ContainerId::from("abc"),
+            "web",
+            vec![
+                change("/var", ChangeType::_0),
+                change("/var/log", ChangeType::_0),
+                change("/var/log/app.log", ChangeType::_1),
+                change("/etc", ChangeType::_0),
+                change("/etc/motd", ChangeType::_2),
+                change("/tmp/cache/a", ChangeType::_1),
+            ],
+            Some(1024),
+        )
+    }
+
+    fn visible_text(changes: &ChangesData) -> Vec<String> {
+        changes.tree.visible().iter().map(|i| i.text()).collect()
+    }
+
+    #[test]
+    /// Paths grouped into a tree, with the kind of change as the value, and missing parents as plain branches
+    fn test_changes_tree() {
+        let mut changes = gen_changes();
+        assert_eq!(changes.len(), 6);
+        assert_eq!(visible_text(&changes), ["etc: C", "tmp", "var: C"]);
+        changes.tree.expand_all();
+        assert_eq!(
+            visible_text(&changes),
+            [
+                "etc: C",
+                "motd: D",
+                "tmp",
+                "cache",
+                "a: A",
+                "var: C",
+                "log: C",
+                "app.log: A"
+            ]
+        );
+    }
+
+    #[test]
+    /// Path filter is case insensitive, kind filter cycles, and both are combined
+    fn test_changes_filter() {
+        let mut changes = gen_changes();
+        for c in "LOG".chars() {
+            changes.filter_push(c);
+        }
+        assert_eq!(changes.matching(), 2);
+        assert_eq!(visible_text(&changes), ["var", "log: C", "app.log: A"]);
+
+        changes.next_kind_filter();
+        assert_eq!(changes.kind_filter, Some(ChangeKind::Added));
+        assert_eq!(changes.matching(), 1);
+        assert_eq!(visible_text(&changes), ["var", "log", "app.log: A"]);
+
+        changes.filter_clear();
+        assert_eq!(changes.matching(), 2);
+        changes.next_kind_filter();
+        changes.next_kind_filter();
+        assert_eq!(changes.kind_filter, Some(ChangeKind::Deleted));
+        assert_eq!(visible_text(&changes), ["etc", "motd: D"]);
+        changes.next_kind_filter();
+        assert_eq!(changes.kind_filter, None);
+        assert_eq!(changes.matching(), 6);
+
+        changes.filter_push('z');
+        assert_eq!(changes.matching(), 0);
+        assert!(changes.tree.state.selected().is_none());
+        changes.filter_pop();
+        assert_eq!(changes.matching(), 6);
+    }
+}
@@ -0,0 +1,124 @@
+use super::{Header, StatefulList};
+
+/// The column picker, every header, the visible headers first in their current order, followed by the hidden headers
+/// The label column is only included when a label has been set, via column_label
+#[derive(Debug, Clone)]
+pub struct ColumnPicker {
+    pub columns: StatefulList<(Header, bool)>,
+}
+
+impl ColumnPicker {
+    pub fn new(visible: &[Header], label: bool) -> Self {
+        let items = visible
+            .iter()
+            .map(|i| (*i, true))
+            .chain(
+                Header::ALL
+                    .into_iter()
+                    .filter(|i| !visible.contains(i) && (label || *i != Header::Label))
+                    .map(|i| (i, false)),
+            )
+            .collect::<Vec<_>>();
+        let mut columns = StatefulList::new(items);
+        columns.start();
+        Self { columns }
+    }
+
+    /// Show, or hide, the selected column, the last visible column can't be hidden
+    pub fn toggle(&mut self) {
+        let visible = self.visible().len();
+        if let Some(item) = self
+            .columns
+            .state
+            .selected()
+            .and_then(|i| self.columns.items.get_mut(i))
+        {
+            if !item.1 || visible > 1 {
+                item.1 = !item.1;
+            }
+        }
+    }
+
+    /// Move the selected column one place up, keeping it selected
+    pub fn move_up(&mut self) {
+        if let Some(index) = self.columns.state.selected().filter(|i| *i > 0) {
+            self.columns.items.swap(index, index - 1);
+            self.columns.state.select(Some(index - 1));
+        }
+    }
+
+    /// Move the selected column one place down, keeping it selected
+    pub fn move_down(&mut self) {
+        if let Some(index) = self
+            .columns
+            .state
+            .selected()
+            .filter(|i| i + 1 < self.columns.items.len())
+        {
+            self.columns.items.swap(index, index + 1);
+            self.columns.state.select(Some(index + 1));
+        }
+    }
+
+    /// The visible columns, in order
+    pub fn visible(&self) -> Vec<Header> {
+        self.columns
+            .items
+            .iter()
+            .filter(|i| i.1)
+            .map(|i| i.0)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use crate::app_data::Header;
+
+    use super::ColumnPicker;
+
+    #[test]
+    /// Visible columns listed first, followed by every hidden column
+    fn test_column_picker_new() {
+        let picker = ColumnPicker::new(&[Header::Cpu, Header::Name], true);
+        assert_eq!(picker.columns.items.len(), Header::ALL.len());
+        assert_eq!(picker.columns.items[0], (Header::Cpu, true));
+        assert_eq!(picker.columns.items[1], (Header::Name, true));
+        assert_eq!(picker.columns.items[2], (Header::State, false));
+        assert_eq!(picker.columns.state.selected(), Some(0));
+        assert_eq!(picker.visible(), vec![Header::Cpu, Header::Name]);
+
+        let picker = ColumnPicker::new(&[Header::Name], false);
+        assert_eq!(picker.columns.items.len(), Header::ALL.len() - 1);
+        assert!(!picker.columns.items.iter().any(|i| i.0 == Header::Label));
+    }
+
+    #[test]
+    /// Columns can be shown, hidden, and moved, but the last visible column can't be hidden
+    fn test_column_picker_edit() {
+        let mut picker = ColumnPicker::new(&[Header::Name], false);
+        picker.toggle();
+        assert_eq!(picker.visible(), vec![Header::Name]);
+
+        picker.columns.next();
+        picker.toggle();
+        assert_eq!(picker.visible(), vec![Header::Name, Header::State]);
+
+        picker.move_up();
+        assert_eq!(picker.columns.state.selected(), Some(0));
+        assert_eq!(picker.visible(), vec![Header::State, Header::Name]);
+
+        picker.move_up();
+        assert_eq!(picker.columns.state.selected(), Some(0));
+
+        picker.move_down();
+        picker.move_down();
+        assert_eq!(picker.columns.state.selected(), Some(2));
+        assert_eq!(picker.visible(), vec![Header::Name, Header::State]);
+
+        picker.columns.start();
+        picker.toggle();
+        assert_eq!(picker.visible(), vec![Header::State]);
+    }
+}
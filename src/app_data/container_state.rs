@@ -1,6 +1,6 @@
 use std::{
     cmp::Ordering,
-    collections::{HashSet, VecDeque},
+    collections::{HashMap, HashSet, VecDeque},
     fmt,
 };
 
@@ -9,8 +9,17 @@ use ratatui::{
     style::Color,
     widgets::{ListItem, ListState},
 };
+use serde::Serialize;
 
-use super::Header;
+use crate::ui::Theme;
+
+use super::{duration, time_since, Header};
+
+/// Label set by Docker Compose on each container, the name of the service in the compose file
+pub const SERVICE_LABEL: &str = "com.docker.compose.service";
+
+/// Max char count of the text columns, e.g. command, so that a long value doesn't push the other columns off screen
+const MAX_TEXT_WIDTH: usize = 32;
 
 const ONE_KB: f64 = 1000.0;
 const ONE_MB: f64 = ONE_KB * 1000.0;
@@ -100,6 +109,25 @@ macro_rules! unit_struct {
 unit_struct!(ContainerName);
 unit_struct!(ContainerImage);
 
+impl ContainerName {
+    /// Check a name against the characters Docker allows in a container name, `[a-zA-Z0-9][a-zA-Z0-9_.-]+`
+    pub fn validate(name: &str) -> Result<(), String> {
+        let mut chars = name.chars();
+        let valid = chars.next().is_some_and(|i| i.is_ascii_alphanumeric())
+            && chars.clone().next().is_some()
+            && chars.all(|i| i.is_ascii_alphanumeric() || ['_', '.', '-'].contains(&i));
+        if valid {
+            Ok(())
+        } else if name.is_empty() {
+            Err("a name is required".to_owned())
+        } else {
+            Err(format!(
+                "invalid name: {name}, only [a-zA-Z0-9][a-zA-Z0-9_.-] are allowed"
+            ))
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ContainerPorts {
     pub ip: Option<String>,
@@ -192,6 +220,18 @@ impl<T> StatefulList<T> {
         }
     }
 
+    /// Replace all items, selecting the given position, else keep the current selection, limited to the new length
+    pub fn replace(&mut self, items: Vec<T>, position: Option<usize>) {
+        self.items = items;
+        let position = position.or_else(|| {
+            self.items
+                .len()
+                .checked_sub(1)
+                .map(|last| self.state.selected().unwrap_or_default().min(last))
+        });
+        self.state.select(position);
+    }
+
     /// Return the current status of the select list, e.g. 2/5,
     pub fn get_state_title(&self) -> String {
         if self.items.is_empty() {
@@ -208,7 +248,8 @@ impl<T> StatefulList<T> {
 }
 
 /// States of the container
-#[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum State {
     Dead,
     Exited,
@@ -223,13 +264,25 @@ impl State {
     pub const fn is_alive(self) -> bool {
         matches!(self, Self::Running)
     }
-    pub const fn get_color(self) -> Color {
+    /// The name of the state, as used by docker
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Dead => "dead",
+            Self::Exited => "exited",
+            Self::Paused => "paused",
+            Self::Removing => "removing",
+            Self::Restarting => "restarting",
+            Self::Running => "running",
+            Self::Unknown => "unknown",
+        }
+    }
+    pub const fn get_color(self, theme: &Theme) -> Color {
         match self {
-            Self::Paused => Color::Yellow,
-            Self::Removing => Color::LightRed,
-            Self::Restarting => Color::LightGreen,
-            Self::Running => Color::Green,
-            _ => Color::Red,
+            Self::Paused => theme.state_paused,
+            Self::Removing => theme.state_removing,
+            Self::Restarting => theme.state_restarting,
+            Self::Running => theme.state_running,
+            _ => theme.state_stopped,
         }
     }
     /// Dirty way to create order for the state, rather than impl Ord
@@ -281,6 +334,72 @@ impl fmt::Display for State {
     }
 }
 
+/// Health check status of the container, parsed from the status string
+#[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Health {
+    Healthy,
+    Starting,
+    Unhealthy,
+    Unset,
+}
+
+impl Health {
+    pub const fn get_color(self, theme: &Theme) -> Color {
+        match self {
+            Self::Healthy => theme.health_healthy,
+            Self::Starting => theme.health_starting,
+            Self::Unhealthy => theme.health_unhealthy,
+            Self::Unset => theme.health_unset,
+        }
+    }
+    /// The name of the health status, without the symbol used in the containers panel
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Healthy => "healthy",
+            Self::Starting => "starting",
+            Self::Unhealthy => "unhealthy",
+            Self::Unset => "unset",
+        }
+    }
+    /// Dirty way to create order for the health, unhealthy first
+    pub const fn order(self) -> u8 {
+        match self {
+            Self::Unhealthy => 0,
+            Self::Starting => 1,
+            Self::Healthy => 2,
+            Self::Unset => 3,
+        }
+    }
+}
+
+/// Docker appends the health to the status, e.g. "Up 2 minutes (healthy)"
+impl From<&str> for Health {
+    fn from(input: &str) -> Self {
+        if input.contains("(unhealthy)") {
+            Self::Unhealthy
+        } else if input.contains("(healthy)") {
+            Self::Healthy
+        } else if input.contains("(health: starting)") {
+            Self::Starting
+        } else {
+            Self::Unset
+        }
+    }
+}
+
+impl fmt::Display for Health {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let disp = match self {
+            Self::Healthy => "✓ healthy",
+            Self::Starting => "↻ starting",
+            Self::Unhealthy => "✖ unhealthy",
+            Self::Unset => "",
+        };
+        write!(f, "{disp}")
+    }
+}
+
 /// Items for the container control list
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DockerControls {
@@ -293,14 +412,14 @@ pub enum DockerControls {
 }
 
 impl DockerControls {
-    pub const fn get_color(self) -> Color {
+    pub const fn get_color(self, theme: &Theme) -> Color {
         match self {
-            Self::Pause => Color::Yellow,
-            Self::Restart => Color::Magenta,
-            Self::Start => Color::Green,
-            Self::Stop => Color::Red,
-            Self::Delete => Color::Gray,
-            Self::Resume => Color::Blue,
+            Self::Pause => theme.control_pause,
+            Self::Restart => theme.control_restart,
+            Self::Start => theme.control_start,
+            Self::Stop => theme.control_stop,
+            Self::Delete => theme.control_delete,
+            Self::Resume => theme.control_resume,
         }
     }
 
@@ -413,11 +532,20 @@ impl ByteStats {
     pub const fn new(value: u64) -> Self {
         Self(value)
     }
+    pub const fn get(self) -> u64 {
+        self.0
+    }
     pub fn update(&mut self, value: u64) {
         self.0 = value;
     }
 }
 
+impl std::iter::Sum for ByteStats {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        Self(iter.map(|i| i.0).sum())
+    }
+}
+
 #[allow(clippy::cast_precision_loss)]
 impl Stats for ByteStats {
     fn get_value(&self) -> f64 {
@@ -438,8 +566,163 @@ impl fmt::Display for ByteStats {
     }
 }
 
-pub type MemTuple = (Vec<(f64, f64)>, ByteStats, State);
-pub type CpuTuple = (Vec<(f64, f64)>, CpuStats, State);
+/// Chart dataset, split into separate segments wherever there's a gap in the samples, so that the gap isn't drawn as a line
+pub type Segments = Vec<Vec<(f64, f64)>>;
+
+pub type MemTuple = (Segments, ByteStats, State);
+pub type CpuTuple = (Segments, CpuStats, State);
+/// Two datasets, either rx & tx, or read & write, in bytes per second, and the max of both
+pub type IoTuple = (Segments, Segments, ByteStats, State);
+
+/// Max number of samples kept in each stats history, samples closer together than retention / this are merged
+const MAX_HISTORY_SAMPLES: u64 = 3600;
+
+/// Shortest span of the chart x-axis, in ms, so that a container with only a few samples isn't stretched across the whole chart
+const MIN_AXIS_SPAN: u64 = 60_000;
+
+/// The x-axis of the charts, as unix timestamps in ms
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TimeAxis {
+    pub start: u64,
+    pub end: u64,
+}
+
+impl TimeAxis {
+    /// Axis ends at the newest sample, and spans back to the oldest sample, or at least MIN_AXIS_SPAN
+    pub fn new(oldest: u64, newest: u64) -> Self {
+        let span = newest.saturating_sub(oldest).max(MIN_AXIS_SPAN);
+        let start = newest.saturating_sub(span);
+        Self {
+            start,
+            end: start + span,
+        }
+    }
+
+    /// Position of a timestamp on the axis, in seconds since the start
+    #[allow(clippy::cast_precision_loss)]
+    fn x(self, timestamp: u64) -> f64 {
+        timestamp.saturating_sub(self.start) as f64 / 1000.0
+    }
+
+    /// Length of the axis in seconds
+    pub fn width(self) -> f64 {
+        self.x(self.end)
+    }
+
+    /// Format a timestamp as `HH:MM:SS`, in UTC, the same as the timestamps of the Docker logs
+    pub fn label(timestamp: u64) -> String {
+        let seconds = timestamp / 1000;
+        format!(
+            "{:02}:{:02}:{:02}",
+            seconds / 3600 % 24,
+            seconds / 60 % 60,
+            seconds % 60
+        )
+    }
+}
+
+/// Timestamped history of a single stat, samples older than the retention period, in ms, are removed
+/// To keep memory bounded with long retention periods, samples closer together than the resolution are merged, keeping the max value, the newest sample is never merged
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct StatsHistory<T>(VecDeque<(u64, T)>);
+
+impl<T: Stats + Ord + Copy + Default> StatsHistory<T> {
+    /// Smallest gap, in ms, between stored samples
+    pub const fn resolution(retention: u64) -> u64 {
+        retention / MAX_HISTORY_SAMPLES
+    }
+
+    /// A sample older than the newest sample, e.g. from a stats stream that was restarted, is dropped, so that the samples stay in order
+    pub fn push(&mut self, timestamp: u64, value: T, retention: u64) {
+        if self.0.back().is_some_and(|i| timestamp < i.0) {
+            return;
+        }
+        let len = self.0.len();
+        if len >= 2 {
+            if let (Some(previous), Some(newest)) = (self.0.get(len - 2), self.0.get(len - 1)) {
+                if newest.0.saturating_sub(previous.0) < Self::resolution(retention) {
+                    let merged = (previous.0, previous.1.max(newest.1));
+                    self.0.truncate(len - 2);
+                    self.0.push_back(merged);
+                }
+            }
+        }
+        self.0.push_back((timestamp, value));
+        let oldest = timestamp.saturating_sub(retention);
+        while self.0.front().is_some_and(|i| i.0 < oldest) {
+            self.0.pop_front();
+        }
+    }
+
+    /// Every sample, oldest first, as a timestamp in ms, and a value
+    pub fn iter(&self) -> impl Iterator<Item = &(u64, T)> {
+        self.0.iter()
+    }
+
+    /// The newest value
+    pub fn back(&self) -> Option<&T> {
+        self.0.back().map(|i| &i.1)
+    }
+
+    /// Timestamps of the oldest and newest samples
+    pub fn time_range(&self) -> Option<(u64, u64)> {
+        self.0.front().zip(self.0.back()).map(|(a, b)| (a.0, b.0))
+    }
+
+    /// Max value of the samples on the axis
+    fn max(&self, axis: TimeAxis) -> T {
+        self.0
+            .iter()
+            .filter(|i| i.0 >= axis.start)
+            .map(|i| i.1)
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// Convert into chart segments, downsampled to at most `points` points across the axis, keeping the max value of each bucket
+    /// A new segment is started when two samples are further apart than `gap` ms, or two buckets, whichever is larger
+    fn dataset(&self, axis: TimeAxis, points: u64, gap: u64) -> Segments {
+        let bucket = (axis.end.saturating_sub(axis.start) / points.max(1)).max(1);
+        let gap = gap.max(bucket * 2);
+        let newest = self.0.len().saturating_sub(1);
+        let mut output: Segments = vec![];
+        let mut previous: Option<(u64, u64)> = None;
+        for (index, (timestamp, value)) in
+            self.0.iter().enumerate().filter(|i| i.1 .0 >= axis.start)
+        {
+            let point = (axis.x(*timestamp), value.get_value());
+            let current_bucket = timestamp.saturating_sub(axis.start) / bucket;
+            match (previous, output.last_mut()) {
+                (Some((previous_bucket, _)), Some(segment))
+                    if previous_bucket == current_bucket && index != newest =>
+                {
+                    if let Some(last) = segment.last_mut() {
+                        if point.1 > last.1 {
+                            *last = point;
+                        }
+                    }
+                }
+                (Some((_, previous_timestamp)), Some(segment))
+                    if timestamp.abs_diff(previous_timestamp) <= gap =>
+                {
+                    segment.push(point);
+                }
+                _ => output.push(vec![point]),
+            }
+            previous = Some((current_bucket, *timestamp));
+        }
+        output
+    }
+}
+
+/// Cumulative network and block io byte counters, from a single stats sample
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct IoCounters {
+    pub read: u64,
+    pub rx: u64,
+    pub tx: u64,
+    pub write: u64,
+}
 
 /// Used to make sure that each log entry, for each container, is unique,
 /// will only push a log entry into the logs vec if timetstamp of said log entry isn't in the hashset
@@ -525,22 +808,36 @@ impl Logs {
 /// Info for each container
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ContainerItem {
-    pub cpu_stats: VecDeque<CpuStats>,
+    pub blk_read_stats: StatsHistory<ByteStats>,
+    pub blk_write_stats: StatsHistory<ByteStats>,
+    pub command: String,
+    pub cpu_stats: StatsHistory<CpuStats>,
     pub created: u64,
     pub docker_controls: StatefulList<DockerControls>,
+    pub health: Health,
     pub id: ContainerId,
     pub image: ContainerImage,
+    pub image_id: String,
+    pub inspected: Option<State>,
+    pub io_sample: Option<(u64, IoCounters)>,
+    pub ip: String,
     pub is_oxker: bool,
+    pub labels: HashMap<String, String>,
     pub last_updated: u64,
     pub logs: Logs,
     pub mem_limit: ByteStats,
-    pub mem_stats: VecDeque<ByteStats>,
+    pub mem_stats: StatsHistory<ByteStats>,
     pub name: ContainerName,
+    pub networks: Vec<String>,
     pub ports: Vec<ContainerPorts>,
+    pub restart_count: Option<u64>,
     pub rx: ByteStats,
+    pub rx_stats: StatsHistory<ByteStats>,
+    pub started: Option<u64>,
     pub state: State,
     pub status: String,
     pub tx: ByteStats,
+    pub tx_stats: StatsHistory<ByteStats>,
 }
 
 /// Basic display information, for when running in debug mode
@@ -574,75 +871,179 @@ impl ContainerItem {
         docker_controls.start();
 
         Self {
-            cpu_stats: VecDeque::with_capacity(60),
+            blk_read_stats: StatsHistory::default(),
+            blk_write_stats: StatsHistory::default(),
+            command: String::new(),
+            cpu_stats: StatsHistory::default(),
             created,
             docker_controls,
+            health: Health::from(status.as_str()),
             id,
             image: image.into(),
+            image_id: String::new(),
+            inspected: None,
+            io_sample: None,
+            ip: String::new(),
             is_oxker,
+            labels: HashMap::new(),
             last_updated: 0,
             logs: Logs::default(),
             mem_limit: ByteStats::default(),
-            mem_stats: VecDeque::with_capacity(60),
+            mem_stats: StatsHistory::default(),
             name: name.into(),
+            networks: vec![],
             ports,
+            restart_count: None,
             rx: ByteStats::default(),
+            rx_stats: StatsHistory::default(),
+            started: None,
             state,
             status,
             tx: ByteStats::default(),
+            tx_stats: StatsHistory::default(),
         }
     }
 
-    /// Find the max value in the cpu stats VecDeque
-    fn max_cpu_stats(&self) -> CpuStats {
-        self.cpu_stats
-            .iter()
-            .max()
-            .map_or_else(CpuStats::default, |value| *value)
-    }
-
-    /// Find the max value in the mem stats VecDeque
-    fn max_mem_stats(&self) -> ByteStats {
-        self.mem_stats
-            .iter()
-            .max()
-            .map_or_else(ByteStats::default, |value| *value)
+    /// The value of a label, None if the container doesn't have the label
+    pub fn label(&self, key: &str) -> Option<&str> {
+        self.labels.get(key).map(String::as_str)
     }
 
-    /// Convert cpu stats into a vec for the charts function
-    #[allow(clippy::cast_precision_loss)]
-    fn get_cpu_dataset(&self) -> Vec<(f64, f64)> {
-        self.cpu_stats
-            .iter()
-            .enumerate()
-            .map(|i| (i.0 as f64, i.1 .0))
-            .collect::<Vec<_>>()
+    /// Start time, as unix time in seconds, only whilst the container is up
+    pub fn up_since(&self) -> Option<u64> {
+        self.started
+            .filter(|_| matches!(self.state, State::Running | State::Paused))
     }
 
-    /// Convert mem stats into a Vec for the charts function
-    #[allow(clippy::cast_precision_loss)]
-    fn get_mem_dataset(&self) -> Vec<(f64, f64)> {
-        self.mem_stats
-            .iter()
-            .enumerate()
-            .map(|i| (i.0 as f64, i.1 .0 as f64))
-            .collect::<Vec<_>>()
+    /// Published ports as public->private, else just the private port, ports published on both ipv4 & ipv6 are only included once
+    pub fn ports_text(&self) -> String {
+        let mut output: Vec<String> = vec![];
+        for port in &self.ports {
+            let text = port.public.map_or_else(
+                || port.private.to_string(),
+                |public| format!("{public}->{}", port.private),
+            );
+            if !output.contains(&text) {
+                output.push(text);
+            }
+        }
+        output.join(", ")
+    }
+
+    /// Text of a column that isn't a stat, None for the other columns
+    /// Label is the key of the label shown in the label column, and now is unix time in seconds
+    pub fn column_text(&self, header: Header, label: Option<&str>, now: i64) -> Option<String> {
+        let seconds = |i: u64| i64::try_from(i).unwrap_or_default();
+        let text = match header {
+            Header::Created => time_since(seconds(self.created), now),
+            Header::Uptime => self.up_since().map_or_else(String::new, |started| {
+                duration(now.saturating_sub(seconds(started)))
+            }),
+            Header::Ports => self.ports_text(),
+            Header::Command => self.command.clone(),
+            Header::Service => self.label(SERVICE_LABEL).unwrap_or_default().to_owned(),
+            Header::Label => label
+                .and_then(|key| self.label(key))
+                .unwrap_or_default()
+                .to_owned(),
+            Header::Restarts => self
+                .restart_count
+                .map_or_else(String::new, |i| i.to_string()),
+            Header::Ip => self.ip.clone(),
+            _ => return None,
+        };
+        if text.chars().count() > MAX_TEXT_WIDTH {
+            Some(format!(
+                "{}…",
+                text.chars().take(MAX_TEXT_WIDTH - 1).collect::<String>()
+            ))
+        } else {
+            Some(text)
+        }
     }
 
-    /// Get all cpu chart data
-    fn get_cpu_chart_data(&self) -> CpuTuple {
-        (self.get_cpu_dataset(), self.max_cpu_stats(), self.state)
+    /// Store the io counters, and push the rate of each, in bytes per second, since the previous sample
+    /// A counter that has gone backwards, e.g. after a container restart, is treated as a rate of zero
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    pub fn push_io(&mut self, io: IoCounters, timestamp: u64, retention: u64) {
+        if let Some((previous_timestamp, previous)) = self.io_sample {
+            let elapsed = timestamp.saturating_sub(previous_timestamp) as f64 / 1000.0;
+            if elapsed > 0.0 {
+                let rate = |current: u64, previous: u64| {
+                    ByteStats::new((current.saturating_sub(previous) as f64 / elapsed) as u64)
+                };
+                for (stats, value) in [
+                    (&mut self.rx_stats, rate(io.rx, previous.rx)),
+                    (&mut self.tx_stats, rate(io.tx, previous.tx)),
+                    (&mut self.blk_read_stats, rate(io.read, previous.read)),
+                    (&mut self.blk_write_stats, rate(io.write, previous.write)),
+                ] {
+                    stats.push(timestamp, value, retention);
+                }
+            }
+        }
+        self.rx.update(io.rx);
+        self.tx.update(io.tx);
+        self.io_sample = Some((timestamp, io));
+    }
+
+    /// The x-axis shared by all of the charts, based on the cpu & memory samples, None if there are no samples
+    pub fn time_axis(&self) -> Option<TimeAxis> {
+        [self.cpu_stats.time_range(), self.mem_stats.time_range()]
+            .into_iter()
+            .flatten()
+            .reduce(|acc, i| (acc.0.min(i.0), acc.1.max(i.1)))
+            .map(|(oldest, newest)| TimeAxis::new(oldest, newest))
+    }
+
+    /// Convert a pair of io rate stats into datasets, alongside the max value of either
+    fn io_chart_data(
+        &self,
+        (a, b): (&StatsHistory<ByteStats>, &StatsHistory<ByteStats>),
+        axis: TimeAxis,
+        points: u64,
+        gap: u64,
+    ) -> IoTuple {
+        (
+            a.dataset(axis, points, gap),
+            b.dataset(axis, points, gap),
+            a.max(axis).max(b.max(axis)),
+            self.state,
+        )
     }
 
-    /// Get all mem chart data
-    fn get_mem_chart_data(&self) -> MemTuple {
-        (self.get_mem_dataset(), self.max_mem_stats(), self.state)
+    /// Get chart info for network rx & tx, and block io read & write, rates
+    pub fn get_io_chart_data(&self, axis: TimeAxis, points: u64, gap: u64) -> (IoTuple, IoTuple) {
+        (
+            self.io_chart_data((&self.rx_stats, &self.tx_stats), axis, points, gap),
+            self.io_chart_data(
+                (&self.blk_read_stats, &self.blk_write_stats),
+                axis,
+                points,
+                gap,
+            ),
+        )
     }
 
     /// Get chart info for cpu & memory in one function
     /// So only need to call .lock() once
-    pub fn get_chart_data(&self) -> (CpuTuple, MemTuple) {
-        (self.get_cpu_chart_data(), self.get_mem_chart_data())
+    pub fn get_chart_data(&self, axis: TimeAxis, points: u64, gap: u64) -> (CpuTuple, MemTuple) {
+        (
+            (
+                self.cpu_stats.dataset(axis, points, gap),
+                self.cpu_stats.max(axis),
+                self.state,
+            ),
+            (
+                self.mem_stats.dataset(axis, points, gap),
+                self.mem_stats.max(axis),
+                self.state,
+            ),
+        )
     }
 }
 
@@ -652,12 +1053,23 @@ pub struct Columns {
     pub name: (Header, u8),
     pub state: (Header, u8),
     pub status: (Header, u8),
+    pub health: (Header, u8),
     pub cpu: (Header, u8),
     pub mem: (Header, u8, u8),
     pub id: (Header, u8),
     pub image: (Header, u8),
     pub net_rx: (Header, u8),
     pub net_tx: (Header, u8),
+    pub net_rx_rate: (Header, u8),
+    pub net_tx_rate: (Header, u8),
+    pub created: (Header, u8),
+    pub uptime: (Header, u8),
+    pub ports: (Header, u8),
+    pub command: (Header, u8),
+    pub service: (Header, u8),
+    pub label: (Header, u8),
+    pub restarts: (Header, u8),
+    pub ip: (Header, u8),
 }
 
 impl Columns {
@@ -667,26 +1079,355 @@ impl Columns {
             name: (Header::Name, 4),
             state: (Header::State, 11),
             status: (Header::Status, 16),
+            health: (Header::Health, 8),
             cpu: (Header::Cpu, 7),
             mem: (Header::Memory, 7, 7),
             id: (Header::Id, 8),
             image: (Header::Image, 5),
             net_rx: (Header::Rx, 7),
             net_tx: (Header::Tx, 7),
+            net_rx_rate: (Header::RxRate, 8),
+            net_tx_rate: (Header::TxRate, 8),
+            created: (Header::Created, 7),
+            uptime: (Header::Uptime, 6),
+            ports: (Header::Ports, 5),
+            command: (Header::Command, 7),
+            service: (Header::Service, 7),
+            label: (Header::Label, 5),
+            restarts: (Header::Restarts, 8),
+            ip: (Header::Ip, 2),
         }
     }
+
+    /// The column of a header, the memory column has two widths, current & limit, so isn't included
+    pub const fn get_mut(&mut self, header: Header) -> Option<&mut (Header, u8)> {
+        match header {
+            Header::Name => Some(&mut self.name),
+            Header::State => Some(&mut self.state),
+            Header::Status => Some(&mut self.status),
+            Header::Health => Some(&mut self.health),
+            Header::Cpu => Some(&mut self.cpu),
+            Header::Memory => None,
+            Header::Id => Some(&mut self.id),
+            Header::Image => Some(&mut self.image),
+            Header::Rx => Some(&mut self.net_rx),
+            Header::Tx => Some(&mut self.net_tx),
+            Header::RxRate => Some(&mut self.net_rx_rate),
+            Header::TxRate => Some(&mut self.net_tx_rate),
+            Header::Created => Some(&mut self.created),
+            Header::Uptime => Some(&mut self.uptime),
+            Header::Ports => Some(&mut self.ports),
+            Header::Command => Some(&mut self.command),
+            Header::Service => Some(&mut self.service),
+            Header::Label => Some(&mut self.label),
+            Header::Restarts => Some(&mut self.restarts),
+            Header::Ip => Some(&mut self.ip),
+        }
+    }
+
+    /// Total width of the column of a header, memory includes the " / " between the current & limit
+    pub fn width(mut self, header: Header) -> u8 {
+        let memory = self.mem.1.saturating_add(self.mem.2).saturating_add(3);
+        self.get_mut(header).map_or(memory, |i| i.1)
+    }
+}
+
+/// Parse a timestamp from the Docker API, e.g. `2024-05-01T12:34:56.123456789Z`, into unix time in seconds, Docker timestamps are always UTC
+/// The zero timestamp, `0001-01-01T00:00:00Z`, used for a container that has never started, is None
+pub fn parse_timestamp(timestamp: &str) -> Option<u64> {
+    let (date, time) = timestamp.split_once('T')?;
+    let mut date = date.splitn(3, '-').map(str::parse::<i64>);
+    let mut time = time.get(..8)?.splitn(3, ':').map(str::parse::<i64>);
+    let next = |parts: &mut dyn Iterator<Item = Result<i64, _>>| parts.next()?.ok();
+    let (year, month, day) = (next(&mut date)?, next(&mut date)?, next(&mut date)?);
+    let (hour, minute, second) = (next(&mut time)?, next(&mut time)?, next(&mut time)?);
+
+    // Days since the unix epoch, see http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146_097 + day_of_era - 719_468;
+
+    u64::try_from(days * 86400 + hour * 3600 + minute * 60 + second)
+        .ok()
+        .filter(|i| *i > 0)
+}
+
+/// Format a unix timestamp in ms as `2024-05-01T12:34:56.123Z`, in UTC, the reverse of `parse_timestamp`
+pub fn format_timestamp(timestamp: u64) -> String {
+    let seconds = timestamp / 1000;
+    let days = i64::try_from(seconds / 86400).unwrap_or_default();
+
+    // Date from days since the unix epoch, see http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}.{:03}Z",
+        seconds / 3600 % 24,
+        seconds / 60 % 60,
+        seconds % 60,
+        timestamp % 1000
+    )
 }
 
 #[cfg(test)]
 mod tests {
+    use std::collections::VecDeque;
+
     use ratatui::widgets::ListItem;
 
     use crate::{
-        app_data::{ContainerImage, Logs},
+        app_data::{ContainerId, ContainerImage, Logs},
         ui::log_sanitizer,
     };
 
-    use super::{ByteStats, ContainerName, CpuStats, LogsTz};
+    use super::{
+        format_timestamp, parse_timestamp, ByteStats, ContainerItem, ContainerName, ContainerPorts,
+        CpuStats, Header, Health, IoCounters, LogsTz, State, StatsHistory, TimeAxis, SERVICE_LABEL,
+    };
+
+    #[test]
+    /// RFC3339 timestamps parsed into unix seconds, the zero time Docker uses for a container that has never started is None
+    fn test_container_state_parse_timestamp() {
+        assert_eq!(
+            parse_timestamp("2023-01-14T19:13:30.783138328Z"),
+            Some(1_673_723_610)
+        );
+        assert_eq!(parse_timestamp("2000-02-29T00:00:00Z"), Some(951_782_400));
+        assert_eq!(parse_timestamp("0001-01-01T00:00:00Z"), None);
+        assert_eq!(parse_timestamp("not a timestamp"), None);
+    }
+
+    #[test]
+    /// Unix timestamps in ms formatted as RFC3339, in UTC, which can be parsed back into the same second
+    fn test_container_state_format_timestamp() {
+        assert_eq!(format_timestamp(0), "1970-01-01T00:00:00.000Z");
+        assert_eq!(
+            format_timestamp(1_673_723_610_783),
+            "2023-01-14T19:13:30.783Z"
+        );
+        assert_eq!(
+            format_timestamp(951_782_400_000),
+            "2000-02-29T00:00:00.000Z"
+        );
+        assert_eq!(
+            format_timestamp(951_868_799_999),
+            "2000-02-29T23:59:59.999Z"
+        );
+        assert_eq!(
+            parse_timestamp(&format_timestamp(1_767_225_599_000)),
+            Some(1_767_225_599)
+        );
+    }
+
+    #[test]
+    /// Text of the optional columns, long text is truncated, and the stat columns are None
+    fn test_container_state_column_text() {
+        let mut item = ContainerItem::new(
+            1_673_723_610,
+            ContainerId::from("1"),
+            "image_1".to_owned(),
+            false,
+            "container_1".to_owned(),
+            vec![
+                ContainerPorts {
+                    ip: Some("0.0.0.0".to_owned()),
+                    private: 80,
+                    public: Some(8080),
+                },
+                ContainerPorts {
+                    ip: Some("::".to_owned()),
+                    private: 80,
+                    public: Some(8080),
+                },
+                ContainerPorts {
+                    ip: None,
+                    private: 443,
+                    public: None,
+                },
+            ],
+            State::Running,
+            "Up 1 hour".to_owned(),
+        );
+        item.command = "x".repeat(40);
+        item.ip = "172.17.0.2".to_owned();
+        item.labels
+            .insert(SERVICE_LABEL.to_owned(), "web".to_owned());
+        item.labels.insert("team".to_owned(), "core".to_owned());
+        item.restart_count = Some(2);
+        item.started = Some(1_673_723_610);
+        let now = 1_673_723_610 + 7200;
+
+        let text = |header| item.column_text(header, Some("team"), now);
+        assert_eq!(text(Header::Created), Some("2 hours ago".to_owned()));
+        assert_eq!(text(Header::Uptime), Some("2 hours".to_owned()));
+        assert_eq!(text(Header::Ports), Some("8080->80, 443".to_owned()));
+        assert_eq!(text(Header::Command), Some(format!("{}…", "x".repeat(31))));
+        assert_eq!(text(Header::Service), Some("web".to_owned()));
+        assert_eq!(text(Header::Label), Some("core".to_owned()));
+        assert_eq!(text(Header::Restarts), Some("2".to_owned()));
+        assert_eq!(text(Header::Ip), Some("172.17.0.2".to_owned()));
+        assert_eq!(text(Header::Cpu), None);
+
+        item.state = State::Exited;
+        assert_eq!(
+            item.column_text(Header::Uptime, None, now),
+            Some(String::new())
+        );
+        assert_eq!(
+            item.column_text(Header::Label, None, now),
+            Some(String::new())
+        );
+    }
+
+    #[test]
+    /// Io rates are bytes per second between two samples, and counters that go backwards give a zero rate
+    fn test_container_state_push_io() {
+        let mut item = ContainerItem::new(
+            1,
+            ContainerId::from("1"),
+            "image_1".to_owned(),
+            false,
+            "container_1".to_owned(),
+            vec![],
+            State::Running,
+            "Up 1 hour".to_owned(),
+        );
+        let io = |rx, tx, read, write| IoCounters {
+            read,
+            rx,
+            tx,
+            write,
+        };
+
+        item.push_io(io(1000, 1000, 1000, 1000), 1000, 60_000);
+        assert!(item.rx_stats.back().is_none());
+
+        item.push_io(io(3000, 5000, 1000, 2000), 3000, 60_000);
+        assert_eq!(item.rx_stats.back(), Some(&ByteStats::new(1000)));
+        assert_eq!(item.tx_stats.back(), Some(&ByteStats::new(2000)));
+        assert_eq!(item.blk_read_stats.back(), Some(&ByteStats::new(0)));
+        assert_eq!(item.blk_write_stats.back(), Some(&ByteStats::new(500)));
+        assert_eq!(item.rx, ByteStats::new(3000));
+
+        // Container restarted, counters reset
+        item.push_io(io(0, 0, 0, 0), 4000, 60_000);
+        assert_eq!(item.rx_stats.back(), Some(&ByteStats::new(0)));
+        assert_eq!(item.rx_stats.time_range(), Some((3000, 4000)));
+    }
+
+    #[test]
+    /// Samples closer together than the resolution are merged, keeping the max, but the newest sample is kept as is
+    fn test_container_state_stats_history_merge() {
+        // 3600s retention gives a resolution of 1s
+        let retention = 3_600_000;
+        let mut history = StatsHistory::default();
+        for (timestamp, value) in [(0, 5), (400, 9), (800, 2), (1600, 1)] {
+            history.push(timestamp, ByteStats::new(value), retention);
+        }
+        assert_eq!(
+            history.0,
+            [(0, ByteStats::new(9)), (1600, ByteStats::new(1))]
+        );
+        assert_eq!(history.back(), Some(&ByteStats::new(1)));
+
+        history.push(2000, ByteStats::new(3), retention);
+        assert_eq!(
+            history.0,
+            [
+                (0, ByteStats::new(9)),
+                (1600, ByteStats::new(1)),
+                (2000, ByteStats::new(3))
+            ]
+        );
+    }
+
+    #[test]
+    /// Samples older than the newest sample are dropped, and a dataset of out of order samples doesn't panic
+    fn test_container_state_stats_history_out_of_order() {
+        let retention = 60_000;
+        let mut history = StatsHistory::default();
+        for (timestamp, value) in [(5000, 1), (3000, 2), (5000, 3), (4000, 4), (6000, 5)] {
+            history.push(timestamp, ByteStats::new(value), retention);
+        }
+        // The two samples at 5000 are merged, as they're closer together than the resolution
+        assert_eq!(
+            history.0,
+            [(5000, ByteStats::new(3)), (6000, ByteStats::new(5))]
+        );
+
+        let history = StatsHistory(VecDeque::from([
+            (5000, ByteStats::new(1)),
+            (3000, ByteStats::new(2)),
+            (6000, ByteStats::new(3)),
+        ]));
+        let axis = TimeAxis::new(0, 6000);
+        assert_eq!(history.dataset(axis, 60, 3000).concat().len(), 3);
+        let axis = TimeAxis { start: 10, end: 0 };
+        assert_eq!(history.dataset(axis, 60, 3000).concat().len(), 3);
+    }
+
+    #[test]
+    /// Datasets downsampled into buckets, keeping the max value, and split into segments at gaps
+    fn test_container_state_stats_history_dataset() {
+        let mut history = StatsHistory::default();
+        for i in 0..20 {
+            history.push(i * 1000, ByteStats::new(i), 60_000);
+        }
+        let axis = TimeAxis::new(0, 19_000);
+        assert_eq!(
+            axis,
+            TimeAxis {
+                start: 0,
+                end: 60_000
+            }
+        );
+
+        // One point per sample
+        let result = history.dataset(axis, 60, 3000);
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].len(), 20);
+
+        // 10s buckets, newest sample always kept
+        let result = history.dataset(axis, 6, 3000);
+        assert_eq!(result, vec![vec![(9.0, 9.0), (18.0, 18.0), (19.0, 19.0)]]);
+
+        history.push(40_000, ByteStats::new(1), 60_000);
+        let result = history.dataset(axis, 60, 3000);
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[1], vec![(40.0, 1.0)]);
+        assert_eq!(history.max(axis), ByteStats::new(19));
+        assert_eq!(TimeAxis::label(90_061_000), "01:01:01");
+    }
+
+    #[test]
+    /// Health parsed from the docker status string
+    fn test_container_state_health_from_status() {
+        let test = |s: &str, h: Health| {
+            assert_eq!(Health::from(s), h);
+        };
+
+        test("Up 2 minutes (healthy)", Health::Healthy);
+        test("Up 2 minutes (unhealthy)", Health::Unhealthy);
+        test("Up 5 seconds (health: starting)", Health::Starting);
+        test("Up 2 minutes", Health::Unset);
+        test("Exited (0) 10 minutes ago", Health::Unset);
+    }
 
     #[test]
     /// Display CpuStats as a string
@@ -720,6 +1461,19 @@ mod tests {
         test(150_000_000_000, "150.00 GB");
     }
 
+    #[test]
+    /// ContainerName validated against the characters Docker allows
+    fn test_container_state_container_name_validate() {
+        for name in ["redis", "r2", "0_redis.cache-1", "ABC"] {
+            assert!(ContainerName::validate(name).is_ok(), "{name}");
+        }
+        for name in [
+            "", "a", "_redis", "-redis", ".redis", "/redis", "red is", "redis!", "rédis",
+        ] {
+            assert!(ContainerName::validate(name).is_err(), "{name}");
+        }
+    }
+
     #[test]
     /// ContainerName as string truncated correctly
     fn test_container_state_container_name_to_string() {
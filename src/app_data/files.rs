@@ -0,0 +1,129 @@
+use super::{ContainerId, StatefulList};
+
+/// A single entry of a directory listing from a container
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileEntry {
+    pub is_dir: bool,
+    pub name: String,
+}
+
+/// Join a directory and an entry name into an absolute path, `..` resolves to the parent directory
+pub fn join_path(dir: &str, name: &str) -> String {
+    let dir = dir.trim_end_matches('/');
+    if name == ".." {
+        match dir.rfind('/') {
+            Some(0) | None => "/".to_owned(),
+            Some(i) => dir[..i].to_owned(),
+        }
+    } else {
+        format!("{dir}/{name}")
+    }
+}
+
+/// The contents of a directory in a container, used to choose a file to download, or a directory to upload into
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileBrowser {
+    pub entries: StatefulList<FileEntry>,
+    pub id: ContainerId,
+    pub name: String,
+    pub path: String,
+}
+
+impl FileBrowser {
+    /// Create from the output of `ls -1Ap`, where directories have a trailing `/`
+    /// Directories are listed first, with a `..` entry to go up a level, unless at the root directory
+    pub fn new(id: ContainerId, name: &str, path: &str, listing: &str) -> Self {
+        let mut entries = listing
+            .lines()
+            .filter(|i| !i.is_empty())
+            .map(|i| {
+                let dir = i.strip_suffix('/');
+                FileEntry {
+                    is_dir: dir.is_some(),
+                    name: dir.unwrap_or(i).to_owned(),
+                }
+            })
+            .collect::<Vec<_>>();
+        entries.sort_by(|a, b| b.is_dir.cmp(&a.is_dir).then_with(|| a.name.cmp(&b.name)));
+        if path != "/" {
+            entries.insert(
+                0,
+                FileEntry {
+                    is_dir: true,
+                    name: "..".to_owned(),
+                },
+            );
+        }
+        let mut entries = StatefulList::new(entries);
+        if !entries.items.is_empty() {
+            entries.start();
+        }
+        Self {
+            entries,
+            id,
+            name: name.to_owned(),
+            path: path.to_owned(),
+        }
+    }
+
+    /// The selected entry, and its full path
+    pub fn get_selected(&self) -> Option<(&FileEntry, String)> {
+        self.entries
+            .state
+            .selected()
+            .and_then(|i| self.entries.items.get(i))
+            .map(|i| (i, join_path(&self.path, &i.name)))
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use crate::app_data::ContainerId;
+
+    use super::{join_path, FileBrowser};
+
+    #[test]
+    /// Paths joined, and parent directories resolved
+    fn test_files_join_path() {
+        assert_eq!(join_path("/", "etc"), "/etc");
+        assert_eq!(join_path("/etc", "hosts"), "/etc/hosts");
+        assert_eq!(join_path("/etc/", "hosts"), "/etc/hosts");
+        assert_eq!(join_path("/etc/nginx", ".."), "/etc");
+        assert_eq!(join_path("/etc", ".."), "/");
+        assert_eq!(join_path("/", ".."), "/");
+    }
+
+    #[test]
+    /// Listing parsed into directories then files, with a parent entry when not at the root
+    fn test_files_new() {
+        let listing = "hosts\nnginx/\napk/\nalpine-release\n";
+        let browser = FileBrowser::new(ContainerId::from("abc"), "web", "/etc", listing);
+        let names = browser
+            .entries
+            .items
+            .iter()
+            .map(|i| (i.name.as_str(), i.is_dir))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            [
+                ("..", true),
+                ("apk", true),
+                ("nginx", true),
+                ("alpine-release", false),
+                ("hosts", false)
+            ]
+        );
+        let (entry, path) = browser.get_selected().unwrap();
+        assert_eq!(entry.name, "..");
+        assert_eq!(path, "/");
+
+        let browser = FileBrowser::new(ContainerId::from("abc"), "web", "/", "etc/\n");
+        assert_eq!(browser.entries.items.len(), 1);
+        assert_eq!(browser.get_selected().unwrap().1, "/etc");
+
+        let browser = FileBrowser::new(ContainerId::from("abc"), "web", "/", "");
+        assert!(browser.get_selected().is_none());
+    }
+}
@@ -0,0 +1,217 @@
+use bollard::service::ImageSummary;
+
+use super::{ByteStats, ContainerItem};
+
+const DANGLING: &str = "<none>:<none>";
+
+/// Convert a unix timestamp into a human readable "time since", e.g. "3 days ago"
+pub fn time_since(timestamp: i64, now: i64) -> String {
+    format!("{} ago", duration(now.saturating_sub(timestamp)))
+}
+
+/// A number of seconds as the largest whole unit, e.g. 3 hours
+pub fn duration(seconds: i64) -> String {
+    let seconds = seconds.max(0);
+    let (value, unit) = match seconds {
+        0..=59 => (seconds, "second"),
+        60..=3599 => (seconds / 60, "minute"),
+        3600..=86_399 => (seconds / 3600, "hour"),
+        86_400..=604_799 => (seconds / 86_400, "day"),
+        604_800..=2_629_799 => (seconds / 604_800, "week"),
+        2_629_800..=31_557_599 => (seconds / 2_629_800, "month"),
+        _ => (seconds / 31_557_600, "year"),
+    };
+    let plural = if value == 1 { "" } else { "s" };
+    format!("{value} {unit}{plural}")
+}
+
+/// Add the `latest` tag to an image name without a tag or digest, as pulling an image without a tag will pull every tag of the image
+pub fn with_tag(image: &str) -> String {
+    let last_segment = image.rsplit('/').next().unwrap_or(image);
+    if image.contains('@') || last_segment.contains(':') {
+        image.to_owned()
+    } else {
+        format!("{image}:latest")
+    }
+}
+
+/// Split an image name into a repository and a tag, the tag defaults to `latest`, a digest can't be used as a tag
+pub fn split_tag(image: &str) -> Result<(String, String), String> {
+    if image.is_empty() {
+        return Err("an image is required".to_owned());
+    }
+    if image.contains('@') {
+        return Err(format!(
+            "invalid image: {image}, a digest can't be used as a tag"
+        ));
+    }
+    let tagged = with_tag(image);
+    match tagged.rsplit_once(':') {
+        Some((repo, tag)) if !repo.is_empty() && !tag.is_empty() => {
+            Ok((repo.to_owned(), tag.to_owned()))
+        }
+        _ => Err(format!("invalid image: {image}")),
+    }
+}
+
+/// Info for each local image
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageItem {
+    pub containers: Vec<String>,
+    pub created: i64,
+    pub dangling: bool,
+    pub id: String,
+    pub name: String,
+    pub size: ByteStats,
+}
+
+impl ImageItem {
+    /// Create a new image item, find the names of any of the given containers that use the image
+    pub fn new(summary: &ImageSummary, containers: &[ContainerItem]) -> Self {
+        let name = summary
+            .repo_tags
+            .iter()
+            .find(|i| i.as_str() != DANGLING)
+            .cloned();
+        let dangling = name.is_none();
+        let containers = containers
+            .iter()
+            .filter(|container| container.image_id == summary.id)
+            .map(|container| container.name.get().to_owned())
+            .collect();
+        Self {
+            containers,
+            created: summary.created,
+            dangling,
+            id: summary.id.clone(),
+            name: name.unwrap_or_else(|| DANGLING.to_owned()),
+            size: ByteStats::new(u64::try_from(summary.size).unwrap_or_default()),
+        }
+    }
+
+    /// The first 12 chars of the id, without the sha256 prefix, as shown by the docker cli
+    pub fn get_short_id(&self) -> String {
+        self.id
+            .trim_start_matches("sha256:")
+            .chars()
+            .take(12)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bollard::service::ImageSummary;
+
+    use crate::app_data::{ContainerId, ContainerItem, State};
+
+    use super::{split_tag, time_since, with_tag, ImageItem};
+
+    fn gen_summary(id: &str, tags: &[&str]) -> ImageSummary {
+        ImageSummary {
+            id: id.to_owned(),
+            repo_tags: tags.iter().map(|i| (*i).to_owned()).collect(),
+            created: 100,
+            size: 1_500_000,
+            ..Default::default()
+        }
+    }
+
+    fn gen_container(name: &str, image: &str, image_id: &str) -> ContainerItem {
+        let mut container = ContainerItem::new(
+            1,
+            ContainerId::from(name),
+            image.to_owned(),
+            false,
+            name.to_owned(),
+            vec![],
+            State::Running,
+            "Up 1 hour".to_owned(),
+        );
+        container.image_id = image_id.to_owned();
+        container
+    }
+
+    #[test]
+    /// Unix timestamps converted into a readable "time since" string
+    fn test_images_time_since() {
+        assert_eq!(time_since(100, 100), "0 seconds ago");
+        assert_eq!(time_since(100, 101), "1 second ago");
+        assert_eq!(time_since(0, 120), "2 minutes ago");
+        assert_eq!(time_since(0, 3600), "1 hour ago");
+        assert_eq!(time_since(0, 86_400 * 3), "3 days ago");
+        assert_eq!(time_since(0, 604_800 * 2), "2 weeks ago");
+        assert_eq!(time_since(0, 31_557_600 * 4), "4 years ago");
+        assert_eq!(time_since(200, 100), "0 seconds ago");
+    }
+
+    #[test]
+    /// Latest tag only added when the image has no tag or digest
+    fn test_images_with_tag() {
+        assert_eq!(with_tag("redis"), "redis:latest");
+        assert_eq!(with_tag("redis:7"), "redis:7");
+        assert_eq!(with_tag("localhost:5000/app"), "localhost:5000/app:latest");
+        assert_eq!(with_tag("localhost:5000/app:1.0"), "localhost:5000/app:1.0");
+        assert_eq!(with_tag("redis@sha256:abc"), "redis@sha256:abc");
+    }
+
+    #[test]
+    /// Image names split into repository and tag, with a default tag, digests and empty parts are an error
+    fn test_images_split_tag() {
+        let split = |repo: &str, tag: &str| Ok((repo.to_owned(), tag.to_owned()));
+        assert_eq!(split_tag("redis"), split("redis", "latest"));
+        assert_eq!(
+            split_tag("debug/redis:broken"),
+            split("debug/redis", "broken")
+        );
+        assert_eq!(
+            split_tag("localhost:5000/app"),
+            split("localhost:5000/app", "latest")
+        );
+        for image in ["", "redis@sha256:abc", ":tag", "redis:"] {
+            assert!(split_tag(image).is_err(), "{image}");
+        }
+    }
+
+    #[test]
+    /// Image name, dangling status, and containers using the image, all set correctly
+    fn test_images_new() {
+        let containers = [
+            gen_container("redis_1", "redis:7", "sha256:123456789abcdef"),
+            gen_container("redis_2", "redis", "sha256:123456789abcdef"),
+            gen_container("postgres", "postgres:16", "sha256:fedcba"),
+            gen_container("by_id", "abcdef", "sha256:abcdef123"),
+        ];
+
+        let image = ImageItem::new(
+            &gen_summary("sha256:123456789abcdef", &["redis:7", "redis:latest"]),
+            &containers,
+        );
+        assert_eq!(image.name, "redis:7");
+        assert!(!image.dangling);
+        assert_eq!(image.get_short_id(), "123456789abc");
+        assert_eq!(image.containers, ["redis_1", "redis_2"]);
+        assert_eq!(image.size.to_string(), "1.50 MB");
+
+        let image = ImageItem::new(&gen_summary("sha256:abcdef123", &[]), &containers);
+        assert_eq!(image.name, "<none>:<none>");
+        assert!(image.dangling);
+        assert_eq!(image.containers, ["by_id"]);
+    }
+
+    #[test]
+    /// Containers only matched on image id, not on an empty image, a hex like image name, or a tag that has since moved to a newer image
+    fn test_images_new_image_id_only() {
+        let containers = [
+            gen_container("empty", "", ""),
+            gen_container("hex", "dead", "sha256:0123"),
+            gen_container("old_redis", "redis:7", "sha256:0123"),
+        ];
+
+        let image = ImageItem::new(&gen_summary("sha256:deadbeef", &["redis:7"]), &containers);
+        assert!(image.containers.is_empty());
+
+        let image = ImageItem::new(&gen_summary("sha256:0123", &[]), &containers);
+        assert_eq!(image.containers, ["hex", "old_redis"]);
+    }
+}
@@ -0,0 +1,539 @@
+use bollard::service::{
+    ContainerConfig, ContainerInspectResponse, ContainerState, HostConfig, MountPoint,
+    NetworkSettings,
+};
+
+use super::{ByteStats, ContainerId, Tree, TreeNode};
+
+/// Environmental variables with a key that contains any of these will have their value hidden
+const SECRET_WORDS: [&str; 5] = ["PASSWORD", "PASSWD", "SECRET", "CREDENTIAL", "PRIVATE"];
+/// Environmental variables with a key that has any of these as a whole `_` separated word, optionally plural, will have their value hidden
+/// Matched as words, as they are often part of unrelated words, e.g. `KEYBOARD` or `AUTHOR`
+const SECRET_TOKENS: [&str; 4] = ["KEY", "APIKEY", "AUTH", "TOKEN"];
+const MASK: &str = "********";
+
+/// The inspect information for a single container, displayed in a collapsible tree
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InspectData {
+    pub id: ContainerId,
+    pub name: String,
+    pub tree: Tree,
+}
+
+/// Split an env var at the first `=`, and mask the value if the key looks like it holds a secret
+pub fn mask_env(env: &str) -> String {
+    match env.split_once('=') {
+        Some((key, value)) if !value.is_empty() => {
+            let upper = key.to_uppercase();
+            let is_token = |token: &str| {
+                SECRET_TOKENS
+                    .iter()
+                    .any(|i| token == *i || token.strip_suffix('S') == Some(i))
+            };
+            if SECRET_WORDS.iter().any(|i| upper.contains(i))
+                || upper.split(['_', '-', '.']).any(is_token)
+            {
+                format!("{key}={MASK}")
+            } else {
+                env.to_owned()
+            }
+        }
+        _ => env.to_owned(),
+    }
+}
+
+/// Join a command, or entrypoint, vec into a single string
+fn join(input: Option<&Vec<String>>) -> Option<String> {
+    input.filter(|i| !i.is_empty()).map(|i| i.join(" "))
+}
+
+/// Convert a possible negative/zero i64 into bytes
+fn bytes(input: Option<i64>) -> Option<String> {
+    input
+        .and_then(|i| u64::try_from(i).ok())
+        .filter(|i| *i > 0)
+        .map(|i| ByteStats::new(i).to_string())
+}
+
+fn state_node(state: &ContainerState, restart_count: Option<i64>) -> TreeNode {
+    let mut children = vec![];
+    if let Some(running) = state.running {
+        children.push(TreeNode::leaf("running", running.to_string()));
+    }
+    if let Some(paused) = state.paused {
+        children.push(TreeNode::leaf("paused", paused.to_string()));
+    }
+    if let Some(pid) = state.pid.filter(|i| *i > 0) {
+        children.push(TreeNode::leaf("pid", pid.to_string()));
+    }
+    if let Some(exit_code) = state.exit_code {
+        children.push(TreeNode::leaf("exit code", exit_code.to_string()));
+    }
+    if let Some(oom) = state.oom_killed {
+        children.push(TreeNode::leaf("oom killed", oom.to_string()));
+    }
+    if let Some(error) = state.error.as_ref().filter(|i| !i.is_empty()) {
+        children.push(TreeNode::leaf("error", error));
+    }
+    if let Some(started) = state.started_at.as_ref() {
+        children.push(TreeNode::leaf("started", started));
+    }
+    if let Some(finished) = state.finished_at.as_ref() {
+        children.push(TreeNode::leaf("finished", finished));
+    }
+    if let Some(count) = restart_count {
+        children.push(TreeNode::leaf("restart count", count.to_string()));
+    }
+    let status = state
+        .status
+        .as_ref()
+        .map_or_else(String::new, ToString::to_string);
+    TreeNode::branch_value("state", status, children)
+}
+
+fn health_node(state: &ContainerState) -> Option<TreeNode> {
+    state.health.as_ref().map(|health| {
+        let mut children = vec![];
+        if let Some(streak) = health.failing_streak {
+            children.push(TreeNode::leaf("failing streak", streak.to_string()));
+        }
+        if let Some(log) = health.log.as_ref().filter(|i| !i.is_empty()) {
+            // Most recent probe first
+            let probes = log
+                .iter()
+                .rev()
+                .map(|probe| {
+                    let exit_code = probe
+                        .exit_code
+                        .map_or_else(String::new, |i| format!("exit code {i}"));
+                    let output = probe
+                        .output
+                        .as_ref()
+                        .map(|output| {
+                            output
+                                .lines()
+                                .filter(|i| !i.trim().is_empty())
+                                .map(|i| TreeNode::leaf("", i.trim_end()))
+                                .collect::<Vec<_>>()
+                        })
+                        .unwrap_or_default();
+                    TreeNode::branch_value(
+                        probe.start.clone().unwrap_or_default(),
+                        exit_code,
+                        output,
+                    )
+                })
+                .collect();
+            children.push(TreeNode::branch(format!("log ({})", log.len()), probes));
+        }
+        let status = health
+            .status
+            .as_ref()
+            .map_or_else(String::new, ToString::to_string);
+        TreeNode::branch_value("health", status, children)
+    })
+}
+
+fn command_nodes(
+    inspect: &ContainerInspectResponse,
+    config: Option<&ContainerConfig>,
+) -> Vec<TreeNode> {
+    let mut output = vec![];
+    let command = inspect.path.as_ref().map(|path| {
+        inspect
+            .args
+            .as_ref()
+            .map_or_else(|| path.clone(), |args| format!("{path} {}", args.join(" ")))
+    });
+    if let Some(command) = command {
+        output.push(TreeNode::leaf("command", command.trim()));
+    }
+    if let Some(config) = config {
+        if let Some(entrypoint) = join(config.entrypoint.as_ref()) {
+            output.push(TreeNode::leaf("entrypoint", entrypoint));
+        }
+        if let Some(cmd) = join(config.cmd.as_ref()) {
+            output.push(TreeNode::leaf("cmd", cmd));
+        }
+        if let Some(dir) = config.working_dir.as_ref().filter(|i| !i.is_empty()) {
+            output.push(TreeNode::leaf("working dir", dir));
+        }
+        if let Some(user) = config.user.as_ref().filter(|i| !i.is_empty()) {
+            output.push(TreeNode::leaf("user", user));
+        }
+    }
+    output
+}
+
+fn env_node(config: &ContainerConfig) -> Option<TreeNode> {
+    config.env.as_ref().filter(|i| !i.is_empty()).map(|env| {
+        let mut env = env.iter().map(|i| mask_env(i)).collect::<Vec<_>>();
+        env.sort();
+        TreeNode::branch(
+            format!("env ({})", env.len()),
+            env.into_iter().map(|i| TreeNode::leaf("", i)).collect(),
+        )
+    })
+}
+
+fn labels_node(config: &ContainerConfig) -> Option<TreeNode> {
+    config
+        .labels
+        .as_ref()
+        .filter(|i| !i.is_empty())
+        .map(|labels| {
+            let mut labels = labels.iter().collect::<Vec<_>>();
+            labels.sort();
+            TreeNode::branch(
+                format!("labels ({})", labels.len()),
+                labels
+                    .into_iter()
+                    .map(|(key, value)| TreeNode::leaf(key, value))
+                    .collect(),
+            )
+        })
+}
+
+fn mounts_node(mounts: &[MountPoint]) -> Option<TreeNode> {
+    if mounts.is_empty() {
+        return None;
+    }
+    let children = mounts
+        .iter()
+        .map(|mount| {
+            let mut children = vec![];
+            if let Some(source) = mount.source.as_ref().filter(|i| !i.is_empty()) {
+                children.push(TreeNode::leaf("source", source));
+            }
+            if let Some(name) = mount.name.as_ref() {
+                children.push(TreeNode::leaf("name", name));
+            }
+            if let Some(typ) = mount.typ.as_ref() {
+                children.push(TreeNode::leaf("type", typ.to_string()));
+            }
+            if let Some(mode) = mount.mode.as_ref().filter(|i| !i.is_empty()) {
+                children.push(TreeNode::leaf("mode", mode));
+            }
+            if let Some(rw) = mount.rw {
+                children.push(TreeNode::leaf("read write", rw.to_string()));
+            }
+            TreeNode::branch(
+                mount.destination.as_ref().map_or("", |i| i.as_str()),
+                children,
+            )
+        })
+        .collect::<Vec<_>>();
+    Some(TreeNode::branch(
+        format!("mounts ({})", children.len()),
+        children,
+    ))
+}
+
+fn networks_node(network_settings: &NetworkSettings) -> Option<TreeNode> {
+    let networks = network_settings
+        .networks
+        .as_ref()
+        .filter(|i| !i.is_empty())?;
+    let mut networks = networks.iter().collect::<Vec<_>>();
+    networks.sort_by(|a, b| a.0.cmp(b.0));
+    let children = networks
+        .into_iter()
+        .map(|(name, endpoint)| {
+            let mut children = vec![];
+            let ip = endpoint
+                .ip_address
+                .as_ref()
+                .filter(|i| !i.is_empty())
+                .map(|ip| {
+                    endpoint
+                        .ip_prefix_len
+                        .map_or_else(|| ip.clone(), |prefix| format!("{ip}/{prefix}"))
+                });
+            if let Some(gateway) = endpoint.gateway.as_ref().filter(|i| !i.is_empty()) {
+                children.push(TreeNode::leaf("gateway", gateway));
+            }
+            if let Some(ipv6) = endpoint
+                .global_ipv6_address
+                .as_ref()
+                .filter(|i| !i.is_empty())
+            {
+                children.push(TreeNode::leaf("ipv6", ipv6));
+            }
+            if let Some(mac) = endpoint.mac_address.as_ref().filter(|i| !i.is_empty()) {
+                children.push(TreeNode::leaf("mac", mac));
+            }
+            if let Some(aliases) = join(endpoint.aliases.as_ref()) {
+                children.push(TreeNode::leaf("aliases", aliases));
+            }
+            TreeNode::branch_value(name, ip.unwrap_or_default(), children)
+        })
+        .collect::<Vec<_>>();
+    Some(TreeNode::branch("networks", children))
+}
+
+fn ports_node(network_settings: &NetworkSettings) -> Option<TreeNode> {
+    let ports = network_settings.ports.as_ref().filter(|i| !i.is_empty())?;
+    let mut ports = ports.iter().collect::<Vec<_>>();
+    ports.sort_by(|a, b| a.0.cmp(b.0));
+    let children = ports
+        .into_iter()
+        .map(|(container_port, bindings)| {
+            let bindings = bindings.as_ref().map_or_else(String::new, |bindings| {
+                bindings
+                    .iter()
+                    .map(|i| {
+                        format!(
+                            "{}:{}",
+                            i.host_ip.as_deref().unwrap_or_default(),
+                            i.host_port.as_deref().unwrap_or_default()
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            });
+            TreeNode::leaf(container_port, bindings)
+        })
+        .collect();
+    Some(TreeNode::branch("ports", children))
+}
+
+fn restart_policy_node(host_config: &HostConfig) -> Option<TreeNode> {
+    host_config.restart_policy.as_ref().map(|policy| {
+        let name = policy
+            .name
+            .as_ref()
+            .map_or_else(String::new, ToString::to_string);
+        let value = match policy.maximum_retry_count {
+            Some(count) if count > 0 => format!("{name} (max retries {count})"),
+            _ => name,
+        };
+        TreeNode::leaf("restart policy", value)
+    })
+}
+
+fn resources_node(host_config: &HostConfig) -> Option<TreeNode> {
+    let mut children = vec![];
+    if let Some(memory) = bytes(host_config.memory) {
+        children.push(TreeNode::leaf("memory", memory));
+    }
+    if let Some(reservation) = bytes(host_config.memory_reservation) {
+        children.push(TreeNode::leaf("memory reservation", reservation));
+    }
+    if let Some(swap) = bytes(host_config.memory_swap) {
+        children.push(TreeNode::leaf("memory swap", swap));
+    }
+    if let Some(nano_cpus) = host_config.nano_cpus.filter(|i| *i > 0) {
+        #[allow(clippy::cast_precision_loss)]
+        let cpus = nano_cpus as f64 / 1_000_000_000.0;
+        children.push(TreeNode::leaf("cpus", format!("{cpus:.2}")));
+    }
+    if let Some(shares) = host_config.cpu_shares.filter(|i| *i > 0) {
+        children.push(TreeNode::leaf("cpu shares", shares.to_string()));
+    }
+    if let Some(quota) = host_config.cpu_quota.filter(|i| *i > 0) {
+        children.push(TreeNode::leaf("cpu quota", quota.to_string()));
+    }
+    if let Some(period) = host_config.cpu_period.filter(|i| *i > 0) {
+        children.push(TreeNode::leaf("cpu period", period.to_string()));
+    }
+    if let Some(cpuset) = host_config.cpuset_cpus.as_ref().filter(|i| !i.is_empty()) {
+        children.push(TreeNode::leaf("cpuset", cpuset));
+    }
+    if let Some(pids) = host_config.pids_limit.filter(|i| *i > 0) {
+        children.push(TreeNode::leaf("pids limit", pids.to_string()));
+    }
+    if children.is_empty() {
+        None
+    } else {
+        Some(TreeNode::branch("resources", children))
+    }
+}
+
+impl From<&ContainerInspectResponse> for Tree {
+    fn from(inspect: &ContainerInspectResponse) -> Self {
+        let mut nodes = vec![];
+        if let Some(id) = inspect.id.as_ref() {
+            nodes.push(TreeNode::leaf("id", id));
+        }
+        if let Some(created) = inspect.created.as_ref() {
+            nodes.push(TreeNode::leaf("created", created));
+        }
+        if let Some(image) = inspect.config.as_ref().and_then(|i| i.image.as_ref()) {
+            nodes.push(TreeNode::leaf("image", image));
+        }
+        if let Some(state) = inspect.state.as_ref() {
+            nodes.push(state_node(state, inspect.restart_count));
+            if let Some(health) = health_node(state) {
+                nodes.push(health);
+            }
+        }
+        nodes.extend(command_nodes(inspect, inspect.config.as_ref()));
+        if let Some(config) = inspect.config.as_ref() {
+            nodes.extend(env_node(config));
+        }
+        if let Some(mounts) = inspect.mounts.as_ref() {
+            nodes.extend(mounts_node(mounts));
+        }
+        if let Some(network_settings) = inspect.network_settings.as_ref() {
+            nodes.extend(networks_node(network_settings));
+            nodes.extend(ports_node(network_settings));
+        }
+        if let Some(host_config) = inspect.host_config.as_ref() {
+            nodes.extend(restart_policy_node(host_config));
+            nodes.extend(resources_node(host_config));
+        }
+        if let Some(config) = inspect.config.as_ref() {
+            nodes.extend(labels_node(config));
+        }
+        Self::new(nodes)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::collections::HashMap;
+
+    use bollard::service::{
+        ContainerConfig, ContainerInspectResponse, ContainerState, EndpointSettings, Health,
+        HealthStatusEnum, HealthcheckResult, HostConfig, NetworkSettings, RestartPolicy,
+        RestartPolicyNameEnum,
+    };
+
+    use super::{health_node, mask_env, Tree};
+
+    #[test]
+    /// Env vars with secret-ish keys have their values masked
+    fn test_inspect_mask_env() {
+        assert_eq!(mask_env("PATH=/usr/bin"), "PATH=/usr/bin");
+        assert_eq!(mask_env("DB_PASSWORD=hunter2"), "DB_PASSWORD=********");
+        assert_eq!(mask_env("api_token=abc=def"), "api_token=********");
+        assert_eq!(
+            mask_env("AWS_SECRET_ACCESS_KEY=x"),
+            "AWS_SECRET_ACCESS_KEY=********"
+        );
+        assert_eq!(mask_env("PASSWORD="), "PASSWORD=");
+        assert_eq!(mask_env("NO_VALUE"), "NO_VALUE");
+        for secret in [
+            "API_KEY",
+            "AUTH_TOKEN",
+            "SSH_KEYS",
+            "stripe-apikey",
+            "DBPASSWORD",
+        ] {
+            assert_eq!(
+                mask_env(&format!("{secret}=x")),
+                format!("{secret}=********")
+            );
+        }
+    }
+
+    #[test]
+    /// Keys that only contain KEY, AUTH, or TOKEN as part of another word aren't masked
+    fn test_inspect_mask_env_words() {
+        for env in [
+            "AUTHOR=me",
+            "MONKEY_COUNT=3",
+            "KEYBOARD_LAYOUT=uk",
+            "TOKENIZER_PATH=/models",
+        ] {
+            assert_eq!(mask_env(env), env);
+        }
+    }
+
+    #[test]
+    /// Inspect response converted into a tree, with the expected top level nodes
+    fn test_inspect_tree_from_response() {
+        let inspect = ContainerInspectResponse {
+            id: Some("abc".to_owned()),
+            path: Some("/docker-entrypoint.sh".to_owned()),
+            args: Some(vec!["redis-server".to_owned()]),
+            config: Some(ContainerConfig {
+                image: Some("redis:latest".to_owned()),
+                env: Some(vec!["REDIS_PASSWORD=abc".to_owned(), "A=b".to_owned()]),
+                ..Default::default()
+            }),
+            host_config: Some(HostConfig {
+                restart_policy: Some(RestartPolicy {
+                    name: Some(RestartPolicyNameEnum::ON_FAILURE),
+                    maximum_retry_count: Some(3),
+                }),
+                memory: Some(1_000_000),
+                ..Default::default()
+            }),
+            network_settings: Some(NetworkSettings {
+                networks: Some(HashMap::from([(
+                    "bridge".to_owned(),
+                    EndpointSettings {
+                        ip_address: Some("172.17.0.2".to_owned()),
+                        ip_prefix_len: Some(16),
+                        ..Default::default()
+                    },
+                )])),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let mut tree = Tree::from(&inspect);
+        let visible = tree.visible().iter().map(|i| i.text()).collect::<Vec<_>>();
+        assert_eq!(
+            visible,
+            [
+                "id: abc",
+                "image: redis:latest",
+                "command: /docker-entrypoint.sh redis-server",
+                "env (2)",
+                "networks",
+                "restart policy: on-failure (max retries 3)",
+                "resources",
+            ]
+        );
+
+        tree.expand_all();
+        let visible = tree.visible().iter().map(|i| i.text()).collect::<Vec<_>>();
+        assert!(visible.contains(&"REDIS_PASSWORD=********".to_owned()));
+        assert!(visible.contains(&"bridge: 172.17.0.2/16".to_owned()));
+        assert!(visible.contains(&"memory: 1.00 MB".to_owned()));
+    }
+
+    #[test]
+    /// Health check log shown most recent probe first, with the probe output as children
+    fn test_inspect_health_log() {
+        let state = ContainerState {
+            health: Some(Health {
+                status: Some(HealthStatusEnum::UNHEALTHY),
+                failing_streak: Some(1),
+                log: Some(vec![
+                    HealthcheckResult {
+                        start: Some("2024-01-01T00:00:00Z".to_owned()),
+                        exit_code: Some(0),
+                        output: Some("ok\n".to_owned()),
+                        ..Default::default()
+                    },
+                    HealthcheckResult {
+                        start: Some("2024-01-01T00:00:30Z".to_owned()),
+                        exit_code: Some(1),
+                        output: Some("connection refused\n\n".to_owned()),
+                        ..Default::default()
+                    },
+                ]),
+            }),
+            ..Default::default()
+        };
+        let mut tree = Tree::new(health_node(&state).into_iter().collect());
+        tree.expand_all();
+        let visible = tree.visible().iter().map(|i| i.text()).collect::<Vec<_>>();
+        assert_eq!(
+            visible,
+            [
+                "health: unhealthy",
+                "failing streak: 1",
+                "log (2)",
+                "2024-01-01T00:00:30Z: exit code 1",
+                "connection refused",
+                "2024-01-01T00:00:00Z: exit code 0",
+                "ok",
+            ]
+        );
+    }
+}
@@ -0,0 +1,270 @@
+use bollard::{
+    container::UpdateContainerOptions,
+    service::{ContainerInspectResponse, HostConfig, RestartPolicy, RestartPolicyNameEnum},
+};
+
+use super::{
+    cpu_quota, format_cpus, format_memory, format_restart, host_nano_cpus, parse_cpus,
+    parse_memory, parse_restart, CPU_PERIOD,
+};
+
+/// The memory and cpu limits currently set on an existing container, so that only changed limits are updated
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CurrentLimits {
+    pub memory: i64,
+    pub memory_swap: i64,
+    /// The cpu limit, in billionths of a cpu, whether set directly, or as a quota and period
+    pub nano_cpus: i64,
+    /// The cpu limit was set directly, with `--cpus`, rather than as a quota and period, Docker won't change one into the other
+    pub uses_nano_cpus: bool,
+}
+
+impl From<&HostConfig> for CurrentLimits {
+    fn from(host_config: &HostConfig) -> Self {
+        Self {
+            memory: host_config.memory.unwrap_or_default(),
+            memory_swap: host_config.memory_swap.unwrap_or_default(),
+            nano_cpus: host_nano_cpus(host_config),
+            uses_nano_cpus: host_config.nano_cpus.is_some_and(|i| i > 0),
+        }
+    }
+}
+
+/// Resource limits, and the restart policy, of an existing container, as entered into the limits form
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LimitsConfig {
+    pub cpu_shares: String,
+    pub cpus: String,
+    pub current: CurrentLimits,
+    pub memory: String,
+    pub restart: String,
+}
+
+impl LimitsConfig {
+    /// Convert into Docker update container options, or return a description of the first invalid setting
+    /// An empty limit is left unchanged, as Docker can't remove a limit from an existing container, whereas an empty restart policy is set to `no`
+    /// A memory, or cpu, limit that is the same as the current limit isn't sent, and a cpu limit is sent in the same form, nano cpus or a quota, as the current limit
+    /// The current memory swap limit is kept, unless it would be less than the new memory limit, in which case it's set to twice the memory limit, as it would be by `docker run`
+    pub fn to_options(&self) -> Result<UpdateContainerOptions<String>, String> {
+        let memory = parse_memory(&self.memory)?.filter(|i| *i != self.current.memory);
+        let nano_cpus = parse_cpus(&self.cpus)?.filter(|i| *i != self.current.nano_cpus);
+        let memory_swap = memory.and_then(|memory| match self.current.memory_swap {
+            -1 => Some(-1),
+            swap if swap >= memory => None,
+            _ => memory.checked_mul(2),
+        });
+        let (direct_cpus, quota_cpus) = if self.current.uses_nano_cpus {
+            (nano_cpus, None)
+        } else {
+            (None, nano_cpus)
+        };
+        let cpu_shares = if self.cpu_shares.is_empty() {
+            None
+        } else {
+            Some(
+                self.cpu_shares
+                    .parse::<isize>()
+                    .ok()
+                    .filter(|i| *i > 0)
+                    .ok_or_else(|| format!("invalid cpu shares: {}", self.cpu_shares))?,
+            )
+        };
+        let restart_policy = parse_restart(&self.restart)?.unwrap_or(RestartPolicy {
+            name: Some(RestartPolicyNameEnum::NO),
+            maximum_retry_count: None,
+        });
+
+        Ok(UpdateContainerOptions {
+            cpu_shares,
+            cpu_period: quota_cpus.map(|_| CPU_PERIOD),
+            cpu_quota: quota_cpus.map(cpu_quota),
+            memory,
+            memory_swap,
+            nano_cpus: direct_cpus,
+            restart_policy: Some(restart_policy),
+            ..Default::default()
+        })
+    }
+}
+
+/// Use the current limits of an existing container
+impl From<&ContainerInspectResponse> for LimitsConfig {
+    fn from(inspect: &ContainerInspectResponse) -> Self {
+        let host_config = inspect.host_config.as_ref();
+        Self {
+            cpu_shares: host_config
+                .and_then(|i| i.cpu_shares)
+                .filter(|i| *i > 0)
+                .map_or_else(String::new, |i| i.to_string()),
+            cpus: format_cpus(host_config.map_or(0, host_nano_cpus)),
+            current: host_config.map(CurrentLimits::from).unwrap_or_default(),
+            memory: format_memory(host_config.and_then(|i| i.memory).unwrap_or_default()),
+            restart: format_restart(host_config.and_then(|i| i.restart_policy.as_ref())),
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use bollard::service::{
+        ContainerInspectResponse, HostConfig, RestartPolicy, RestartPolicyNameEnum,
+    };
+
+    use super::{CurrentLimits, LimitsConfig};
+
+    #[test]
+    /// Limits converted into update options, empty limits left unchanged, and invalid limits are an error
+    fn test_limits_to_options() {
+        let options = LimitsConfig {
+            cpu_shares: "512".to_owned(),
+            cpus: "1.5".to_owned(),
+            memory: "256m".to_owned(),
+            restart: "on-failure:3".to_owned(),
+            ..Default::default()
+        }
+        .to_options()
+        .unwrap();
+        assert_eq!(options.cpu_shares, Some(512));
+        assert_eq!(options.cpu_period, Some(100_000));
+        assert_eq!(options.cpu_quota, Some(150_000));
+        assert_eq!(options.memory, Some(256 * 1024 * 1024));
+        assert_eq!(options.memory_swap, Some(512 * 1024 * 1024));
+        assert_eq!(options.nano_cpus, None);
+        assert_eq!(
+            options.restart_policy,
+            Some(RestartPolicy {
+                name: Some(RestartPolicyNameEnum::ON_FAILURE),
+                maximum_retry_count: Some(3),
+            })
+        );
+
+        let options = LimitsConfig::default().to_options().unwrap();
+        assert_eq!(options.cpu_shares, None);
+        assert_eq!(options.cpu_quota, None);
+        assert_eq!(options.memory, None);
+        assert_eq!(options.memory_swap, None);
+        assert_eq!(
+            options.restart_policy.unwrap().name,
+            Some(RestartPolicyNameEnum::NO)
+        );
+
+        for config in [
+            LimitsConfig {
+                cpu_shares: "0".to_owned(),
+                ..Default::default()
+            },
+            LimitsConfig {
+                cpus: "x".to_owned(),
+                ..Default::default()
+            },
+            LimitsConfig {
+                memory: "1t".to_owned(),
+                ..Default::default()
+            },
+            LimitsConfig {
+                restart: "sometimes".to_owned(),
+                ..Default::default()
+            },
+        ] {
+            assert!(config.to_options().is_err(), "{config:?}");
+        }
+    }
+
+    #[test]
+    /// Only changed limits are sent, the cpu limit in the same form as the current limit, and the swap limit kept unless it's below the new memory limit
+    fn test_limits_to_options_current() {
+        let mb = 1024 * 1024;
+        let gen_config = |cpus: &str, memory: &str, current| LimitsConfig {
+            cpus: cpus.to_owned(),
+            current,
+            memory: memory.to_owned(),
+            ..Default::default()
+        };
+
+        let current = CurrentLimits {
+            memory: 256 * mb,
+            memory_swap: 1024 * mb,
+            nano_cpus: 1_000_000_000,
+            uses_nano_cpus: true,
+        };
+
+        // Unchanged
+        let options = gen_config("1", "256m", current).to_options().unwrap();
+        assert_eq!(options.memory, None);
+        assert_eq!(options.memory_swap, None);
+        assert_eq!(options.nano_cpus, None);
+        assert_eq!(options.cpu_quota, None);
+        assert_eq!(options.cpu_period, None);
+
+        // Changed, nano cpus kept as nano cpus, and the swap kept as it's above the new memory limit
+        let options = gen_config("2", "512m", current).to_options().unwrap();
+        assert_eq!(options.memory, Some(512 * mb));
+        assert_eq!(options.memory_swap, None);
+        assert_eq!(options.nano_cpus, Some(2_000_000_000));
+        assert_eq!(options.cpu_quota, None);
+        assert_eq!(options.cpu_period, None);
+
+        // Swap would be below the new memory limit
+        let options = gen_config("", "2g", current).to_options().unwrap();
+        assert_eq!(options.memory, Some(2048 * mb));
+        assert_eq!(options.memory_swap, Some(4096 * mb));
+
+        // Unlimited swap is kept unlimited
+        let current = CurrentLimits {
+            memory_swap: -1,
+            ..current
+        };
+        let options = gen_config("", "2g", current).to_options().unwrap();
+        assert_eq!(options.memory_swap, Some(-1));
+
+        // A quota is kept as a quota
+        let current = CurrentLimits {
+            uses_nano_cpus: false,
+            ..current
+        };
+        let options = gen_config("0.5", "", current).to_options().unwrap();
+        assert_eq!(options.nano_cpus, None);
+        assert_eq!(options.cpu_quota, Some(50_000));
+        assert_eq!(options.cpu_period, Some(100_000));
+    }
+
+    #[test]
+    /// Current limits read from an inspected container
+    fn test_limits_from_inspect() {
+        let inspect = ContainerInspectResponse {
+            host_config: Some(HostConfig {
+                cpu_shares: Some(1024),
+                cpu_period: Some(100_000),
+                cpu_quota: Some(25_000),
+                memory: Some(2 * 1024 * 1024 * 1024),
+                memory_swap: Some(4 * 1024 * 1024 * 1024),
+                restart_policy: Some(RestartPolicy {
+                    name: Some(RestartPolicyNameEnum::UNLESS_STOPPED),
+                    maximum_retry_count: Some(0),
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        assert_eq!(
+            LimitsConfig::from(&inspect),
+            LimitsConfig {
+                cpu_shares: "1024".to_owned(),
+                cpus: "0.25".to_owned(),
+                current: CurrentLimits {
+                    memory: 2 * 1024 * 1024 * 1024,
+                    memory_swap: 4 * 1024 * 1024 * 1024,
+                    nano_cpus: 250_000_000,
+                    uses_nano_cpus: false,
+                },
+                memory: "2g".to_owned(),
+                restart: "unless-stopped".to_owned(),
+            }
+        );
+        assert_eq!(
+            LimitsConfig::from(&ContainerInspectResponse::default()),
+            LimitsConfig::default()
+        );
+    }
+}
@@ -0,0 +1,429 @@
+use bollard::container::{LogOutput, LogsOptions};
+use cansi::v3::categorise_text;
+use serde::Serialize;
+
+/// The file formats logs can be saved as
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogFormat {
+    Csv,
+    JsonLines,
+    Plain,
+}
+
+impl LogFormat {
+    fn parse(input: &str) -> Result<Self, String> {
+        match input.to_lowercase().as_str() {
+            "" | "plain" | "log" | "txt" => Ok(Self::Plain),
+            "jsonl" | "json" | "ndjson" => Ok(Self::JsonLines),
+            "csv" => Ok(Self::Csv),
+            _ => Err(format!("invalid format: {input}")),
+        }
+    }
+
+    pub const fn extension(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::JsonLines => "jsonl",
+            Self::Plain => "log",
+        }
+    }
+}
+
+/// Check that the name of a file, to be saved into the save directory, isn't empty, and isn't a path, which could be outside of the save directory
+pub fn validate_file_name(file: &str) -> Result<(), String> {
+    if file.is_empty() {
+        Err("a file name is required".to_owned())
+    } else if file.contains(['/', '\\']) || file == "." || file == ".." {
+        Err(format!("invalid file name: {file}, a path isn't allowed"))
+    } else {
+        Ok(())
+    }
+}
+
+/// Quote a csv field, if it contains a comma, quote, or line break, doubling any quotes inside it
+pub fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_owned()
+    }
+}
+
+/// Parse a duration, with an s, m, h, or d suffix, into seconds, an empty duration is None
+/// Seconds are assumed when there's no suffix
+fn parse_ago(label: &str, input: &str) -> Result<Option<u64>, String> {
+    if input.is_empty() {
+        return Ok(None);
+    }
+    let (number, multiplier) = match input.chars().last() {
+        Some('s') => (&input[..input.len() - 1], 1),
+        Some('m') => (&input[..input.len() - 1], 60),
+        Some('h') => (&input[..input.len() - 1], 3600),
+        Some('d') => (&input[..input.len() - 1], 86400),
+        _ => (input, 1),
+    };
+    number
+        .trim()
+        .parse::<u64>()
+        .map(|i| Some(i.saturating_mul(multiplier)))
+        .map_err(|_| format!("invalid {label}: {input}"))
+}
+
+/// A single log line, with the timestamp and the stream it was written to
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LogLine {
+    pub timestamp: String,
+    pub stream: &'static str,
+    pub message: String,
+}
+
+impl LogLine {
+    /// Docker log output, requested with timestamps, in the format `2023-01-14T19:13:30.783138328Z Lorem ipsum dolor sit amet`, with any ANSI formatting removed
+    /// Empty lines are ignored
+    pub fn from_output(output: &LogOutput) -> Option<Self> {
+        let (stream, message) = match output {
+            LogOutput::StdErr { message } => ("stderr", message),
+            LogOutput::StdOut { message } => ("stdout", message),
+            LogOutput::StdIn { message } => ("stdin", message),
+            LogOutput::Console { message } => ("console", message),
+        };
+        let text = categorise_text(&String::from_utf8_lossy(message))
+            .into_iter()
+            .map(|i| i.text)
+            .collect::<String>();
+        let text = text.trim_end_matches(['\r', '\n']);
+        let (timestamp, message) = text.split_once(' ').unwrap_or((text, ""));
+        if message.trim().is_empty() {
+            None
+        } else {
+            Some(Self {
+                timestamp: timestamp.to_owned(),
+                stream,
+                message: message.to_owned(),
+            })
+        }
+    }
+}
+
+/// Settings of a log export, as entered into the log export form
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LogExportConfig {
+    pub file: String,
+    pub filter: String,
+    pub format: String,
+    pub gzip: String,
+    pub since: String,
+    pub until: String,
+}
+
+impl LogExportConfig {
+    /// Convert into a log export, with the time range relative to now, or return a description of the first invalid setting
+    pub fn to_export(&self, now: u64) -> Result<LogExport, String> {
+        validate_file_name(&self.file)?;
+        let format = LogFormat::parse(&self.format)?;
+        let gzip = match self.gzip.to_lowercase().as_str() {
+            "" | "no" | "n" | "false" => false,
+            "yes" | "y" | "true" => true,
+            _ => return Err(format!("invalid gzip: {}, yes or no", self.gzip)),
+        };
+        let since = parse_ago("since", &self.since)?;
+        let until = parse_ago("until", &self.until)?;
+        if let (Some(since), Some(until)) = (since, until) {
+            if until >= since {
+                return Err("until needs to be more recent than since".to_owned());
+            }
+        }
+        let ago = |i: Option<u64>| {
+            i.map_or(0, |i| {
+                i64::try_from(now.saturating_sub(i))
+                    .unwrap_or_default()
+                    .max(1)
+            })
+        };
+        Ok(LogExport {
+            file: self.file.clone(),
+            filter: self.filter.clone(),
+            format,
+            gzip,
+            since: ago(since),
+            until: ago(until),
+        })
+    }
+}
+
+/// A validated log export, since and until are unix timestamps, with 0 being unset
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogExport {
+    pub file: String,
+    pub filter: String,
+    pub format: LogFormat,
+    pub gzip: bool,
+    pub since: i64,
+    pub until: i64,
+}
+
+impl LogExport {
+    /// The file name, with the extension of the format, and of gzip, added unless already present
+    pub fn file_name(&self) -> String {
+        let gzip = if self.gzip { ".gz" } else { "" };
+        let mut name = self
+            .file
+            .strip_suffix(gzip)
+            .unwrap_or(&self.file)
+            .to_owned();
+        let extension = format!(".{}", self.format.extension());
+        if !name.ends_with(&extension) {
+            name.push_str(&extension);
+        }
+        name.push_str(gzip);
+        name
+    }
+
+    /// Docker logs options, of both stdout and stderr, with timestamps, limited to the time range
+    pub fn options(&self) -> LogsOptions<String> {
+        LogsOptions {
+            stdout: true,
+            stderr: true,
+            timestamps: true,
+            since: self.since,
+            until: self.until,
+            ..Default::default()
+        }
+    }
+
+    /// Check if a line matches the filter, an empty filter matches every line
+    pub fn matches(&self, line: &LogLine) -> bool {
+        self.filter.is_empty() || line.message.contains(&self.filter)
+    }
+
+    /// The first line of the file, if the format has one
+    pub const fn header(&self) -> Option<&'static str> {
+        match self.format {
+            LogFormat::Csv => Some("timestamp,stream,message\n"),
+            _ => None,
+        }
+    }
+
+    /// Format a line for the file, plain lines only have a timestamp if timestamps are shown
+    pub fn format_line(&self, line: &LogLine, timestamp: bool) -> String {
+        match self.format {
+            LogFormat::Csv => format!(
+                "{},{},{}\n",
+                csv_field(&line.timestamp),
+                line.stream,
+                csv_field(&line.message)
+            ),
+            LogFormat::JsonLines => {
+                format!("{}\n", serde_json::to_string(line).unwrap_or_default())
+            }
+            LogFormat::Plain => {
+                if timestamp {
+                    format!("{} {}\n", line.timestamp, line.message)
+                } else {
+                    format!("{}\n", line.message)
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use bollard::container::LogOutput;
+
+    use super::{csv_field, LogExport, LogExportConfig, LogFormat, LogLine};
+
+    fn gen_config() -> LogExportConfig {
+        LogExportConfig {
+            file: "redis_1".to_owned(),
+            format: "plain".to_owned(),
+            gzip: "no".to_owned(),
+            ..Default::default()
+        }
+    }
+
+    fn gen_export(format: &str) -> LogExport {
+        LogExportConfig {
+            format: format.to_owned(),
+            ..gen_config()
+        }
+        .to_export(0)
+        .unwrap()
+    }
+
+    fn gen_line(message: &str) -> LogLine {
+        LogLine {
+            timestamp: "2023-01-14T19:13:30.783138328Z".to_owned(),
+            stream: "stderr",
+            message: message.to_owned(),
+        }
+    }
+
+    #[test]
+    /// Log output is split into timestamp and message, with ANSI formatting and line breaks removed, and empty lines ignored
+    fn test_log_export_line_from_output() {
+        let output = LogOutput::StdErr {
+            message: b"2023-01-14T19:13:30.783138328Z \x1b[31mready\x1b[0m to go\n"
+                .to_vec()
+                .into(),
+        };
+        assert_eq!(LogLine::from_output(&output), Some(gen_line("ready to go")));
+
+        let output = LogOutput::StdOut {
+            message: b"2023-01-14T19:13:30.783138328Z \n".to_vec().into(),
+        };
+        assert_eq!(LogLine::from_output(&output), None);
+    }
+
+    #[test]
+    /// The time range is converted to unix timestamps, and every setting is validated
+    fn test_log_export_config() {
+        let now = 1_700_000_000;
+        let result = gen_config().to_export(now).unwrap();
+        assert_eq!(
+            result,
+            LogExport {
+                file: "redis_1".to_owned(),
+                filter: String::new(),
+                format: LogFormat::Plain,
+                gzip: false,
+                since: 0,
+                until: 0,
+            }
+        );
+
+        let config = LogExportConfig {
+            format: "JSONL".to_owned(),
+            gzip: "yes".to_owned(),
+            since: "2h".to_owned(),
+            until: "15m".to_owned(),
+            filter: "error".to_owned(),
+            ..gen_config()
+        };
+        let result = config.to_export(now).unwrap();
+        assert_eq!(result.format, LogFormat::JsonLines);
+        assert!(result.gzip);
+        assert_eq!(result.since, 1_700_000_000 - 7200);
+        assert_eq!(result.until, 1_700_000_000 - 900);
+        assert_eq!(result.filter, "error");
+        let options = result.options();
+        assert!(options.stdout && options.stderr && options.timestamps);
+        assert_eq!(options.since, result.since);
+
+        for (config, err) in [
+            (
+                LogExportConfig {
+                    file: String::new(),
+                    ..gen_config()
+                },
+                "a file name is required",
+            ),
+            (
+                LogExportConfig {
+                    file: "../redis_1".to_owned(),
+                    ..gen_config()
+                },
+                "invalid file name: ../redis_1, a path isn't allowed",
+            ),
+            (
+                LogExportConfig {
+                    file: "/tmp/redis_1".to_owned(),
+                    ..gen_config()
+                },
+                "invalid file name: /tmp/redis_1, a path isn't allowed",
+            ),
+            (
+                LogExportConfig {
+                    file: "..".to_owned(),
+                    ..gen_config()
+                },
+                "invalid file name: .., a path isn't allowed",
+            ),
+            (
+                LogExportConfig {
+                    format: "xml".to_owned(),
+                    ..gen_config()
+                },
+                "invalid format: xml",
+            ),
+            (
+                LogExportConfig {
+                    gzip: "maybe".to_owned(),
+                    ..gen_config()
+                },
+                "invalid gzip: maybe, yes or no",
+            ),
+            (
+                LogExportConfig {
+                    since: "1w".to_owned(),
+                    ..gen_config()
+                },
+                "invalid since: 1w",
+            ),
+            (
+                LogExportConfig {
+                    since: "5m".to_owned(),
+                    until: "1h".to_owned(),
+                    ..gen_config()
+                },
+                "until needs to be more recent than since",
+            ),
+        ] {
+            assert_eq!(config.to_export(now).unwrap_err(), err);
+        }
+    }
+
+    #[test]
+    /// Extensions are added to the file name, unless already present
+    fn test_log_export_file_name() {
+        let export = |file: &str, format: &str, gzip: &str| {
+            LogExportConfig {
+                file: file.to_owned(),
+                format: format.to_owned(),
+                gzip: gzip.to_owned(),
+                ..Default::default()
+            }
+            .to_export(0)
+            .unwrap()
+            .file_name()
+        };
+        assert_eq!(export("redis_1", "plain", "no"), "redis_1.log");
+        assert_eq!(export("redis_1", "csv", "yes"), "redis_1.csv.gz");
+        assert_eq!(export("redis_1.jsonl", "jsonl", "yes"), "redis_1.jsonl.gz");
+        assert_eq!(export("redis_1.csv.gz", "csv", "yes"), "redis_1.csv.gz");
+        assert_eq!(export("redis_1.txt", "plain", "no"), "redis_1.txt.log");
+    }
+
+    #[test]
+    /// Each format writes the timestamp, stream, and message, and only plain lines can skip the timestamp
+    fn test_log_export_format_line() {
+        let line = gen_line("said \"hello, world\"");
+        let csv = gen_export("csv");
+        assert_eq!(csv.header(), Some("timestamp,stream,message\n"));
+        assert_eq!(
+            csv.format_line(&line, false),
+            "2023-01-14T19:13:30.783138328Z,stderr,\"said \"\"hello, world\"\"\"\n"
+        );
+        let json = gen_export("jsonl");
+        assert_eq!(json.header(), None);
+        assert_eq!(
+            json.format_line(&line, false),
+            "{\"timestamp\":\"2023-01-14T19:13:30.783138328Z\",\"stream\":\"stderr\",\"message\":\"said \\\"hello, world\\\"\"}\n"
+        );
+        let plain = gen_export("plain");
+        assert_eq!(
+            plain.format_line(&line, true),
+            "2023-01-14T19:13:30.783138328Z said \"hello, world\"\n"
+        );
+        assert_eq!(plain.format_line(&line, false), "said \"hello, world\"\n");
+        assert_eq!(csv_field("plain"), "plain");
+
+        let mut filtered = gen_export("plain");
+        assert!(filtered.matches(&line));
+        filtered.filter = "hello".to_owned();
+        assert!(filtered.matches(&line));
+        filtered.filter = "goodbye".to_owned();
+        assert!(!filtered.matches(&line));
+    }
+}
@@ -1,32 +1,97 @@
-use bollard::models::ContainerSummary;
+use bollard::models::{ContainerSummary, ImageSummary, Network, Volume};
 use core::fmt;
 use parking_lot::Mutex;
 use ratatui::widgets::{ListItem, ListState};
+use serde::{Deserialize, Serialize};
 use std::{
+    net::IpAddr,
     sync::Arc,
     time::{SystemTime, UNIX_EPOCH},
 };
 
+mod changes;
+mod column_picker;
 mod container_state;
+mod files;
+mod images;
+mod inspect;
+mod limits;
+mod log_export;
+mod networks;
+mod overview;
+mod processes;
+mod run;
+mod stats_export;
+mod tree;
+mod volumes;
 
 use crate::{
     app_error::AppError,
+    config::SortConfig,
     parse_args::CliArgs,
+    session::{Session, SessionItem},
     ui::{log_sanitizer, GuiState, Status},
     ENTRY_POINT,
 };
+pub use changes::*;
+pub use column_picker::*;
 pub use container_state::*;
+pub use files::*;
+pub use images::*;
+pub use inspect::*;
+pub use limits::*;
+pub use log_export::*;
+pub use networks::*;
+pub use overview::*;
+pub use processes::*;
+pub use run::*;
+pub use stats_export::*;
+pub use tree::*;
+pub use volumes::*;
+
+/// Get the name of a container from a ContainerSummary, without the leading `/`
+fn summary_name(summary: &ContainerSummary) -> String {
+    summary
+        .names
+        .as_ref()
+        .and_then(|names| names.first())
+        .map_or_else(String::new, |name| name.trim_start_matches('/').to_owned())
+}
+
+/// Docker commands that can be executed on images, volumes, and networks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResourceCommand {
+    Create,
+    Prune,
+    Pull,
+    Remove,
+}
+
+impl fmt::Display for ResourceCommand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let disp = match self {
+            Self::Create => "create",
+            Self::Prune => "prune",
+            Self::Pull => "pull",
+            Self::Remove => "remove",
+        };
+        write!(f, "{disp}")
+    }
+}
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum SortedOrder {
     Asc,
     Desc,
 }
 
-#[derive(Debug, Clone, Copy, Eq, Hash, PartialEq)]
+#[derive(Debug, Clone, Copy, Eq, Hash, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Header {
     State,
     Status,
+    Health,
     Cpu,
     Memory,
     Id,
@@ -34,6 +99,89 @@ pub enum Header {
     Image,
     Rx,
     Tx,
+    RxRate,
+    TxRate,
+    Created,
+    Uptime,
+    Ports,
+    Command,
+    Service,
+    Label,
+    Restarts,
+    Ip,
+}
+
+impl Header {
+    /// Every header, in the default order of the columns
+    pub const ALL: [Self; 20] = [
+        Self::Name,
+        Self::State,
+        Self::Status,
+        Self::Health,
+        Self::Cpu,
+        Self::Memory,
+        Self::Id,
+        Self::Image,
+        Self::Rx,
+        Self::Tx,
+        Self::RxRate,
+        Self::TxRate,
+        Self::Created,
+        Self::Uptime,
+        Self::Ports,
+        Self::Command,
+        Self::Service,
+        Self::Label,
+        Self::Restarts,
+        Self::Ip,
+    ];
+
+    /// The columns shown when none are set in the config file
+    pub const DEFAULT_COLUMNS: [Self; 12] = [
+        Self::Name,
+        Self::State,
+        Self::Status,
+        Self::Health,
+        Self::Cpu,
+        Self::Memory,
+        Self::Id,
+        Self::Image,
+        Self::Rx,
+        Self::Tx,
+        Self::RxRate,
+        Self::TxRate,
+    ];
+
+    /// Name used in the config file
+    pub const fn config_name(self) -> &'static str {
+        match self {
+            Self::State => "state",
+            Self::Status => "status",
+            Self::Health => "health",
+            Self::Cpu => "cpu",
+            Self::Memory => "memory",
+            Self::Id => "id",
+            Self::Name => "name",
+            Self::Image => "image",
+            Self::Rx => "rx",
+            Self::Tx => "tx",
+            Self::RxRate => "rx_rate",
+            Self::TxRate => "tx_rate",
+            Self::Created => "created",
+            Self::Uptime => "uptime",
+            Self::Ports => "ports",
+            Self::Command => "command",
+            Self::Service => "service",
+            Self::Label => "label",
+            Self::Restarts => "restarts",
+            Self::Ip => "ip",
+        }
+    }
+
+    /// The restart count, and start time, of a container are only available by inspecting it
+    pub const fn needs_inspect(self) -> bool {
+        matches!(self, Self::Restarts | Self::Uptime)
+    }
 }
 
 /// Convert Header enum into strings to display
@@ -42,6 +190,7 @@ impl fmt::Display for Header {
         let disp = match self {
             Self::State => "state",
             Self::Status => "status",
+            Self::Health => "health",
             Self::Cpu => "cpu",
             Self::Memory => "memory/limit",
             Self::Id => "id",
@@ -49,6 +198,16 @@ impl fmt::Display for Header {
             Self::Image => "image",
             Self::Rx => "↓ rx",
             Self::Tx => "↑ tx",
+            Self::RxRate => "↓ rx/s",
+            Self::TxRate => "↑ tx/s",
+            Self::Created => "created",
+            Self::Uptime => "uptime",
+            Self::Ports => "ports",
+            Self::Command => "command",
+            Self::Service => "service",
+            Self::Label => "label",
+            Self::Restarts => "restarts",
+            Self::Ip => "ip",
         };
         write!(f, "{disp:>x$}", x = f.width().unwrap_or(1))
     }
@@ -58,18 +217,40 @@ impl fmt::Display for Header {
 #[derive(Debug, Clone)]
 #[cfg(not(test))]
 pub struct AppData {
+    changes: Option<ChangesData>,
+    column_picker: Option<ColumnPicker>,
     containers: StatefulList<ContainerItem>,
     error: Option<AppError>,
+    files: Option<FileBrowser>,
+    host: Option<HostInfo>,
+    images: StatefulList<ImageItem>,
+    inspect: Option<InspectData>,
+    network_filter: Option<NetworkItem>,
+    networks: StatefulList<NetworkItem>,
+    processes: Option<ProcessesData>,
+    session: Session,
     sorted_by: Option<(Header, SortedOrder)>,
+    volumes: StatefulList<VolumeItem>,
     pub args: CliArgs,
 }
 
 #[derive(Debug, Clone)]
 #[cfg(test)]
 pub struct AppData {
+    pub changes: Option<ChangesData>,
+    pub column_picker: Option<ColumnPicker>,
     pub containers: StatefulList<ContainerItem>,
     pub error: Option<AppError>,
+    pub files: Option<FileBrowser>,
+    pub host: Option<HostInfo>,
+    pub images: StatefulList<ImageItem>,
+    pub inspect: Option<InspectData>,
+    pub network_filter: Option<NetworkItem>,
+    pub networks: StatefulList<NetworkItem>,
+    pub processes: Option<ProcessesData>,
+    pub session: Session,
     pub sorted_by: Option<(Header, SortedOrder)>,
+    pub volumes: StatefulList<VolumeItem>,
     pub args: CliArgs,
 }
 
@@ -77,10 +258,21 @@ impl AppData {
     /// Generate a default app_state
     pub fn default(args: CliArgs) -> Self {
         Self {
+            sorted_by: args.sort,
             args,
+            changes: None,
+            column_picker: None,
             containers: StatefulList::new(vec![]),
             error: None,
-            sorted_by: None,
+            files: None,
+            host: None,
+            images: StatefulList::new(vec![]),
+            inspect: None,
+            network_filter: None,
+            networks: StatefulList::new(vec![]),
+            processes: None,
+            session: Session::default(),
+            volumes: StatefulList::new(vec![]),
         }
     }
 
@@ -132,8 +324,10 @@ impl AppData {
 
     /// Sort the containers vec, based on a heading (and if clash, then by name), either ascending or descending,
     /// If not sort set, then sort by created time
+    #[allow(clippy::too_many_lines)]
     pub fn sort_containers(&mut self) {
         if let Some((head, ord)) = self.sorted_by {
+            let label = self.args.column_label.clone();
             let sort_closure = |a: &ContainerItem, b: &ContainerItem| -> std::cmp::Ordering {
                 let item_ord = match ord {
                     SortedOrder::Asc => (a, b),
@@ -151,6 +345,12 @@ impl AppData {
                         .status
                         .cmp(&item_ord.1.status)
                         .then_with(|| item_ord.0.name.get().cmp(item_ord.1.name.get())),
+                    Header::Health => item_ord
+                        .0
+                        .health
+                        .order()
+                        .cmp(&item_ord.1.health.order())
+                        .then_with(|| item_ord.0.name.get().cmp(item_ord.1.name.get())),
                     Header::Cpu => item_ord
                         .0
                         .cpu_stats
@@ -185,6 +385,18 @@ impl AppData {
                         .tx
                         .cmp(&item_ord.1.tx)
                         .then_with(|| item_ord.0.name.get().cmp(item_ord.1.name.get())),
+                    Header::RxRate => item_ord
+                        .0
+                        .rx_stats
+                        .back()
+                        .cmp(&item_ord.1.rx_stats.back())
+                        .then_with(|| item_ord.0.name.get().cmp(item_ord.1.name.get())),
+                    Header::TxRate => item_ord
+                        .0
+                        .tx_stats
+                        .back()
+                        .cmp(&item_ord.1.tx_stats.back())
+                        .then_with(|| item_ord.0.name.get().cmp(item_ord.1.name.get())),
 
                     Header::Name => item_ord
                         .0
@@ -192,6 +404,49 @@ impl AppData {
                         .get()
                         .cmp(item_ord.1.name.get())
                         .then_with(|| item_ord.0.id.cmp(&item_ord.1.id)),
+                    Header::Created => item_ord
+                        .0
+                        .created
+                        .cmp(&item_ord.1.created)
+                        .then_with(|| item_ord.0.name.get().cmp(item_ord.1.name.get())),
+                    // Started most recently has the shortest uptime, and a container that isn't up has no uptime at all
+                    Header::Uptime => std::cmp::Reverse(item_ord.0.up_since().unwrap_or(u64::MAX))
+                        .cmp(&std::cmp::Reverse(
+                            item_ord.1.up_since().unwrap_or(u64::MAX),
+                        ))
+                        .then_with(|| item_ord.0.name.get().cmp(item_ord.1.name.get())),
+                    Header::Ports => item_ord
+                        .0
+                        .ports_text()
+                        .cmp(&item_ord.1.ports_text())
+                        .then_with(|| item_ord.0.name.get().cmp(item_ord.1.name.get())),
+                    Header::Command => item_ord
+                        .0
+                        .command
+                        .cmp(&item_ord.1.command)
+                        .then_with(|| item_ord.0.name.get().cmp(item_ord.1.name.get())),
+                    Header::Service => item_ord
+                        .0
+                        .label(SERVICE_LABEL)
+                        .cmp(&item_ord.1.label(SERVICE_LABEL))
+                        .then_with(|| item_ord.0.name.get().cmp(item_ord.1.name.get())),
+                    Header::Label => label
+                        .as_deref()
+                        .and_then(|key| item_ord.0.label(key))
+                        .cmp(&label.as_deref().and_then(|key| item_ord.1.label(key)))
+                        .then_with(|| item_ord.0.name.get().cmp(item_ord.1.name.get())),
+                    Header::Restarts => item_ord
+                        .0
+                        .restart_count
+                        .cmp(&item_ord.1.restart_count)
+                        .then_with(|| item_ord.0.name.get().cmp(item_ord.1.name.get())),
+                    Header::Ip => item_ord
+                        .0
+                        .ip
+                        .parse::<IpAddr>()
+                        .ok()
+                        .cmp(&item_ord.1.ip.parse::<IpAddr>().ok())
+                        .then_with(|| item_ord.0.name.get().cmp(item_ord.1.name.get())),
                 }
             };
             self.containers.items.sort_by(sort_closure);
@@ -206,46 +461,129 @@ impl AppData {
 
     /// Container state methods
 
-    /// Just get the total number of containers
+    /// Check if a container is shown, when filtered by network only containers attached to that network are shown
+    fn is_container_visible(&self, container: &ContainerItem) -> bool {
+        self.network_filter
+            .as_ref()
+            .is_none_or(|network| container.networks.contains(&network.id))
+    }
+
+    /// The index, in the containers Vec, of each container that is shown
+    fn visible_container_indexes(&self) -> Vec<usize> {
+        self.containers
+            .items
+            .iter()
+            .enumerate()
+            .filter(|(_, i)| self.is_container_visible(i))
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// The position of the selected container in the shown containers, None if no container is selected, or the selected container is hidden
+    fn visible_container_position(&self) -> Option<usize> {
+        let selected = self.containers.state.selected()?;
+        self.visible_container_indexes()
+            .iter()
+            .position(|i| *i == selected)
+    }
+
+    /// Select a shown container, the closure is given the current position in the shown containers, and the number of shown containers, and returns the new position
+    fn select_container_by_position(
+        &mut self,
+        position: impl FnOnce(Option<usize>, usize) -> usize,
+    ) {
+        let visible = self.visible_container_indexes();
+        if let Some(last) = visible.len().checked_sub(1) {
+            let current = self
+                .containers
+                .state
+                .selected()
+                .and_then(|selected| visible.iter().position(|i| *i == selected));
+            let index = position(current, visible.len()).min(last);
+            self.containers.state.select(visible.get(index).copied());
+        }
+    }
+
+    /// If the selected container is hidden by the network filter, select the first shown container, or none if no containers are shown
+    fn select_visible_container(&mut self) {
+        if self.visible_container_position().is_none() {
+            let first = self.visible_container_indexes().first().copied();
+            self.containers.state.select(first);
+        }
+    }
+
+    /// Get the number of containers shown, which excludes any containers hidden by the network filter
     pub fn get_container_len(&self) -> usize {
-        self.containers.items.len()
+        self.visible_container_indexes().len()
     }
 
-    /// Get all the ContainerItems
+    /// Get all the ContainerItems, including any hidden by the network filter
     pub fn get_container_items(&self) -> &[ContainerItem] {
         &self.containers.items
     }
 
+    /// Get the ContainerItems that are shown, which excludes any containers hidden by the network filter
+    pub fn get_visible_container_items(&self) -> Vec<&ContainerItem> {
+        self.containers
+            .items
+            .iter()
+            .filter(|i| self.is_container_visible(i))
+            .collect()
+    }
+
     /// Get title for containers section
     pub fn container_title(&self) -> String {
-        self.containers.get_state_title()
+        let len = self.get_container_len();
+        let title = if len == 0 {
+            String::new()
+        } else {
+            let position = self.visible_container_position().map_or(0, |i| i + 1);
+            format!(" {position}/{len}")
+        };
+        match self.network_filter.as_ref() {
+            Some(network) => format!("{title} - network: {}", network.name),
+            None => title,
+        }
     }
 
     /// Select the first container
     pub fn containers_start(&mut self) {
-        self.containers.start();
+        self.select_container_by_position(|_, _| 0);
     }
 
     /// select the last container
     pub fn containers_end(&mut self) {
-        self.containers.end();
+        self.select_container_by_position(|_, len| len);
     }
 
     /// Select the next container
     pub fn containers_next(&mut self) {
-        self.containers.next();
+        self.select_container_by_position(|current, _| current.map_or(0, |i| i + 1));
     }
 
     /// select the previous container
     pub fn containers_previous(&mut self) {
-        self.containers.previous();
+        self.select_container_by_position(|current, _| current.map_or(0, |i| i.saturating_sub(1)));
     }
 
     /// Get ListState of containers
+    #[cfg(test)]
     pub fn get_container_state(&mut self) -> &mut ListState {
         &mut self.containers.state
     }
 
+    /// Get a ListState of the shown containers, with the selected container as a position in the shown containers, to be used when drawing
+    pub fn get_visible_container_state(&self) -> ListState {
+        ListState::default()
+            .with_offset(self.containers.state.offset())
+            .with_selected(self.visible_container_position())
+    }
+
+    /// Keep the scroll offset of the drawn containers list
+    pub fn set_container_offset(&mut self, offset: usize) {
+        *self.containers.state.offset_mut() = offset;
+    }
+
     /// Get Option of the current selected container
     pub fn get_selected_container(&self) -> Option<&ContainerItem> {
         self.containers
@@ -254,6 +592,22 @@ impl AppData {
             .and_then(|i| self.containers.items.get(i))
     }
 
+    /// Get the stats history of either the selected container, or all containers
+    pub fn get_container_stats(&self, all: bool) -> Vec<ContainerStats> {
+        if all {
+            self.containers
+                .items
+                .iter()
+                .map(ContainerStats::from)
+                .collect()
+        } else {
+            self.get_selected_container()
+                .map(ContainerStats::from)
+                .into_iter()
+                .collect()
+        }
+    }
+
     /// Find the longest port when it's transformed into a string, defaults are header lens (ip, private, public)
     pub fn get_longest_port(&self) -> (usize, usize, usize) {
         let mut longest_ip = 5;
@@ -457,12 +811,440 @@ impl AppData {
     /// Chart data related methods
 
     /// Get mutable Option of the currently selected container chart data
-    pub fn get_chart_data(&mut self) -> Option<(CpuTuple, MemTuple)> {
-        self.containers
+    /// Datasets are downsampled to at most `points` points
+    pub fn get_chart_data(&self, points: u16) -> Option<(Option<TimeAxis>, CpuTuple, MemTuple)> {
+        self.get_selected_container().map(|i| {
+            let axis = i.time_axis();
+            let (cpu, mem) =
+                i.get_chart_data(axis.unwrap_or_default(), points.into(), self.chart_gap());
+            (axis, cpu, mem)
+        })
+    }
+
+    /// Get the network and block io chart data of the currently selected container
+    pub fn get_io_chart_data(&self, points: u16) -> Option<(IoTuple, IoTuple)> {
+        self.get_selected_container().map(|i| {
+            i.get_io_chart_data(
+                i.time_axis().unwrap_or_default(),
+                points.into(),
+                self.chart_gap(),
+            )
+        })
+    }
+
+    /// Samples further apart than this, in ms, are drawn as a gap in the charts, rather than joined by a line
+    /// Allows for a missed update, and for samples merged due to a long stats history
+    fn chart_gap(&self) -> u64 {
+        (u64::from(self.args.docker_interval) * 3)
+            .max(StatsHistory::<CpuStats>::resolution(self.args.stats_history) * 2)
+    }
+
+    // File browser related methods
+
+    /// Set the file browser, will replace any existing directory listing
+    pub fn set_files(&mut self, files: FileBrowser) {
+        self.files = Some(files);
+    }
+
+    /// Remove the file browser
+    pub fn clear_files(&mut self) {
+        self.files = None;
+    }
+
+    /// Get mutable Option of the file browser, mutable so that the ListState can be used for rendering
+    pub const fn get_files(&mut self) -> Option<&mut FileBrowser> {
+        self.files.as_mut()
+    }
+
+    // Inspect related methods
+
+    /// Set the inspect data, will replace any existing inspect data
+    pub fn set_inspect(&mut self, inspect: InspectData) {
+        self.inspect = Some(inspect);
+    }
+
+    /// Remove the inspect data
+    pub fn clear_inspect(&mut self) {
+        self.inspect = None;
+    }
+
+    /// Get mutable Option of the inspect data, mutable so that the tree ListState can be used for rendering
+    pub const fn get_inspect(&mut self) -> Option<&mut InspectData> {
+        self.inspect.as_mut()
+    }
+
+    // Changes related methods
+
+    /// Set the filesystem changes, will replace any existing changes
+    pub fn set_changes(&mut self, changes: ChangesData) {
+        self.changes = Some(changes);
+    }
+
+    /// Remove the filesystem changes
+    pub fn clear_changes(&mut self) {
+        self.changes = None;
+    }
+
+    /// Get mutable Option of the filesystem changes, mutable so that the tree ListState can be used for rendering
+    pub const fn get_changes(&mut self) -> Option<&mut ChangesData> {
+        self.changes.as_mut()
+    }
+
+    // Host overview related methods
+
+    /// Set the host information, will replace any existing information
+    pub fn set_host(&mut self, host: HostInfo) {
+        self.host = Some(host);
+    }
+
+    /// Remove the host information
+    pub fn clear_host(&mut self) {
+        self.host = None;
+    }
+
+    pub const fn get_host(&self) -> Option<&HostInfo> {
+        self.host.as_ref()
+    }
+
+    /// Latest cpu & memory stats of all containers added together, and the number of containers in each state
+    pub fn get_container_totals(&self) -> ContainerTotals {
+        ContainerTotals::new(&self.containers.items)
+    }
+
+    // Column picker related methods
+
+    /// Open the column picker, with the current columns
+    pub fn set_column_picker(&mut self) {
+        self.column_picker = Some(ColumnPicker::new(
+            &self.args.columns,
+            self.args.column_label.is_some(),
+        ));
+    }
+
+    /// Remove the column picker
+    pub fn clear_column_picker(&mut self) {
+        self.column_picker = None;
+    }
+
+    /// Get mutable Option of the column picker, mutable so that the ListState can be used for rendering
+    pub const fn get_column_picker(&mut self) -> Option<&mut ColumnPicker> {
+        self.column_picker.as_mut()
+    }
+
+    /// Replace the visible columns with those chosen in the column picker, and close the picker
+    pub fn apply_column_picker(&mut self) -> Option<Vec<Header>> {
+        let columns = self.column_picker.take()?.visible();
+        if let Some((header, _)) = self.sorted_by {
+            if !columns.contains(&header) {
+                self.sorted_by = None;
+            }
+        }
+        self.args.columns.clone_from(&columns);
+        Some(columns)
+    }
+
+    // Processes related methods
+
+    /// Set the processes, will replace any existing processes
+    pub fn set_processes(&mut self, processes: ProcessesData) {
+        self.processes = Some(processes);
+    }
+
+    /// Remove the processes
+    pub fn clear_processes(&mut self) {
+        self.processes = None;
+    }
+
+    /// Get mutable Option of the processes, mutable so that the ListState can be used for rendering
+    pub const fn get_processes(&mut self) -> Option<&mut ProcessesData> {
+        self.processes.as_mut()
+    }
+
+    // Image related methods
+
+    /// Replace the images list, keeping the same image selected if it still exists
+    pub fn update_images(&mut self, images: &[ImageSummary]) {
+        let selected_id = self.get_selected_image().map(|i| i.id.clone());
+        let mut items = images
+            .iter()
+            .map(|i| ImageItem::new(i, &self.containers.items))
+            .collect::<Vec<_>>();
+        items.sort_by(|a, b| b.created.cmp(&a.created).then_with(|| a.name.cmp(&b.name)));
+        let position = selected_id.and_then(|id| items.iter().position(|i| i.id == id));
+        self.images.replace(items, position);
+    }
+
+    /// Get all the ImageItems
+    pub fn get_image_items(&self) -> &[ImageItem] {
+        &self.images.items
+    }
+
+    /// Get title for images section
+    pub fn image_title(&self) -> String {
+        self.images.get_state_title()
+    }
+
+    /// Get ListState of images
+    pub const fn get_image_state(&mut self) -> &mut ListState {
+        &mut self.images.state
+    }
+
+    /// Get Option of the current selected image
+    pub fn get_selected_image(&self) -> Option<&ImageItem> {
+        self.images
             .state
             .selected()
-            .and_then(|i| self.containers.items.get_mut(i))
-            .map(|i| i.get_chart_data())
+            .and_then(|i| self.images.items.get(i))
+    }
+
+    /// Get the name of an image by id
+    pub fn get_image_name_by_id(&self, id: &str) -> Option<String> {
+        self.images
+            .items
+            .iter()
+            .find(|i| i.id == id)
+            .map(|i| i.name.clone())
+    }
+
+    /// Select the first image
+    pub fn images_start(&mut self) {
+        self.images.start();
+    }
+
+    /// Select the last image
+    pub fn images_end(&mut self) {
+        self.images.end();
+    }
+
+    /// Select the next image
+    pub fn images_next(&mut self) {
+        self.images.next();
+    }
+
+    /// Select the previous image
+    pub fn images_previous(&mut self) {
+        self.images.previous();
+    }
+
+    // Volume related methods
+
+    /// Replace the volumes list, keeping the same volume selected if it still exists
+    pub fn update_volumes(&mut self, volumes: &[Volume], containers: &[ContainerSummary]) {
+        let selected_name = self.get_selected_volume().map(|i| i.name.clone());
+        let mut items = volumes
+            .iter()
+            .map(|i| VolumeItem::new(i, containers))
+            .collect::<Vec<_>>();
+        items.sort_by(|a, b| a.name.cmp(&b.name));
+        let position = selected_name.and_then(|name| items.iter().position(|i| i.name == name));
+        self.volumes.replace(items, position);
+    }
+
+    /// Get all the VolumeItems
+    pub fn get_volume_items(&self) -> &[VolumeItem] {
+        &self.volumes.items
+    }
+
+    /// Get title for volumes section
+    pub fn volume_title(&self) -> String {
+        self.volumes.get_state_title()
+    }
+
+    /// Get ListState of volumes
+    pub const fn get_volume_state(&mut self) -> &mut ListState {
+        &mut self.volumes.state
+    }
+
+    /// Get Option of the current selected volume
+    pub fn get_selected_volume(&self) -> Option<&VolumeItem> {
+        self.volumes
+            .state
+            .selected()
+            .and_then(|i| self.volumes.items.get(i))
+    }
+
+    /// Select the first volume
+    pub fn volumes_start(&mut self) {
+        self.volumes.start();
+    }
+
+    /// Select the last volume
+    pub fn volumes_end(&mut self) {
+        self.volumes.end();
+    }
+
+    /// Select the next volume
+    pub fn volumes_next(&mut self) {
+        self.volumes.next();
+    }
+
+    /// Select the previous volume
+    pub fn volumes_previous(&mut self) {
+        self.volumes.previous();
+    }
+
+    // Network related methods
+
+    /// Replace the networks list, keeping the same network selected if it still exists
+    /// If the network being used to filter the containers no longer exists, the filter is removed
+    pub fn update_networks(&mut self, networks: &[Network], containers: &[ContainerSummary]) {
+        let selected_id = self.get_selected_network().map(|i| i.id.clone());
+        let mut items = networks
+            .iter()
+            .map(|i| NetworkItem::new(i, containers))
+            .collect::<Vec<_>>();
+        items.sort_by(|a, b| a.name.cmp(&b.name));
+        let position = selected_id.and_then(|id| items.iter().position(|i| i.id == id));
+        if let Some(filter) = self.session.network_filter.take() {
+            self.network_filter = filter
+                .position(&items, |i| (i.id.as_str(), i.name.as_str()))
+                .and_then(|index| items.get(index).cloned());
+        }
+        if let Some(filter) = self.network_filter.as_ref() {
+            if !items.iter().any(|i| i.id == filter.id) {
+                self.network_filter = None;
+            }
+        }
+        self.networks.replace(items, position);
+        self.select_visible_container();
+    }
+
+    /// Get all the NetworkItems
+    pub fn get_network_items(&self) -> &[NetworkItem] {
+        &self.networks.items
+    }
+
+    /// Get title for networks section
+    pub fn network_title(&self) -> String {
+        self.networks.get_state_title()
+    }
+
+    /// Get ListState of networks
+    pub const fn get_network_state(&mut self) -> &mut ListState {
+        &mut self.networks.state
+    }
+
+    /// Get Option of the current selected network
+    pub fn get_selected_network(&self) -> Option<&NetworkItem> {
+        self.networks
+            .state
+            .selected()
+            .and_then(|i| self.networks.items.get(i))
+    }
+
+    /// Get the name of a network by id
+    pub fn get_network_name_by_id(&self, id: &str) -> Option<String> {
+        self.networks
+            .items
+            .iter()
+            .find(|i| i.id == id)
+            .map(|i| i.name.clone())
+    }
+
+    /// Get the id of the network the containers are currently filtered by
+    pub fn get_network_filter(&self) -> Option<String> {
+        self.network_filter.as_ref().map(|i| i.id.clone())
+    }
+
+    /// Only show containers attached to the currently selected network, or remove the filter if it's already the selected network
+    pub fn toggle_network_filter(&mut self) {
+        let selected = self.get_selected_network().cloned();
+        self.network_filter = match (self.network_filter.take(), selected) {
+            (Some(current), Some(selected)) if current.id == selected.id => None,
+            (_, selected) => selected,
+        };
+        self.select_visible_container();
+    }
+
+    /// Remove the network filter, so that all containers are shown
+    pub fn clear_network_filter(&mut self) {
+        self.network_filter = None;
+        self.session.network_filter = None;
+        self.select_visible_container();
+    }
+
+    // Session related methods
+
+    /// Restore the sort of the previous session, the selected container and network filter are restored once the containers, and networks, have been fetched
+    /// A sort by a column that is no longer shown is ignored
+    pub fn restore_session(&mut self, session: &Session) {
+        if session.unsorted == Some(true) {
+            self.sorted_by = None;
+        } else if let Some(sort) = session
+            .sort
+            .filter(|sort| self.args.columns.contains(&sort.header))
+        {
+            self.sorted_by = Some((sort.header, sort.order.unwrap_or(SortedOrder::Asc)));
+        }
+        self.session.container.clone_from(&session.container);
+        self.session
+            .network_filter
+            .clone_from(&session.network_filter);
+    }
+
+    /// Check if the network filter of the previous session is yet to be restored
+    pub const fn is_network_filter_pending(&self) -> bool {
+        self.session.network_filter.is_some()
+    }
+
+    /// Select the container of the previous session, by id, or by name if it has been recreated, once the containers have been fetched
+    pub fn restore_container(&mut self) {
+        if self.containers.items.is_empty() {
+            return;
+        }
+        if let Some(index) = self.session.container.take().and_then(|container| {
+            container.position(&self.containers.items, |i| (i.id.get(), i.name.get()))
+        }) {
+            self.containers.state.select(Some(index));
+        }
+        self.select_visible_container();
+    }
+
+    /// The current session, any part of the previous session yet to be restored is kept as it was
+    pub fn get_session(&self) -> Session {
+        Session {
+            container: self
+                .get_selected_container()
+                .map(|i| SessionItem {
+                    id: i.id.get().to_owned(),
+                    name: i.name.get().to_owned(),
+                })
+                .or_else(|| self.session.container.clone()),
+            network_filter: self
+                .network_filter
+                .as_ref()
+                .map(|i| SessionItem {
+                    id: i.id.clone(),
+                    name: i.name.clone(),
+                })
+                .or_else(|| self.session.network_filter.clone()),
+            sort: self.sorted_by.map(|(header, order)| SortConfig {
+                header,
+                order: Some(order),
+            }),
+            unsorted: self.sorted_by.is_none().then_some(true),
+            ..Session::default()
+        }
+    }
+
+    /// Select the first network
+    pub fn networks_start(&mut self) {
+        self.networks.start();
+    }
+
+    /// Select the last network
+    pub fn networks_end(&mut self) {
+        self.networks.end();
+    }
+
+    /// Select the next network
+    pub fn networks_next(&mut self) {
+        self.networks.next();
+    }
+
+    /// Select the previous network
+    pub fn networks_previous(&mut self) {
+        self.networks.previous();
     }
 
     /// Error related methods
@@ -501,6 +1283,7 @@ impl AppData {
     pub fn get_width(&self) -> Columns {
         let mut columns = Columns::new();
         let count = |x: &str| u8::try_from(x.chars().count()).unwrap_or(12);
+        let now = i64::try_from(Self::get_systemtime()).unwrap_or_default();
 
         // Should probably find a refactor here somewhere
         for container in &self.containers.items {
@@ -528,15 +1311,30 @@ impl AppData {
             columns.name.1 = columns.name.1.max(count(&container.name.to_string()));
             columns.net_rx.1 = columns.net_rx.1.max(count(&container.rx.to_string()));
             columns.net_tx.1 = columns.net_tx.1.max(count(&container.tx.to_string()));
+            let rate_count = |stats: &StatsHistory<ByteStats>| {
+                count(&stats.back().copied().unwrap_or_default().to_string())
+            };
+            columns.net_rx_rate.1 = columns.net_rx_rate.1.max(rate_count(&container.rx_stats));
+            columns.net_tx_rate.1 = columns.net_tx_rate.1.max(rate_count(&container.tx_stats));
             columns.state.1 = columns.state.1.max(count(&container.state.to_string()));
             columns.status.1 = columns.status.1.max(count(&container.status));
+            columns.health.1 = columns.health.1.max(count(&container.health.to_string()));
+            for header in &self.args.columns {
+                if let (Some(text), Some(column)) = (
+                    container.column_text(*header, self.args.column_label.as_deref(), now),
+                    columns.get_mut(*header),
+                ) {
+                    column.1 = column.1.max(count(&text));
+                }
+            }
         }
         columns
     }
 
     /// Update related methods
 
-    /// Update container mem, cpu, & network stats, in single function so only need to call .lock() once
+    /// Update container mem, cpu, network, & block io stats, in single function so only need to call .lock() once
+    /// The timestamp, as unix time in ms, is when the stats were received, samples older than the stats history are removed
     /// Will also, if a sort is set, sort the containers
     pub fn update_stats_by_id(
         &mut self,
@@ -544,26 +1342,23 @@ impl AppData {
         cpu_stat: Option<f64>,
         mem_stat: Option<u64>,
         mem_limit: u64,
-        rx: u64,
-        tx: u64,
+        io: IoCounters,
+        timestamp: u64,
     ) {
+        let retention = self.args.stats_history;
         if let Some(container) = self.get_container_by_id(id) {
-            if container.cpu_stats.len() >= 60 {
-                container.cpu_stats.pop_front();
-            }
-            if container.mem_stats.len() >= 60 {
-                container.mem_stats.pop_front();
-            }
-
             if let Some(cpu) = cpu_stat {
-                container.cpu_stats.push_back(CpuStats::new(cpu));
+                container
+                    .cpu_stats
+                    .push(timestamp, CpuStats::new(cpu), retention);
             }
             if let Some(mem) = mem_stat {
-                container.mem_stats.push_back(ByteStats::new(mem));
+                container
+                    .mem_stats
+                    .push(timestamp, ByteStats::new(mem), retention);
             }
 
-            container.rx.update(rx);
-            container.tx.update(tx);
+            container.push_io(io, timestamp, retention);
             container.mem_limit.update(mem_limit);
         }
         // need to benchmark this?
@@ -571,6 +1366,7 @@ impl AppData {
     }
 
     /// Update, or insert, containers
+    #[allow(clippy::too_many_lines)]
     pub fn update_containers(&mut self, all_containers: &mut [ContainerSummary]) {
         let all_ids = self
             .containers
@@ -588,7 +1384,8 @@ impl AppData {
             self.containers.start();
         }
 
-        for (index, id) in all_ids.iter().enumerate() {
+        // Iterate in reverse, so that removing a container doesn't change the index of those yet to be checked
+        for (index, id) in all_ids.iter().enumerate().rev() {
             if !all_containers
                 .iter()
                 .filter_map(|i| i.id.as_ref())
@@ -642,12 +1439,20 @@ impl AppData {
                 let created = i
                     .created
                     .map_or(0, |i| u64::try_from(i).unwrap_or_default());
+
+                let command = i.command.clone().unwrap_or_default();
+                let image_id = i.image_id.clone().unwrap_or_default();
+                let labels = i.labels.clone().unwrap_or_default();
+                let ip = Self::first_ip(i);
+                let networks = network_ids(i);
+
                 // If container info already in containers Vec, then just update details
                 if let Some(item) = self.get_container_by_id(&id) {
                     if item.name.get() != name {
                         item.name.set(name);
                     };
                     if item.status != status {
+                        item.health = Health::from(status.as_str());
                         item.status = status;
                     };
                     if item.state != state {
@@ -667,15 +1472,67 @@ impl AppData {
                     if item.image.get() != image {
                         item.image.set(image);
                     };
+                    item.command = command;
+                    item.image_id = image_id;
+                    item.ip = ip;
+                    item.labels = labels;
+                    item.networks = networks;
                 } else {
                     // container not known, so make new ContainerItem and push into containers Vec
-                    let container = ContainerItem::new(
+                    let mut container = ContainerItem::new(
                         created, id, image, is_oxker, name, ports, state, status,
                     );
+                    container.command = command;
+                    container.image_id = image_id;
+                    container.ip = ip;
+                    container.labels = labels;
+                    container.networks = networks;
                     self.containers.items.push(container);
                 }
             }
         }
+        self.select_visible_container();
+    }
+
+    /// Get the ids of all containers which haven't been inspected since their state last changed
+    pub fn get_details_required(&self) -> Vec<ContainerId> {
+        self.containers
+            .items
+            .iter()
+            .filter(|i| !i.is_oxker && i.inspected != Some(i.state))
+            .map(|i| i.id.clone())
+            .collect()
+    }
+
+    /// Update the details of a given container, based on id, which are only available from an inspect
+    pub fn update_details_by_id(
+        &mut self,
+        id: &ContainerId,
+        state: State,
+        started: Option<u64>,
+        restart_count: Option<u64>,
+    ) {
+        if let Some(container) = self.get_container_by_id(id) {
+            container.inspected = Some(state);
+            container.restart_count = restart_count;
+            container.started = started;
+        }
+    }
+
+    /// The first ip address of a container, taking the networks in alphabetical order, empty if not attached to any network
+    fn first_ip(container: &ContainerSummary) -> String {
+        let mut networks = container
+            .network_settings
+            .as_ref()
+            .and_then(|i| i.networks.as_ref())
+            .map_or(vec![], |i| i.iter().collect::<Vec<_>>());
+        networks.sort_by(|a, b| a.0.cmp(b.0));
+        networks
+            .into_iter()
+            .filter_map(|(_, endpoint)| endpoint.ip_address.as_ref())
+            .find(|i| !i.is_empty())
+            .cloned()
+            .unwrap_or_default()
     }
 
     /// update logs of a given container, based on id
@@ -723,7 +1580,6 @@ mod tests {
 
     use super::*;
     use crate::tests::{gen_appdata, gen_container_summary, gen_containers};
-    use std::collections::VecDeque;
 
     // ******* //
     // Sort by //
@@ -829,6 +1685,39 @@ mod tests {
         assert_eq!(c.id, ContainerId::from("3"));
     }
 
+    #[test]
+    /// Sort by header: health, unhealthy first when ascending
+    fn test_app_data_set_sort_by_header_health() {
+        let (_ids, containers) = gen_containers();
+
+        let mut app_data = gen_appdata(&containers);
+
+        if let Some(i) = app_data.get_container_by_id(&ContainerId::from("2")) {
+            i.health = Health::Unhealthy;
+        }
+
+        if let Some(i) = app_data.get_container_by_id(&ContainerId::from("3")) {
+            i.health = Health::Healthy;
+        }
+
+        // Sort by health
+        // ascending
+        app_data.set_sorted(Some((Header::Health, SortedOrder::Asc)));
+        let result = app_data.get_container_items();
+        let (a, b, c) = (&result[0], &result[1], &result[2]);
+        assert_eq!(a.id, ContainerId::from("2"));
+        assert_eq!(b.id, ContainerId::from("3"));
+        assert_eq!(c.id, ContainerId::from("1"));
+
+        // descending
+        app_data.set_sorted(Some((Header::Health, SortedOrder::Desc)));
+        let result = app_data.get_container_items();
+        let (a, b, c) = (&result[0], &result[1], &result[2]);
+        assert_eq!(a.id, ContainerId::from("1"));
+        assert_eq!(b.id, ContainerId::from("3"));
+        assert_eq!(c.id, ContainerId::from("2"));
+    }
+
     #[test]
     /// Sort by header: cpu
     fn test_app_data_set_sort_by_header_cpu() {
@@ -840,13 +1729,13 @@ mod tests {
         assert_eq!(result, &containers);
 
         if let Some(i) = app_data.get_container_by_id(&ContainerId::from("1")) {
-            i.cpu_stats = VecDeque::from([CpuStats::new(10.1)]);
+            i.cpu_stats.push(0, CpuStats::new(10.1), 60_000);
         }
         if let Some(i) = app_data.get_container_by_id(&ContainerId::from("2")) {
-            i.cpu_stats = VecDeque::from([CpuStats::new(8.1)]);
+            i.cpu_stats.push(0, CpuStats::new(8.1), 60_000);
         }
         if let Some(i) = app_data.get_container_by_id(&ContainerId::from("3")) {
-            i.cpu_stats = VecDeque::from([CpuStats::new(20.3)]);
+            i.cpu_stats.push(0, CpuStats::new(20.3), 60_000);
         }
 
         // descending
@@ -877,13 +1766,13 @@ mod tests {
         assert_eq!(result, &containers);
 
         if let Some(i) = app_data.get_container_by_id(&ContainerId::from("1")) {
-            i.mem_stats = VecDeque::from([ByteStats::new(40)]);
+            i.mem_stats.push(0, ByteStats::new(40), 60_000);
         }
         if let Some(i) = app_data.get_container_by_id(&ContainerId::from("2")) {
-            i.mem_stats = VecDeque::from([ByteStats::new(80)]);
+            i.mem_stats.push(0, ByteStats::new(80), 60_000);
         }
         if let Some(i) = app_data.get_container_by_id(&ContainerId::from("3")) {
-            i.mem_stats = VecDeque::from([ByteStats::new(2)]);
+            i.mem_stats.push(0, ByteStats::new(2), 60_000);
         }
 
         // descending
@@ -1031,6 +1920,66 @@ mod tests {
         assert_eq!(c.id, ContainerId::from("1"));
     }
 
+    #[test]
+    /// Sort by header: ip, compared as addresses rather than as text
+    fn test_app_data_set_sort_by_header_ip() {
+        let (_ids, containers) = gen_containers();
+
+        let mut app_data = gen_appdata(&containers);
+
+        for (id, ip) in [("1", "172.17.0.2"), ("2", "10.0.0.10"), ("3", "10.0.0.9")] {
+            if let Some(i) = app_data.get_container_by_id(&ContainerId::from(id)) {
+                i.ip = ip.to_owned();
+            }
+        }
+
+        // descending
+        app_data.set_sorted(Some((Header::Ip, SortedOrder::Desc)));
+        let result = app_data.get_container_items();
+        let (a, b, c) = (&result[0], &result[1], &result[2]);
+        assert_eq!(a.id, ContainerId::from("1"));
+        assert_eq!(b.id, ContainerId::from("2"));
+        assert_eq!(c.id, ContainerId::from("3"));
+
+        // ascending
+        app_data.set_sorted(Some((Header::Ip, SortedOrder::Asc)));
+        let result = app_data.get_container_items();
+        let (a, b, c) = (&result[0], &result[1], &result[2]);
+        assert_eq!(a.id, ContainerId::from("3"));
+        assert_eq!(b.id, ContainerId::from("2"));
+        assert_eq!(c.id, ContainerId::from("1"));
+    }
+
+    #[test]
+    /// Sort by header: uptime, the most recently started container has the shortest uptime
+    fn test_app_data_set_sort_by_header_uptime() {
+        let (_ids, containers) = gen_containers();
+
+        let mut app_data = gen_appdata(&containers);
+
+        for (id, started) in [("1", 200), ("2", 100), ("3", 300)] {
+            if let Some(i) = app_data.get_container_by_id(&ContainerId::from(id)) {
+                i.started = Some(started);
+            }
+        }
+
+        // descending
+        app_data.set_sorted(Some((Header::Uptime, SortedOrder::Desc)));
+        let result = app_data.get_container_items();
+        let (a, b, c) = (&result[0], &result[1], &result[2]);
+        assert_eq!(a.id, ContainerId::from("2"));
+        assert_eq!(b.id, ContainerId::from("1"));
+        assert_eq!(c.id, ContainerId::from("3"));
+
+        // ascending
+        app_data.set_sorted(Some((Header::Uptime, SortedOrder::Asc)));
+        let result = app_data.get_container_items();
+        let (a, b, c) = (&result[0], &result[1], &result[2]);
+        assert_eq!(a.id, ContainerId::from("3"));
+        assert_eq!(b.id, ContainerId::from("1"));
+        assert_eq!(c.id, ContainerId::from("2"));
+    }
+
     #[test]
     /// Sort by header when selected headers match
     fn test_app_data_set_sort_by_header_match() {
@@ -1679,32 +2628,48 @@ mod tests {
         let (_ids, containers) = gen_containers();
         let mut app_data = gen_appdata(&containers);
 
-        let result = app_data.get_chart_data();
+        let result = app_data.get_chart_data(100);
         assert!(result.is_none());
 
         app_data.containers_start();
 
         if let Some(item) = app_data.get_container_by_id(&ContainerId::from("1")) {
-            item.cpu_stats = VecDeque::from([CpuStats::new(1.1), CpuStats::new(1.2)]);
-            item.mem_stats = VecDeque::from([ByteStats::new(1), ByteStats::new(2)]);
+            item.cpu_stats.push(1000, CpuStats::new(1.1), 60_000);
+            item.cpu_stats.push(2000, CpuStats::new(1.2), 60_000);
+            item.mem_stats.push(1000, ByteStats::new(1), 60_000);
+            item.mem_stats.push(2000, ByteStats::new(2), 60_000);
         }
 
-        let result = app_data.get_chart_data();
+        let result = app_data.get_chart_data(100);
         assert_eq!(
             result,
             Some((
+                Some(TimeAxis {
+                    start: 0,
+                    end: 60_000
+                }),
                 (
-                    vec![(0.0, 1.1), (1.0, 1.2)],
+                    vec![vec![(1.0, 1.1), (2.0, 1.2)]],
                     CpuStats::new(1.2),
                     State::Running
                 ),
                 (
-                    vec![(0.0, 1.0), (1.0, 2.0)],
+                    vec![vec![(1.0, 1.0), (2.0, 2.0)]],
                     ByteStats::new(2),
                     State::Running
                 )
             ))
         );
+
+        // A gap of more than three docker intervals starts a new segment
+        if let Some(item) = app_data.get_container_by_id(&ContainerId::from("1")) {
+            item.cpu_stats.push(10_000, CpuStats::new(1.3), 60_000);
+        }
+        let result = app_data.get_chart_data(100).unwrap();
+        assert_eq!(
+            result.1 .0,
+            vec![vec![(1.0, 1.1), (2.0, 1.2)], vec![(10.0, 1.3)]]
+        );
     }
 
     // ************* //
@@ -1722,12 +2687,23 @@ mod tests {
             name: (Header::Name, 11),
             state: (Header::State, 11),
             status: (Header::Status, 16),
+            health: (Header::Health, 8),
             cpu: (Header::Cpu, 7),
             mem: (Header::Memory, 7, 7),
             id: (Header::Id, 8),
             image: (Header::Image, 7),
             net_rx: (Header::Rx, 7),
             net_tx: (Header::Tx, 7),
+            net_rx_rate: (Header::RxRate, 8),
+            net_tx_rate: (Header::TxRate, 8),
+            created: (Header::Created, 7),
+            uptime: (Header::Uptime, 6),
+            ports: (Header::Ports, 5),
+            command: (Header::Command, 7),
+            service: (Header::Service, 7),
+            label: (Header::Label, 5),
+            restarts: (Header::Restarts, 8),
+            ip: (Header::Ip, 2),
         };
         assert_eq!(result, expected);
     }
@@ -1817,15 +2793,33 @@ mod tests {
         let result = app_data.get_container_items();
         assert_eq!(result[0], containers[0]);
 
-        app_data.update_stats_by_id(&ids[0], Some(10.0), Some(10), 10, 10, 10);
+        let io = IoCounters {
+            read: 10,
+            rx: 10,
+            tx: 10,
+            write: 10,
+        };
+        app_data.update_stats_by_id(&ids[0], Some(10.0), Some(10), 10, io, 1000);
 
         let result = app_data.get_container_items();
         assert_ne!(result[0], containers[0]);
-        assert_eq!(result[0].cpu_stats, VecDeque::from([CpuStats::new(10.0)]));
-        assert_eq!(result[0].mem_stats, VecDeque::from([ByteStats::new(10)]));
+        assert_eq!(result[0].cpu_stats.back(), Some(&CpuStats::new(10.0)));
+        assert_eq!(result[0].cpu_stats.time_range(), Some((1000, 1000)));
+        assert_eq!(result[0].mem_stats.back(), Some(&ByteStats::new(10)));
         assert_eq!(result[0].mem_limit, ByteStats::new(10));
         assert_eq!(result[0].rx, ByteStats::new(10));
         assert_eq!(result[0].tx, ByteStats::new(10));
+        // Rates need two samples
+        assert!(result[0].rx_stats.back().is_none());
+        assert_eq!(result[0].io_sample, Some((1000, io)));
+
+        // Samples older than the stats history removed
+        app_data.update_stats_by_id(&ids[0], Some(20.0), Some(20), 20, io, 301_000);
+        let result = app_data.get_container_items();
+        assert_eq!(result[0].cpu_stats.time_range(), Some((1000, 301_000)));
+        app_data.update_stats_by_id(&ids[0], Some(30.0), Some(30), 30, io, 302_000);
+        let result = app_data.get_container_items();
+        assert_eq!(result[0].cpu_stats.time_range(), Some((301_000, 302_000)));
     }
 
     #[test]
@@ -1846,6 +2840,270 @@ mod tests {
         assert_eq!(result_post[1].state, State::Dead);
     }
 
+    #[test]
+    /// Multiple containers removed in a single update, only the remaining container is kept
+    fn test_app_data_update_containers_remove_multiple() {
+        let (_ids, containers) = gen_containers();
+        let mut app_data = gen_appdata(&containers);
+        let mut input = [gen_container_summary(3, "running")];
+
+        app_data.update_containers(&mut input);
+        let result = app_data.get_container_items();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].id, ContainerId::from("3"));
+    }
+
+    fn gen_network(id: &str, name: &str) -> Network {
+        Network {
+            id: Some(id.to_owned()),
+            name: Some(name.to_owned()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    /// Network filter toggled on and off for the selected network, and shown in the containers title
+    /// Containers not on the network are hidden, and skipped when selecting, but are kept in app_data
+    fn test_app_data_toggle_network_filter() {
+        let (ids, containers) = gen_containers();
+        let mut app_data = gen_appdata(&containers);
+        for (index, network) in ["1", "2", "1"].into_iter().enumerate() {
+            app_data.containers.items[index].networks = vec![network.to_owned()];
+        }
+        app_data.containers_start();
+        app_data.update_networks(
+            &[gen_network("1", "backend"), gen_network("2", "frontend")],
+            &[],
+        );
+        app_data.networks_start();
+        assert_eq!(app_data.get_network_filter(), None);
+        assert_eq!(app_data.container_title(), " 1/3");
+
+        app_data.toggle_network_filter();
+        assert_eq!(app_data.get_network_filter(), Some("1".to_owned()));
+        assert_eq!(app_data.container_title(), " 1/2 - network: backend");
+        assert_eq!(app_data.get_container_len(), 2);
+        assert_eq!(app_data.get_container_items().len(), 3);
+        let visible = app_data
+            .get_visible_container_items()
+            .iter()
+            .map(|i| i.id.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(visible, [ids[0].clone(), ids[2].clone()]);
+
+        // The hidden container is skipped
+        app_data.containers_next();
+        assert_eq!(app_data.get_selected_container_id(), Some(ids[2].clone()));
+        assert_eq!(app_data.container_title(), " 2/2 - network: backend");
+        assert_eq!(app_data.get_visible_container_state().selected(), Some(1));
+        app_data.containers_next();
+        assert_eq!(app_data.get_selected_container_id(), Some(ids[2].clone()));
+        app_data.containers_previous();
+        assert_eq!(app_data.get_selected_container_id(), Some(ids[0].clone()));
+        app_data.containers_end();
+        assert_eq!(app_data.get_selected_container_id(), Some(ids[2].clone()));
+
+        // A different network replaces the current filter, the selected container is now hidden, so the first shown container is selected
+        app_data.networks_next();
+        app_data.toggle_network_filter();
+        assert_eq!(app_data.get_network_filter(), Some("2".to_owned()));
+        assert_eq!(app_data.get_selected_container_id(), Some(ids[1].clone()));
+        assert_eq!(app_data.container_title(), " 1/1 - network: frontend");
+
+        // Same network removes the filter
+        app_data.toggle_network_filter();
+        assert_eq!(app_data.get_network_filter(), None);
+        assert_eq!(app_data.container_title(), " 2/3");
+
+        app_data.toggle_network_filter();
+        app_data.clear_network_filter();
+        assert_eq!(app_data.get_network_filter(), None);
+        assert_eq!(app_data.get_container_len(), 3);
+    }
+
+    #[test]
+    /// Networks sorted by name, selection kept by id, and the filter removed when the network no longer exists
+    fn test_app_data_update_networks() {
+        let (_ids, containers) = gen_containers();
+        let mut app_data = gen_appdata(&containers);
+        app_data.update_networks(
+            &[gen_network("2", "frontend"), gen_network("1", "backend")],
+            &[],
+        );
+        let names = app_data
+            .get_network_items()
+            .iter()
+            .map(|i| i.name.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["backend", "frontend"]);
+        assert_eq!(app_data.get_selected_network().unwrap().name, "backend");
+
+        app_data.networks_end();
+        app_data.toggle_network_filter();
+        app_data.update_networks(
+            &[
+                gen_network("0", "a_network"),
+                gen_network("1", "backend"),
+                gen_network("2", "frontend"),
+            ],
+            &[],
+        );
+        assert_eq!(app_data.get_selected_network().unwrap().name, "frontend");
+        assert_eq!(app_data.get_network_filter(), Some("2".to_owned()));
+
+        app_data.update_networks(&[gen_network("1", "backend")], &[]);
+        assert_eq!(app_data.get_selected_network().unwrap().name, "backend");
+        assert_eq!(app_data.get_network_filter(), None);
+
+        app_data.update_networks(&[], &[]);
+        assert!(app_data.get_selected_network().is_none());
+        assert_eq!(app_data.network_title(), "");
+    }
+
+    #[test]
+    /// The sort of the previous session is restored, unless its column isn't shown, and the container is selected by id, or by name
+    fn test_app_data_restore_session() {
+        let (_ids, containers) = gen_containers();
+        let mut app_data = gen_appdata(&containers);
+        let item = |id: &str, name: &str| SessionItem {
+            id: id.to_owned(),
+            name: name.to_owned(),
+        };
+        let mut session = Session {
+            container: Some(item("2", "container_2")),
+            sort: Some(SortConfig {
+                header: Header::Name,
+                order: Some(SortedOrder::Desc),
+            }),
+            ..Session::default()
+        };
+        app_data.restore_session(&session);
+        assert_eq!(app_data.sorted_by, Some((Header::Name, SortedOrder::Desc)));
+        app_data.sort_containers();
+        app_data.restore_container();
+        assert_eq!(
+            app_data.get_selected_container_id(),
+            Some(ContainerId::from("2"))
+        );
+
+        // Recreated container has a new id
+        let mut app_data = gen_appdata(&containers);
+        session.container = Some(item("4", "container_3"));
+        session.sort = Some(SortConfig {
+            header: Header::Ip,
+            order: None,
+        });
+        app_data.restore_session(&session);
+        assert_eq!(app_data.sorted_by, None);
+        app_data.restore_container();
+        assert_eq!(
+            app_data.get_selected_container_id(),
+            Some(ContainerId::from("3"))
+        );
+        assert!(app_data.session.container.is_none());
+
+        // Missing container leaves the selection unchanged
+        let mut app_data = gen_appdata(&containers);
+        app_data.containers.start();
+        session.container = Some(item("4", "container_4"));
+        app_data.restore_session(&session);
+        app_data.restore_container();
+        assert_eq!(
+            app_data.get_selected_container_id(),
+            Some(ContainerId::from("1"))
+        );
+    }
+
+    #[test]
+    /// A cleared sort is saved as unsorted, and restored as unsorted, rather than the sort from the config file
+    fn test_app_data_restore_session_unsorted() {
+        let (_ids, containers) = gen_containers();
+        let config_sort = Some((Header::Cpu, SortedOrder::Desc));
+        let mut app_data = gen_appdata(&containers);
+        app_data.sorted_by = config_sort;
+        assert_eq!(app_data.get_session().unsorted, None);
+
+        app_data.reset_sorted();
+        let session = app_data.get_session();
+        assert_eq!(session.sort, None);
+        assert_eq!(session.unsorted, Some(true));
+
+        let mut app_data = gen_appdata(&containers);
+        app_data.sorted_by = config_sort;
+        app_data.restore_session(&session);
+        assert_eq!(app_data.sorted_by, None);
+
+        // No saved sort, so the config sort is kept
+        let mut app_data = gen_appdata(&containers);
+        app_data.sorted_by = config_sort;
+        app_data.restore_session(&Session::default());
+        assert_eq!(app_data.sorted_by, config_sort);
+    }
+
+    #[test]
+    /// The network filter of the previous session is restored by id, or by name, once the networks are fetched
+    fn test_app_data_restore_session_network_filter() {
+        let (_ids, containers) = gen_containers();
+        let mut app_data = gen_appdata(&containers);
+        app_data.restore_session(&Session {
+            network_filter: Some(SessionItem {
+                id: "9".to_owned(),
+                name: "frontend".to_owned(),
+            }),
+            ..Session::default()
+        });
+        assert!(app_data.is_network_filter_pending());
+        assert_eq!(app_data.get_network_filter(), None);
+
+        // Yet to be restored, so kept as it was
+        assert_eq!(
+            app_data.get_session().network_filter.unwrap().name,
+            "frontend"
+        );
+
+        app_data.update_networks(
+            &[gen_network("1", "backend"), gen_network("2", "frontend")],
+            &[],
+        );
+        assert!(!app_data.is_network_filter_pending());
+        assert_eq!(app_data.get_network_filter(), Some("2".to_owned()));
+        assert_eq!(app_data.get_session().network_filter.unwrap().id, "2");
+    }
+
+    #[test]
+    /// The current session has the selected container, network filter, and sort, or unsorted if there's no sort
+    fn test_app_data_get_session() {
+        let (_ids, containers) = gen_containers();
+        let mut app_data = gen_appdata(&containers);
+        assert_eq!(
+            app_data.get_session(),
+            Session {
+                unsorted: Some(true),
+                ..Session::default()
+            }
+        );
+
+        app_data.containers.end();
+        app_data.set_sort_by_header(Header::Cpu);
+        let session = app_data.get_session();
+        assert_eq!(
+            session.container,
+            Some(SessionItem {
+                id: "3".to_owned(),
+                name: "container_3".to_owned()
+            })
+        );
+        assert_eq!(
+            session.sort,
+            Some(SortConfig {
+                header: Header::Cpu,
+                order: Some(SortedOrder::Asc)
+            })
+        );
+        assert_eq!(session.unsorted, None);
+        assert!(session.network_filter.is_none());
+    }
+
     #[test]
     /// Update logs don't work if container is_oxker: true
     fn test_app_data_update_log_by_id_is_oxker() {
@@ -0,0 +1,161 @@
+use bollard::service::{ContainerSummary, Network};
+
+use super::summary_name;
+
+/// The ids of every network a container is attached to, sorted
+pub fn network_ids(container: &ContainerSummary) -> Vec<String> {
+    let mut ids = container
+        .network_settings
+        .as_ref()
+        .and_then(|i| i.networks.as_ref())
+        .map_or(vec![], |networks| {
+            networks
+                .values()
+                .filter_map(|i| i.network_id.clone())
+                .collect()
+        });
+    ids.sort();
+    ids
+}
+
+/// Check if a container is attached to the given network id
+pub fn on_network(container: &ContainerSummary, network_id: &str) -> bool {
+    network_ids(container).iter().any(|i| i == network_id)
+}
+
+/// Info for each network
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NetworkItem {
+    pub containers: Vec<String>,
+    pub driver: String,
+    pub id: String,
+    pub name: String,
+    pub subnet: String,
+}
+
+impl NetworkItem {
+    /// Create a new network item, find the names of any of the given containers attached to the network
+    pub fn new(network: &Network, containers: &[ContainerSummary]) -> Self {
+        let id = network.id.clone().unwrap_or_default();
+        let containers = containers
+            .iter()
+            .filter(|i| on_network(i, &id))
+            .map(summary_name)
+            .collect();
+        let subnet = network
+            .ipam
+            .as_ref()
+            .and_then(|i| i.config.as_ref())
+            .map_or_else(String::new, |config| {
+                config
+                    .iter()
+                    .filter_map(|i| i.subnet.as_deref())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            });
+        Self {
+            containers,
+            driver: network.driver.clone().unwrap_or_default(),
+            id,
+            name: network.name.clone().unwrap_or_default(),
+            subnet,
+        }
+    }
+
+    /// The first 12 chars of the id, as shown by the docker cli
+    pub fn get_short_id(&self) -> String {
+        self.id.chars().take(12).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use bollard::service::{
+        ContainerSummary, ContainerSummaryNetworkSettings, EndpointSettings, Ipam, IpamConfig,
+        Network,
+    };
+
+    use super::{network_ids, on_network, NetworkItem};
+
+    fn gen_container(name: &str, network_ids: &[&str]) -> ContainerSummary {
+        let networks = network_ids
+            .iter()
+            .map(|id| {
+                (
+                    format!("network_{id}"),
+                    EndpointSettings {
+                        network_id: Some((*id).to_owned()),
+                        ..Default::default()
+                    },
+                )
+            })
+            .collect::<HashMap<_, _>>();
+        ContainerSummary {
+            names: Some(vec![format!("/{name}")]),
+            network_settings: Some(ContainerSummaryNetworkSettings {
+                networks: Some(networks),
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    /// Containers correctly matched to a network id
+    fn test_networks_on_network() {
+        let container = gen_container("redis", &["abc", "def"]);
+        assert!(on_network(&container, "abc"));
+        assert!(on_network(&container, "def"));
+        assert!(!on_network(&container, "ghi"));
+        assert!(!on_network(&ContainerSummary::default(), "abc"));
+    }
+
+    #[test]
+    /// Network ids of a container, sorted, empty when not attached to any network
+    fn test_networks_network_ids() {
+        let container = gen_container("redis", &["def", "abc"]);
+        assert_eq!(network_ids(&container), ["abc", "def"]);
+        assert!(network_ids(&ContainerSummary::default()).is_empty());
+    }
+
+    #[test]
+    /// Network details, subnets, and attached containers, set correctly
+    fn test_networks_new() {
+        let containers = [
+            gen_container("redis", &["abcdef1234567890"]),
+            gen_container("postgres", &["abcdef1234567890", "other"]),
+            gen_container("rabbitmq", &["other"]),
+        ];
+        let network = Network {
+            name: Some("backend".to_owned()),
+            id: Some("abcdef1234567890".to_owned()),
+            driver: Some("bridge".to_owned()),
+            ipam: Some(Ipam {
+                config: Some(vec![
+                    IpamConfig {
+                        subnet: Some("172.20.0.0/16".to_owned()),
+                        ..Default::default()
+                    },
+                    IpamConfig {
+                        subnet: Some("fd00::/64".to_owned()),
+                        ..Default::default()
+                    },
+                ]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let result = NetworkItem::new(&network, &containers);
+        assert_eq!(result.name, "backend");
+        assert_eq!(result.driver, "bridge");
+        assert_eq!(result.get_short_id(), "abcdef123456");
+        assert_eq!(result.subnet, "172.20.0.0/16, fd00::/64");
+        assert_eq!(result.containers, ["redis", "postgres"]);
+
+        let result = NetworkItem::new(&Network::default(), &containers);
+        assert!(result.containers.is_empty());
+        assert!(result.subnet.is_empty());
+    }
+}
@@ -0,0 +1,252 @@
+use bollard::{
+    service::{SystemDataUsageResponse, SystemInfo},
+    system::Version,
+};
+
+use super::{ByteStats, ContainerItem, CpuStats, State, Stats};
+
+/// Convert an optional, possibly negative, size from the Docker API into ByteStats
+fn bytes(value: Option<i64>) -> ByteStats {
+    ByteStats::new(
+        value
+            .and_then(|i| u64::try_from(i).ok())
+            .unwrap_or_default(),
+    )
+}
+
+/// Count of items, alongside their total size on disk
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DiskUsage {
+    pub count: usize,
+    pub size: ByteStats,
+}
+
+/// Information about the Docker host, from the info, version, and system df APIs
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct HostInfo {
+    pub api_version: String,
+    pub architecture: String,
+    pub build_cache: DiskUsage,
+    pub containers: DiskUsage,
+    pub cpus: u64,
+    pub images: DiskUsage,
+    pub kernel: String,
+    pub mem_total: ByteStats,
+    pub name: String,
+    pub os: String,
+    pub storage_driver: String,
+    pub version: String,
+    pub volumes: DiskUsage,
+}
+
+impl HostInfo {
+    /// Image size is the size of all the layers, as layers shared between images would otherwise be counted multiple times
+    /// Container size is the size of the writable layer of each container
+    pub fn new(info: &SystemInfo, version: &Version, df: &SystemDataUsageResponse) -> Self {
+        let text = |i: Option<&String>| i.cloned().unwrap_or_default();
+        let volumes = df.volumes.as_deref().unwrap_or_default();
+        let build_cache = df.build_cache.as_deref().unwrap_or_default();
+        let containers = df.containers.as_deref().unwrap_or_default();
+        Self {
+            api_version: text(version.api_version.as_ref()),
+            architecture: text(info.architecture.as_ref()),
+            build_cache: DiskUsage {
+                count: build_cache.len(),
+                size: build_cache.iter().map(|i| bytes(i.size)).sum(),
+            },
+            containers: DiskUsage {
+                count: containers.len(),
+                size: containers.iter().map(|i| bytes(i.size_rw)).sum(),
+            },
+            cpus: info
+                .ncpu
+                .and_then(|i| u64::try_from(i).ok())
+                .unwrap_or_default(),
+            images: DiskUsage {
+                count: df.images.as_ref().map_or(0, Vec::len),
+                size: bytes(df.layers_size),
+            },
+            kernel: text(info.kernel_version.as_ref()),
+            mem_total: bytes(info.mem_total),
+            name: text(info.name.as_ref()),
+            os: text(info.operating_system.as_ref()),
+            storage_driver: text(info.driver.as_ref()),
+            version: text(version.version.as_ref().or(info.server_version.as_ref())),
+            volumes: DiskUsage {
+                count: volumes.len(),
+                size: volumes
+                    .iter()
+                    .map(|i| bytes(i.usage_data.as_ref().map(|i| i.size)))
+                    .sum(),
+            },
+        }
+    }
+}
+
+/// The latest cpu & memory stats of every running container added together, and the number of containers in each state
+/// Stopped containers keep their stats history, so are excluded from the totals
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ContainerTotals {
+    pub cpu: f64,
+    pub mem: ByteStats,
+    pub states: Vec<(State, usize)>,
+}
+
+impl ContainerTotals {
+    pub fn new(containers: &[ContainerItem]) -> Self {
+        let mut states: Vec<(State, usize)> = vec![];
+        for container in containers {
+            if let Some(i) = states.iter_mut().find(|i| i.0 == container.state) {
+                i.1 += 1;
+            } else {
+                states.push((container.state, 1));
+            }
+        }
+        states.sort_by_key(|i| std::cmp::Reverse(i.1));
+        Self {
+            cpu: containers
+                .iter()
+                .filter(|i| i.state.is_alive())
+                .filter_map(|i| i.cpu_stats.back())
+                .fold(0.0, |acc, i| acc + i.get_value()),
+            mem: containers
+                .iter()
+                .filter(|i| i.state.is_alive())
+                .filter_map(|i| i.mem_stats.back().copied())
+                .sum(),
+            states,
+        }
+    }
+
+    /// Cpu usage as a share of the whole host, Docker reports 100% per cpu, so divide by the number of cpus
+    #[allow(clippy::cast_precision_loss)]
+    pub fn host_cpu(&self, cpus: u64) -> CpuStats {
+        CpuStats::new(self.cpu / cpus.max(1) as f64)
+    }
+
+    /// Memory usage as a ratio of the total memory of the host, between 0 and 1
+    pub fn mem_ratio(&self, mem_total: ByteStats) -> f64 {
+        if mem_total == ByteStats::default() {
+            0.0
+        } else {
+            (self.mem.get_value() / mem_total.get_value()).clamp(0.0, 1.0)
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use bollard::{
+        service::{
+            BuildCache, ImageSummary, SystemDataUsageResponse, SystemInfo, Volume, VolumeUsageData,
+        },
+        system::Version,
+    };
+
+    use crate::{
+        app_data::{ByteStats, ContainerId, CpuStats, State},
+        tests::gen_item,
+    };
+
+    use super::{ContainerTotals, DiskUsage, HostInfo};
+
+    #[test]
+    /// Host info parsed, with image size from the layers size, and sizes summed
+    fn test_overview_host_info() {
+        let info = SystemInfo {
+            name: Some("host".to_owned()),
+            ncpu: Some(8),
+            mem_total: Some(16_000_000_000),
+            driver: Some("overlay2".to_owned()),
+            server_version: Some("25.0.0".to_owned()),
+            ..Default::default()
+        };
+        let version = Version {
+            version: Some("26.1.0".to_owned()),
+            api_version: Some("1.45".to_owned()),
+            ..Default::default()
+        };
+        let volume = |size| Volume {
+            usage_data: Some(VolumeUsageData { size, ref_count: 1 }),
+            ..Default::default()
+        };
+        let df = SystemDataUsageResponse {
+            layers_size: Some(3_000_000),
+            images: Some(vec![ImageSummary::default(), ImageSummary::default()]),
+            volumes: Some(vec![volume(1000), volume(-1), volume(500)]),
+            build_cache: Some(vec![BuildCache {
+                size: Some(2000),
+                ..Default::default()
+            }]),
+            containers: None,
+        };
+        let result = HostInfo::new(&info, &version, &df);
+        assert_eq!(result.name, "host");
+        assert_eq!(result.cpus, 8);
+        assert_eq!(result.mem_total, ByteStats::new(16_000_000_000));
+        assert_eq!(result.storage_driver, "overlay2");
+        assert_eq!(result.version, "26.1.0");
+        assert_eq!(result.api_version, "1.45");
+        assert_eq!(
+            result.images,
+            DiskUsage {
+                count: 2,
+                size: ByteStats::new(3_000_000)
+            }
+        );
+        assert_eq!(
+            result.volumes,
+            DiskUsage {
+                count: 3,
+                size: ByteStats::new(1500)
+            }
+        );
+        assert_eq!(result.build_cache.size, ByteStats::new(2000));
+        assert_eq!(result.containers, DiskUsage::default());
+    }
+
+    #[test]
+    /// Latest stats of each container added together, and containers counted by state
+    fn test_overview_container_totals() {
+        let mut containers = (1..=3)
+            .map(|i| gen_item(&ContainerId::from(i.to_string().as_str()), i))
+            .collect::<Vec<_>>();
+        containers[0]
+            .cpu_stats
+            .push(0, CpuStats::new(150.0), 60_000);
+        containers[0]
+            .mem_stats
+            .push(0, ByteStats::new(4000), 60_000);
+        containers[1].cpu_stats.push(0, CpuStats::new(50.0), 60_000);
+        containers[1]
+            .mem_stats
+            .push(0, ByteStats::new(4000), 60_000);
+        containers[2].state = State::Exited;
+
+        let result = ContainerTotals::new(&containers);
+        assert_eq!(result.states, [(State::Running, 2), (State::Exited, 1)]);
+        assert_eq!(result.mem, ByteStats::new(8000));
+        assert_eq!(result.host_cpu(8), CpuStats::new(25.0));
+        assert!((result.mem_ratio(ByteStats::new(16000)) - 0.5).abs() < f64::EPSILON);
+        assert!(result.mem_ratio(ByteStats::new(0)).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    /// The last stats of a stopped container aren't included in the totals
+    fn test_overview_container_totals_stopped() {
+        let mut containers = (1..=2)
+            .map(|i| gen_item(&ContainerId::from(i.to_string().as_str()), i))
+            .collect::<Vec<_>>();
+        for container in &mut containers {
+            container.cpu_stats.push(0, CpuStats::new(100.0), 60_000);
+            container.mem_stats.push(0, ByteStats::new(4000), 60_000);
+        }
+        containers[1].state = State::Exited;
+
+        let result = ContainerTotals::new(&containers);
+        assert_eq!(result.states, [(State::Running, 1), (State::Exited, 1)]);
+        assert_eq!(result.host_cpu(1), CpuStats::new(100.0));
+        assert_eq!(result.mem, ByteStats::new(4000));
+    }
+}
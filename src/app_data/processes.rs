@@ -0,0 +1,327 @@
+use bollard::service::ContainerTopResponse;
+
+use super::{ByteStats, ContainerId, StatefulList};
+
+/// Arguments passed to `ps` on the Docker host, rss is in KiB
+pub const PS_ARGS: &str = "-eo pid,user,pcpu,rss,args";
+
+/// Run inside a container to list its processes, each line is a pid, and the nul separated command line, read from `/proc` so that `ps` isn't required
+pub const PROC_CMDLINES: [&str; 3] = [
+    "sh",
+    "-c",
+    r#"cd /proc && for pid in [0-9]*; do printf '%s ' "$pid"; cat "$pid/cmdline"; echo; done 2>/dev/null"#,
+];
+
+/// A single process running in a container, as reported by `docker top`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcessItem {
+    pub command: String,
+    pub cpu: String,
+    pub memory: String,
+    pub pid: String,
+    pub user: String,
+}
+
+/// The processes of a single container, refreshed on every docker update
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcessesData {
+    pub id: ContainerId,
+    pub name: String,
+    pub processes: StatefulList<ProcessItem>,
+}
+
+impl ProcessesData {
+    pub fn new(id: ContainerId, name: &str, top: &ContainerTopResponse) -> Self {
+        let mut output = Self {
+            id,
+            name: name.to_owned(),
+            processes: StatefulList::new(vec![]),
+        };
+        output.update(top);
+        output
+    }
+
+    /// Replace the processes, keeping the same pid selected if it's still running
+    /// Columns are found by their title, as the ps output differs between Docker hosts
+    pub fn update(&mut self, top: &ContainerTopResponse) {
+        let titles = top.titles.as_deref().unwrap_or_default();
+        let column = |names: &[&str]| titles.iter().position(|i| names.contains(&i.as_str()));
+        let pid = column(&["PID"]);
+        let user = column(&["USER", "UID"]);
+        let cpu = column(&["%CPU", "C"]);
+        let rss = column(&["RSS"]);
+        let command = column(&["COMMAND", "CMD"]);
+
+        let items = top
+            .processes
+            .as_deref()
+            .unwrap_or_default()
+            .iter()
+            .map(|row| {
+                let get = |index: Option<usize>| {
+                    index.and_then(|i| row.get(i)).cloned().unwrap_or_default()
+                };
+                ProcessItem {
+                    command: get(command),
+                    cpu: get(cpu),
+                    memory: get(rss)
+                        .parse::<u64>()
+                        .map(|i| ByteStats::new(i * 1024).to_string())
+                        .unwrap_or_default(),
+                    pid: get(pid),
+                    user: get(user),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let selected_pid = self.get_selected().map(|i| i.pid.clone());
+        let position = selected_pid.and_then(|pid| items.iter().position(|i| i.pid == pid));
+        self.processes.replace(items, position);
+    }
+
+    /// Get the currently selected process
+    pub fn get_selected(&self) -> Option<&ProcessItem> {
+        self.processes
+            .state
+            .selected()
+            .and_then(|i| self.processes.items.get(i))
+    }
+
+    /// The selected process, as its position amongst the processes with the same command, so that it can be found inside the container
+    pub fn get_selected_match(&self) -> Option<ProcessMatch> {
+        let selected = self.get_selected()?;
+        let mut pids = self
+            .processes
+            .items
+            .iter()
+            .filter(|i| i.command == selected.command)
+            .filter_map(|i| i.pid.parse::<u64>().ok())
+            .collect::<Vec<_>>();
+        pids.sort_unstable();
+        let pid = selected.pid.parse::<u64>().ok()?;
+        Some(ProcessMatch {
+            command: selected.command.clone(),
+            count: pids.len(),
+            index: pids.iter().position(|i| *i == pid)?,
+        })
+    }
+}
+
+/// A process of a container, identified by its command, and its position, ordered by pid, amongst the processes with the same command
+/// Pids inside the container differ from those on the Docker host, but are allocated in the same order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcessMatch {
+    pub command: String,
+    pub count: usize,
+    pub index: usize,
+}
+
+impl ProcessMatch {
+    /// Find the pid, as seen inside the container, from the output of `PROC_CMDLINES`
+    /// None when the number of processes with the same command differs, as the process can't then be told apart
+    pub fn find_pid(&self, listing: &str) -> Option<String> {
+        let mut pids = listing
+            .lines()
+            .filter_map(|i| i.split_once(' '))
+            .filter(|(_, cmdline)| cmdline.replace('\0', " ").trim_end() == self.command)
+            .filter_map(|(pid, _)| pid.parse::<u64>().ok())
+            .collect::<Vec<_>>();
+        pids.sort_unstable();
+        if pids.len() == self.count {
+            pids.get(self.index).map(ToString::to_string)
+        } else {
+            None
+        }
+    }
+}
+
+/// Get the pid, inside its own pid namespace, from the contents of `/proc/[pid]/status`
+fn parse_nspid(status: &str) -> Option<String> {
+    status
+        .lines()
+        .find_map(|i| i.strip_prefix("NSpid:"))
+        .and_then(|i| i.split_whitespace().last())
+        .map(std::borrow::ToOwned::to_owned)
+}
+
+/// Check if the contents of `/proc/[pid]/cgroup` belong to the given container, the full container id is part of the cgroup path with either cgroup driver
+fn in_container_cgroup(cgroup: &str, id: &ContainerId) -> bool {
+    !id.get().is_empty() && cgroup.lines().any(|i| i.contains(id.get()))
+}
+
+/// `docker top` reports pids as seen by the Docker host, convert into the pid as seen inside the container
+/// Only possible when oxker can see the processes of the Docker host, which is verified by the process being in the cgroup of the container
+/// None when using a remote host, or when oxker is in a container without access to the host pids, in which case the pid is found by `ProcessMatch` from inside the container
+pub fn container_pid(id: &ContainerId, host_pid: &str) -> Option<String> {
+    if host_pid.is_empty() || !host_pid.chars().all(|i| i.is_ascii_digit()) {
+        return None;
+    }
+    let proc = std::path::Path::new("/proc").join(host_pid);
+    let cgroup = std::fs::read_to_string(proc.join("cgroup")).ok()?;
+    if !in_container_cgroup(&cgroup, id) {
+        return None;
+    }
+    std::fs::read_to_string(proc.join("status"))
+        .ok()
+        .and_then(|i| parse_nspid(&i))
+}
+
+/// Validate a signal name, or number, e.g. TERM, SIGKILL, or 9, and remove any SIG prefix, as not every `kill` accepts it
+pub fn parse_signal(signal: &str) -> Result<String, String> {
+    let signal = signal.trim();
+    let signal = signal
+        .strip_prefix("SIG")
+        .or_else(|| signal.strip_prefix("sig"))
+        .unwrap_or(signal);
+    if signal.is_empty() || !signal.chars().all(|i| i.is_ascii_alphanumeric()) {
+        Err(format!("invalid signal: {signal}"))
+    } else {
+        Ok(signal.to_uppercase())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use bollard::service::ContainerTopResponse;
+
+    use crate::app_data::ContainerId;
+
+    use super::{
+        container_pid, in_container_cgroup, parse_nspid, parse_signal, ProcessMatch, ProcessesData,
+    };
+
+    fn gen_top(pids: &[&str]) -> ContainerTopResponse {
+        ContainerTopResponse {
+            titles: Some(
+                ["PID", "USER", "%CPU", "RSS", "COMMAND"]
+                    .map(std::borrow::ToOwned::to_owned)
+                    .to_vec(),
+            ),
+            processes: Some(
+                pids.iter()
+                    .map(|pid| {
+                        [*pid, "root", "1.5", "2048", "nginx -g daemon off;"]
+                            .map(std::borrow::ToOwned::to_owned)
+                            .to_vec()
+                    })
+                    .collect(),
+            ),
+        }
+    }
+
+    #[test]
+    /// Columns found by title, rss converted into bytes, and selection kept by pid
+    fn test_processes_update() {
+        let mut data = ProcessesData::new(ContainerId::from("abc"), "web", &gen_top(&["10", "20"]));
+        let item = data.get_selected().unwrap();
+        assert_eq!(item.pid, "10");
+        assert_eq!(item.user, "root");
+        assert_eq!(item.cpu, "1.5");
+        assert_eq!(item.memory, "2.10 MB");
+        assert_eq!(item.command, "nginx -g daemon off;");
+
+        data.processes.next();
+        assert_eq!(data.get_selected().unwrap().pid, "20");
+        data.update(&gen_top(&["5", "10", "20"]));
+        assert_eq!(data.get_selected().unwrap().pid, "20");
+
+        // Unknown columns are left empty
+        data.update(&ContainerTopResponse {
+            titles: Some(vec!["UID".to_owned(), "PID".to_owned(), "CMD".to_owned()]),
+            processes: Some(vec![vec![
+                "1000".to_owned(),
+                "7".to_owned(),
+                "sh".to_owned(),
+            ]]),
+        });
+        let item = data.get_selected().unwrap();
+        assert_eq!((item.pid.as_str(), item.user.as_str()), ("7", "1000"));
+        assert_eq!((item.cpu.as_str(), item.memory.as_str()), ("", ""));
+    }
+
+    #[test]
+    /// Last NSpid value is the pid inside the container
+    fn test_processes_parse_nspid() {
+        let status = "Name:\tnginx\nPid:\t4321\nNSpid:\t4321\t1\nPPid:\t4300\n";
+        assert_eq!(parse_nspid(status).unwrap(), "1");
+        assert!(parse_nspid("Name:\tnginx\n").is_none());
+    }
+
+    #[test]
+    /// Process only matched to a container when the cgroup path contains the container id
+    fn test_processes_in_container_cgroup() {
+        let id = ContainerId::from("4d2f0c8a9b1e");
+        let v2 = "0::/system.slice/docker-4d2f0c8a9b1e.scope\n";
+        let v1 = "12:memory:/docker/4d2f0c8a9b1e\n11:cpu,cpuacct:/docker/4d2f0c8a9b1e\n";
+        assert!(in_container_cgroup(v2, &id));
+        assert!(in_container_cgroup(v1, &id));
+        assert!(!in_container_cgroup(
+            "0::/user.slice/user-1000.slice\n",
+            &id
+        ));
+        assert!(!in_container_cgroup(v2, &ContainerId::from("")));
+    }
+
+    #[test]
+    /// No container pid when the host pid isn't a pid, or the local process isn't in the container, rather than returning the host pid
+    fn test_processes_container_pid_unverified() {
+        let id = ContainerId::from("4d2f0c8a9b1e");
+        assert!(container_pid(&id, "").is_none());
+        assert!(container_pid(&id, "../self").is_none());
+        assert!(container_pid(&id, &std::process::id().to_string()).is_none());
+    }
+
+    #[test]
+    /// Selected process matched by its position amongst the processes with the same command, ordered by pid
+    fn test_processes_get_selected_match() {
+        let mut data = ProcessesData::new(
+            ContainerId::from("abc"),
+            "web",
+            &gen_top(&["300", "1200", "95"]),
+        );
+        data.processes.state.select(Some(1));
+        assert_eq!(
+            data.get_selected_match(),
+            Some(ProcessMatch {
+                command: "nginx -g daemon off;".to_owned(),
+                count: 3,
+                index: 2,
+            })
+        );
+    }
+
+    #[test]
+    /// Pid found inside the container from the /proc listing, and None when the matching processes can't be told apart
+    fn test_processes_find_pid() {
+        let listing = "1 nginx\0-g\0daemon off;\0\n7 nginx: worker process\0\n8 nginx: worker process\0\n40 sh\0-c\0cd /proc\0\n41 \n";
+        let gen_match = |command: &str, count, index| ProcessMatch {
+            command: command.to_owned(),
+            count,
+            index,
+        };
+        assert_eq!(
+            gen_match("nginx -g daemon off;", 1, 0).find_pid(listing),
+            Some("1".to_owned())
+        );
+        assert_eq!(
+            gen_match("nginx: worker process", 2, 1).find_pid(listing),
+            Some("8".to_owned())
+        );
+        assert!(gen_match("nginx: worker process", 3, 1)
+            .find_pid(listing)
+            .is_none());
+        assert!(gen_match("redis-server", 1, 0).find_pid(listing).is_none());
+    }
+
+    #[test]
+    /// Signals validated, and SIG prefix removed
+    fn test_processes_parse_signal() {
+        assert_eq!(parse_signal("TERM").unwrap(), "TERM");
+        assert_eq!(parse_signal(" SIGKILL ").unwrap(), "KILL");
+        assert_eq!(parse_signal("sighup").unwrap(), "HUP");
+        assert_eq!(parse_signal("9").unwrap(), "9");
+        assert!(parse_signal("").is_err());
+        assert!(parse_signal("TERM; rm").is_err());
+    }
+}
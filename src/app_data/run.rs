@@ -0,0 +1,917 @@
+use std::collections::HashMap;
+
+use bollard::{
+    container::{Config, NetworkingConfig},
+    service::{
+        ContainerInspectResponse, EndpointSettings, HostConfig, PortBinding, PortMap,
+        RestartPolicy, RestartPolicyNameEnum,
+    },
+};
+
+use super::ContainerName;
+
+/// Split a comma separated string into trimmed, non empty, items
+/// A comma, or backslash, inside an item is escaped with a backslash, any other backslash is kept as is
+fn split_list(input: &str) -> Vec<String> {
+    let mut output = vec![];
+    let mut current = String::new();
+    let mut chars = input.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if matches!(chars.peek(), Some(',' | '\\')) => {
+                current.extend(chars.next());
+            }
+            ',' => output.push(std::mem::take(&mut current)),
+            c => current.push(c),
+        }
+    }
+    output.push(current);
+    output
+        .into_iter()
+        .map(|i| i.trim().to_owned())
+        .filter(|i| !i.is_empty())
+        .collect()
+}
+
+/// Join items into a comma separated string, escaping any comma, or backslash, so that split_list returns the same items
+fn join_list(items: &[String]) -> String {
+    items
+        .iter()
+        .map(|i| i.replace('\\', "\\\\").replace(',', "\\,"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Split a command into arguments, on whitespace, unless it's inside single or double quotes
+fn split_command(input: &str) -> Result<Vec<String>, String> {
+    let mut output = vec![];
+    let mut current = String::new();
+    let mut in_arg = false;
+    let mut quote = None;
+    for c in input.chars() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), c) => current.push(c),
+            (None, '"' | '\'') => {
+                quote = Some(c);
+                in_arg = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_arg {
+                    output.push(std::mem::take(&mut current));
+                    in_arg = false;
+                }
+            }
+            (None, c) => {
+                current.push(c);
+                in_arg = true;
+            }
+        }
+    }
+    if quote.is_some() {
+        return Err("unterminated quote in command".to_owned());
+    }
+    if in_arg {
+        output.push(current);
+    }
+    Ok(output)
+}
+
+/// Join command arguments, quoting any argument that contains whitespace, or is empty
+fn join_command(command: &[String]) -> String {
+    command
+        .iter()
+        .map(|i| {
+            if i.is_empty() || i.contains(char::is_whitespace) {
+                if i.contains('"') {
+                    format!("'{i}'")
+                } else {
+                    format!("\"{i}\"")
+                }
+            } else {
+                i.clone()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Parse a single port, and check that it's a valid port number
+fn parse_port(port: &str, input: &str) -> Result<String, String> {
+    port.parse::<u16>()
+        .map(|i| i.to_string())
+        .map_err(|_| format!("invalid port: {input}"))
+}
+
+/// Exposed ports, as expected by the Docker API, a map of `port/protocol` to an empty map
+#[allow(clippy::zero_sized_map_values)]
+type ExposedPorts = HashMap<String, HashMap<(), ()>>;
+
+/// Parse ports in the same format as `docker run -p`, `[[ip:]host:]container[/protocol]`
+/// Returns the exposed ports, and the port bindings
+#[allow(clippy::zero_sized_map_values)]
+fn parse_ports(input: &str) -> Result<(ExposedPorts, PortMap), String> {
+    let mut exposed = HashMap::new();
+    let mut bindings: PortMap = HashMap::new();
+    for item in split_list(input) {
+        let item = item.as_str();
+        let (ports, protocol) = item.split_once('/').unwrap_or((item, "tcp"));
+        if !["tcp", "udp", "sctp"].contains(&protocol) {
+            return Err(format!("invalid port protocol: {item}"));
+        }
+        let (host_ip, host_port, container_port) =
+            match ports.rsplitn(3, ':').collect::<Vec<_>>().as_slice() {
+                [container] => (None, None, *container),
+                [container, host] => (None, Some(*host), *container),
+                [container, host, ip] => (Some(*ip), Some(*host), *container),
+                _ => return Err(format!("invalid port: {item}")),
+            };
+        let key = format!("{}/{protocol}", parse_port(container_port, item)?);
+        let host_port = host_port
+            .filter(|i| !i.is_empty())
+            .map(|i| parse_port(i, item))
+            .transpose()?;
+        exposed.insert(key.clone(), HashMap::new());
+        bindings
+            .entry(key)
+            .or_insert_with(|| Some(vec![]))
+            .get_or_insert_with(Vec::new)
+            .push(PortBinding {
+                host_ip: host_ip.map(std::borrow::ToOwned::to_owned),
+                host_port,
+            });
+    }
+    Ok((exposed, bindings))
+}
+
+/// Parse comma separated `KEY=value` pairs, a value can contain an escaped comma, `\,`
+/// An unescaped comma is also allowed, as any item without an `=` is joined to the previous item
+fn parse_env(input: &str) -> Result<Vec<String>, String> {
+    let mut output: Vec<String> = vec![];
+    for item in split_list(input) {
+        if item.contains('=') {
+            output.push(item);
+        } else if let Some(last) = output.last_mut() {
+            last.push(',');
+            last.push_str(&item);
+        } else {
+            return Err(format!("invalid environment variable: {item}"));
+        }
+    }
+    Ok(output)
+}
+
+/// Parse volumes in the same format as `docker run -v`, `source:destination[:options]`
+fn parse_volumes(input: &str) -> Result<Vec<String>, String> {
+    split_list(input)
+        .into_iter()
+        .map(|i| {
+            if i.split(':').filter(|i| !i.is_empty()).count() >= 2 {
+                Ok(i)
+            } else {
+                Err(format!("invalid volume: {i}"))
+            }
+        })
+        .collect()
+}
+
+/// Parse a restart policy in the same format as `docker run --restart`, `no`, `always`, `unless-stopped`, or `on-failure[:max-retries]`
+pub fn parse_restart(input: &str) -> Result<Option<RestartPolicy>, String> {
+    let (name, retries) = input.split_once(':').unwrap_or((input, ""));
+    let name = match name {
+        "" => return Ok(None),
+        "no" => RestartPolicyNameEnum::NO,
+        "always" => RestartPolicyNameEnum::ALWAYS,
+        "unless-stopped" => RestartPolicyNameEnum::UNLESS_STOPPED,
+        "on-failure" => RestartPolicyNameEnum::ON_FAILURE,
+        _ => return Err(format!("invalid restart policy: {input}")),
+    };
+    let maximum_retry_count = if retries.is_empty() {
+        None
+    } else if name == RestartPolicyNameEnum::ON_FAILURE {
+        Some(
+            retries
+                .parse::<i64>()
+                .map_err(|_| format!("invalid restart policy: {input}"))?,
+        )
+    } else {
+        return Err(format!("invalid restart policy: {input}"));
+    };
+    Ok(Some(RestartPolicy {
+        name: Some(name),
+        maximum_retry_count,
+    }))
+}
+
+/// Parse a memory limit in the same format as `docker run --memory`, a number of bytes, with an optional `b`, `k`, `m`, or `g` suffix
+pub fn parse_memory(input: &str) -> Result<Option<i64>, String> {
+    if input.is_empty() {
+        return Ok(None);
+    }
+    let lower = input.to_lowercase();
+    let bytes = lower.strip_suffix('b').unwrap_or(&lower);
+    let (number, multiplier) = match bytes.strip_suffix(['k', 'm', 'g']) {
+        Some(number) if bytes.ends_with('k') => (number, 1 << 10),
+        Some(number) if bytes.ends_with('m') => (number, 1 << 20),
+        Some(number) => (number, 1 << 30),
+        None => (bytes, 1),
+    };
+    number
+        .parse::<i64>()
+        .ok()
+        .filter(|i| *i > 0)
+        .and_then(|i| i.checked_mul(multiplier))
+        .map(Some)
+        .ok_or_else(|| format!("invalid memory limit: {input}"))
+}
+
+/// Parse a cpu limit in the same format as `docker run --cpus`, a decimal number of cpus, returned as billionths of a cpu
+pub fn parse_cpus(input: &str) -> Result<Option<i64>, String> {
+    if input.is_empty() {
+        return Ok(None);
+    }
+    let error = || format!("invalid cpu limit: {input}");
+    let (whole, fraction) = input.split_once('.').unwrap_or((input, ""));
+    if fraction.len() > 9 || !fraction.chars().all(|i| i.is_ascii_digit()) {
+        return Err(error());
+    }
+    let whole = if whole.is_empty() {
+        0
+    } else {
+        whole.parse::<i64>().map_err(|_| error())?
+    };
+    let fraction = format!("{fraction:0<9}")
+        .parse::<i64>()
+        .map_err(|_| error())?;
+    whole
+        .checked_mul(1_000_000_000)
+        .and_then(|i| i.checked_add(fraction))
+        .filter(|i| *i > 0)
+        .map(Some)
+        .ok_or_else(error)
+}
+
+/// Format a memory limit, in bytes, using the largest suffix that the limit is a whole multiple of
+pub fn format_memory(bytes: i64) -> String {
+    if bytes <= 0 {
+        return String::new();
+    }
+    [("g", 1 << 30), ("m", 1 << 20), ("k", 1 << 10)]
+        .iter()
+        .find(|(_, size)| bytes % size == 0)
+        .map_or_else(
+            || bytes.to_string(),
+            |(suffix, size)| format!("{}{suffix}", bytes / size),
+        )
+}
+
+/// Format a cpu limit, in billionths of a cpu, as a decimal number of cpus
+pub fn format_cpus(nano_cpus: i64) -> String {
+    if nano_cpus <= 0 {
+        return String::new();
+    }
+    let whole = nano_cpus / 1_000_000_000;
+    let fraction = nano_cpus % 1_000_000_000;
+    if fraction == 0 {
+        whole.to_string()
+    } else {
+        format!("{whole}.{}", format!("{fraction:09}").trim_end_matches('0'))
+    }
+}
+
+/// Format a restart policy in the same format as `docker run --restart`, no policy is an empty string
+pub fn format_restart(policy: Option<&RestartPolicy>) -> String {
+    policy.map_or_else(String::new, |policy| {
+        match (policy.name, policy.maximum_retry_count) {
+            (Some(RestartPolicyNameEnum::ON_FAILURE), Some(count)) if count > 0 => {
+                format!("on-failure:{count}")
+            }
+            (Some(RestartPolicyNameEnum::EMPTY | RestartPolicyNameEnum::NO) | None, _) => {
+                String::new()
+            }
+            (Some(name), _) => name.to_string(),
+        }
+    })
+}
+
+/// The period, in microseconds, that a cpu quota is measured over, the Docker default
+pub const CPU_PERIOD: i64 = 100_000;
+
+/// Convert a cpu limit, in billionths of a cpu, into a quota, in microseconds per `CPU_PERIOD`
+pub const fn cpu_quota(nano_cpus: i64) -> i64 {
+    nano_cpus / (1_000_000_000 / CPU_PERIOD)
+}
+
+/// The cpu limit of a container, in billionths of a cpu, whether set directly, or as a quota and period
+pub fn host_nano_cpus(host_config: &HostConfig) -> i64 {
+    match (
+        host_config.nano_cpus,
+        host_config.cpu_quota,
+        host_config.cpu_period,
+    ) {
+        (Some(nano_cpus), _, _) if nano_cpus > 0 => nano_cpus,
+        (_, Some(quota), period) if quota > 0 => {
+            quota.saturating_mul(1_000_000_000) / period.filter(|i| *i > 0).unwrap_or(CPU_PERIOD)
+        }
+        _ => 0,
+    }
+}
+
+/// Settings used to create, and then start, a new container, as entered into the run, or recreate, form
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RunConfig {
+    pub command: String,
+    pub cpus: String,
+    pub env: String,
+    pub image: String,
+    pub memory: String,
+    pub name: String,
+    pub ports: String,
+    pub restart: String,
+    pub volumes: String,
+}
+
+impl RunConfig {
+    /// Convert into a Docker create container config, or return a description of the first invalid setting
+    /// A tty is always allocated, and stdin kept open, as with `docker run -dit`, so that shell based images keep running
+    pub fn to_config(&self) -> Result<Config<String>, String> {
+        if !self.name.is_empty() {
+            ContainerName::validate(&self.name)?;
+        }
+        self.apply(Config {
+            tty: Some(true),
+            open_stdin: Some(true),
+            ..Default::default()
+        })
+    }
+
+    /// Replace the editable settings of an existing config, all other settings are left as they are
+    /// The memory swap limit is reset when the memory limit is changed, as it can't be less than the memory limit
+    pub fn apply(&self, mut config: Config<String>) -> Result<Config<String>, String> {
+        if self.image.is_empty() {
+            return Err("an image is required".to_owned());
+        }
+        let (exposed_ports, port_bindings) = parse_ports(&self.ports)?;
+        let env = parse_env(&self.env)?;
+        let binds = parse_volumes(&self.volumes)?;
+        let restart_policy = parse_restart(&self.restart)?;
+        let cmd = split_command(&self.command)?;
+        let memory = parse_memory(&self.memory)?;
+        let nano_cpus = parse_cpus(&self.cpus)?;
+
+        config.image = Some(self.image.clone());
+        config.cmd = (!cmd.is_empty()).then_some(cmd);
+        config.env = (!env.is_empty()).then_some(env);
+        config.exposed_ports = (!exposed_ports.is_empty()).then_some(exposed_ports);
+
+        let mut host_config = config.host_config.take().unwrap_or_default();
+        if host_config.memory.filter(|i| *i > 0) != memory {
+            host_config.memory_swap = None;
+        }
+        host_config.binds = (!binds.is_empty()).then_some(binds);
+        host_config.port_bindings = (!port_bindings.is_empty()).then_some(port_bindings);
+        host_config.restart_policy = restart_policy;
+        host_config.memory = memory;
+        host_config.nano_cpus = None;
+        host_config.cpu_period = nano_cpus.map(|_| CPU_PERIOD);
+        host_config.cpu_quota = nano_cpus.map(cpu_quota);
+        config.host_config = Some(host_config);
+        Ok(config)
+    }
+}
+
+/// The user configurable settings of a network endpoint, the addresses and ids assigned by Docker are dropped, as is the alias of the container short id
+fn endpoint_settings(settings: EndpointSettings, id: &str) -> EndpointSettings {
+    EndpointSettings {
+        aliases: settings
+            .aliases
+            .map(|i| i.into_iter().filter(|i| !id.starts_with(i)).collect()),
+        driver_opts: settings.driver_opts,
+        ipam_config: settings.ipam_config,
+        links: settings.links,
+        ..Default::default()
+    }
+}
+
+/// The config needed to create an exact replacement of an existing container, along with any additional networks it needs to be connected to once created
+/// Only a single network can be set at creation, the one used by the network mode, as required by older Docker versions
+/// A hostname that is just the short container id, as Docker sets by default, is removed, so that the new container gets its own id as a hostname
+pub fn existing_config(
+    inspect: &ContainerInspectResponse,
+) -> (Config<String>, Vec<(String, EndpointSettings)>) {
+    let id = inspect.id.clone().unwrap_or_default();
+    let mut config = Config::from(inspect.config.clone().unwrap_or_default());
+    if config
+        .hostname
+        .as_ref()
+        .is_some_and(|i| !i.is_empty() && id.starts_with(i.as_str()))
+    {
+        config.hostname = None;
+    }
+    config.host_config.clone_from(&inspect.host_config);
+
+    let network_mode = match config
+        .host_config
+        .as_ref()
+        .and_then(|i| i.network_mode.as_deref())
+    {
+        None | Some("default") => "bridge".to_owned(),
+        Some(mode) => mode.to_owned(),
+    };
+    let mut networks = inspect
+        .network_settings
+        .as_ref()
+        .and_then(|i| i.networks.clone())
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(name, settings)| (name, endpoint_settings(settings, &id)))
+        .collect::<Vec<_>>();
+    networks.sort_by(|a, b| a.0.cmp(&b.0));
+
+    if let Some(index) = networks.iter().position(|(name, _)| name == &network_mode) {
+        let (name, settings) = networks.remove(index);
+        config.networking_config = Some(NetworkingConfig {
+            endpoints_config: HashMap::from([(name, settings)]),
+        });
+    }
+    (config, networks)
+}
+
+/// Use the settings of an existing container, the name is suffixed with `_copy`, as names need to be unique
+impl From<&ContainerInspectResponse> for RunConfig {
+    fn from(inspect: &ContainerInspectResponse) -> Self {
+        let config = inspect.config.as_ref();
+        let host_config = inspect.host_config.as_ref();
+
+        let mut ports = host_config
+            .and_then(|i| i.port_bindings.as_ref())
+            .map_or_else(Vec::new, |bindings| {
+                bindings
+                    .iter()
+                    .flat_map(|(key, bindings)| {
+                        let container_port = key.trim_end_matches("/tcp");
+                        bindings
+                            .as_ref()
+                            .map_or_else(Vec::new, Clone::clone)
+                            .into_iter()
+                            .map(move |binding| {
+                                let host_ip = binding.host_ip.unwrap_or_default();
+                                let host_port = binding.host_port.unwrap_or_default();
+                                match (host_ip.is_empty(), host_port.is_empty()) {
+                                    (_, true) => container_port.to_owned(),
+                                    (true, false) => format!("{host_port}:{container_port}"),
+                                    (false, false) => {
+                                        format!("{host_ip}:{host_port}:{container_port}")
+                                    }
+                                }
+                            })
+                    })
+                    .collect::<Vec<_>>()
+            });
+        ports.sort();
+
+        Self {
+            command: config
+                .and_then(|i| i.cmd.as_ref())
+                .map_or_else(String::new, |i| join_command(i)),
+            cpus: format_cpus(host_config.map_or(0, host_nano_cpus)),
+            env: config
+                .and_then(|i| i.env.as_ref())
+                .map_or_else(String::new, |i| join_list(i)),
+            image: config.and_then(|i| i.image.clone()).unwrap_or_default(),
+            memory: format_memory(host_config.and_then(|i| i.memory).unwrap_or_default()),
+            name: inspect.name.as_ref().map_or_else(String::new, |i| {
+                format!("{}_copy", i.trim_start_matches('/'))
+            }),
+            ports: join_list(&ports),
+            restart: format_restart(host_config.and_then(|i| i.restart_policy.as_ref())),
+            volumes: host_config
+                .and_then(|i| i.binds.as_ref())
+                .map_or_else(String::new, |i| join_list(i)),
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::collections::HashMap;
+
+    use bollard::{
+        container::Config,
+        service::{
+            ContainerConfig, ContainerInspectResponse, EndpointSettings, HostConfig,
+            NetworkSettings, PortBinding, RestartPolicy, RestartPolicyNameEnum,
+        },
+    };
+
+    use super::{
+        existing_config, format_cpus, format_memory, host_nano_cpus, join_command, join_list,
+        parse_cpus, parse_env, parse_memory, parse_ports, parse_restart, parse_volumes,
+        split_command, RunConfig,
+    };
+
+    #[test]
+    /// Commands split on whitespace, except when quoted, and joined back together
+    fn test_run_split_command() {
+        assert_eq!(split_command("").unwrap(), Vec::<String>::new());
+        assert_eq!(
+            split_command("sleep   infinity").unwrap(),
+            ["sleep", "infinity"]
+        );
+        assert_eq!(
+            split_command(r#"sh -c "echo hello world" ''"#).unwrap(),
+            ["sh", "-c", "echo hello world", ""]
+        );
+        assert_eq!(
+            split_command(r#"echo 'say "hi"'"#).unwrap(),
+            ["echo", r#"say "hi""#]
+        );
+        assert!(split_command("echo \"oops").is_err());
+
+        for input in ["sleep infinity", r#"sh -c "echo hello world" """#] {
+            let split = split_command(input).unwrap();
+            assert_eq!(join_command(&split), input);
+        }
+    }
+
+    #[test]
+    /// Ports parsed into exposed ports and bindings, invalid ports are an error
+    fn test_run_parse_ports() {
+        let (exposed, bindings) =
+            parse_ports("8080:80, 127.0.0.1:5353:53/udp, 3000, 8081:80").unwrap();
+        let mut keys = exposed.keys().cloned().collect::<Vec<_>>();
+        keys.sort();
+        assert_eq!(keys, ["3000/tcp", "53/udp", "80/tcp"]);
+        assert_eq!(
+            bindings.get("80/tcp").unwrap().as_ref().unwrap(),
+            &[
+                PortBinding {
+                    host_ip: None,
+                    host_port: Some("8080".to_owned()),
+                },
+                PortBinding {
+                    host_ip: None,
+                    host_port: Some("8081".to_owned()),
+                }
+            ]
+        );
+        assert_eq!(
+            bindings.get("53/udp").unwrap().as_ref().unwrap(),
+            &[PortBinding {
+                host_ip: Some("127.0.0.1".to_owned()),
+                host_port: Some("5353".to_owned()),
+            }]
+        );
+        assert_eq!(
+            bindings.get("3000/tcp").unwrap().as_ref().unwrap(),
+            &[PortBinding::default()]
+        );
+
+        assert!(parse_ports("").unwrap().0.is_empty());
+        for input in ["abc", "8080:80/xyz", "70000:80", "x:80"] {
+            assert!(parse_ports(input).is_err(), "{input}");
+        }
+    }
+
+    #[test]
+    /// Environment variables, volumes, and restart policies parsed, invalid values are an error
+    fn test_run_parse_env_volumes_restart() {
+        assert_eq!(parse_env("A=1, B=a,b, C=").unwrap(), ["A=1", "B=a,b", "C="]);
+        assert_eq!(
+            parse_env(r"DSN=a\, b, WIN=C:\tmp").unwrap(),
+            ["DSN=a, b", r"WIN=C:\tmp"]
+        );
+        assert!(parse_env("no_equals").is_err());
+
+        assert_eq!(
+            parse_volumes("data:/data, /host:/container:ro").unwrap(),
+            ["data:/data", "/host:/container:ro"]
+        );
+        assert!(parse_volumes("/data").is_err());
+        assert!(parse_volumes(":/data").is_err());
+
+        assert_eq!(parse_restart("").unwrap(), None);
+        assert_eq!(
+            parse_restart("unless-stopped").unwrap().unwrap().name,
+            Some(RestartPolicyNameEnum::UNLESS_STOPPED)
+        );
+        assert_eq!(
+            parse_restart("on-failure:3").unwrap(),
+            Some(RestartPolicy {
+                name: Some(RestartPolicyNameEnum::ON_FAILURE),
+                maximum_retry_count: Some(3),
+            })
+        );
+        for input in ["sometimes", "always:3", "on-failure:x"] {
+            assert!(parse_restart(input).is_err(), "{input}");
+        }
+    }
+
+    #[test]
+    /// Image is required, and all settings converted into a create container config
+    fn test_run_to_config() {
+        assert!(RunConfig::default().to_config().is_err());
+        assert!(RunConfig {
+            image: "redis".to_owned(),
+            name: "my cache".to_owned(),
+            ..Default::default()
+        }
+        .to_config()
+        .is_err());
+
+        let config = RunConfig {
+            command: "sleep infinity".to_owned(),
+            cpus: "0.5".to_owned(),
+            env: "A=1".to_owned(),
+            image: "nicolaka/netshoot".to_owned(),
+            memory: "512m".to_owned(),
+            name: String::new(),
+            ports: "8080:80".to_owned(),
+            restart: "always".to_owned(),
+            volumes: "data:/data".to_owned(),
+        }
+        .to_config()
+        .unwrap();
+        assert_eq!(config.image, Some("nicolaka/netshoot".to_owned()));
+        assert_eq!(
+            config.cmd,
+            Some(vec!["sleep".to_owned(), "infinity".to_owned()])
+        );
+        assert_eq!(config.env, Some(vec!["A=1".to_owned()]));
+        assert_eq!(config.tty, Some(true));
+        let host_config = config.host_config.unwrap();
+        assert_eq!(host_config.binds, Some(vec!["data:/data".to_owned()]));
+        assert!(host_config.port_bindings.unwrap().contains_key("80/tcp"));
+        assert_eq!(
+            host_config.restart_policy.unwrap().name,
+            Some(RestartPolicyNameEnum::ALWAYS)
+        );
+        assert_eq!(host_config.memory, Some(512 * 1024 * 1024));
+        assert_eq!(host_config.nano_cpus, None);
+        assert_eq!(host_config.cpu_period, Some(100_000));
+        assert_eq!(host_config.cpu_quota, Some(50_000));
+
+        let config = RunConfig {
+            image: "redis".to_owned(),
+            ..Default::default()
+        }
+        .to_config()
+        .unwrap();
+        assert_eq!(config.cmd, None);
+        assert_eq!(config.env, None);
+        assert_eq!(config.exposed_ports, None);
+    }
+
+    #[test]
+    /// Memory and cpu limits parsed, and formatted back, invalid limits are an error
+    fn test_run_parse_limits() {
+        assert_eq!(parse_memory("").unwrap(), None);
+        assert_eq!(parse_memory("1024").unwrap(), Some(1024));
+        assert_eq!(parse_memory("100b").unwrap(), Some(100));
+        assert_eq!(parse_memory("2k").unwrap(), Some(2048));
+        assert_eq!(parse_memory("512MB").unwrap(), Some(512 * 1024 * 1024));
+        assert_eq!(parse_memory("1g").unwrap(), Some(1024 * 1024 * 1024));
+        for input in ["m", "-1m", "0", "1.5g", "1t", "99999999999g"] {
+            assert!(parse_memory(input).is_err(), "{input}");
+        }
+
+        assert_eq!(parse_cpus("").unwrap(), None);
+        assert_eq!(parse_cpus("2").unwrap(), Some(2_000_000_000));
+        assert_eq!(parse_cpus("1.5").unwrap(), Some(1_500_000_000));
+        assert_eq!(parse_cpus(".25").unwrap(), Some(250_000_000));
+        for input in ["0", "0.0", "abc", "1.x", "-1", "0.0000000001"] {
+            assert!(parse_cpus(input).is_err(), "{input}");
+        }
+
+        assert_eq!(format_memory(0), "");
+        assert_eq!(format_memory(1000), "1000");
+        assert_eq!(format_memory(1536 * 1024), "1536k");
+        assert_eq!(format_memory(512 * 1024 * 1024), "512m");
+        assert_eq!(format_memory(2 * 1024 * 1024 * 1024), "2g");
+        assert_eq!(format_cpus(0), "");
+        assert_eq!(format_cpus(2_000_000_000), "2");
+        assert_eq!(format_cpus(1_500_000_000), "1.5");
+        assert_eq!(format_cpus(250_000_000), "0.25");
+
+        let gen_host_config = |nano_cpus, cpu_quota, cpu_period| HostConfig {
+            nano_cpus,
+            cpu_quota,
+            cpu_period,
+            ..Default::default()
+        };
+        assert_eq!(host_nano_cpus(&HostConfig::default()), 0);
+        assert_eq!(
+            host_nano_cpus(&gen_host_config(Some(1_500_000_000), None, None)),
+            1_500_000_000
+        );
+        assert_eq!(
+            host_nano_cpus(&gen_host_config(Some(0), Some(50_000), Some(100_000))),
+            500_000_000
+        );
+        assert_eq!(
+            host_nano_cpus(&gen_host_config(None, Some(50_000), Some(50_000))),
+            1_000_000_000
+        );
+        assert_eq!(
+            host_nano_cpus(&gen_host_config(None, Some(200_000), None)),
+            2_000_000_000
+        );
+    }
+
+    #[test]
+    /// Only the editable settings of an existing config are replaced, swap is reset only when the memory limit changes
+    fn test_run_apply() {
+        let existing = Config {
+            image: Some("redis:6".to_owned()),
+            env: Some(vec!["A=1".to_owned()]),
+            hostname: Some("cache".to_owned()),
+            host_config: Some(HostConfig {
+                memory: Some(512 * 1024 * 1024),
+                memory_swap: Some(1024 * 1024 * 1024),
+                network_mode: Some("backend".to_owned()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let run_config = RunConfig {
+            image: "redis:7".to_owned(),
+            memory: "512m".to_owned(),
+            ..Default::default()
+        };
+
+        let config = run_config.apply(existing.clone()).unwrap();
+        assert_eq!(config.image, Some("redis:7".to_owned()));
+        assert_eq!(config.env, None);
+        assert_eq!(config.hostname, Some("cache".to_owned()));
+        assert_eq!(config.tty, None);
+        let host_config = config.host_config.unwrap();
+        assert_eq!(host_config.network_mode, Some("backend".to_owned()));
+        assert_eq!(host_config.memory_swap, Some(1024 * 1024 * 1024));
+
+        let config = RunConfig {
+            memory: "1g".to_owned(),
+            ..run_config
+        }
+        .apply(existing)
+        .unwrap();
+        let host_config = config.host_config.unwrap();
+        assert_eq!(host_config.memory, Some(1024 * 1024 * 1024));
+        assert_eq!(host_config.memory_swap, None);
+    }
+
+    #[test]
+    /// Existing config keeps all settings, a default hostname is removed, and networks split into the network mode network and additional networks
+    fn test_run_existing_config() {
+        let gen_endpoint = |aliases: &[&str]| EndpointSettings {
+            aliases: Some(aliases.iter().map(|i| (*i).to_owned()).collect()),
+            ip_address: Some("172.20.0.2".to_owned()),
+            network_id: Some("network_id".to_owned()),
+            ..Default::default()
+        };
+        let inspect = ContainerInspectResponse {
+            id: Some("abcdef1234567890".to_owned()),
+            config: Some(ContainerConfig {
+                hostname: Some("abcdef123456".to_owned()),
+                image: Some("redis:7".to_owned()),
+                user: Some("redis".to_owned()),
+                ..Default::default()
+            }),
+            host_config: Some(HostConfig {
+                network_mode: Some("backend".to_owned()),
+                ..Default::default()
+            }),
+            network_settings: Some(NetworkSettings {
+                networks: Some(HashMap::from([
+                    (
+                        "backend".to_owned(),
+                        gen_endpoint(&["abcdef123456", "cache"]),
+                    ),
+                    ("monitoring".to_owned(), gen_endpoint(&[])),
+                ])),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let (config, networks) = existing_config(&inspect);
+        assert_eq!(config.hostname, None);
+        assert_eq!(config.user, Some("redis".to_owned()));
+        assert_eq!(
+            config.host_config.unwrap().network_mode,
+            Some("backend".to_owned())
+        );
+        assert_eq!(
+            config.networking_config.unwrap().endpoints_config,
+            HashMap::from([(
+                "backend".to_owned(),
+                EndpointSettings {
+                    aliases: Some(vec!["cache".to_owned()]),
+                    ..Default::default()
+                }
+            )])
+        );
+        assert_eq!(
+            networks,
+            [(
+                "monitoring".to_owned(),
+                EndpointSettings {
+                    aliases: Some(vec![]),
+                    ..Default::default()
+                }
+            )]
+        );
+
+        let (config, networks) = existing_config(&ContainerInspectResponse::default());
+        assert!(config.networking_config.is_none());
+        assert!(networks.is_empty());
+    }
+
+    #[test]
+    /// Settings cloned from an inspected container, and converted back into an equivalent config
+    fn test_run_from_inspect() {
+        let inspect = ContainerInspectResponse {
+            name: Some("/redis".to_owned()),
+            config: Some(ContainerConfig {
+                image: Some("redis:7".to_owned()),
+                cmd: Some(vec!["redis-server".to_owned(), "--save 60 1".to_owned()]),
+                env: Some(vec!["A=1".to_owned(), "B=2".to_owned()]),
+                ..Default::default()
+            }),
+            host_config: Some(HostConfig {
+                binds: Some(vec!["redis_data:/data".to_owned()]),
+                port_bindings: Some(HashMap::from([
+                    (
+                        "6379/tcp".to_owned(),
+                        Some(vec![PortBinding {
+                            host_ip: Some(String::new()),
+                            host_port: Some("6379".to_owned()),
+                        }]),
+                    ),
+                    (
+                        "53/udp".to_owned(),
+                        Some(vec![PortBinding {
+                            host_ip: Some("127.0.0.1".to_owned()),
+                            host_port: Some("5353".to_owned()),
+                        }]),
+                    ),
+                ])),
+                restart_policy: Some(RestartPolicy {
+                    name: Some(RestartPolicyNameEnum::ON_FAILURE),
+                    maximum_retry_count: Some(5),
+                }),
+                memory: Some(256 * 1024 * 1024),
+                nano_cpus: Some(1_500_000_000),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let result = RunConfig::from(&inspect);
+        assert_eq!(
+            result,
+            RunConfig {
+                command: r#"redis-server "--save 60 1""#.to_owned(),
+                cpus: "1.5".to_owned(),
+                env: "A=1, B=2".to_owned(),
+                image: "redis:7".to_owned(),
+                memory: "256m".to_owned(),
+                name: "redis_copy".to_owned(),
+                ports: "127.0.0.1:5353:53/udp, 6379:6379".to_owned(),
+                restart: "on-failure:5".to_owned(),
+                volumes: "redis_data:/data".to_owned(),
+            }
+        );
+        assert!(result.to_config().is_ok());
+
+        let result = RunConfig::from(&ContainerInspectResponse::default());
+        assert_eq!(result, RunConfig::default());
+    }
+
+    #[test]
+    /// Env vars, and volumes, containing commas, or backslashes, are unchanged when joined into the form, and parsed again
+    fn test_run_join_list_round_trip() {
+        let env = [
+            "JAVA_OPTS=-Xms1g,-Xmx2g",
+            "DSN=a, b",
+            r"PATTERN=\d+\,",
+            "EMPTY=",
+        ]
+        .map(ToOwned::to_owned);
+        assert_eq!(parse_env(&join_list(&env)).unwrap(), env);
+
+        let volumes = ["/host/a,b:/data".to_owned(), "data:/data:ro".to_owned()];
+        assert_eq!(parse_volumes(&join_list(&volumes)).unwrap(), volumes);
+
+        let inspect = ContainerInspectResponse {
+            config: Some(ContainerConfig {
+                env: Some(env.to_vec()),
+                image: Some("redis:7".to_owned()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let config = RunConfig::from(&inspect);
+        assert_eq!(config.to_config().unwrap().env, Some(env.to_vec()));
+    }
+}
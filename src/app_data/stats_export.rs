@@ -0,0 +1,305 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use super::{csv_field, format_timestamp, validate_file_name, ContainerItem, Stats};
+
+/// The file formats the stats history can be saved as
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatsFormat {
+    Csv,
+    Json,
+}
+
+impl StatsFormat {
+    pub const fn extension(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Json => "json",
+        }
+    }
+}
+
+const CSV_HEADER: &str = "id,name,timestamp,cpu_percent,memory_bytes,memory_limit_bytes,rx_bytes_per_second,tx_bytes_per_second";
+
+/// Round a cpu percentage to two decimal places, the same precision as shown in the containers panel
+pub fn round_cpu(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+/// A single sample of the stats history of a container, a stat without a sample at the timestamp is None
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StatsSample {
+    pub timestamp: String,
+    pub cpu_percent: Option<f64>,
+    pub memory_bytes: Option<u64>,
+    pub rx_bytes_per_second: Option<u64>,
+    pub tx_bytes_per_second: Option<u64>,
+}
+
+impl StatsSample {
+    fn new(timestamp: u64) -> Self {
+        Self {
+            timestamp: format_timestamp(timestamp),
+            cpu_percent: None,
+            memory_bytes: None,
+            rx_bytes_per_second: None,
+            tx_bytes_per_second: None,
+        }
+    }
+}
+
+/// The stats history of a container, the memory limit is the current limit, as it isn't kept in the history
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ContainerStats {
+    pub id: String,
+    pub name: String,
+    pub memory_limit_bytes: u64,
+    pub samples: Vec<StatsSample>,
+}
+
+impl From<&ContainerItem> for ContainerStats {
+    /// Join the cpu, memory, rx, and tx histories by timestamp, oldest first
+    fn from(item: &ContainerItem) -> Self {
+        fn sample(samples: &mut BTreeMap<u64, StatsSample>, timestamp: u64) -> &mut StatsSample {
+            samples
+                .entry(timestamp)
+                .or_insert_with(|| StatsSample::new(timestamp))
+        }
+        let mut samples = BTreeMap::new();
+        for (timestamp, value) in item.cpu_stats.iter() {
+            sample(&mut samples, *timestamp).cpu_percent = Some(round_cpu(value.get_value()));
+        }
+        for (timestamp, value) in item.mem_stats.iter() {
+            sample(&mut samples, *timestamp).memory_bytes = Some(value.get());
+        }
+        for (timestamp, value) in item.rx_stats.iter() {
+            sample(&mut samples, *timestamp).rx_bytes_per_second = Some(value.get());
+        }
+        for (timestamp, value) in item.tx_stats.iter() {
+            sample(&mut samples, *timestamp).tx_bytes_per_second = Some(value.get());
+        }
+        Self {
+            id: item.id.get().to_owned(),
+            name: item.name.get().to_owned(),
+            memory_limit_bytes: item.mem_limit.get(),
+            samples: samples.into_values().collect(),
+        }
+    }
+}
+
+/// Settings of a stats export, as entered into the stats export form
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StatsExportConfig {
+    pub containers: String,
+    pub file: String,
+    pub format: String,
+}
+
+impl StatsExportConfig {
+    /// Convert into a stats export, or return a description of the first invalid setting
+    pub fn to_export(&self) -> Result<StatsExport, String> {
+        validate_file_name(&self.file)?;
+        let all = match self.containers.to_lowercase().as_str() {
+            "" | "selected" => false,
+            "all" => true,
+            _ => return Err(format!("invalid containers: {}", self.containers)),
+        };
+        let format = match self.format.to_lowercase().as_str() {
+            "" | "csv" => StatsFormat::Csv,
+            "json" => StatsFormat::Json,
+            _ => return Err(format!("invalid format: {}", self.format)),
+        };
+        Ok(StatsExport {
+            all,
+            file: self.file.clone(),
+            format,
+        })
+    }
+}
+
+/// A validated stats export, of either the selected container, or all containers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatsExport {
+    pub all: bool,
+    pub file: String,
+    pub format: StatsFormat,
+}
+
+impl StatsExport {
+    /// The file name, with the extension of the format added, unless already present
+    pub fn file_name(&self) -> String {
+        let extension = format!(".{}", self.format.extension());
+        if self.file.ends_with(&extension) {
+            self.file.clone()
+        } else {
+            format!("{}{extension}", self.file)
+        }
+    }
+
+    /// The contents of the file, csv has a row for each sample of each container, json is a list of containers, each with a list of samples
+    pub fn output(&self, containers: &[ContainerStats]) -> String {
+        match self.format {
+            StatsFormat::Csv => {
+                let text = |value: Option<String>| value.unwrap_or_default();
+                let rows = containers.iter().flat_map(|container| {
+                    container.samples.iter().map(move |sample| {
+                        format!(
+                            "{},{},{},{},{},{},{},{}",
+                            container.id,
+                            csv_field(&container.name),
+                            sample.timestamp,
+                            text(sample.cpu_percent.map(|i| format!("{i:.2}"))),
+                            text(sample.memory_bytes.map(|i| i.to_string())),
+                            container.memory_limit_bytes,
+                            text(sample.rx_bytes_per_second.map(|i| i.to_string())),
+                            text(sample.tx_bytes_per_second.map(|i| i.to_string())),
+                        )
+                    })
+                });
+                std::iter::once(CSV_HEADER.to_owned())
+                    .chain(rows)
+                    .map(|row| row + "\n")
+                    .collect()
+            }
+            StatsFormat::Json => serde_json::to_string_pretty(containers).unwrap_or_default(),
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use crate::{
+        app_data::{ByteStats, ContainerId, CpuStats, IoCounters},
+        tests::gen_item,
+    };
+
+    use super::{ContainerStats, StatsExport, StatsExportConfig, StatsFormat};
+
+    /// Minute of the day used as the timestamp of the samples, 2023-01-14T19:13:00Z
+    const NOW: u64 = 1_673_723_580_000;
+
+    fn gen_stats() -> ContainerStats {
+        let mut item = gen_item(&ContainerId::from("1"), 1);
+        item.mem_limit = ByteStats::new(4096);
+        for (index, cpu) in [1.234, 2.468, 3.702].into_iter().enumerate() {
+            let index = index as u64;
+            let timestamp = NOW + index * 1000;
+            item.cpu_stats.push(timestamp, CpuStats::new(cpu), 60_000);
+            item.mem_stats
+                .push(timestamp, ByteStats::new(1024 * (index + 1)), 60_000);
+            item.push_io(
+                IoCounters {
+                    rx: 100 * index,
+                    tx: 10 * index,
+                    ..IoCounters::default()
+                },
+                timestamp,
+                60_000,
+            );
+        }
+        ContainerStats::from(&item)
+    }
+
+    #[test]
+    /// Every stat history joined by timestamp, with rx & tx missing from the first sample, as a rate needs two samples
+    fn test_stats_export_container_stats() {
+        let stats = gen_stats();
+        assert_eq!(stats.id, "1");
+        assert_eq!(stats.name, "container_1");
+        assert_eq!(stats.memory_limit_bytes, 4096);
+        assert_eq!(stats.samples.len(), 3);
+        assert_eq!(stats.samples[0].timestamp, "2023-01-14T19:13:00.000Z");
+        assert_eq!(stats.samples[0].cpu_percent, Some(1.23));
+        assert_eq!(stats.samples[0].rx_bytes_per_second, None);
+        assert_eq!(stats.samples[2].memory_bytes, Some(3072));
+        assert_eq!(stats.samples[2].rx_bytes_per_second, Some(100));
+        assert_eq!(stats.samples[2].tx_bytes_per_second, Some(10));
+    }
+
+    #[test]
+    /// Csv has a header, and a row for each sample, with missing stats left empty, json is a list of containers
+    fn test_stats_export_output() {
+        let stats = gen_stats();
+        let export = |format: StatsFormat| StatsExport {
+            all: false,
+            file: "stats".to_owned(),
+            format,
+        };
+        let csv = export(StatsFormat::Csv).output(std::slice::from_ref(&stats));
+        assert_eq!(
+            csv.lines().collect::<Vec<_>>(),
+            [
+                "id,name,timestamp,cpu_percent,memory_bytes,memory_limit_bytes,rx_bytes_per_second,tx_bytes_per_second",
+                "1,container_1,2023-01-14T19:13:00.000Z,1.23,1024,4096,,",
+                "1,container_1,2023-01-14T19:13:01.000Z,2.47,2048,4096,100,10",
+                "1,container_1,2023-01-14T19:13:02.000Z,3.70,3072,4096,100,10",
+            ]
+        );
+
+        let json = export(StatsFormat::Json).output(&[stats]);
+        let value = serde_json::from_str::<serde_json::Value>(&json).unwrap();
+        assert_eq!(value[0]["name"], "container_1");
+        assert_eq!(value[0]["memory_limit_bytes"], 4096);
+        assert_eq!(value[0]["samples"][0]["cpu_percent"], 1.23);
+        assert!(value[0]["samples"][0]["rx_bytes_per_second"].is_null());
+        assert_eq!(value[0]["samples"][2]["memory_bytes"], 3072);
+    }
+
+    #[test]
+    /// Settings are validated, and the extension is added to the file name
+    fn test_stats_export_config() {
+        let config = StatsExportConfig {
+            containers: "ALL".to_owned(),
+            file: "stats".to_owned(),
+            format: "json".to_owned(),
+        };
+        let export = config.to_export().unwrap();
+        assert_eq!(
+            export,
+            StatsExport {
+                all: true,
+                file: "stats".to_owned(),
+                format: StatsFormat::Json,
+            }
+        );
+        assert_eq!(export.file_name(), "stats.json");
+
+        let export = StatsExportConfig {
+            file: "stats.csv".to_owned(),
+            ..Default::default()
+        }
+        .to_export()
+        .unwrap();
+        assert!(!export.all);
+        assert_eq!(export.file_name(), "stats.csv");
+
+        for (config, err) in [
+            (StatsExportConfig::default(), "a file name is required"),
+            (
+                StatsExportConfig {
+                    file: "stats/../../x".to_owned(),
+                    ..Default::default()
+                },
+                "invalid file name: stats/../../x, a path isn't allowed",
+            ),
+            (
+                StatsExportConfig {
+                    containers: "some".to_owned(),
+                    ..config.clone()
+                },
+                "invalid containers: some",
+            ),
+            (
+                StatsExportConfig {
+                    format: "xml".to_owned(),
+                    ..config
+                },
+                "invalid format: xml",
+            ),
+        ] {
+            assert_eq!(config.to_export().unwrap_err(), err);
+        }
+    }
+}
@@ -0,0 +1,303 @@
+use ratatui::widgets::ListState;
+
+/// A single node, used to build up a Tree, before it gets flattened
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TreeNode {
+    label: String,
+    value: Option<String>,
+    children: Vec<Self>,
+}
+
+impl TreeNode {
+    /// A node with a value, but no children
+    pub fn leaf(label: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            value: Some(value.into()),
+            children: vec![],
+        }
+    }
+
+    /// A node with children, but no value
+    pub fn branch(label: impl Into<String>, children: Vec<Self>) -> Self {
+        Self {
+            label: label.into(),
+            value: None,
+            children,
+        }
+    }
+
+    /// A node with both a value and children
+    pub fn branch_value(
+        label: impl Into<String>,
+        value: impl Into<String>,
+        children: Vec<Self>,
+    ) -> Self {
+        Self {
+            label: label.into(),
+            value: Some(value.into()),
+            children,
+        }
+    }
+}
+
+/// Flattened, pre-ordered, tree entry
+/// The descendants of an item are all the items that directly follow it which have a greater depth
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TreeItem {
+    pub depth: usize,
+    pub label: String,
+    pub value: Option<String>,
+    pub expanded: bool,
+    pub has_children: bool,
+}
+
+impl TreeItem {
+    /// Text to display for the item, without indentation or expanded marker
+    pub fn text(&self) -> String {
+        match self.value.as_ref() {
+            Some(value) if self.label.is_empty() => value.clone(),
+            Some(value) => format!("{}: {value}", self.label),
+            None => self.label.clone(),
+        }
+    }
+}
+
+/// A collapsible tree, all branches start collapsed
+/// The state refers to the index of the *visible* items, not the index of all items
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Tree {
+    items: Vec<TreeItem>,
+    pub state: ListState,
+}
+
+impl Tree {
+    pub fn new(nodes: Vec<TreeNode>) -> Self {
+        fn flatten(node: TreeNode, depth: usize, output: &mut Vec<TreeItem>) {
+            output.push(TreeItem {
+                depth,
+                has_children: !node.children.is_empty(),
+                label: node.label,
+                value: node.value,
+                expanded: false,
+            });
+            for child in node.children {
+                flatten(child, depth + 1, output);
+            }
+        }
+        let mut items = vec![];
+        for node in nodes {
+            flatten(node, 0, &mut items);
+        }
+        let mut state = ListState::default();
+        if !items.is_empty() {
+            state.select(Some(0));
+        }
+        Self { items, state }
+    }
+
+    /// Indexes, into self.items, of all the items that aren't hidden by a collapsed ancestor
+    fn visible_indexes(&self) -> Vec<usize> {
+        let mut output = vec![];
+        let mut collapsed_depth = None;
+        for (index, item) in self.items.iter().enumerate() {
+            if let Some(depth) = collapsed_depth {
+                if item.depth > depth {
+                    continue;
+                }
+                collapsed_depth = None;
+            }
+            output.push(index);
+            if item.has_children && !item.expanded {
+                collapsed_depth = Some(item.depth);
+            }
+        }
+        output
+    }
+
+    /// All the items that are currently visible
+    pub fn visible(&self) -> Vec<&TreeItem> {
+        self.visible_indexes()
+            .into_iter()
+            .filter_map(|i| self.items.get(i))
+            .collect()
+    }
+
+    /// Index, into self.items, of the currently selected item
+    fn selected_index(&self) -> Option<usize> {
+        self.state
+            .selected()
+            .and_then(|i| self.visible_indexes().get(i).copied())
+    }
+
+    pub fn next(&mut self) {
+        let len = self.visible_indexes().len();
+        if len > 0 {
+            let i = self.state.selected().map_or(0, |i| (i + 1).min(len - 1));
+            self.state.select(Some(i));
+        }
+    }
+
+    pub fn previous(&mut self) {
+        if !self.items.is_empty() {
+            let i = self.state.selected().map_or(0, |i| i.saturating_sub(1));
+            self.state.select(Some(i));
+        }
+    }
+
+    pub fn start(&mut self) {
+        if !self.items.is_empty() {
+            self.state.select(Some(0));
+        }
+    }
+
+    pub fn end(&mut self) {
+        let len = self.visible_indexes().len();
+        if len > 0 {
+            self.state.select(Some(len - 1));
+        }
+    }
+
+    /// Toggle the expanded state of the selected item, if it has children
+    pub fn toggle(&mut self) {
+        if let Some(index) = self.selected_index() {
+            if let Some(item) = self.items.get_mut(index) {
+                if item.has_children {
+                    item.expanded = !item.expanded;
+                }
+            }
+        }
+    }
+
+    /// Expand the selected item, if it has children
+    pub fn expand(&mut self) {
+        if let Some(index) = self.selected_index() {
+            if let Some(item) = self.items.get_mut(index) {
+                if item.has_children {
+                    item.expanded = true;
+                }
+            }
+        }
+    }
+
+    /// Collapse the selected item, or if it's already collapsed, select its parent
+    pub fn collapse(&mut self) {
+        if let Some(index) = self.selected_index() {
+            if let Some(item) = self.items.get_mut(index) {
+                if item.has_children && item.expanded {
+                    item.expanded = false;
+                    return;
+                }
+            }
+            let depth = self.items.get(index).map_or(0, |i| i.depth);
+            if depth > 0 {
+                if let Some(parent) = self.items[..index].iter().rposition(|i| i.depth < depth) {
+                    let position = self.visible_indexes().iter().position(|i| *i == parent);
+                    self.state.select(position);
+                }
+            }
+        }
+    }
+
+    /// Expand every item that has children
+    pub fn expand_all(&mut self) {
+        for item in &mut self.items {
+            if item.has_children {
+                item.expanded = true;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::{Tree, TreeNode};
+
+    fn gen_tree() -> Tree {
+        Tree::new(vec![
+            TreeNode::leaf("id", "1"),
+            TreeNode::branch(
+                "env",
+                vec![TreeNode::leaf("", "A=1"), TreeNode::leaf("", "B=2")],
+            ),
+            TreeNode::branch(
+                "networks",
+                vec![TreeNode::branch(
+                    "bridge",
+                    vec![TreeNode::leaf("ip", "172.17.0.2")],
+                )],
+            ),
+        ])
+    }
+
+    fn selected_label(tree: &Tree) -> String {
+        tree.visible()[tree.state.selected().unwrap()].label.clone()
+    }
+
+    fn visible_text(tree: &Tree) -> Vec<String> {
+        tree.visible().iter().map(|i| i.text()).collect()
+    }
+
+    #[test]
+    /// Branches start collapsed, only top level items visible
+    fn test_tree_new_collapsed() {
+        let tree = gen_tree();
+        assert_eq!(visible_text(&tree), ["id: 1", "env", "networks"]);
+        assert_eq!(tree.state.selected(), Some(0));
+        assert!(Tree::new(vec![]).state.selected().is_none());
+    }
+
+    #[test]
+    /// Toggle expands and collapses the selected branch, leaves can't be toggled
+    fn test_tree_toggle() {
+        let mut tree = gen_tree();
+        tree.toggle();
+        assert_eq!(visible_text(&tree), ["id: 1", "env", "networks"]);
+
+        tree.next();
+        tree.toggle();
+        assert_eq!(
+            visible_text(&tree),
+            ["id: 1", "env", "A=1", "B=2", "networks"]
+        );
+        tree.toggle();
+        assert_eq!(visible_text(&tree), ["id: 1", "env", "networks"]);
+    }
+
+    #[test]
+    /// Navigation stays within visible bounds
+    fn test_tree_next_previous() {
+        let mut tree = gen_tree();
+        tree.previous();
+        assert_eq!(tree.state.selected(), Some(0));
+        tree.end();
+        assert_eq!(selected_label(&tree), "networks");
+        tree.next();
+        assert_eq!(selected_label(&tree), "networks");
+        tree.expand();
+        tree.next();
+        assert_eq!(selected_label(&tree), "bridge");
+        tree.start();
+        assert_eq!(selected_label(&tree), "id");
+    }
+
+    #[test]
+    /// Collapse on a leaf, or collapsed branch, selects the parent
+    fn test_tree_collapse() {
+        let mut tree = gen_tree();
+        tree.expand_all();
+        tree.end();
+        assert_eq!(selected_label(&tree), "ip");
+        tree.collapse();
+        assert_eq!(selected_label(&tree), "bridge");
+        tree.collapse();
+        assert_eq!(selected_label(&tree), "bridge");
+        assert_eq!(
+            visible_text(&tree),
+            ["id: 1", "env", "A=1", "B=2", "networks", "bridge"]
+        );
+        tree.collapse();
+        assert_eq!(selected_label(&tree), "networks");
+    }
+}
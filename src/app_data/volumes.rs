@@ -0,0 +1,83 @@
+use bollard::service::{ContainerSummary, Volume};
+
+use super::summary_name;
+
+/// Info for each volume
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VolumeItem {
+    pub containers: Vec<String>,
+    pub driver: String,
+    pub mountpoint: String,
+    pub name: String,
+}
+
+impl VolumeItem {
+    /// Create a new volume item, find the names of any of the given containers that mount the volume
+    pub fn new(volume: &Volume, containers: &[ContainerSummary]) -> Self {
+        let containers = containers
+            .iter()
+            .filter(|container| {
+                container.mounts.as_ref().is_some_and(|mounts| {
+                    mounts
+                        .iter()
+                        .any(|mount| mount.name.as_ref() == Some(&volume.name))
+                })
+            })
+            .map(summary_name)
+            .collect();
+        Self {
+            containers,
+            driver: volume.driver.clone(),
+            mountpoint: volume.mountpoint.clone(),
+            name: volume.name.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bollard::service::{ContainerSummary, MountPoint, Volume};
+
+    use super::VolumeItem;
+
+    #[test]
+    /// Volume details, and containers that mount the volume, set correctly
+    fn test_volumes_new() {
+        let gen_container = |name: &str, volume: Option<&str>| ContainerSummary {
+            names: Some(vec![format!("/{name}")]),
+            mounts: Some(
+                volume
+                    .map(|i| MountPoint {
+                        name: Some(i.to_owned()),
+                        ..Default::default()
+                    })
+                    .into_iter()
+                    .collect(),
+            ),
+            ..Default::default()
+        };
+        let containers = [
+            gen_container("redis", Some("redis_data")),
+            gen_container("postgres", Some("postgres_data")),
+            gen_container("no_volume", None),
+        ];
+        let volume = Volume {
+            name: "redis_data".to_owned(),
+            driver: "local".to_owned(),
+            mountpoint: "/var/lib/docker/volumes/redis_data/_data".to_owned(),
+            ..Default::default()
+        };
+
+        let result = VolumeItem::new(&volume, &containers);
+        assert_eq!(result.name, "redis_data");
+        assert_eq!(result.driver, "local");
+        assert_eq!(
+            result.mountpoint,
+            "/var/lib/docker/volumes/redis_data/_data"
+        );
+        assert_eq!(result.containers, ["redis"]);
+
+        let result = VolumeItem::new(&Volume::default(), &containers);
+        assert!(result.containers.is_empty());
+    }
+}
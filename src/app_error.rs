@@ -1,15 +1,33 @@
-use crate::app_data::DockerControls;
+use crate::app_data::{DockerControls, ResourceCommand};
 use std::fmt;
 
 /// app errors to set in global state
 #[allow(unused)]
 #[derive(Debug, Clone, Copy)]
 pub enum AppError {
+    ConfigSave,
+    DockerChanges,
     DockerCommand(DockerControls),
+    DockerCommit,
+    DockerDownload,
     DockerExec,
+    DockerExport,
+    DockerFiles,
+    DockerImage(ResourceCommand),
+    DockerInspect,
+    DockerLimits,
     DockerLogs,
+    DockerNetwork(ResourceCommand),
+    DockerOverview,
+    DockerProcesses,
+    DockerRecreate,
+    DockerRename,
+    DockerRun,
+    DockerSignal,
+    DockerUpload,
     DockerConnect,
     DockerInterval,
+    DockerVolume(ResourceCommand),
     InputPoll,
     MouseCapture(bool),
     Terminal,
@@ -19,11 +37,29 @@ pub enum AppError {
 impl fmt::Display for AppError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
+            Self::ConfigSave => write!(f, "Unable to save the config file"),
+            Self::DockerChanges => write!(f, "Unable to get container changes"),
             Self::DockerCommand(s) => write!(f, "Unable to {s} container"),
+            Self::DockerCommit => write!(f, "Unable to commit container"),
+            Self::DockerDownload => write!(f, "Unable to download from container"),
             Self::DockerExec => write!(f, "Unable to exec into container"),
+            Self::DockerExport => write!(f, "Unable to export container"),
+            Self::DockerFiles => write!(f, "Unable to list container files"),
+            Self::DockerImage(s) => write!(f, "Unable to {s} image"),
+            Self::DockerInspect => write!(f, "Unable to inspect container"),
+            Self::DockerLimits => write!(f, "Unable to update container limits"),
             Self::DockerLogs => write!(f, "Unable to save logs"),
+            Self::DockerNetwork(s) => write!(f, "Unable to {s} network"),
+            Self::DockerOverview => write!(f, "Unable to get Docker host information"),
+            Self::DockerProcesses => write!(f, "Unable to list container processes"),
+            Self::DockerRecreate => write!(f, "Unable to recreate container"),
+            Self::DockerRename => write!(f, "Unable to rename container"),
+            Self::DockerRun => write!(f, "Unable to run container"),
+            Self::DockerSignal => write!(f, "Unable to signal process"),
+            Self::DockerUpload => write!(f, "Unable to upload to container"),
             Self::DockerConnect => write!(f, "Unable to access docker daemon"),
             Self::DockerInterval => write!(f, "Docker update interval needs to be greater than 0"),
+            Self::DockerVolume(s) => write!(f, "Unable to {s} volume"),
             Self::InputPoll => write!(f, "Unable to poll user input"),
             Self::MouseCapture(x) => {
                 let reason = if *x { "en" } else { "dis" };
@@ -0,0 +1,361 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    app_data::{Header, SortedOrder},
+    input_handler::Action,
+    ui::{LayoutPreset, PanelLayout},
+};
+
+const CONFIG_DIR: &str = "oxker";
+const CONFIG_FILE: &str = "config.toml";
+
+/// Errors from reading, parsing, or validating the config file & cli arguments
+#[derive(Debug)]
+pub enum ConfigError {
+    Invalid(String),
+    Parse(PathBuf, toml::de::Error),
+    Read(PathBuf, std::io::Error),
+    Write(PathBuf, std::io::Error),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Invalid(x) => write!(f, "Invalid setting: {x}"),
+            Self::Parse(path, e) => write!(f, "Unable to parse {}\n{e}", path.display()),
+            Self::Read(path, e) => write!(f, "Unable to read {}: {e}", path.display()),
+            Self::Write(path, e) => write!(f, "Unable to write {}: {e}", path.display()),
+        }
+    }
+}
+
+/// How Docker logs are processed before being displayed
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LogMode {
+    Color,
+    Raw,
+    #[default]
+    Strip,
+}
+
+/// The initial sort of the containers, ascending unless an order is given
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SortConfig {
+    pub header: Header,
+    pub order: Option<SortedOrder>,
+}
+
+/// The layout of the main view, a preset, defaulting to the default preset, with any of its sizes, or sections, changed
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LayoutConfig {
+    pub charts: Option<u16>,
+    pub commands: Option<u16>,
+    pub containers: Option<u16>,
+    pub maximised: Option<bool>,
+    pub preset: Option<LayoutPreset>,
+    pub show_charts: Option<bool>,
+    pub show_commands: Option<bool>,
+}
+
+/// The keys of an action, either a single key, or a list of keys, an empty list leaves the action unbound
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(untagged)]
+pub enum KeyBinding {
+    One(String),
+    Many(Vec<String>),
+}
+
+/// Settings read from the config file, every setting is optional, and a cli argument takes priority over its matching setting
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub column_label: Option<String>,
+    pub columns: Option<Vec<Header>>,
+    pub docker_interval: Option<u32>,
+    pub history: Option<String>,
+    pub host: Option<String>,
+    pub keys: HashMap<Action, KeyBinding>,
+    pub layout: LayoutConfig,
+    pub log_mode: Option<LogMode>,
+    pub metrics_listen: Option<String>,
+    pub save_dir: Option<String>,
+    pub show_self: Option<bool>,
+    pub show_timestamps: Option<bool>,
+    pub sort: Option<SortConfig>,
+    pub theme: Option<String>,
+    pub themes: HashMap<String, HashMap<String, String>>,
+    pub use_cli: Option<bool>,
+}
+
+impl Config {
+    /// Default location of the config file, `oxker/config.toml` in the users config directory, e.g. `$HOME/.config/oxker/config.toml`
+    pub fn default_path() -> Option<PathBuf> {
+        directories::BaseDirs::new()
+            .map(|base_dirs| base_dirs.config_dir().join(CONFIG_DIR).join(CONFIG_FILE))
+    }
+
+    /// Parse the contents of a config file, path is only used in the error message
+    pub fn parse(path: &Path, contents: &str) -> Result<Self, ConfigError> {
+        toml::from_str(contents).map_err(|e| ConfigError::Parse(path.to_owned(), e))
+    }
+
+    /// Read the config file at the given path, or the default path if not given
+    /// A missing file at the default path isn't an error, as the config file is optional, but a missing file at a given path is
+    pub fn read(path: Option<&Path>) -> Result<Self, ConfigError> {
+        let (path, required) = match path {
+            Some(path) => (path.to_owned(), true),
+            None => match Self::default_path() {
+                Some(path) => (path, false),
+                None => return Ok(Self::default()),
+            },
+        };
+        match std::fs::read_to_string(&path) {
+            Ok(contents) => Self::parse(&path, &contents),
+            Err(e) if !required && e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(ConfigError::Read(path, e)),
+        }
+    }
+
+    /// Set the columns key of a config file
+    pub fn set_columns(document: &mut toml_edit::DocumentMut, columns: &[Header]) {
+        document["columns"] = toml_edit::value(
+            columns
+                .iter()
+                .map(|i| i.config_name())
+                .collect::<toml_edit::Array>(),
+        );
+    }
+
+    /// Set every key of the layout table of a config file
+    pub fn set_layout(document: &mut toml_edit::DocumentMut, layout: &PanelLayout) {
+        if !document.contains_table("layout") {
+            document["layout"] = toml_edit::table();
+        }
+        let table = &mut document["layout"];
+        table["preset"] = toml_edit::value(layout.preset.to_string());
+        table["containers"] = toml_edit::value(i64::from(layout.containers));
+        table["commands"] = toml_edit::value(i64::from(layout.commands));
+        table["charts"] = toml_edit::value(i64::from(layout.charts));
+        table["show_commands"] = toml_edit::value(layout.show_commands);
+        table["show_charts"] = toml_edit::value(layout.show_charts);
+        table["maximised"] = toml_edit::value(layout.maximised);
+    }
+
+    /// Edit the contents of a config file, keeping all other settings, and comments, as they are
+    pub fn edit_contents(
+        contents: &str,
+        edit: impl FnOnce(&mut toml_edit::DocumentMut),
+    ) -> Result<String, toml_edit::TomlError> {
+        let mut document = contents.parse::<toml_edit::DocumentMut>()?;
+        edit(&mut document);
+        Ok(document.to_string())
+    }
+
+    /// Edit the config file, creating the file, and its parent directories, if they don't exist
+    pub fn edit(
+        path: &Path,
+        edit: impl FnOnce(&mut toml_edit::DocumentMut),
+    ) -> Result<(), ConfigError> {
+        let write_err = |e| ConfigError::Write(path.to_owned(), e);
+        let contents = match std::fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(ConfigError::Read(path.to_owned(), e)),
+        };
+        let output = Self::edit_contents(&contents, edit)
+            .map_err(|e| write_err(std::io::Error::new(std::io::ErrorKind::InvalidData, e)))?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(write_err)?;
+        }
+        std::fs::write(path, output).map_err(write_err)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::{collections::HashMap, path::Path};
+
+    use crate::{
+        app_data::{Header, SortedOrder},
+        input_handler::Action,
+        ui::{LayoutPreset, PanelLayout},
+    };
+
+    use super::{Config, ConfigError, KeyBinding, LayoutConfig, LogMode, SortConfig};
+
+    #[test]
+    /// Every setting parsed from a config file
+    fn test_config_parse() {
+        let contents = r##"
+column_label = "com.example.team"
+columns = ["name", "state", "cpu", "label", "uptime"]
+docker_interval = 2000
+history = "15m"
+host = "/run/user/1000/podman/podman.sock"
+log_mode = "color"
+metrics_listen = "127.0.0.1:9187"
+save_dir = "~/oxker"
+show_self = true
+show_timestamps = false
+sort = { header = "rx_rate", order = "desc" }
+theme = "solarized"
+use_cli = true
+
+[themes.solarized]
+base = "light"
+border = "#268bd2"
+
+[keys]
+quit = "ctrl+q"
+down = ["down", "ctrl+n"]
+
+[layout]
+preset = "logs"
+containers = 8
+"##;
+        let result = Config::parse(Path::new("config.toml"), contents).unwrap();
+        assert_eq!(
+            result,
+            Config {
+                column_label: Some("com.example.team".to_owned()),
+                columns: Some(vec![
+                    Header::Name,
+                    Header::State,
+                    Header::Cpu,
+                    Header::Label,
+                    Header::Uptime
+                ]),
+                docker_interval: Some(2000),
+                history: Some("15m".to_owned()),
+                host: Some("/run/user/1000/podman/podman.sock".to_owned()),
+                keys: HashMap::from([
+                    (Action::Quit, KeyBinding::One("ctrl+q".to_owned())),
+                    (
+                        Action::Down,
+                        KeyBinding::Many(vec!["down".to_owned(), "ctrl+n".to_owned()])
+                    ),
+                ]),
+                layout: LayoutConfig {
+                    containers: Some(8),
+                    preset: Some(LayoutPreset::Logs),
+                    ..LayoutConfig::default()
+                },
+                log_mode: Some(LogMode::Color),
+                metrics_listen: Some("127.0.0.1:9187".to_owned()),
+                save_dir: Some("~/oxker".to_owned()),
+                show_self: Some(true),
+                show_timestamps: Some(false),
+                sort: Some(SortConfig {
+                    header: Header::RxRate,
+                    order: Some(SortedOrder::Desc)
+                }),
+                theme: Some("solarized".to_owned()),
+                themes: HashMap::from([(
+                    "solarized".to_owned(),
+                    HashMap::from([
+                        ("base".to_owned(), "light".to_owned()),
+                        ("border".to_owned(), "#268bd2".to_owned()),
+                    ])
+                )]),
+                use_cli: Some(true),
+            }
+        );
+
+        let result = Config::parse(Path::new("config.toml"), "").unwrap();
+        assert_eq!(result, Config::default());
+    }
+
+    #[test]
+    /// Unknown settings, and invalid values, are errors which include the path of the file
+    fn test_config_parse_err() {
+        for contents in [
+            "docker_interva = 2000",
+            "docker_interval = -1",
+            "log_mode = \"colour\"",
+            "columns = [\"name\", \"size\"]",
+            "sort = { header = \"size\" }",
+            "show_self = 1",
+            "[keys]\nquit = 1",
+            "[keys]\nquitt = \"q\"",
+            "[themes.custom]\nborder = 1",
+            "[layout]\npreset = \"wide\"",
+        ] {
+            let result = Config::parse(Path::new("/tmp/config.toml"), contents);
+            assert!(matches!(result, Err(ConfigError::Parse(..))));
+            assert!(result
+                .unwrap_err()
+                .to_string()
+                .starts_with("Unable to parse /tmp/config.toml"));
+        }
+    }
+
+    #[test]
+    /// A missing file is only an error when the path is given
+    fn test_config_read_missing() {
+        let result = Config::read(Some(Path::new("/oxker/missing.toml")));
+        assert!(matches!(result, Err(ConfigError::Read(..))));
+    }
+
+    #[test]
+    /// Setting the columns keeps every other setting, and comment, and replaces any existing columns
+    fn test_config_set_columns() {
+        let contents = "# oxker config\ndocker_interval = 2000 # ms\ncolumns = [\"name\"]\n";
+        let result = Config::edit_contents(contents, |document| {
+            Config::set_columns(document, &[Header::Name, Header::RxRate]);
+        })
+        .unwrap();
+        assert_eq!(
+            result,
+            "# oxker config\ndocker_interval = 2000 # ms\ncolumns = [\"name\", \"rx_rate\"]\n"
+        );
+        let config = Config::parse(Path::new("/tmp/config.toml"), &result).unwrap();
+        assert_eq!(config.columns, Some(vec![Header::Name, Header::RxRate]));
+
+        let result =
+            Config::edit_contents("", |document| Config::set_columns(document, &[Header::Ip]))
+                .unwrap();
+        assert_eq!(result, "columns = [\"ip\"]\n");
+    }
+
+    #[test]
+    /// The layout table is created if missing, and can be read back as the same layout
+    fn test_config_set_layout() {
+        let layout = PanelLayout {
+            charts: 40,
+            containers: 20,
+            show_commands: false,
+            ..LayoutPreset::Charts.layout()
+        };
+        let contents = "docker_interval = 2000\n";
+        let result =
+            Config::edit_contents(contents, |document| Config::set_layout(document, &layout))
+                .unwrap();
+        assert_eq!(
+            result,
+            "docker_interval = 2000\n\n[layout]\npreset = \"charts\"\ncontainers = 20\ncommands = 10\ncharts = 40\nshow_commands = false\nshow_charts = true\nmaximised = false\n"
+        );
+        let config = Config::parse(Path::new("/tmp/config.toml"), &result).unwrap();
+        assert_eq!(
+            config.layout,
+            LayoutConfig {
+                charts: Some(40),
+                commands: Some(10),
+                containers: Some(20),
+                maximised: Some(false),
+                preset: Some(LayoutPreset::Charts),
+                show_charts: Some(true),
+                show_commands: Some(false),
+            }
+        );
+    }
+}
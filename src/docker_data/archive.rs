@@ -0,0 +1,289 @@
+use std::{
+    collections::BTreeMap,
+    ffi::OsString,
+    fs,
+    io::{self, Read, Write},
+    path::{Component, Path, PathBuf},
+};
+
+use bytes::{Buf, Bytes};
+use tar::{Archive, Builder, EntryType, Header};
+use tokio::sync::mpsc::{Receiver, Sender};
+
+/// Size of the chunks an archive is streamed in
+pub const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Sends everything written to it over a channel, so that an archive built on a blocking thread can be streamed as a request body
+pub struct ChannelWriter(Sender<Bytes>);
+
+impl ChannelWriter {
+    pub const fn new(tx: Sender<Bytes>) -> Self {
+        Self(tx)
+    }
+}
+
+impl Write for ChannelWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0
+            .blocking_send(Bytes::copy_from_slice(buf))
+            .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "upload closed"))?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Reads the chunks of a streamed response, received over a channel, so that an archive can be read on a blocking thread
+/// An error received over the channel, such as the stream failing part way through, is returned from `read`, so a truncated archive isn't mistaken for a complete one
+pub struct ChannelReader {
+    chunk: Bytes,
+    rx: Receiver<io::Result<Bytes>>,
+}
+
+impl ChannelReader {
+    pub const fn new(rx: Receiver<io::Result<Bytes>>) -> Self {
+        Self {
+            chunk: Bytes::new(),
+            rx,
+        }
+    }
+}
+
+impl Read for ChannelReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.chunk.is_empty() {
+            match self.rx.blocking_recv() {
+                Some(chunk) => self.chunk = chunk?,
+                None => return Ok(0),
+            }
+        }
+        let len = buf.len().min(self.chunk.len());
+        buf[..len].copy_from_slice(&self.chunk[..len]);
+        self.chunk.advance(len);
+        Ok(len)
+    }
+}
+
+/// Write a tar archive containing a single file, as required by the Docker upload api, reading the file as it's written
+/// A name too long for a ustar header is stored as a GNU long name
+pub fn tar_file(
+    output: impl Write,
+    name: &str,
+    data: impl Read,
+    size: u64,
+    mtime: u64,
+) -> io::Result<()> {
+    if name.is_empty() || name.contains('/') {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("unsupported file name: {name}"),
+        ));
+    }
+    let mut header = Header::new_gnu();
+    header.set_entry_type(EntryType::Regular);
+    header.set_mode(0o644);
+    header.set_mtime(mtime);
+    header.set_size(size);
+    let mut builder = Builder::new(output);
+    builder.append_data(&mut header, name, data.take(size))?;
+    builder.into_inner()?.flush()
+}
+
+/// The path of an entry, relative to the file, or directory, that was downloaded, None for the downloaded path itself
+/// Any entry outside of the downloaded path is an error
+fn relative_path(path: &Path) -> io::Result<Option<PathBuf>> {
+    let mut components = path.components();
+    if !components.all(|i| matches!(i, Component::Normal(_))) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("invalid path in archive: {}", path.display()),
+        ));
+    }
+    let relative = path.components().skip(1).collect::<PathBuf>();
+    Ok(Some(relative).filter(|i| i.components().next().is_some()))
+}
+
+/// Extract an archive downloaded from a container, of a single file, or a directory, into `output`
+/// The archive is unpacked into a temporary directory next to `output`, which is then renamed, so a failed download doesn't leave a partial file, or directory, behind
+pub fn extract(input: impl Read, output: &Path) -> io::Result<()> {
+    let mut staging = OsString::from(output.as_os_str());
+    staging.push(".partial");
+    let staging = PathBuf::from(staging);
+    fs::create_dir(&staging)?;
+    let result = Archive::new(input).unpack(&staging).and_then(|()| {
+        let mut entries = fs::read_dir(&staging)?.collect::<io::Result<Vec<_>>>()?;
+        match (entries.pop(), entries.is_empty()) {
+            (Some(entry), true) => fs::rename(entry.path(), output),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "expected a single file, or directory, in archive",
+            )),
+        }
+    });
+    fs::remove_dir_all(&staging).ok();
+    result
+}
+
+/// List the entries of a directory from an archive of it downloaded from a container, in the same format as `ls -1Ap`, one per line, with directories having a trailing `/`
+/// Used when `ls` can't be run in the container, an archive of a single file is an error, as with `ls` when the path isn't a directory
+pub fn list_dir(input: impl Read) -> io::Result<String> {
+    let mut entries = BTreeMap::new();
+    let mut is_dir = false;
+    for entry in Archive::new(input).entries()? {
+        let entry = entry?;
+        let path = entry.path()?;
+        match relative_path(&path)? {
+            None => is_dir = entry.header().entry_type().is_dir(),
+            Some(relative) => {
+                let mut components = relative.components();
+                if let Some(name) = components.next() {
+                    let nested = components.next().is_some();
+                    *entries
+                        .entry(name.as_os_str().to_string_lossy().into_owned())
+                        .or_default() |= nested || entry.header().entry_type().is_dir();
+                }
+            }
+        }
+    }
+    if !is_dir {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "not a directory",
+        ));
+    }
+    Ok(entries
+        .into_iter()
+        .map(|(name, is_dir)| {
+            if is_dir {
+                format!("{name}/\n")
+            } else {
+                format!("{name}\n")
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::io::Read;
+
+    use tar::{Builder, EntryType, Header};
+
+    use super::{extract, list_dir, tar_file};
+
+    /// Build an archive as the Docker download api would, each entry is a path, and the contents of a file, or None for a directory
+    fn archive(entries: &[(&str, Option<&str>)]) -> Vec<u8> {
+        let mut builder = Builder::new(vec![]);
+        for (path, data) in entries {
+            let mut header = Header::new_gnu();
+            if let Some(data) = data {
+                header.set_entry_type(EntryType::Regular);
+                header.set_mode(0o644);
+                header.set_size(data.len() as u64);
+                builder
+                    .append_data(&mut header, path, data.as_bytes())
+                    .unwrap();
+            } else {
+                header.set_entry_type(EntryType::Directory);
+                header.set_mode(0o755);
+                header.set_size(0);
+                builder.append_data(&mut header, path, &[][..]).unwrap();
+            }
+        }
+        builder.into_inner().unwrap()
+    }
+
+    /// A new empty directory in the system temporary directory
+    fn temp_dir(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("oxker_test_{name}_{}", std::process::id()));
+        std::fs::remove_dir_all(&dir).ok();
+        std::fs::create_dir(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    /// Single file archive read back, including a name too long for a ustar header
+    fn test_archive_tar_file() {
+        let data = b"hello world";
+        for name in ["hello.txt".to_owned(), "a".repeat(150)] {
+            let mut output = vec![];
+            tar_file(&mut output, &name, &data[..], 11, 1_700_000_000).unwrap();
+            let mut archive = tar::Archive::new(output.as_slice());
+            let mut entries = archive.entries().unwrap();
+            let mut entry = entries.next().unwrap().unwrap();
+            assert_eq!(entry.path().unwrap().to_str().unwrap(), name);
+            assert_eq!(entry.header().mtime().unwrap(), 1_700_000_000);
+            let mut contents = vec![];
+            entry.read_to_end(&mut contents).unwrap();
+            assert_eq!(contents, data);
+            drop(entry);
+            assert!(entries.next().is_none());
+        }
+
+        assert!(tar_file(vec![], "", &data[..], 11, 0).is_err());
+        assert!(tar_file(vec![], "a/b", &data[..], 11, 0).is_err());
+    }
+
+    #[test]
+    /// A file, and a directory, are both extracted into the output path, and an invalid archive leaves nothing behind
+    fn test_archive_extract() {
+        let dir = temp_dir("extract");
+
+        let output = dir.join("hosts_1");
+        extract(archive(&[("hosts", Some("127.0.0.1"))]).as_slice(), &output).unwrap();
+        assert_eq!(std::fs::read_to_string(&output).unwrap(), "127.0.0.1");
+
+        let output = dir.join("nginx_1");
+        let input = archive(&[
+            ("nginx/", None),
+            ("nginx/nginx.conf", Some("worker_processes 1;")),
+            ("nginx/conf.d/", None),
+            ("nginx/conf.d/default.conf", Some("server {}")),
+        ]);
+        extract(input.as_slice(), &output).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(output.join("nginx.conf")).unwrap(),
+            "worker_processes 1;"
+        );
+        assert_eq!(
+            std::fs::read_to_string(output.join("conf.d/default.conf")).unwrap(),
+            "server {}"
+        );
+
+        let output = dir.join("empty_1");
+        assert!(extract(archive(&[]).as_slice(), &output).is_err());
+        let input = archive(&[("a", Some("a")), ("b", Some("b"))]);
+        assert!(extract(input.as_slice(), &output).is_err());
+        assert!(!output.exists());
+
+        let mut names = std::fs::read_dir(&dir)
+            .unwrap()
+            .map(|i| i.unwrap().file_name().into_string().unwrap())
+            .collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(names, ["hosts_1", "nginx_1"]);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    /// Directory listed from an archive in the same format as `ls -1Ap`, a nested path without its own directory entry is still a directory
+    fn test_archive_list_dir() {
+        let input = archive(&[
+            ("etc/", None),
+            ("etc/hosts", Some("127.0.0.1")),
+            ("etc/nginx/", None),
+            ("etc/nginx/nginx.conf", Some("")),
+            ("etc/apk/keys/key.pub", Some("")),
+            ("etc/.hidden", Some("")),
+        ]);
+        assert_eq!(
+            list_dir(input.as_slice()).unwrap(),
+            ".hidden\napk/\nhosts\nnginx/\n"
+        );
+
+        assert!(list_dir(archive(&[("hosts", Some("127.0.0.1"))]).as_slice()).is_err());
+    }
+}
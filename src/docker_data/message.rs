@@ -1,19 +1,103 @@
-use std::sync::Arc;
+use std::{path::PathBuf, sync::Arc};
 
-use crate::app_data::ContainerId;
-use bollard::Docker;
+use crate::{
+    app_data::{ContainerId, LogExport, ProcessMatch, RunConfig},
+    ui::DeleteTarget,
+};
+use bollard::{
+    container::{Config, UpdateContainerOptions},
+    Docker,
+};
 use tokio::sync::oneshot::Sender;
 
 #[derive(Debug)]
 pub enum DockerMessage {
-    ConfirmDelete(ContainerId),
+    Changes(ContainerId),
+    Commit {
+        id: ContainerId,
+        repo: String,
+        tag: String,
+        comment: String,
+    },
+    ConfirmDelete(DeleteTarget),
     Delete(ContainerId),
+    Download {
+        id: ContainerId,
+        path: String,
+        output: PathBuf,
+    },
     Exec(Sender<Arc<Docker>>),
+    Export {
+        id: ContainerId,
+        path: PathBuf,
+    },
+    Files {
+        id: ContainerId,
+        name: String,
+        path: String,
+    },
+    ImagePrune,
+    ImagePull(String),
+    ImageRemove(String),
+    Inspect(ContainerId),
+    Limits {
+        id: ContainerId,
+        options: Box<UpdateContainerOptions<String>>,
+    },
+    LimitsForm(ContainerId),
+    NetworkCreate {
+        name: String,
+        driver: String,
+        subnet: String,
+    },
+    NetworkPrune,
+    NetworkRemove(String),
+    Overview,
     Pause(ContainerId),
     Quit,
+    Recreate {
+        id: ContainerId,
+        config: Box<RunConfig>,
+    },
+    Processes(ContainerId),
+    RecreateForm(ContainerId),
+    Rename {
+        id: ContainerId,
+        name: String,
+    },
     Restart(ContainerId),
+    Run {
+        name: String,
+        config: Box<Config<String>>,
+    },
+    RunClone(ContainerId),
+    SaveLogs {
+        id: ContainerId,
+        path: PathBuf,
+        export: Box<LogExport>,
+    },
+    Signal {
+        id: ContainerId,
+        pid: String,
+        signal: String,
+    },
+    SignalForm {
+        id: ContainerId,
+        process: ProcessMatch,
+    },
     Start(ContainerId),
     Stop(ContainerId),
     Resume(ContainerId),
     Update,
+    Upload {
+        id: ContainerId,
+        local: PathBuf,
+        dir: String,
+    },
+    VolumeCreate {
+        name: String,
+        driver: String,
+    },
+    VolumePrune,
+    VolumeRemove(String),
 }
@@ -1,19 +1,33 @@
 use bollard::{
     container::{
-        ListContainersOptions, LogsOptions, MemoryStatsStats, RemoveContainerOptions,
-        StartContainerOptions, Stats, StatsOptions,
+        Config, CreateContainerOptions, DownloadFromContainerOptions, InspectContainerOptions,
+        ListContainersOptions, LogOutput, LogsOptions, MemoryStatsStats, RemoveContainerOptions,
+        RenameContainerOptions, StartContainerOptions, Stats, StatsOptions, TopOptions,
+        UploadToContainerOptions,
     },
-    service::ContainerSummary,
+    errors::Error,
+    exec::{CreateExecOptions, StartExecResults},
+    image::{CommitContainerOptions, CreateImageOptions, ListImagesOptions, PruneImagesOptions},
+    network::{
+        ConnectNetworkOptions, CreateNetworkOptions, ListNetworksOptions, PruneNetworksOptions,
+    },
+    service::{ContainerSummary, EndpointSettings, Ipam, IpamConfig},
+    volume::{CreateVolumeOptions, ListVolumesOptions, PruneVolumesOptions},
     Docker,
 };
-use futures_util::StreamExt;
+use flate2::{write::GzEncoder, Compression};
+use futures_util::{Stream, StreamExt};
 use parking_lot::Mutex;
 use std::{
     collections::HashMap,
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
     sync::{
         atomic::{AtomicBool, AtomicUsize},
         Arc,
     },
+    time::SystemTime,
 };
 use tokio::{
     sync::mpsc::{Receiver, Sender},
@@ -22,19 +36,29 @@ use tokio::{
 use uuid::Uuid;
 
 use crate::{
-    app_data::{AppData, ContainerId, DockerControls, State},
+    app_data::{
+        existing_config, parse_timestamp, with_tag, AppData, ByteStats, ChangesData, ContainerId,
+        DockerControls, FileBrowser, HostInfo, InspectData, IoCounters, LimitsConfig, LogExport,
+        LogLine, ProcessesData, ResourceCommand, RunConfig, State, Tree, PROC_CMDLINES, PS_ARGS,
+    },
     app_error::AppError,
     parse_args::CliArgs,
-    ui::{GuiState, Status},
+    ui::{Form, GuiState, SelectablePanel, Status},
     ENTRY_POINT,
 };
+mod archive;
 mod message;
+use archive::{extract, list_dir, tar_file, ChannelReader, ChannelWriter, CHUNK_SIZE};
 pub use message::DockerMessage;
 
+/// Errors from streaming data to, or from, Docker, which can also include io errors
+type StreamError = Box<dyn std::error::Error + Send + Sync>;
+
 #[derive(Debug, Clone, Eq, Hash, PartialEq)]
 enum SpawnId {
     Stats((ContainerId, Binate)),
     Log(ContainerId),
+    Details(ContainerId),
 }
 
 /// Cpu & Mem stats take twice as long as the update interval to get a value, so will have two being executed at the same time
@@ -140,29 +164,22 @@ impl DockerData {
 
                 let mem_limit = stats.memory_stats.limit.unwrap_or_default();
 
-                let op_key = stats
-                    .networks
-                    .as_ref()
-                    .and_then(|networks| networks.keys().next().cloned());
-
                 let cpu_stats = if state.is_alive() {
                     Some(Self::calculate_usage(&stats))
                 } else {
                     None
                 };
-                let (rx, tx) = if let Some(key) = op_key {
-                    stats
-                        .networks
-                        .unwrap_or_default()
-                        .get(&key)
-                        .map_or((0, 0), |f| (f.rx_bytes, f.tx_bytes))
-                } else {
-                    (0, 0)
-                };
 
-                app_data
-                    .lock()
-                    .update_stats_by_id(&id, cpu_stats, mem_stat, mem_limit, rx, tx);
+                app_data.lock().update_stats_by_id(
+                    &id,
+                    cpu_stats,
+                    mem_stat,
+                    mem_limit,
+                    Self::io_counters(&stats),
+                    SystemTime::now()
+                        .duration_since(SystemTime::UNIX_EPOCH)
+                        .map_or(0, |i| u64::try_from(i.as_millis()).unwrap_or_default()),
+                );
             }
         }
         spawns.lock().remove(&spawn_id);
@@ -171,6 +188,35 @@ impl DockerData {
         }
     }
 
+    /// Sum the network bytes of every interface, and the block io bytes of every device
+    /// The op is capitalised with cgroups v1, but lower case with cgroups v2
+    fn io_counters(stats: &Stats) -> IoCounters {
+        let (rx, tx) = stats.networks.as_ref().map_or((0, 0), |networks| {
+            networks
+                .values()
+                .fold((0, 0), |acc, i| (acc.0 + i.rx_bytes, acc.1 + i.tx_bytes))
+        });
+        let blkio = |op: &str| {
+            stats
+                .blkio_stats
+                .io_service_bytes_recursive
+                .as_ref()
+                .map_or(0, |entries| {
+                    entries
+                        .iter()
+                        .filter(|i| i.op.eq_ignore_ascii_case(op))
+                        .map(|i| i.value)
+                        .sum()
+                })
+        };
+        IoCounters {
+            read: blkio("read"),
+            rx,
+            tx,
+            write: blkio("write"),
+        }
+    }
+
     /// Update all stats, spawn each container into own tokio::spawn thread
     fn update_all_container_stats(&mut self, all_ids: &[(State, ContainerId)]) {
         for (state, id) in all_ids {
@@ -202,14 +248,7 @@ impl DockerData {
     /// Just make sure that items sent are guaranteed to have an id
     /// If in a containerised runtime, will ignore any container that uses the `/app/oxker` as an entry point, unless the `-s` flag is set
     pub async fn update_all_containers(&mut self) -> Vec<(State, ContainerId)> {
-        let containers = self
-            .docker
-            .list_containers(Some(ListContainersOptions::<String> {
-                all: true,
-                ..Default::default()
-            }))
-            .await
-            .unwrap_or_default();
+        let containers = self.list_all_containers().await;
 
         let mut output = containers
             .into_iter()
@@ -270,6 +309,51 @@ impl DockerData {
         app_data.lock().update_log_by_id(output, &id);
     }
 
+    /// Inspect a single container, to get the start time & restart count, which aren't included in the container list
+    /// remove it from spawns hashmap when complete
+    async fn update_container_details(
+        app_data: Arc<Mutex<AppData>>,
+        docker: Arc<Docker>,
+        id: ContainerId,
+        spawns: Arc<Mutex<HashMap<SpawnId, JoinHandle<()>>>>,
+    ) {
+        if let Ok(inspect) = docker.inspect_container(id.get(), None).await {
+            let state = inspect.state.as_ref();
+            let started = state
+                .and_then(|i| i.started_at.as_ref())
+                .and_then(|i| parse_timestamp(i));
+            let status = State::from(
+                state
+                    .and_then(|i| i.status)
+                    .map_or_else(|| "dead".to_owned(), |i| i.to_string())
+                    .as_str(),
+            );
+            let restart_count = inspect
+                .restart_count
+                .map(|i| u64::try_from(i).unwrap_or_default());
+            app_data
+                .lock()
+                .update_details_by_id(&id, status, started, restart_count);
+        }
+        spawns.lock().remove(&SpawnId::Details(id));
+    }
+
+    /// Inspect every container whose details are out of date, only executed when a column needs the inspect data
+    fn update_all_details(&self) {
+        let required = self.app_data.lock().get_details_required();
+        for id in required {
+            let app_data = Arc::clone(&self.app_data);
+            let docker = Arc::clone(&self.docker);
+            let spawns = Arc::clone(&self.spawns);
+            self.spawns
+                .lock()
+                .entry(SpawnId::Details(id.clone()))
+                .or_insert_with(|| {
+                    tokio::spawn(Self::update_container_details(app_data, docker, id, spawns))
+                });
+        }
+    }
+
     /// Update all logs, spawn each container into own tokio::spawn thread
     fn init_all_logs(&mut self, all_ids: &[(State, ContainerId)]) {
         for (_, id) in all_ids {
@@ -284,6 +368,57 @@ impl DockerData {
         }
     }
 
+    /// Get every container, including stopped containers
+    async fn list_all_containers(&self) -> Vec<ContainerSummary> {
+        self.docker
+            .list_containers(Some(ListContainersOptions::<String> {
+                all: true,
+                ..Default::default()
+            }))
+            .await
+            .unwrap_or_default()
+    }
+
+    /// Get all volumes, only executed when the volumes panel is visible
+    async fn update_all_volumes(&self) {
+        if let Ok(response) = self
+            .docker
+            .list_volumes(None::<ListVolumesOptions<String>>)
+            .await
+        {
+            let containers = self.list_all_containers().await;
+            self.app_data
+                .lock()
+                .update_volumes(&response.volumes.unwrap_or_default(), &containers);
+        }
+    }
+
+    /// Get all networks, only executed when the networks panel is visible
+    async fn update_all_networks(&self) {
+        if let Ok(networks) = self
+            .docker
+            .list_networks(None::<ListNetworksOptions<String>>)
+            .await
+        {
+            let containers = self.list_all_containers().await;
+            self.app_data.lock().update_networks(&networks, &containers);
+        }
+    }
+
+    /// Get all local images, only executed when the images panel is visible
+    async fn update_all_images(&self) {
+        if let Ok(images) = self
+            .docker
+            .list_images(Some(ListImagesOptions::<String> {
+                all: false,
+                ..Default::default()
+            }))
+            .await
+        {
+            self.app_data.lock().update_images(&images);
+        }
+    }
+
     /// Update all cpu_mem, and selected container log (if a log update join_handle isn't currently being executed)
     async fn update_everything(&mut self) {
         let all_ids = self.update_all_containers().await;
@@ -302,7 +437,427 @@ impl DockerData {
                 });
         };
         self.update_all_container_stats(&all_ids);
-        self.app_data.lock().sort_containers();
+        let inspect_columns = self
+            .app_data
+            .lock()
+            .args
+            .columns
+            .iter()
+            .any(|i| i.needs_inspect());
+        if inspect_columns {
+            self.update_all_details();
+        }
+        self.update_processes().await;
+        {
+            let mut locked_data = self.app_data.lock();
+            locked_data.sort_containers();
+            locked_data.restore_container();
+        }
+        let lower_panel = self.gui_state.lock().get_lower_panel();
+        match lower_panel {
+            SelectablePanel::Images => self.update_all_images().await,
+            SelectablePanel::Volumes => self.update_all_volumes().await,
+            SelectablePanel::Networks => self.update_all_networks().await,
+            _ => (),
+        }
+    }
+
+    /// Pull an image, waiting until the pull is complete
+    async fn pull_image(docker: &Docker, image: &str) -> Result<(), Error> {
+        let mut stream = docker.create_image(
+            Some(CreateImageOptions {
+                from_image: with_tag(image),
+                ..Default::default()
+            }),
+            None,
+            None,
+        );
+        while let Some(response) = stream.next().await {
+            response?;
+        }
+        Ok(())
+    }
+
+    /// Create, and then start, a container, returning the id of the new container
+    /// If the image isn't available locally then it's pulled, and the create is retried
+    async fn run_container(
+        docker: &Docker,
+        name: &str,
+        config: Config<String>,
+    ) -> Result<String, Error> {
+        let options = (!name.is_empty()).then_some(CreateContainerOptions {
+            name,
+            platform: None,
+        });
+        let response = match docker
+            .create_container(options.clone(), config.clone())
+            .await
+        {
+            Ok(response) => response,
+            Err(Error::DockerResponseServerError {
+                status_code: 404, ..
+            }) => {
+                Self::pull_image(docker, config.image.as_deref().unwrap_or_default()).await?;
+                docker.create_container(options, config).await?
+            }
+            Err(e) => return Err(e),
+        };
+        docker
+            .start_container(&response.id, None::<StartContainerOptions<String>>)
+            .await?;
+        Ok(response.id)
+    }
+
+    /// Pass each chunk of a byte stream to `write`, showing the amount received so far in the info box
+    async fn receive_stream<T: AsRef<[u8]>>(
+        mut stream: impl Stream<Item = Result<T, Error>> + Unpin,
+        gui_state: &Arc<Mutex<GuiState>>,
+        action: &str,
+        mut write: impl FnMut(&[u8]) -> std::io::Result<()>,
+    ) -> Result<(), StreamError> {
+        let mut received = 0;
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk?;
+            write(chunk.as_ref())?;
+            received += chunk.as_ref().len() as u64;
+            gui_state
+                .lock()
+                .set_info_box(&format!("{action}: {}", ByteStats::new(received)));
+        }
+        Ok(())
+    }
+
+    /// Write each line of a log stream that matches the filter, showing the number of lines written so far in the info box, returns the number of lines written
+    async fn write_logs(
+        mut stream: impl Stream<Item = Result<LogOutput, Error>> + Unpin,
+        writer: &mut impl Write,
+        export: &LogExport,
+        timestamp: bool,
+        gui_state: &Arc<Mutex<GuiState>>,
+    ) -> Result<usize, StreamError> {
+        let mut count = 0;
+        if let Some(header) = export.header() {
+            writer.write_all(header.as_bytes())?;
+        }
+        while let Some(output) = stream.next().await {
+            if let Some(line) = LogLine::from_output(&output?).filter(|i| export.matches(i)) {
+                writer.write_all(export.format_line(&line, timestamp).as_bytes())?;
+                count += 1;
+                if count % 1000 == 0 {
+                    gui_state
+                        .lock()
+                        .set_info_box(&format!("saving logs: {count} lines"));
+                }
+            }
+        }
+        Ok(count)
+    }
+
+    /// Write the logs of a container, within the time range of the export, into a file, gzipped if chosen, returns the number of lines written
+    async fn save_logs(
+        docker: &Docker,
+        id: &ContainerId,
+        path: &Path,
+        export: &LogExport,
+        timestamp: bool,
+        gui_state: &Arc<Mutex<GuiState>>,
+    ) -> Result<usize, StreamError> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        let stream = docker.logs(id.get(), Some(export.options()));
+        let count = if export.gzip {
+            let mut encoder = GzEncoder::new(writer, Compression::default());
+            let count =
+                Self::write_logs(stream, &mut encoder, export, timestamp, gui_state).await?;
+            encoder.finish()?.flush()?;
+            count
+        } else {
+            let count = Self::write_logs(stream, &mut writer, export, timestamp, gui_state).await?;
+            writer.flush()?;
+            count
+        };
+        Ok(count)
+    }
+
+    /// Write the filesystem of a container into a tarball, showing the amount written so far in the info box
+    async fn export_container(
+        docker: &Docker,
+        id: &ContainerId,
+        path: &Path,
+        gui_state: &Arc<Mutex<GuiState>>,
+    ) -> Result<(), StreamError> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        let stream = docker.export_container(id.get());
+        Self::receive_stream(stream, gui_state, "exporting", |i| writer.write_all(i)).await?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Run a command in a container, and return its stdout, so the container needs to be running
+    /// A non-zero exit code is an error
+    async fn exec_output(
+        docker: &Docker,
+        id: &ContainerId,
+        cmd: Vec<&str>,
+    ) -> Result<String, StreamError> {
+        let program = cmd.first().copied().unwrap_or_default().to_owned();
+        let options = CreateExecOptions {
+            attach_stdout: Some(true),
+            cmd: Some(cmd),
+            ..Default::default()
+        };
+        let exec = docker.create_exec(id.get(), options).await?;
+        let mut stdout = String::new();
+        if let StartExecResults::Attached { mut output, .. } =
+            docker.start_exec(&exec.id, None).await?
+        {
+            while let Some(message) = output.next().await {
+                if let LogOutput::StdOut { message } = message? {
+                    stdout.push_str(&String::from_utf8_lossy(&message));
+                }
+            }
+        }
+        match docker.inspect_exec(&exec.id).await?.exit_code {
+            Some(0) => Ok(stdout),
+            code => Err(format!("{program} exit code: {code:?}").into()),
+        }
+    }
+
+    /// List a directory in a container, with `ls`, directories have a trailing `/`
+    /// If `ls` can't be run, as the container is stopped, or doesn't have `ls`, the directory is downloaded, and listed from the headers of the archive, which is slower for large directories
+    async fn list_files(
+        docker: &Docker,
+        id: &ContainerId,
+        path: &str,
+        gui_state: &Arc<Mutex<GuiState>>,
+    ) -> Result<String, StreamError> {
+        match Self::exec_output(docker, id, vec!["ls", "-1Ap", "--", path]).await {
+            Ok(listing) => Ok(listing),
+            Err(_) => Self::read_archive(docker, id, path, gui_state, "listing", list_dir).await,
+        }
+    }
+
+    /// Download an archive of a file, or directory, from a container, and pass it to `read`, showing the amount received so far in the info box
+    /// `read` is run on a blocking thread, as archives are read synchronously, and is sent the archive in chunks as it's received
+    async fn read_archive<T: Send + 'static>(
+        docker: &Docker,
+        id: &ContainerId,
+        path: &str,
+        gui_state: &Arc<Mutex<GuiState>>,
+        action: &str,
+        read: impl FnOnce(ChannelReader) -> std::io::Result<T> + Send + 'static,
+    ) -> Result<T, StreamError> {
+        let (tx, rx) = tokio::sync::mpsc::channel(4);
+        let reader = tokio::task::spawn_blocking(move || read(ChannelReader::new(rx)));
+        let mut stream =
+            docker.download_from_container(id.get(), Some(DownloadFromContainerOptions { path }));
+        let mut received = 0;
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.map_err(std::io::Error::other);
+            received += chunk.as_ref().map_or(0, |i| i.len() as u64);
+            // The reader has already finished, or failed, either way its result is returned
+            if tx.send(chunk).await.is_err() {
+                break;
+            }
+            gui_state
+                .lock()
+                .set_info_box(&format!("{action}: {}", ByteStats::new(received)));
+        }
+        drop(tx);
+        Ok(reader.await??)
+    }
+
+    /// Refresh the processes of the container in the processes panel, if it's visible
+    /// Errors are ignored, as the container may have just stopped, and the panel would otherwise flash errors on every update
+    async fn update_processes(&self) {
+        let id = self.app_data.lock().get_processes().map(|i| i.id.clone());
+        if let Some(id) = id {
+            let top = self
+                .docker
+                .top_processes(id.get(), Some(TopOptions { ps_args: PS_ARGS }))
+                .await;
+            if let Ok(top) = top {
+                if let Some(processes) = self.app_data.lock().get_processes() {
+                    if processes.id == id {
+                        processes.update(&top);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Download a file, or directory, from a container, extracted from the archive the Docker api returns into `output`
+    async fn download_from_container(
+        docker: &Docker,
+        id: &ContainerId,
+        path: &str,
+        output: &Path,
+        gui_state: &Arc<Mutex<GuiState>>,
+    ) -> Result<(), StreamError> {
+        let output = output.to_owned();
+        Self::read_archive(docker, id, path, gui_state, "downloading", move |i| {
+            extract(i, &output)
+        })
+        .await
+    }
+
+    /// Upload a local file into a directory in a container, returns the name of the file
+    /// The archive is built on a blocking thread, reading the file as it's uploaded, so that a large file isn't read into memory
+    async fn upload_to_container(
+        docker: &Docker,
+        id: &ContainerId,
+        local: &Path,
+        dir: &str,
+    ) -> Result<String, StreamError> {
+        let name = local
+            .file_name()
+            .and_then(|i| i.to_str())
+            .ok_or("invalid file name")?
+            .to_owned();
+        let file = File::open(local)?;
+        let metadata = file.metadata()?;
+        let mtime = metadata
+            .modified()?
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map_or(0, |i| i.as_secs());
+        let (tx, rx) = tokio::sync::mpsc::channel(4);
+        let writer = tokio::task::spawn_blocking({
+            let name = name.clone();
+            move || {
+                let output = BufWriter::with_capacity(CHUNK_SIZE, ChannelWriter::new(tx));
+                tar_file(output, &name, file, metadata.len(), mtime)
+            }
+        });
+        let body =
+            futures_util::stream::unfold(
+                rx,
+                |mut rx| async move { rx.recv().await.map(|i| (i, rx)) },
+            );
+        let options = UploadToContainerOptions {
+            path: dir,
+            ..Default::default()
+        };
+        let upload = docker
+            .upload_to_container_streaming(id.get(), Some(options), body)
+            .await;
+        // A failed upload closes the body, so the writer fails too, and the upload error is the cause
+        upload?;
+        writer.await??;
+        Ok(name)
+    }
+
+    /// Create a container, connect it to any additional networks, and remove it again if any of the connections fail
+    async fn create_connected_container(
+        docker: &Docker,
+        name: &str,
+        config: Config<String>,
+        networks: Vec<(String, EndpointSettings)>,
+    ) -> Result<String, Error> {
+        let options = Some(CreateContainerOptions {
+            name,
+            platform: None,
+        });
+        let id = docker.create_container(options, config).await?.id;
+        for (network, endpoint_config) in networks {
+            let options = ConnectNetworkOptions {
+                container: id.as_str(),
+                endpoint_config,
+            };
+            if let Err(e) = docker.connect_network(&network, options).await {
+                let options = Some(RemoveContainerOptions {
+                    force: true,
+                    ..Default::default()
+                });
+                docker.remove_container(&id, options).await.ok();
+                return Err(e);
+            }
+        }
+        Ok(id)
+    }
+
+    /// Replace a container with a new container, that has the same name and networks, but modified settings, returns the name of the container
+    /// The image is pulled before anything is stopped, and the existing container is renamed, rather than removed, until the new container has been created, so that it can be restored if anything goes wrong
+    async fn recreate_container(
+        docker: &Docker,
+        id: &ContainerId,
+        run_config: &RunConfig,
+    ) -> Result<String, AppError> {
+        let error = |_| AppError::DockerRecreate;
+        let inspect = docker
+            .inspect_container(id.get(), None)
+            .await
+            .map_err(error)?;
+        let name = inspect
+            .name
+            .as_deref()
+            .unwrap_or_default()
+            .trim_start_matches('/')
+            .to_owned();
+        let running = inspect
+            .state
+            .as_ref()
+            .and_then(|i| i.running)
+            .unwrap_or_default();
+        let (config, networks) = existing_config(&inspect);
+        let config = run_config
+            .apply(config)
+            .map_err(|_| AppError::DockerRecreate)?;
+
+        if docker.inspect_image(&run_config.image).await.is_err() {
+            Self::pull_image(docker, &run_config.image)
+                .await
+                .map_err(error)?;
+        }
+
+        if running {
+            docker.stop_container(id.get(), None).await.map_err(error)?;
+        }
+        let backup = format!("{name}_{}", id.get().chars().take(12).collect::<String>());
+        docker
+            .rename_container(id.get(), RenameContainerOptions { name: backup })
+            .await
+            .map_err(error)?;
+
+        match Self::create_connected_container(docker, &name, config, networks).await {
+            Ok(new_id) => {
+                let options = Some(RemoveContainerOptions {
+                    force: true,
+                    ..Default::default()
+                });
+                docker
+                    .remove_container(id.get(), options)
+                    .await
+                    .map_err(error)?;
+                if running {
+                    docker
+                        .start_container(&new_id, None::<StartContainerOptions<String>>)
+                        .await
+                        .map_err(error)?;
+                }
+                Ok(name)
+            }
+            Err(e) => {
+                docker
+                    .rename_container(id.get(), RenameContainerOptions { name: &name })
+                    .await
+                    .ok();
+                if running {
+                    docker
+                        .start_container(id.get(), None::<StartContainerOptions<String>>)
+                        .await
+                        .ok();
+                }
+                Err(error(e))
+            }
+        }
+    }
+
+    /// Set the global error as an image, volume, or network error, and set gui_state to error
+    fn set_resource_error(
+        app_data: &Arc<Mutex<AppData>>,
+        error: AppError,
+        gui_state: &Arc<Mutex<GuiState>>,
+    ) {
+        app_data.lock().set_error(error, gui_state, Status::Error);
     }
 
     /// Initialize docker container data, before any messages are received
@@ -310,6 +865,11 @@ impl DockerData {
         self.gui_state.lock().status_push(Status::Init);
         let loading_uuid = Uuid::new_v4();
         let loading_handle = GuiState::start_loading_animation(&self.gui_state, loading_uuid);
+        // The network filter of the previous session needs the networks, so that the containers are filtered from the first draw
+        let network_filter_pending = self.app_data.lock().is_network_filter_pending();
+        if network_filter_pending {
+            self.update_all_networks().await;
+        }
         let all_ids = self.update_all_containers().await;
 
         self.update_all_container_stats(&all_ids);
@@ -323,6 +883,7 @@ impl DockerData {
                 self.init = None;
             }
         }
+        self.app_data.lock().restore_container();
         self.gui_state
             .lock()
             .stop_loading_animation(&loading_handle, loading_uuid);
@@ -351,9 +912,167 @@ impl DockerData {
             let uuid = Uuid::new_v4();
             // TODO need to refactor these
             match message {
+                DockerMessage::Changes(id) => {
+                    tokio::spawn(async move {
+                        let handle = GuiState::start_loading_animation(&gui_state, uuid);
+                        match docker.container_changes(id.get()).await {
+                            Ok(changes) => {
+                                // The size of the writable layer is only calculated when requested
+                                let inspect = docker
+                                    .inspect_container(
+                                        id.get(),
+                                        Some(InspectContainerOptions { size: true }),
+                                    )
+                                    .await
+                                    .ok();
+                                let name = inspect
+                                    .as_ref()
+                                    .and_then(|i| i.name.as_ref())
+                                    .map_or_else(String::new, |i| {
+                                        i.trim_start_matches('/').to_owned()
+                                    });
+                                let size_rw = inspect.and_then(|i| i.size_rw);
+                                app_data.lock().set_changes(ChangesData::new(
+                                    id,
+                                    &name,
+                                    changes.unwrap_or_default(),
+                                    size_rw,
+                                ));
+                                gui_state.lock().status_push(Status::Changes);
+                            }
+                            Err(_) => {
+                                app_data.lock().set_error(
+                                    AppError::DockerChanges,
+                                    &gui_state,
+                                    Status::Error,
+                                );
+                            }
+                        }
+                        gui_state.lock().stop_loading_animation(&handle, uuid);
+                    });
+                }
                 DockerMessage::Exec(docker_tx) => {
                     docker_tx.send(Arc::clone(&self.docker)).ok();
                 }
+                DockerMessage::Download { id, path, output } => {
+                    tokio::spawn(async move {
+                        let handle = GuiState::start_loading_animation(&gui_state, uuid);
+                        if Self::download_from_container(&docker, &id, &path, &output, &gui_state)
+                            .await
+                            .is_ok()
+                        {
+                            gui_state
+                                .lock()
+                                .set_info_box(&format!("downloaded to {}", output.display()));
+                        } else {
+                            app_data.lock().set_error(
+                                AppError::DockerDownload,
+                                &gui_state,
+                                Status::Error,
+                            );
+                        }
+                        gui_state.lock().stop_loading_animation(&handle, uuid);
+                    });
+                }
+                DockerMessage::Export { id, path } => {
+                    tokio::spawn(async move {
+                        let handle = GuiState::start_loading_animation(&gui_state, uuid);
+                        if Self::export_container(&docker, &id, &path, &gui_state)
+                            .await
+                            .is_ok()
+                        {
+                            gui_state
+                                .lock()
+                                .set_info_box(&format!("exported to {}", path.display()));
+                        } else {
+                            // Don't leave a partially written tarball behind
+                            std::fs::remove_file(&path).ok();
+                            app_data.lock().set_error(
+                                AppError::DockerExport,
+                                &gui_state,
+                                Status::Error,
+                            );
+                        }
+                        gui_state.lock().stop_loading_animation(&handle, uuid);
+                    });
+                }
+                DockerMessage::SaveLogs { id, path, export } => {
+                    let timestamp = self.args.timestamp;
+                    tokio::spawn(async move {
+                        let handle = GuiState::start_loading_animation(&gui_state, uuid);
+                        match Self::save_logs(&docker, &id, &path, &export, timestamp, &gui_state)
+                            .await
+                        {
+                            Ok(0) => {
+                                std::fs::remove_file(&path).ok();
+                                gui_state.lock().set_info_box("no log lines to save");
+                            }
+                            Ok(count) => {
+                                gui_state.lock().set_info_box(&format!(
+                                    "saved {count} lines to {}",
+                                    path.display()
+                                ));
+                            }
+                            Err(_) => {
+                                // Don't leave a partially written file behind
+                                std::fs::remove_file(&path).ok();
+                                app_data.lock().set_error(
+                                    AppError::DockerLogs,
+                                    &gui_state,
+                                    Status::Error,
+                                );
+                            }
+                        }
+                        gui_state.lock().stop_loading_animation(&handle, uuid);
+                    });
+                }
+                DockerMessage::Files { id, name, path } => {
+                    tokio::spawn(async move {
+                        let handle = GuiState::start_loading_animation(&gui_state, uuid);
+                        match Self::list_files(&docker, &id, &path, &gui_state).await {
+                            Ok(listing) => {
+                                app_data
+                                    .lock()
+                                    .set_files(FileBrowser::new(id, &name, &path, &listing));
+                                gui_state.lock().status_push(Status::Files);
+                            }
+                            Err(_) => {
+                                app_data.lock().set_error(
+                                    AppError::DockerFiles,
+                                    &gui_state,
+                                    Status::Error,
+                                );
+                            }
+                        }
+                        gui_state.lock().stop_loading_animation(&handle, uuid);
+                    });
+                }
+                DockerMessage::Inspect(id) => {
+                    tokio::spawn(async move {
+                        let handle = GuiState::start_loading_animation(&gui_state, uuid);
+                        match docker.inspect_container(id.get(), None).await {
+                            Ok(inspect) => {
+                                let name = inspect.name.as_ref().map_or_else(String::new, |i| {
+                                    i.trim_start_matches('/').to_owned()
+                                });
+                                app_data.lock().set_inspect(InspectData {
+                                    tree: Tree::from(&inspect),
+                                    id,
+                                    name,
+                                });
+                                gui_state.lock().status_push(Status::Inspect);
+                            }
+                            Err(_) => {
+                                app_data.lock().set_error(
+                                    AppError::DockerInspect,
+                                    &gui_state,
+                                    Status::Error,
+                                );
+                            }
+                        }
+                        gui_state.lock().stop_loading_animation(&handle, uuid);
+                    });
+                }
                 DockerMessage::Pause(id) => {
                     tokio::spawn(async move {
                         let handle = GuiState::start_loading_animation(&gui_state, uuid);
@@ -374,6 +1093,37 @@ impl DockerData {
                     });
                     self.update_everything().await;
                 }
+                DockerMessage::Signal { id, pid, signal } => {
+                    tokio::spawn(async move {
+                        let handle = GuiState::start_loading_animation(&gui_state, uuid);
+                        let cmd = vec!["kill", "-s", &signal, &pid];
+                        if Self::exec_output(&docker, &id, cmd).await.is_ok() {
+                            gui_state
+                                .lock()
+                                .set_info_box(&format!("sent {signal} to pid {pid}"));
+                        } else {
+                            app_data.lock().set_error(
+                                AppError::DockerSignal,
+                                &gui_state,
+                                Status::Error,
+                            );
+                        }
+                        gui_state.lock().stop_loading_animation(&handle, uuid);
+                    });
+                }
+                DockerMessage::SignalForm { id, process } => {
+                    tokio::spawn(async move {
+                        let handle = GuiState::start_loading_animation(&gui_state, uuid);
+                        // Left empty, for the user to enter, if the container can't list its processes
+                        let pid = Self::exec_output(&docker, &id, PROC_CMDLINES.to_vec())
+                            .await
+                            .ok()
+                            .and_then(|i| process.find_pid(&i))
+                            .unwrap_or_default();
+                        gui_state.lock().set_form(Some(Form::signal(id, &pid)));
+                        gui_state.lock().stop_loading_animation(&handle, uuid);
+                    });
+                }
                 DockerMessage::Start(id) => {
                     tokio::spawn(async move {
                         let handle = GuiState::start_loading_animation(&gui_state, uuid);
@@ -428,12 +1178,467 @@ impl DockerData {
                         gui_state.lock().stop_loading_animation(&handle, uuid);
                     });
                     self.update_everything().await;
-                    self.gui_state.lock().set_delete_container(None);
+                    self.gui_state.lock().set_delete_target(None);
+                }
+                DockerMessage::Commit {
+                    id,
+                    repo,
+                    tag,
+                    comment,
+                } => {
+                    tokio::spawn(async move {
+                        let handle = GuiState::start_loading_animation(&gui_state, uuid);
+                        let options = CommitContainerOptions {
+                            container: id.get(),
+                            repo: &repo,
+                            tag: &tag,
+                            comment: &comment,
+                            pause: true,
+                            ..Default::default()
+                        };
+                        if docker
+                            .commit_container(options, Config::<String>::default())
+                            .await
+                            .is_ok()
+                        {
+                            gui_state
+                                .lock()
+                                .set_info_box(&format!("committed to {repo}:{tag}"));
+                        } else {
+                            app_data.lock().set_error(
+                                AppError::DockerCommit,
+                                &gui_state,
+                                Status::Error,
+                            );
+                        }
+                        gui_state.lock().stop_loading_animation(&handle, uuid);
+                    });
+                }
+                DockerMessage::ConfirmDelete(target) => {
+                    self.gui_state.lock().set_delete_target(Some(target));
+                }
+                DockerMessage::ImageRemove(id) => {
+                    tokio::spawn(async move {
+                        let handle = GuiState::start_loading_animation(&gui_state, uuid);
+                        if docker.remove_image(&id, None, None).await.is_err() {
+                            Self::set_resource_error(
+                                &app_data,
+                                AppError::DockerImage(ResourceCommand::Remove),
+                                &gui_state,
+                            );
+                        }
+                        gui_state.lock().stop_loading_animation(&handle, uuid);
+                    });
+                    self.update_everything().await;
+                    self.gui_state.lock().set_delete_target(None);
+                }
+                DockerMessage::ImagePrune => {
+                    tokio::spawn(async move {
+                        let handle = GuiState::start_loading_animation(&gui_state, uuid);
+                        match docker
+                            .prune_images(None::<PruneImagesOptions<String>>)
+                            .await
+                        {
+                            Ok(response) => {
+                                let count = response.images_deleted.map_or(0, |i| i.len());
+                                let reclaimed = ByteStats::new(
+                                    response
+                                        .space_reclaimed
+                                        .map_or(0, |i| u64::try_from(i).unwrap_or_default()),
+                                );
+                                gui_state.lock().set_info_box(&format!(
+                                    "pruned {count} images, reclaimed {reclaimed}"
+                                ));
+                            }
+                            Err(_) => {
+                                Self::set_resource_error(
+                                    &app_data,
+                                    AppError::DockerImage(ResourceCommand::Prune),
+                                    &gui_state,
+                                );
+                            }
+                        }
+                        gui_state.lock().stop_loading_animation(&handle, uuid);
+                    });
+                    self.update_everything().await;
+                    self.gui_state.lock().set_delete_target(None);
+                }
+                DockerMessage::ImagePull(image) => {
+                    // Pulls can take a long time, so don't block the message handler
+                    tokio::spawn(async move {
+                        let handle = GuiState::start_loading_animation(&gui_state, uuid);
+                        if Self::pull_image(&docker, &image).await.is_ok() {
+                            gui_state.lock().set_info_box(&format!("pulled {image}"));
+                        } else {
+                            Self::set_resource_error(
+                                &app_data,
+                                AppError::DockerImage(ResourceCommand::Pull),
+                                &gui_state,
+                            );
+                        }
+                        gui_state.lock().stop_loading_animation(&handle, uuid);
+                    });
+                }
+                DockerMessage::NetworkCreate {
+                    name,
+                    driver,
+                    subnet,
+                } => {
+                    let ipam = if subnet.is_empty() {
+                        Ipam::default()
+                    } else {
+                        Ipam {
+                            config: Some(vec![IpamConfig {
+                                subnet: Some(subnet),
+                                ..Default::default()
+                            }]),
+                            ..Default::default()
+                        }
+                    };
+                    tokio::spawn(async move {
+                        let handle = GuiState::start_loading_animation(&gui_state, uuid);
+                        let options = CreateNetworkOptions {
+                            name: name.as_str(),
+                            driver: driver.as_str(),
+                            ipam,
+                            ..Default::default()
+                        };
+                        if docker.create_network(options).await.is_ok() {
+                            gui_state
+                                .lock()
+                                .set_info_box(&format!("created network {name}"));
+                        } else {
+                            Self::set_resource_error(
+                                &app_data,
+                                AppError::DockerNetwork(ResourceCommand::Create),
+                                &gui_state,
+                            );
+                        }
+                        gui_state.lock().stop_loading_animation(&handle, uuid);
+                    });
+                }
+                DockerMessage::NetworkRemove(id) => {
+                    tokio::spawn(async move {
+                        let handle = GuiState::start_loading_animation(&gui_state, uuid);
+                        if docker.remove_network(&id).await.is_err() {
+                            Self::set_resource_error(
+                                &app_data,
+                                AppError::DockerNetwork(ResourceCommand::Remove),
+                                &gui_state,
+                            );
+                        }
+                        gui_state.lock().stop_loading_animation(&handle, uuid);
+                    });
+                    self.update_everything().await;
+                    self.gui_state.lock().set_delete_target(None);
+                }
+                DockerMessage::NetworkPrune => {
+                    tokio::spawn(async move {
+                        let handle = GuiState::start_loading_animation(&gui_state, uuid);
+                        match docker
+                            .prune_networks(None::<PruneNetworksOptions<String>>)
+                            .await
+                        {
+                            Ok(response) => {
+                                let count = response.networks_deleted.map_or(0, |i| i.len());
+                                gui_state
+                                    .lock()
+                                    .set_info_box(&format!("pruned {count} networks"));
+                            }
+                            Err(_) => {
+                                Self::set_resource_error(
+                                    &app_data,
+                                    AppError::DockerNetwork(ResourceCommand::Prune),
+                                    &gui_state,
+                                );
+                            }
+                        }
+                        gui_state.lock().stop_loading_animation(&handle, uuid);
+                    });
+                    self.update_everything().await;
+                    self.gui_state.lock().set_delete_target(None);
+                }
+                DockerMessage::Run { name, config } => {
+                    // May need to pull the image first, so don't block the message handler
+                    tokio::spawn(async move {
+                        let handle = GuiState::start_loading_animation(&gui_state, uuid);
+                        match Self::run_container(&docker, &name, *config).await {
+                            Ok(id) => {
+                                let name = if name.is_empty() {
+                                    id.chars().take(8).collect()
+                                } else {
+                                    name
+                                };
+                                gui_state.lock().set_info_box(&format!("started {name}"));
+                            }
+                            Err(_) => {
+                                app_data.lock().set_error(
+                                    AppError::DockerRun,
+                                    &gui_state,
+                                    Status::Error,
+                                );
+                            }
+                        }
+                        gui_state.lock().stop_loading_animation(&handle, uuid);
+                    });
+                }
+                DockerMessage::Limits { id, options } => {
+                    tokio::spawn(async move {
+                        let handle = GuiState::start_loading_animation(&gui_state, uuid);
+                        if docker.update_container(id.get(), *options).await.is_ok() {
+                            gui_state.lock().set_info_box("limits updated");
+                        } else {
+                            app_data.lock().set_error(
+                                AppError::DockerLimits,
+                                &gui_state,
+                                Status::Error,
+                            );
+                        }
+                        gui_state.lock().stop_loading_animation(&handle, uuid);
+                    });
+                }
+                DockerMessage::LimitsForm(id) => {
+                    tokio::spawn(async move {
+                        let handle = GuiState::start_loading_animation(&gui_state, uuid);
+                        match docker.inspect_container(id.get(), None).await {
+                            Ok(inspect) => {
+                                gui_state.lock().set_form(Some(Form::limits(
+                                    id,
+                                    &LimitsConfig::from(&inspect),
+                                )));
+                            }
+                            Err(_) => {
+                                app_data.lock().set_error(
+                                    AppError::DockerInspect,
+                                    &gui_state,
+                                    Status::Error,
+                                );
+                            }
+                        }
+                        gui_state.lock().stop_loading_animation(&handle, uuid);
+                    });
+                }
+                DockerMessage::Recreate { id, config } => {
+                    tokio::spawn(async move {
+                        let handle = GuiState::start_loading_animation(&gui_state, uuid);
+                        match Self::recreate_container(&docker, &id, &config).await {
+                            Ok(name) => {
+                                gui_state.lock().set_info_box(&format!("recreated {name}"));
+                            }
+                            Err(error) => {
+                                app_data.lock().set_error(error, &gui_state, Status::Error);
+                            }
+                        }
+                        gui_state.lock().stop_loading_animation(&handle, uuid);
+                    });
+                }
+                DockerMessage::Overview => {
+                    tokio::spawn(async move {
+                        let handle = GuiState::start_loading_animation(&gui_state, uuid);
+                        match tokio::try_join!(docker.info(), docker.version(), docker.df()) {
+                            Ok((info, version, df)) => {
+                                app_data
+                                    .lock()
+                                    .set_host(HostInfo::new(&info, &version, &df));
+                                gui_state.lock().status_push(Status::Overview);
+                            }
+                            Err(_) => {
+                                app_data.lock().set_error(
+                                    AppError::DockerOverview,
+                                    &gui_state,
+                                    Status::Error,
+                                );
+                            }
+                        }
+                        gui_state.lock().stop_loading_animation(&handle, uuid);
+                    });
+                }
+                DockerMessage::Processes(id) => {
+                    tokio::spawn(async move {
+                        let handle = GuiState::start_loading_animation(&gui_state, uuid);
+                        match docker
+                            .top_processes(id.get(), Some(TopOptions { ps_args: PS_ARGS }))
+                            .await
+                        {
+                            Ok(top) => {
+                                let name = app_data
+                                    .lock()
+                                    .get_container_name_by_id(&id)
+                                    .map_or_else(String::new, |i| i.get().to_owned());
+                                app_data
+                                    .lock()
+                                    .set_processes(ProcessesData::new(id, &name, &top));
+                                gui_state.lock().status_push(Status::Processes);
+                            }
+                            Err(_) => {
+                                app_data.lock().set_error(
+                                    AppError::DockerProcesses,
+                                    &gui_state,
+                                    Status::Error,
+                                );
+                            }
+                        }
+                        gui_state.lock().stop_loading_animation(&handle, uuid);
+                    });
+                }
+                DockerMessage::RecreateForm(id) => {
+                    tokio::spawn(async move {
+                        let handle = GuiState::start_loading_animation(&gui_state, uuid);
+                        match docker.inspect_container(id.get(), None).await {
+                            Ok(inspect) => {
+                                gui_state
+                                    .lock()
+                                    .set_form(Some(Form::recreate(id, &RunConfig::from(&inspect))));
+                            }
+                            Err(_) => {
+                                app_data.lock().set_error(
+                                    AppError::DockerInspect,
+                                    &gui_state,
+                                    Status::Error,
+                                );
+                            }
+                        }
+                        gui_state.lock().stop_loading_animation(&handle, uuid);
+                    });
+                }
+                DockerMessage::Rename { id, name } => {
+                    let options = RenameContainerOptions {
+                        name: name.as_str(),
+                    };
+                    if docker.rename_container(id.get(), options).await.is_ok() {
+                        gui_state.lock().set_info_box(&format!("renamed to {name}"));
+                    } else {
+                        app_data.lock().set_error(
+                            AppError::DockerRename,
+                            &gui_state,
+                            Status::Error,
+                        );
+                    }
+                    self.update_everything().await;
+                }
+                DockerMessage::RunClone(id) => {
+                    tokio::spawn(async move {
+                        let handle = GuiState::start_loading_animation(&gui_state, uuid);
+                        match docker.inspect_container(id.get(), None).await {
+                            Ok(inspect) => {
+                                gui_state
+                                    .lock()
+                                    .set_form(Some(Form::run(&RunConfig::from(&inspect))));
+                            }
+                            Err(_) => {
+                                app_data.lock().set_error(
+                                    AppError::DockerInspect,
+                                    &gui_state,
+                                    Status::Error,
+                                );
+                            }
+                        }
+                        gui_state.lock().stop_loading_animation(&handle, uuid);
+                    });
+                }
+                DockerMessage::VolumeCreate { name, driver } => {
+                    tokio::spawn(async move {
+                        let handle = GuiState::start_loading_animation(&gui_state, uuid);
+                        let options = CreateVolumeOptions {
+                            name: name.as_str(),
+                            driver: driver.as_str(),
+                            ..Default::default()
+                        };
+                        match docker.create_volume(options).await {
+                            Ok(volume) => {
+                                gui_state
+                                    .lock()
+                                    .set_info_box(&format!("created volume {}", volume.name));
+                            }
+                            Err(_) => {
+                                Self::set_resource_error(
+                                    &app_data,
+                                    AppError::DockerVolume(ResourceCommand::Create),
+                                    &gui_state,
+                                );
+                            }
+                        }
+                        gui_state.lock().stop_loading_animation(&handle, uuid);
+                    });
+                }
+                DockerMessage::VolumeRemove(name) => {
+                    tokio::spawn(async move {
+                        let handle = GuiState::start_loading_animation(&gui_state, uuid);
+                        if docker.remove_volume(&name, None).await.is_err() {
+                            Self::set_resource_error(
+                                &app_data,
+                                AppError::DockerVolume(ResourceCommand::Remove),
+                                &gui_state,
+                            );
+                        }
+                        gui_state.lock().stop_loading_animation(&handle, uuid);
+                    });
+                    self.update_everything().await;
+                    self.gui_state.lock().set_delete_target(None);
                 }
-                DockerMessage::ConfirmDelete(id) => {
-                    self.gui_state.lock().set_delete_container(Some(id));
+                DockerMessage::VolumePrune => {
+                    tokio::spawn(async move {
+                        let handle = GuiState::start_loading_animation(&gui_state, uuid);
+                        match docker
+                            .prune_volumes(None::<PruneVolumesOptions<String>>)
+                            .await
+                        {
+                            Ok(response) => {
+                                let count = response.volumes_deleted.map_or(0, |i| i.len());
+                                let reclaimed = ByteStats::new(
+                                    response
+                                        .space_reclaimed
+                                        .map_or(0, |i| u64::try_from(i).unwrap_or_default()),
+                                );
+                                gui_state.lock().set_info_box(&format!(
+                                    "pruned {count} volumes, reclaimed {reclaimed}"
+                                ));
+                            }
+                            Err(_) => {
+                                Self::set_resource_error(
+                                    &app_data,
+                                    AppError::DockerVolume(ResourceCommand::Prune),
+                                    &gui_state,
+                                );
+                            }
+                        }
+                        gui_state.lock().stop_loading_animation(&handle, uuid);
+                    });
+                    self.update_everything().await;
+                    self.gui_state.lock().set_delete_target(None);
                 }
                 DockerMessage::Update => self.update_everything().await,
+                DockerMessage::Upload { id, local, dir } => {
+                    tokio::spawn(async move {
+                        let handle = GuiState::start_loading_animation(&gui_state, uuid);
+                        match Self::upload_to_container(&docker, &id, &local, &dir).await {
+                            Ok(file) => {
+                                gui_state
+                                    .lock()
+                                    .set_info_box(&format!("uploaded {file} to {dir}"));
+                                // Refresh the listing, so that the uploaded file is visible
+                                let name = app_data.lock().get_files().map(|i| i.name.clone());
+                                if let Some(name) = name {
+                                    if let Ok(listing) =
+                                        Self::list_files(&docker, &id, &dir, &gui_state).await
+                                    {
+                                        app_data
+                                            .lock()
+                                            .set_files(FileBrowser::new(id, &name, &dir, &listing));
+                                    }
+                                }
+                            }
+                            Err(_) => {
+                                app_data.lock().set_error(
+                                    AppError::DockerUpload,
+                                    &gui_state,
+                                    Status::Error,
+                                );
+                            }
+                        }
+                        gui_state.lock().stop_loading_animation(&handle, uuid);
+                    });
+                }
                 DockerMessage::Quit => {
                     self.spawns
                         .lock()
@@ -0,0 +1,875 @@
+use std::{collections::HashMap, fmt};
+
+use crossterm::event::{KeyCode, KeyModifiers};
+use serde::Deserialize;
+
+use crate::config::{ConfigError, KeyBinding};
+
+/// Every action that can be bound to a key, or a chord of keys, in the main view, or in one of the panels
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    Changes,
+    ChangesClear,
+    ChangesFilter,
+    ChangesKind,
+    ColumnMoveDown,
+    ColumnMoveUp,
+    ColumnToggle,
+    Columns,
+    ColumnsSave,
+    Commit,
+    ConfirmNo,
+    ConfirmYes,
+    Create,
+    CyclePanel,
+    Delete,
+    Down,
+    Download,
+    End,
+    Exec,
+    Export,
+    ExportStats,
+    FileOpen,
+    Files,
+    GrowPanel,
+    Help,
+    Inspect,
+    Limits,
+    MaximisePanel,
+    MouseCapture,
+    NetworkFilter,
+    NextLayout,
+    NextPanel,
+    Overview,
+    OverviewRefresh,
+    PageDown,
+    PageUp,
+    PreviousPanel,
+    Processes,
+    Prune,
+    Pull,
+    Quit,
+    Recreate,
+    Rename,
+    Run,
+    RunCopy,
+    SaveLogs,
+    Select,
+    ShrinkPanel,
+    Signal,
+    SortCommand,
+    SortCpu,
+    SortCreated,
+    SortHealth,
+    SortId,
+    SortImage,
+    SortIp,
+    SortLabel,
+    SortMemory,
+    SortName,
+    SortPorts,
+    SortReset,
+    SortRestarts,
+    SortRx,
+    SortRxRate,
+    SortService,
+    SortState,
+    SortStatus,
+    SortTx,
+    SortTxRate,
+    SortUptime,
+    Start,
+    ToggleCharts,
+    ToggleCommands,
+    TreeCollapse,
+    TreeExpand,
+    TreeExpandAll,
+    TreeToggle,
+    Up,
+    Upload,
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let disp = match self {
+            Self::Changes => "changes",
+            Self::ChangesClear => "changes_clear",
+            Self::ChangesFilter => "changes_filter",
+            Self::ChangesKind => "changes_kind",
+            Self::ColumnMoveDown => "column_move_down",
+            Self::ColumnMoveUp => "column_move_up",
+            Self::ColumnToggle => "column_toggle",
+            Self::Columns => "columns",
+            Self::ColumnsSave => "columns_save",
+            Self::Commit => "commit",
+            Self::ConfirmNo => "confirm_no",
+            Self::ConfirmYes => "confirm_yes",
+            Self::Create => "create",
+            Self::CyclePanel => "cycle_panel",
+            Self::Delete => "delete",
+            Self::Down => "down",
+            Self::Download => "download",
+            Self::End => "end",
+            Self::Exec => "exec",
+            Self::Export => "export",
+            Self::ExportStats => "export_stats",
+            Self::FileOpen => "file_open",
+            Self::Files => "files",
+            Self::GrowPanel => "grow_panel",
+            Self::Help => "help",
+            Self::Inspect => "inspect",
+            Self::Limits => "limits",
+            Self::MaximisePanel => "maximise_panel",
+            Self::MouseCapture => "mouse_capture",
+            Self::NetworkFilter => "network_filter",
+            Self::NextLayout => "next_layout",
+            Self::NextPanel => "next_panel",
+            Self::Overview => "overview",
+            Self::OverviewRefresh => "overview_refresh",
+            Self::PageDown => "page_down",
+            Self::PageUp => "page_up",
+            Self::PreviousPanel => "previous_panel",
+            Self::Processes => "processes",
+            Self::Prune => "prune",
+            Self::Pull => "pull",
+            Self::Quit => "quit",
+            Self::Recreate => "recreate",
+            Self::Rename => "rename",
+            Self::Run => "run",
+            Self::RunCopy => "run_copy",
+            Self::SaveLogs => "save_logs",
+            Self::Select => "select",
+            Self::ShrinkPanel => "shrink_panel",
+            Self::Signal => "signal",
+            Self::SortCommand => "sort_command",
+            Self::SortCpu => "sort_cpu",
+            Self::SortCreated => "sort_created",
+            Self::SortHealth => "sort_health",
+            Self::SortId => "sort_id",
+            Self::SortImage => "sort_image",
+            Self::SortIp => "sort_ip",
+            Self::SortLabel => "sort_label",
+            Self::SortMemory => "sort_memory",
+            Self::SortName => "sort_name",
+            Self::SortPorts => "sort_ports",
+            Self::SortReset => "sort_reset",
+            Self::SortRestarts => "sort_restarts",
+            Self::SortRx => "sort_rx",
+            Self::SortRxRate => "sort_rx_rate",
+            Self::SortService => "sort_service",
+            Self::SortState => "sort_state",
+            Self::SortStatus => "sort_status",
+            Self::SortTx => "sort_tx",
+            Self::SortTxRate => "sort_tx_rate",
+            Self::SortUptime => "sort_uptime",
+            Self::Start => "start",
+            Self::ToggleCharts => "toggle_charts",
+            Self::ToggleCommands => "toggle_commands",
+            Self::TreeCollapse => "tree_collapse",
+            Self::TreeExpand => "tree_expand",
+            Self::TreeExpandAll => "tree_expand_all",
+            Self::TreeToggle => "tree_toggle",
+            Self::Up => "up",
+            Self::Upload => "upload",
+        };
+        write!(f, "{disp}")
+    }
+}
+
+/// The view key presses are resolved in, the main view, or a panel shown on top of it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyContext {
+    Changes,
+    Columns,
+    DeleteConfirm,
+    Files,
+    Inspect,
+    Main,
+    Overview,
+    Processes,
+}
+
+impl KeyContext {
+    const ALL: [Self; 8] = [
+        Self::Changes,
+        Self::Columns,
+        Self::DeleteConfirm,
+        Self::Files,
+        Self::Inspect,
+        Self::Main,
+        Self::Overview,
+        Self::Processes,
+    ];
+}
+
+impl Action {
+    /// Actions that change the selected line, these also apply in the inspect, changes, processes, columns, and files panels
+    pub const fn is_navigation(self) -> bool {
+        matches!(
+            self,
+            Self::Up | Self::Down | Self::PageUp | Self::PageDown | Self::Start | Self::End
+        )
+    }
+
+    /// The panels an action belongs to, empty for an action of the main view
+    const fn panels(self) -> &'static [KeyContext] {
+        match self {
+            Self::ChangesClear | Self::ChangesFilter | Self::ChangesKind => &[KeyContext::Changes],
+            Self::ColumnMoveDown | Self::ColumnMoveUp | Self::ColumnToggle | Self::ColumnsSave => {
+                &[KeyContext::Columns]
+            }
+            Self::ConfirmNo | Self::ConfirmYes => &[KeyContext::DeleteConfirm],
+            Self::Download | Self::FileOpen | Self::Upload => &[KeyContext::Files],
+            Self::OverviewRefresh => &[KeyContext::Overview],
+            Self::Signal => &[KeyContext::Processes],
+            Self::TreeCollapse | Self::TreeExpand | Self::TreeExpandAll | Self::TreeToggle => {
+                &[KeyContext::Changes, KeyContext::Inspect]
+            }
+            _ => &[],
+        }
+    }
+
+    /// The action that opens, and closes, a panel
+    const fn opens(self) -> Option<KeyContext> {
+        match self {
+            Self::Changes => Some(KeyContext::Changes),
+            Self::Columns => Some(KeyContext::Columns),
+            Self::Files => Some(KeyContext::Files),
+            Self::Inspect => Some(KeyContext::Inspect),
+            Self::Overview => Some(KeyContext::Overview),
+            Self::Processes => Some(KeyContext::Processes),
+            _ => None,
+        }
+    }
+
+    /// Check if an action can be used in the given context
+    /// A panel has its own actions, as well as the navigation actions, quit, and the action that closes the panel
+    fn is_active(self, context: KeyContext) -> bool {
+        let panels = self.panels();
+        if panels.is_empty() {
+            context == KeyContext::Main
+                || self.is_navigation()
+                || self == Self::Quit
+                || self.opens() == Some(context)
+        } else {
+            panels.contains(&context)
+        }
+    }
+
+    /// Check if two actions can both be used in any one context, and so can't share a key
+    fn shares_context(self, other: Self) -> bool {
+        KeyContext::ALL
+            .iter()
+            .any(|i| self.is_active(*i) && other.is_active(*i))
+    }
+}
+
+/// The default keys of every action, the order here is the order of the bindings in the keymap
+const DEFAULT_KEYS: [(Action, &[&str]); 79] = [
+    (Action::NextPanel, &["tab"]),
+    (Action::PreviousPanel, &["shift+tab"]),
+    (Action::Up, &["up", "k"]),
+    (Action::Down, &["down", "j"]),
+    (Action::PageUp, &["pgup"]),
+    (Action::PageDown, &["pgdown"]),
+    (Action::Start, &["home"]),
+    (Action::End, &["end"]),
+    (Action::Select, &["enter"]),
+    (Action::Exec, &["e"]),
+    (Action::Help, &["h"]),
+    (Action::Inspect, &["i"]),
+    (Action::Limits, &["l"]),
+    (Action::Run, &["r"]),
+    (Action::RunCopy, &["y"]),
+    (Action::Recreate, &["a"]),
+    (Action::Rename, &["n"]),
+    (Action::Commit, &["o"]),
+    (Action::Export, &["x"]),
+    (Action::Changes, &["g"]),
+    (Action::Processes, &["t"]),
+    (Action::Overview, &["w"]),
+    (Action::Columns, &["z"]),
+    (Action::Files, &["b"]),
+    (Action::SaveLogs, &["s"]),
+    (Action::ExportStats, &["ctrl+s"]),
+    (Action::CyclePanel, &["v"]),
+    (Action::Create, &["c"]),
+    (Action::Delete, &["d"]),
+    (Action::Prune, &["p"]),
+    (Action::Pull, &["u"]),
+    (Action::NetworkFilter, &["f"]),
+    (Action::MouseCapture, &["m"]),
+    (Action::GrowPanel, &["+", "="]),
+    (Action::ShrinkPanel, &["-"]),
+    (Action::MaximisePanel, &["."]),
+    (Action::ToggleCommands, &["["]),
+    (Action::ToggleCharts, &["]"]),
+    (Action::NextLayout, &[","]),
+    (Action::SortReset, &["0"]),
+    (Action::SortName, &["1"]),
+    (Action::SortState, &["2"]),
+    (Action::SortStatus, &["3"]),
+    (Action::SortCpu, &["4"]),
+    (Action::SortMemory, &["5"]),
+    (Action::SortId, &["6"]),
+    (Action::SortImage, &["7"]),
+    (Action::SortRx, &["8"]),
+    (Action::SortTx, &["9"]),
+    (Action::SortHealth, &[]),
+    (Action::SortRxRate, &[]),
+    (Action::SortTxRate, &[]),
+    (Action::SortCreated, &[]),
+    (Action::SortUptime, &[]),
+    (Action::SortPorts, &[]),
+    (Action::SortCommand, &[]),
+    (Action::SortService, &[]),
+    (Action::SortLabel, &[]),
+    (Action::SortRestarts, &[]),
+    (Action::SortIp, &[]),
+    (Action::TreeToggle, &["enter", "space"]),
+    (Action::TreeExpand, &["right", "l"]),
+    (Action::TreeCollapse, &["left"]),
+    (Action::TreeExpandAll, &["a"]),
+    (Action::ChangesFilter, &["/"]),
+    (Action::ChangesKind, &["f"]),
+    (Action::ChangesClear, &["backspace"]),
+    (Action::Signal, &["x"]),
+    (Action::OverviewRefresh, &["r"]),
+    (Action::ColumnToggle, &["space"]),
+    (Action::ColumnMoveUp, &["["]),
+    (Action::ColumnMoveDown, &["]"]),
+    (Action::ColumnsSave, &["enter"]),
+    (Action::FileOpen, &["enter"]),
+    (Action::Download, &["d"]),
+    (Action::Upload, &["u"]),
+    (Action::ConfirmYes, &["y"]),
+    (Action::ConfirmNo, &["n"]),
+    (Action::Quit, &["q"]),
+];
+
+/// A single key press, chars are case insensitive, so are always stored lowercase, and shift is only kept as part of shift+tab
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Key {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl Key {
+    /// Normalise a key event, so that it can be compared against the bindings
+    /// A char with a single char lowercase form, including non-ascii chars, e.g. `Ф`, is stored lowercase, the same as when parsed from the config
+    pub fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        let code = match code {
+            KeyCode::Char(c) => {
+                let mut lower = c.to_lowercase();
+                match (lower.next(), lower.next()) {
+                    (Some(l), None) => KeyCode::Char(l),
+                    _ => KeyCode::Char(c),
+                }
+            }
+            x => x,
+        };
+        Self {
+            code,
+            modifiers: modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT),
+        }
+    }
+
+    /// Parse a single key, e.g. `a`, `ctrl+d`, `shift+tab`, `pgdown`, `f1`
+    fn parse(input: &str) -> Result<Self, String> {
+        let input = input.trim();
+        let (prefix, name) = match input.strip_suffix('+') {
+            Some(prefix) if prefix.is_empty() || prefix.ends_with('+') => {
+                (prefix.trim_end_matches('+'), "+")
+            }
+            _ => input.rsplit_once('+').unwrap_or(("", input)),
+        };
+        let mut modifiers = KeyModifiers::NONE;
+        let mut shift = false;
+        for modifier in prefix.split('+').filter(|i| !i.is_empty()) {
+            match modifier.to_lowercase().as_str() {
+                "ctrl" | "control" => modifiers |= KeyModifiers::CONTROL,
+                "alt" => modifiers |= KeyModifiers::ALT,
+                "shift" => shift = true,
+                _ => return Err(format!("unknown modifier \"{modifier}\" in \"{input}\"")),
+            }
+        }
+        let lower = name.to_lowercase();
+        let code = match lower.as_str() {
+            "backspace" => KeyCode::Backspace,
+            "backtab" => KeyCode::BackTab,
+            "del" | "delete" => KeyCode::Delete,
+            "down" => KeyCode::Down,
+            "end" => KeyCode::End,
+            "enter" => KeyCode::Enter,
+            "esc" => KeyCode::Esc,
+            "home" => KeyCode::Home,
+            "insert" => KeyCode::Insert,
+            "left" => KeyCode::Left,
+            "pagedown" | "pgdown" => KeyCode::PageDown,
+            "pageup" | "pgup" => KeyCode::PageUp,
+            "right" => KeyCode::Right,
+            "space" => KeyCode::Char(' '),
+            "tab" if shift => KeyCode::BackTab,
+            "tab" => KeyCode::Tab,
+            "up" => KeyCode::Up,
+            x => {
+                let mut chars = name.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => KeyCode::Char(c),
+                    (Some('f' | 'F'), Some(_)) => x[1..]
+                        .parse::<u8>()
+                        .ok()
+                        .filter(|i| (1..=12).contains(i))
+                        .map(KeyCode::F)
+                        .ok_or_else(|| format!("unknown key \"{input}\""))?,
+                    _ => return Err(format!("unknown key \"{input}\"")),
+                }
+            }
+        };
+        if shift && code != KeyCode::BackTab {
+            return Err(format!(
+                "shift is only supported with tab, as keys are case insensitive, in \"{input}\""
+            ));
+        }
+        Ok(Self::new(code, modifiers))
+    }
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "alt+")?;
+        }
+        match self.code {
+            KeyCode::Backspace => write!(f, "backspace"),
+            KeyCode::BackTab => write!(f, "shift+tab"),
+            KeyCode::Char(' ') => write!(f, "space"),
+            KeyCode::Char(c) => write!(f, "{c}"),
+            KeyCode::Delete => write!(f, "delete"),
+            KeyCode::Down => write!(f, "↓"),
+            KeyCode::End => write!(f, "End"),
+            KeyCode::Enter => write!(f, "enter"),
+            KeyCode::Esc => write!(f, "esc"),
+            KeyCode::F(x) => write!(f, "f{x}"),
+            KeyCode::Home => write!(f, "Home"),
+            KeyCode::Insert => write!(f, "insert"),
+            KeyCode::Left => write!(f, "←"),
+            KeyCode::PageDown => write!(f, "PgDown"),
+            KeyCode::PageUp => write!(f, "PgUp"),
+            KeyCode::Right => write!(f, "→"),
+            KeyCode::Tab => write!(f, "tab"),
+            KeyCode::Up => write!(f, "↑"),
+            x => write!(f, "{x:?}"),
+        }
+    }
+}
+
+/// Parse a binding, a single key, or a chord of keys separated by spaces, e.g. `g g`, `ctrl+w j`
+fn parse_chord(input: &str) -> Result<Vec<Key>, String> {
+    let chord = input
+        .split_whitespace()
+        .map(Key::parse)
+        .collect::<Result<Vec<_>, _>>()?;
+    if chord.is_empty() {
+        Err("empty key binding".to_owned())
+    } else {
+        Ok(chord)
+    }
+}
+
+/// Display a chord, with each key separated by a space
+fn chord_label(chord: &[Key]) -> String {
+    chord
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Every key binding, in the order of DEFAULT_KEYS, an action can have multiple bindings, or none at all
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Keymap {
+    bindings: Vec<(Action, Vec<Key>)>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self {
+            bindings: DEFAULT_KEYS
+                .iter()
+                .flat_map(|(action, keys)| {
+                    keys.iter()
+                        .filter_map(|key| parse_chord(key).ok())
+                        .map(|chord| (*action, chord))
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Keymap {
+    /// Create the keymap from the defaults, with the bindings of any action in the config file replacing its default bindings
+    /// A key bound to two actions, or a binding which is the start of a chord, is an error, as one of them could never be used
+    /// Actions that are never used in the same context, e.g. limits in the main view, and expand in the inspect panel, can share a key
+    pub fn new(config: &HashMap<Action, KeyBinding>) -> Result<Self, ConfigError> {
+        let mut bindings = vec![];
+        for (action, defaults) in DEFAULT_KEYS {
+            let keys = config.get(&action).map_or_else(
+                || defaults.iter().map(ToString::to_string).collect(),
+                |binding| match binding {
+                    KeyBinding::One(key) => vec![key.clone()],
+                    KeyBinding::Many(keys) => keys.clone(),
+                },
+            );
+            for key in keys {
+                let chord = parse_chord(&key)
+                    .map_err(|e| ConfigError::Invalid(format!("keys.{action} - {e}")))?;
+                bindings.push((action, chord));
+            }
+        }
+
+        for (index, (action, chord)) in bindings.iter().enumerate() {
+            for (other_action, other_chord) in bindings
+                .iter()
+                .skip(index + 1)
+                .filter(|(other, _)| action.shares_context(*other))
+            {
+                let (short, long) = if chord.len() <= other_chord.len() {
+                    ((action, chord), (other_action, other_chord))
+                } else {
+                    ((other_action, other_chord), (action, chord))
+                };
+                if long.1.starts_with(short.1) {
+                    let label = chord_label(short.1);
+                    let e = if short.1.len() == long.1.len() {
+                        format!(
+                            "keys - \"{label}\" is bound to both {} and {}",
+                            short.0, long.0
+                        )
+                    } else {
+                        format!(
+                            "keys - \"{label}\" of {} is the start of \"{}\" of {}",
+                            short.0,
+                            chord_label(long.1),
+                            long.0
+                        )
+                    };
+                    return Err(ConfigError::Invalid(e));
+                }
+            }
+        }
+        Ok(Self { bindings })
+    }
+
+    /// Resolve a key press into an action of the given context, pending holds the keys of a partially entered chord
+    /// A key that doesn't continue the pending chord is treated as the start of a new binding
+    pub fn resolve(&self, pending: &mut Vec<Key>, key: Key, context: KeyContext) -> Option<Action> {
+        let mut bindings = self
+            .bindings
+            .iter()
+            .filter(|(action, _)| action.is_active(context));
+        pending.push(key);
+        if let Some((action, _)) = bindings.clone().find(|(_, chord)| chord == pending) {
+            pending.clear();
+            return Some(*action);
+        }
+        if bindings.any(|(_, chord)| chord.starts_with(pending)) {
+            return None;
+        }
+        let retry = pending.len() > 1;
+        pending.clear();
+        if retry {
+            self.resolve(pending, key, context)
+        } else {
+            None
+        }
+    }
+
+    /// The labels of every binding of an action, e.g. `["↑", "k"]`
+    pub fn labels(&self, action: Action) -> Vec<String> {
+        self.bindings
+            .iter()
+            .filter(|(i, _)| *i == action)
+            .map(|(_, chord)| chord_label(chord))
+            .collect()
+    }
+
+    /// The label of the first binding of an action
+    pub fn label(&self, action: Action) -> Option<String> {
+        self.labels(action).into_iter().next()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    use crate::config::{ConfigError, KeyBinding};
+
+    use super::{Action, Key, KeyContext, Keymap, DEFAULT_KEYS};
+
+    #[test]
+    /// Keys parsed, with modifiers, and chars stored lowercase
+    fn test_keymap_key_parse() {
+        let key = |code, modifiers| Key::new(code, modifiers);
+        assert_eq!(
+            Key::parse("a"),
+            Ok(key(KeyCode::Char('a'), KeyModifiers::NONE))
+        );
+        assert_eq!(
+            Key::parse("A"),
+            Ok(key(KeyCode::Char('a'), KeyModifiers::NONE))
+        );
+        assert_eq!(
+            Key::parse("ctrl+d"),
+            Ok(key(KeyCode::Char('d'), KeyModifiers::CONTROL))
+        );
+        assert_eq!(
+            Key::parse("Ctrl+Alt+PgDown"),
+            Ok(key(
+                KeyCode::PageDown,
+                KeyModifiers::CONTROL | KeyModifiers::ALT
+            ))
+        );
+        assert_eq!(
+            Key::parse("shift+tab"),
+            Ok(key(KeyCode::BackTab, KeyModifiers::NONE))
+        );
+        assert_eq!(
+            Key::parse("+"),
+            Ok(key(KeyCode::Char('+'), KeyModifiers::NONE))
+        );
+        assert_eq!(
+            Key::parse("alt++"),
+            Ok(key(KeyCode::Char('+'), KeyModifiers::ALT))
+        );
+        assert_eq!(Key::parse("f5"), Ok(key(KeyCode::F(5), KeyModifiers::NONE)));
+        assert_eq!(
+            Key::parse("space"),
+            Ok(key(KeyCode::Char(' '), KeyModifiers::NONE))
+        );
+
+        for input in ["", "f13", "hyper+a", "shift+a", "abc"] {
+            assert!(Key::parse(input).is_err());
+        }
+    }
+
+    #[test]
+    /// A key event with shift, e.g. an uppercase char, matches its lowercase binding
+    fn test_keymap_key_new() {
+        assert_eq!(
+            Key::new(KeyCode::Char('A'), KeyModifiers::SHIFT),
+            Key::new(KeyCode::Char('a'), KeyModifiers::NONE)
+        );
+        assert_eq!(
+            Key::new(KeyCode::BackTab, KeyModifiers::SHIFT),
+            Key::parse("shift+tab").unwrap()
+        );
+        for (input, c) in [("ф", 'Ф'), ("Ф", 'ф'), ("Ф", 'Ф'), ("é", 'É')] {
+            assert_eq!(
+                Key::parse(input).unwrap(),
+                Key::new(KeyCode::Char(c), KeyModifiers::SHIFT),
+                "{input} {c}"
+            );
+        }
+        // Lowercases into two chars, so kept as is
+        assert_eq!(
+            Key::parse("İ").unwrap(),
+            Key::new(KeyCode::Char('İ'), KeyModifiers::NONE)
+        );
+    }
+
+    #[test]
+    /// Every default binding is valid, and an action with no default binding is unbound
+    fn test_keymap_default() {
+        let keymap = Keymap::default();
+        let count = DEFAULT_KEYS.iter().map(|i| i.1.len()).sum::<usize>();
+        assert_eq!(keymap.bindings.len(), count);
+        assert_eq!(Keymap::new(&HashMap::new()).unwrap(), keymap);
+        assert_eq!(keymap.labels(Action::Up), ["↑", "k"]);
+        assert_eq!(
+            keymap.label(Action::PreviousPanel),
+            Some("shift+tab".to_owned())
+        );
+        assert!(keymap.labels(Action::SortHealth).is_empty());
+        assert!(keymap.labels(Action::SortIp).is_empty());
+    }
+
+    #[test]
+    /// Bindings from the config replace the defaults of an action, and keys are resolved into actions, including chords
+    fn test_keymap_resolve() {
+        let config = HashMap::from([
+            (Action::Changes, KeyBinding::One("g g".to_owned())),
+            (
+                Action::Down,
+                KeyBinding::Many(vec!["down".to_owned(), "ctrl+n".to_owned()]),
+            ),
+            (Action::Quit, KeyBinding::Many(vec![])),
+            (Action::SortRxRate, KeyBinding::One("ctrl+r".to_owned())),
+        ]);
+        let keymap = Keymap::new(&config).unwrap();
+        let key = |c| Key::new(KeyCode::Char(c), KeyModifiers::NONE);
+        let mut pending = vec![];
+
+        assert_eq!(
+            keymap.resolve(
+                &mut pending,
+                Key::new(KeyCode::Char('r'), KeyModifiers::CONTROL),
+                KeyContext::Main
+            ),
+            Some(Action::SortRxRate)
+        );
+
+        assert_eq!(
+            keymap.resolve(&mut pending, key('j'), KeyContext::Main),
+            None
+        );
+        assert_eq!(
+            keymap.resolve(&mut pending, key('q'), KeyContext::Main),
+            None
+        );
+        assert_eq!(
+            keymap.resolve(
+                &mut pending,
+                Key::new(KeyCode::Char('n'), KeyModifiers::CONTROL),
+                KeyContext::Main
+            ),
+            Some(Action::Down)
+        );
+        assert_eq!(
+            keymap.resolve(&mut pending, key('n'), KeyContext::Main),
+            Some(Action::Rename)
+        );
+
+        assert_eq!(
+            keymap.resolve(&mut pending, key('g'), KeyContext::Main),
+            None
+        );
+        assert_eq!(pending.len(), 1);
+        assert_eq!(
+            keymap.resolve(&mut pending, key('g'), KeyContext::Main),
+            Some(Action::Changes)
+        );
+        assert!(pending.is_empty());
+
+        // A key that doesn't complete the chord starts a new binding
+        assert_eq!(
+            keymap.resolve(&mut pending, key('g'), KeyContext::Main),
+            None
+        );
+        assert_eq!(
+            keymap.resolve(&mut pending, key('i'), KeyContext::Main),
+            Some(Action::Inspect)
+        );
+        assert!(pending.is_empty());
+
+        assert_eq!(keymap.labels(Action::Changes), ["g g"]);
+        assert!(keymap.labels(Action::Quit).is_empty());
+    }
+
+    #[test]
+    /// Keys resolved into the actions of the current context, with navigation, quit, and the action that opened the panel, in every panel
+    fn test_keymap_resolve_context() {
+        let keymap = Keymap::default();
+        let resolve = |c, context| {
+            keymap.resolve(
+                &mut vec![],
+                Key::new(KeyCode::Char(c), KeyModifiers::NONE),
+                context,
+            )
+        };
+        assert_eq!(resolve('l', KeyContext::Main), Some(Action::Limits));
+        assert_eq!(resolve('l', KeyContext::Inspect), Some(Action::TreeExpand));
+        assert_eq!(resolve('l', KeyContext::Changes), Some(Action::TreeExpand));
+        assert_eq!(resolve('l', KeyContext::Files), None);
+        assert_eq!(resolve('d', KeyContext::Main), Some(Action::Delete));
+        assert_eq!(resolve('d', KeyContext::Files), Some(Action::Download));
+        assert_eq!(resolve('x', KeyContext::Processes), Some(Action::Signal));
+        assert_eq!(
+            resolve('y', KeyContext::DeleteConfirm),
+            Some(Action::ConfirmYes)
+        );
+        assert_eq!(
+            resolve('n', KeyContext::DeleteConfirm),
+            Some(Action::ConfirmNo)
+        );
+        assert_eq!(
+            resolve(' ', KeyContext::Columns),
+            Some(Action::ColumnToggle)
+        );
+        assert_eq!(resolve('[', KeyContext::Main), Some(Action::ToggleCommands));
+        assert_eq!(
+            resolve('[', KeyContext::Columns),
+            Some(Action::ColumnMoveUp)
+        );
+        assert_eq!(resolve('/', KeyContext::Main), None);
+
+        assert_eq!(resolve('j', KeyContext::Inspect), Some(Action::Down));
+        assert_eq!(resolve('q', KeyContext::Overview), Some(Action::Quit));
+        assert_eq!(resolve('i', KeyContext::Inspect), Some(Action::Inspect));
+        assert_eq!(resolve('g', KeyContext::Inspect), None);
+        assert_eq!(resolve('z', KeyContext::Processes), None);
+    }
+
+    #[test]
+    /// Actions only clash when they can both be used in the same context
+    fn test_keymap_new_context() {
+        let new = |action, key: &str| {
+            Keymap::new(&HashMap::from([(action, KeyBinding::One(key.to_owned()))]))
+        };
+        assert!(new(Action::Signal, "l").is_ok());
+        assert!(new(Action::ConfirmYes, "e").is_ok());
+        assert_eq!(
+            new(Action::Download, "b").unwrap_err().to_string(),
+            "Invalid setting: keys - \"b\" is bound to both files and download"
+        );
+        assert_eq!(
+            new(Action::TreeExpandAll, "k").unwrap_err().to_string(),
+            "Invalid setting: keys - \"k\" is bound to both up and tree_expand_all"
+        );
+        assert_eq!(
+            new(Action::ChangesKind, "a").unwrap_err().to_string(),
+            "Invalid setting: keys - \"a\" is bound to both tree_expand_all and changes_kind"
+        );
+    }
+
+    #[test]
+    /// Invalid keys, a key bound twice, or a key which is the start of a chord, are errors
+    fn test_keymap_new_err() {
+        let err = |action, key: &str| {
+            Keymap::new(&HashMap::from([(action, KeyBinding::One(key.to_owned()))]))
+                .unwrap_err()
+                .to_string()
+        };
+        assert_eq!(
+            err(Action::Quit, "ctrl+"),
+            "Invalid setting: keys.quit - unknown key \"ctrl+\""
+        );
+        assert_eq!(
+            err(Action::Quit, " "),
+            "Invalid setting: keys.quit - empty key binding"
+        );
+        assert_eq!(
+            err(Action::Export, "k"),
+            "Invalid setting: keys - \"k\" is bound to both up and export"
+        );
+        assert_eq!(
+            err(Action::Quit, "g q"),
+            "Invalid setting: keys - \"g\" of changes is the start of \"g q\" of quit"
+        );
+        assert!(matches!(
+            Keymap::new(&HashMap::from([(
+                Action::Quit,
+                KeyBinding::One("x".to_owned())
+            )])),
+            Err(ConfigError::Invalid(_))
+        ));
+    }
+}
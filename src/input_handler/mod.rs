@@ -1,6 +1,5 @@
 use std::{
-    fs::OpenOptions,
-    io::{BufWriter, Write},
+    path::PathBuf,
     sync::{
         atomic::{AtomicBool, Ordering},
         Arc,
@@ -8,35 +7,52 @@ use std::{
     time::SystemTime,
 };
 
-use bollard::{container::LogsOptions, Docker};
-use cansi::v3::categorise_text;
+use bollard::Docker;
 use crossterm::{
     event::{DisableMouseCapture, KeyCode, KeyModifiers, MouseButton, MouseEvent, MouseEventKind},
     execute,
 };
-use futures_util::StreamExt;
 use parking_lot::Mutex;
 use ratatui::layout::Rect;
 use tokio::sync::mpsc::{Receiver, Sender};
 use uuid::Uuid;
 
+mod keymap;
 mod message;
+
 use crate::{
-    app_data::{AppData, DockerControls, Header},
+    app_data::{
+        container_pid, parse_signal, split_tag, AppData, ContainerName, DockerControls, Header,
+        RunConfig,
+    },
     app_error::AppError,
+    config::Config,
     docker_data::DockerMessage,
     exec::{tty_readable, ExecMode},
-    ui::{DeleteButton, GuiState, SelectablePanel, Status, Ui},
+    ui::{
+        DeleteButton, DeleteTarget, Form, FormField, FormKind, GuiState, PanelLayout, Section,
+        SelectablePanel, Status, Ui,
+    },
 };
+pub use keymap::{Action, Key, KeyContext, Keymap};
 pub use message::InputMessages;
 
+/// Seconds since the unix epoch, used to give saved files a unique name
+fn unix_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map_or(0, |i| i.as_secs())
+}
+
 /// Handle all input events
 #[derive(Debug)]
 pub struct InputHandler {
     app_data: Arc<Mutex<AppData>>,
+    chord: Vec<Key>,
     docker_tx: Sender<DockerMessage>,
     gui_state: Arc<Mutex<GuiState>>,
     is_running: Arc<AtomicBool>,
+    keymap: Keymap,
     mouse_capture: bool,
     rec: Receiver<InputMessages>,
 }
@@ -49,14 +65,18 @@ impl InputHandler {
         docker_tx: Sender<DockerMessage>,
         gui_state: Arc<Mutex<GuiState>>,
         is_running: Arc<AtomicBool>,
+        mouse_capture: bool,
     ) {
+        let keymap = app_data.lock().args.keymap.clone();
         let mut inner = Self {
             app_data,
+            chord: vec![],
             docker_tx,
             gui_state,
             is_running,
+            keymap,
             rec,
-            mouse_capture: true,
+            mouse_capture,
         };
         inner.start().await;
     }
@@ -70,7 +90,14 @@ impl InputHandler {
                     if !self.gui_state.lock().status_contains(&[
                         Status::Error,
                         Status::Help,
+                        Status::Changes,
                         Status::DeleteConfirm,
+                        Status::Files,
+                        Status::Form,
+                        Status::Inspect,
+                        Status::Overview,
+                        Status::Processes,
+                        Status::Columns,
                     ]) {
                         self.mouse_press(mouse_event);
                     }
@@ -97,6 +124,7 @@ impl InputHandler {
     /// Send a quit message to docker, to abort all spawns, if an error is returned, set is_running to false here instead
     /// If gui_status is Error or Init, then just set the is_running to false immediately, for a quicker exit
     async fn quit(&self) {
+        self.save_session();
         let error_init = self
             .gui_state
             .lock()
@@ -107,17 +135,802 @@ impl InputHandler {
         }
     }
 
-    /// This is executed from the Delete Confirm dialog, and will send an internal message to actually remove the given container
+    /// Save the current session to the state file, to be restored on the next launch
+    /// Any failure is ignored, as there's no way to show an error whilst quitting
+    fn save_session(&self) {
+        let (mut session, path) = {
+            let locked_data = self.app_data.lock();
+            (
+                locked_data.get_session(),
+                locked_data.args.state_path.clone(),
+            )
+        };
+        if let Some(path) = path {
+            let (selected_panel, lower_panel) = {
+                let locked_gui = self.gui_state.lock();
+                (
+                    locked_gui.get_selected_panel(),
+                    locked_gui.get_lower_panel(),
+                )
+            };
+            session.lower_panel = Some(lower_panel);
+            session.mouse_capture = Some(self.mouse_capture);
+            session.selected_panel = Some(selected_panel);
+            session.write(&path).ok();
+        }
+    }
+
+    /// This is executed from the Delete Confirm dialog, and will send an internal message to actually remove the given item
     async fn confirm_delete(&self) {
-        let id = self.gui_state.lock().get_delete_container();
-        if let Some(id) = id {
-            self.docker_tx.send(DockerMessage::Delete(id)).await.ok();
+        let target = self.gui_state.lock().get_delete_target();
+        if let Some(target) = target {
+            let message = match target {
+                DeleteTarget::Container(id) => DockerMessage::Delete(id),
+                DeleteTarget::Image(id) => DockerMessage::ImageRemove(id),
+                DeleteTarget::Network(id) => DockerMessage::NetworkRemove(id),
+                DeleteTarget::PruneImages => DockerMessage::ImagePrune,
+                DeleteTarget::PruneNetworks => DockerMessage::NetworkPrune,
+                DeleteTarget::PruneVolumes => DockerMessage::VolumePrune,
+                DeleteTarget::Volume(name) => DockerMessage::VolumeRemove(name),
+            };
+            self.docker_tx.send(message).await.ok();
         }
     }
 
-    /// This is executed from the Delete Confirm dialog, and will clear the delete_container information (removes id and closes panel)
+    /// This is executed from the Delete Confirm dialog, and will clear the delete_target information (removes target and closes panel)
     fn clear_delete(&self) {
-        self.gui_state.lock().set_delete_container(None);
+        self.gui_state.lock().set_delete_target(None);
+    }
+
+    /// Check if the given panel is the currently selected panel
+    fn panel_selected(&self, panel: SelectablePanel) -> bool {
+        self.gui_state.lock().get_selected_panel() == panel
+    }
+
+    /// Open the recreate container form, pre-filled with the settings of the selected container
+    async fn a_key(&self) {
+        let option_id = self.app_data.lock().get_selected_container_id();
+        if let Some(id) = option_id {
+            self.docker_tx
+                .send(DockerMessage::RecreateForm(id))
+                .await
+                .ok();
+        }
+    }
+
+    /// Open the create form, if the volumes or networks panel is selected
+    fn c_key(&self) {
+        let selected_panel = self.gui_state.lock().get_selected_panel();
+        let form = match selected_panel {
+            SelectablePanel::Volumes => Form::new(
+                FormKind::VolumeCreate,
+                vec![
+                    FormField::new("name", ""),
+                    FormField::new("driver", "local"),
+                ],
+            ),
+            SelectablePanel::Networks => Form::new(
+                FormKind::NetworkCreate,
+                vec![
+                    FormField::new("name", ""),
+                    FormField::new("driver", "bridge"),
+                    FormField::new("subnet", ""),
+                ],
+            ),
+            _ => return,
+        };
+        self.gui_state.lock().set_form(Some(form));
+    }
+
+    /// Confirm the removal of the selected image, volume, or network, depending on which panel is selected
+    async fn d_key(&self) {
+        let selected_panel = self.gui_state.lock().get_selected_panel();
+        let target = {
+            let locked_data = self.app_data.lock();
+            match selected_panel {
+                SelectablePanel::Images => locked_data
+                    .get_selected_image()
+                    .map(|i| DeleteTarget::Image(i.id.clone())),
+                SelectablePanel::Volumes => locked_data
+                    .get_selected_volume()
+                    .map(|i| DeleteTarget::Volume(i.name.clone())),
+                SelectablePanel::Networks => locked_data
+                    .get_selected_network()
+                    .map(|i| DeleteTarget::Network(i.id.clone())),
+                _ => None,
+            }
+        };
+        if let Some(target) = target {
+            self.docker_tx
+                .send(DockerMessage::ConfirmDelete(target))
+                .await
+                .ok();
+        }
+    }
+
+    /// Show only the containers attached to the selected network, if the networks panel is selected, else remove any network filter
+    async fn f_key(&self) {
+        if self.panel_selected(SelectablePanel::Networks) {
+            self.app_data.lock().toggle_network_filter();
+        } else {
+            self.app_data.lock().clear_network_filter();
+        }
+        self.docker_tx.send(DockerMessage::Update).await.ok();
+    }
+
+    /// Confirm the pruning of all unused images, volumes, or networks, depending on which panel is selected
+    async fn p_key(&self) {
+        let selected_panel = self.gui_state.lock().get_selected_panel();
+        let target = match selected_panel {
+            SelectablePanel::Images => DeleteTarget::PruneImages,
+            SelectablePanel::Volumes => DeleteTarget::PruneVolumes,
+            SelectablePanel::Networks => DeleteTarget::PruneNetworks,
+            _ => return,
+        };
+        self.docker_tx
+            .send(DockerMessage::ConfirmDelete(target))
+            .await
+            .ok();
+    }
+
+    /// Open the pull image form, if the images panel is selected, pre-filled with the selected image name
+    fn u_key(&self) {
+        if self.panel_selected(SelectablePanel::Images) {
+            let name = self
+                .app_data
+                .lock()
+                .get_selected_image()
+                .filter(|i| !i.dangling)
+                .map_or_else(String::new, |i| i.name.clone());
+            self.gui_state.lock().set_form(Some(Form::new(
+                FormKind::ImagePull,
+                vec![FormField::new("image", name)],
+            )));
+        }
+    }
+
+    /// Cycle the panel visible in the lower section of the screen, and request an update so it's populated straight away
+    async fn v_key(&self) {
+        self.gui_state.lock().next_lower_panel();
+        self.docker_tx.send(DockerMessage::Update).await.ok();
+    }
+
+    /// The message to send when a form is submitted, None if there's nothing to do, or a description of the first invalid setting
+    #[allow(clippy::too_many_lines)]
+    fn form_message(&self, form: &Form) -> Result<Option<DockerMessage>, String> {
+        let message = match &form.kind {
+            FormKind::Commit(id) => {
+                let (repo, tag) = split_tag(&form.value("image"))?;
+                DockerMessage::Commit {
+                    id: id.clone(),
+                    repo,
+                    tag,
+                    comment: form.value("message"),
+                }
+            }
+            FormKind::ImagePull => {
+                let image = form.value("image");
+                if image.is_empty() {
+                    return Err("an image is required".to_owned());
+                }
+                DockerMessage::ImagePull(image)
+            }
+            FormKind::Limits(id, _) => DockerMessage::Limits {
+                id: id.clone(),
+                options: Box::new(form.limits_config().to_options()?),
+            },
+            FormKind::LogExport(id) => {
+                let export = form.log_export_config().to_export(unix_timestamp())?;
+                let save_dir = self.app_data.lock().args.save_dir.clone();
+                let Some(save_dir) = save_dir else {
+                    return Err("no directory to save into, set with --save-dir".to_owned());
+                };
+                DockerMessage::SaveLogs {
+                    id: id.clone(),
+                    path: save_dir.join(export.file_name()),
+                    export: Box::new(export),
+                }
+            }
+            FormKind::NetworkCreate => {
+                let name = form.value("name");
+                if name.is_empty() {
+                    return Err("a name is required".to_owned());
+                }
+                DockerMessage::NetworkCreate {
+                    name,
+                    driver: form.value("driver"),
+                    subnet: form.value("subnet"),
+                }
+            }
+            FormKind::Recreate(id) => {
+                let run_config = form.run_config();
+                run_config.to_config()?;
+                DockerMessage::Recreate {
+                    id: id.clone(),
+                    config: Box::new(run_config),
+                }
+            }
+            FormKind::Rename(id) => {
+                let name = form.value("name");
+                ContainerName::validate(&name)?;
+                let current_name = self.app_data.lock().get_container_name_by_id(id);
+                if current_name.is_none_or(|i| i.get() == name) {
+                    return Ok(None);
+                }
+                DockerMessage::Rename {
+                    id: id.clone(),
+                    name,
+                }
+            }
+            FormKind::Signal(id) => {
+                let pid = form.value("pid");
+                if pid.is_empty() || !pid.chars().all(|i| i.is_ascii_digit()) {
+                    return Err(format!("invalid pid: {pid}"));
+                }
+                DockerMessage::Signal {
+                    id: id.clone(),
+                    pid,
+                    signal: parse_signal(&form.value("signal"))?,
+                }
+            }
+            FormKind::Run => {
+                let run_config = form.run_config();
+                DockerMessage::Run {
+                    config: Box::new(run_config.to_config()?),
+                    name: run_config.name,
+                }
+            }
+            FormKind::StatsExport => return self.save_stats(form).map(|()| None),
+            FormKind::Upload(id, dir) => {
+                let file = form.value("file");
+                let local = PathBuf::from(&file);
+                if !local.is_file() {
+                    return Err(format!("no such file: {file}"));
+                }
+                DockerMessage::Upload {
+                    id: id.clone(),
+                    local,
+                    dir: dir.clone(),
+                }
+            }
+            FormKind::VolumeCreate => DockerMessage::VolumeCreate {
+                name: form.value("name"),
+                driver: form.value("driver"),
+            },
+        };
+        Ok(Some(message))
+    }
+
+    /// Execute the action of the currently open form, and then close it
+    /// If any setting is invalid, the form is kept open, with an error, so that it can be corrected
+    async fn submit_form(&self) {
+        let form = self.gui_state.lock().get_form().cloned();
+        if let Some(form) = form {
+            match self.form_message(&form) {
+                Ok(Some(message)) => {
+                    self.docker_tx.send(message).await.ok();
+                }
+                Ok(None) => (),
+                Err(error) => {
+                    if let Some(form) = self.gui_state.lock().get_form() {
+                        form.error = Some(error);
+                    }
+                    return;
+                }
+            }
+        }
+        self.gui_state.lock().set_form(None);
+    }
+
+    /// Handle key presses whilst a form is visible
+    async fn form_key(&self, key_code: KeyCode) {
+        match key_code {
+            KeyCode::Esc => self.gui_state.lock().set_form(None),
+            KeyCode::Enter => self.submit_form().await,
+            _ => {
+                if let Some(form) = self.gui_state.lock().get_form() {
+                    match key_code {
+                        KeyCode::Tab | KeyCode::Down => form.next_field(),
+                        KeyCode::BackTab | KeyCode::Up => form.previous_field(),
+                        KeyCode::Left => form.cursor_left(),
+                        KeyCode::Right => form.cursor_right(),
+                        KeyCode::Home => form.cursor_start(),
+                        KeyCode::End => form.cursor_end(),
+                        KeyCode::Backspace => form.backspace(),
+                        KeyCode::Delete => form.delete(),
+                        KeyCode::Char(c) => form.insert(c),
+                        _ => (),
+                    }
+                }
+            }
+        }
+    }
+
+    /// Request the inspect data of the currently selected container
+    async fn i_key(&self) {
+        let option_id = self.app_data.lock().get_selected_container_id();
+        if let Some(id) = option_id {
+            self.docker_tx.send(DockerMessage::Inspect(id)).await.ok();
+        }
+    }
+
+    /// Open the run container form, with every setting empty
+    fn r_key(&self) {
+        self.gui_state
+            .lock()
+            .set_form(Some(Form::run(&RunConfig::default())));
+    }
+
+    /// Open the commit container form, for the selected container
+    fn o_key(&self) {
+        let option_id = self.app_data.lock().get_selected_container_id();
+        if let Some(id) = option_id {
+            self.gui_state.lock().set_form(Some(Form::commit(id)));
+        }
+    }
+
+    /// Export the filesystem of the selected container into a `[container_name]_[timestamp].tar` file, in the same directory as saved logs
+    async fn x_key(&self) {
+        let save_dir = self.app_data.lock().args.save_dir.clone();
+        let container = self.app_data.lock().get_selected_container_id_state_name();
+        if let (Some(save_dir), Some((id, _, name))) = (save_dir, container) {
+            let path = save_dir.join(format!("{name}_{}.tar", unix_timestamp()));
+            self.docker_tx
+                .send(DockerMessage::Export { id, path })
+                .await
+                .ok();
+        }
+    }
+
+    /// Open the rename container form, pre-filled with the name of the selected container
+    fn n_key(&self) {
+        let option_container = self
+            .app_data
+            .lock()
+            .get_selected_container()
+            .map(|i| (i.id.clone(), i.name.get().to_owned()));
+        if let Some((id, name)) = option_container {
+            self.gui_state
+                .lock()
+                .set_form(Some(Form::rename(id, &name)));
+        }
+    }
+
+    /// Open the limits form, pre-filled with the current limits of the selected container
+    async fn l_key(&self) {
+        let option_id = self.app_data.lock().get_selected_container_id();
+        if let Some(id) = option_id {
+            self.docker_tx
+                .send(DockerMessage::LimitsForm(id))
+                .await
+                .ok();
+        }
+    }
+
+    /// Open the run container form, pre-filled with the settings of the selected container
+    async fn y_key(&self) {
+        let option_id = self.app_data.lock().get_selected_container_id();
+        if let Some(id) = option_id {
+            self.docker_tx.send(DockerMessage::RunClone(id)).await.ok();
+        }
+    }
+
+    /// Close the inspect panel, and remove the inspect data
+    fn clear_inspect(&self) {
+        self.app_data.lock().clear_inspect();
+        self.gui_state.lock().status_del(Status::Inspect);
+    }
+
+    /// Show the filesystem changes of the selected container
+    async fn g_key(&self) {
+        let option_id = self.app_data.lock().get_selected_container_id();
+        if let Some(id) = option_id {
+            self.docker_tx.send(DockerMessage::Changes(id)).await.ok();
+        }
+    }
+
+    /// Close the changes panel, and remove the changes data
+    fn clear_changes(&self) {
+        self.app_data.lock().clear_changes();
+        self.gui_state.lock().status_del(Status::Changes);
+    }
+
+    /// Text is being typed into the changes path filter
+    fn changes_editing(&self) -> bool {
+        self.app_data
+            .lock()
+            .get_changes()
+            .is_some_and(|i| i.editing)
+    }
+
+    /// Handle key presses whilst the changes panel is visible
+    /// When editing the path filter every char is added to the filter, enter or esc stop editing
+    fn changes_key(&self, key_code: KeyCode, action: Option<Action>) {
+        let mut locked_data = self.app_data.lock();
+        let Some(changes) = locked_data.get_changes() else {
+            return;
+        };
+        if changes.editing {
+            match key_code {
+                KeyCode::Char(c) => changes.filter_push(c),
+                KeyCode::Backspace => changes.filter_pop(),
+                KeyCode::Enter | KeyCode::Esc => changes.editing = false,
+                _ => (),
+            }
+            return;
+        }
+        if key_code == KeyCode::Esc || action == Some(Action::Changes) {
+            drop(locked_data);
+            self.clear_changes();
+            return;
+        }
+        let tree = &mut changes.tree;
+        match action {
+            Some(Action::Up) => tree.previous(),
+            Some(Action::Down) => tree.next(),
+            Some(Action::PageUp) => {
+                for _ in 0..=6 {
+                    tree.previous();
+                }
+            }
+            Some(Action::PageDown) => {
+                for _ in 0..=6 {
+                    tree.next();
+                }
+            }
+            Some(Action::Start) => tree.start(),
+            Some(Action::End) => tree.end(),
+            Some(Action::ChangesFilter) => changes.editing = true,
+            Some(Action::ChangesKind) => changes.next_kind_filter(),
+            Some(Action::ChangesClear) => changes.filter_clear(),
+            Some(Action::TreeToggle) => tree.toggle(),
+            Some(Action::TreeExpand) => tree.expand(),
+            Some(Action::TreeCollapse) => tree.collapse(),
+            Some(Action::TreeExpandAll) => tree.expand_all(),
+            _ => (),
+        }
+    }
+
+    /// Show the processes of the selected container
+    async fn t_key(&self) {
+        let option_id = self.app_data.lock().get_selected_container_id();
+        if let Some(id) = option_id {
+            self.docker_tx.send(DockerMessage::Processes(id)).await.ok();
+        }
+    }
+
+    /// Close the processes panel, and remove the processes data
+    fn clear_processes(&self) {
+        self.app_data.lock().clear_processes();
+        self.gui_state.lock().status_del(Status::Processes);
+    }
+
+    /// Open the signal form for the selected process, the pid inside the container is found from the Docker host if possible, else from inside the container
+    async fn signal_form(&self) {
+        let selected = self.app_data.lock().get_processes().and_then(|processes| {
+            processes.get_selected().map(|i| {
+                (
+                    processes.id.clone(),
+                    container_pid(&processes.id, &i.pid),
+                    processes.get_selected_match(),
+                )
+            })
+        });
+        match selected {
+            Some((id, Some(pid), _)) => {
+                self.gui_state.lock().set_form(Some(Form::signal(id, &pid)));
+            }
+            Some((id, None, Some(process))) => {
+                self.docker_tx
+                    .send(DockerMessage::SignalForm { id, process })
+                    .await
+                    .ok();
+            }
+            Some((id, None, None)) => {
+                self.gui_state.lock().set_form(Some(Form::signal(id, "")));
+            }
+            None => (),
+        }
+    }
+
+    /// Handle key presses whilst the processes panel is visible
+    async fn processes_key(&self, key_code: KeyCode, action: Option<Action>) {
+        if key_code == KeyCode::Esc || action == Some(Action::Processes) {
+            self.clear_processes();
+            return;
+        }
+        if action == Some(Action::Signal) {
+            self.signal_form().await;
+            return;
+        }
+        let mut locked_data = self.app_data.lock();
+        if let Some(processes) = locked_data.get_processes() {
+            let list = &mut processes.processes;
+            match action {
+                Some(Action::Up) => list.previous(),
+                Some(Action::Down) => list.next(),
+                Some(Action::PageUp) => {
+                    for _ in 0..=6 {
+                        list.previous();
+                    }
+                }
+                Some(Action::PageDown) => {
+                    for _ in 0..=6 {
+                        list.next();
+                    }
+                }
+                Some(Action::Start) => list.start(),
+                Some(Action::End) => list.end(),
+                _ => (),
+            }
+        }
+    }
+
+    /// Show the host overview, the host information is fetched every time the overview is opened
+    async fn w_key(&self) {
+        self.docker_tx.send(DockerMessage::Overview).await.ok();
+    }
+
+    /// Close the host overview, and remove the host information
+    fn clear_overview(&self) {
+        self.app_data.lock().clear_host();
+        self.gui_state.lock().status_del(Status::Overview);
+    }
+
+    /// Handle key presses whilst the host overview is visible
+    async fn overview_key(&self, key_code: KeyCode, action: Option<Action>) {
+        if key_code == KeyCode::Esc || action == Some(Action::Overview) {
+            self.clear_overview();
+        } else if action == Some(Action::OverviewRefresh) {
+            self.w_key().await;
+        }
+    }
+
+    /// Open the column picker
+    fn z_key(&self) {
+        self.app_data.lock().set_column_picker();
+        self.gui_state.lock().status_push(Status::Columns);
+    }
+
+    /// Close the column picker, without changing the columns
+    fn clear_column_picker(&self) {
+        self.app_data.lock().clear_column_picker();
+        self.gui_state.lock().status_del(Status::Columns);
+    }
+
+    /// Use the columns chosen in the column picker, and save them to the config file
+    fn apply_column_picker(&self) {
+        let (columns, path) = {
+            let mut locked_data = self.app_data.lock();
+            (
+                locked_data.apply_column_picker(),
+                locked_data.args.config_path.clone(),
+            )
+        };
+        self.gui_state.lock().status_del(Status::Columns);
+        if let (Some(columns), Some(path)) = (columns, path) {
+            if Config::edit(&path, |document| Config::set_columns(document, &columns)).is_ok() {
+                self.gui_state
+                    .lock()
+                    .set_info_box(&format!("columns saved to {}", path.display()));
+            } else {
+                self.app_data.lock().set_error(
+                    AppError::ConfigSave,
+                    &self.gui_state,
+                    Status::Error,
+                );
+            }
+        }
+    }
+
+    /// Save the current layout to the config file, if there is a config file path
+    fn save_layout(&self) {
+        let layout = self.gui_state.lock().get_layout();
+        let path = self.app_data.lock().args.config_path.clone();
+        if let Some(path) = path {
+            if Config::edit(&path, |document| Config::set_layout(document, &layout)).is_err() {
+                self.app_data.lock().set_error(
+                    AppError::ConfigSave,
+                    &self.gui_state,
+                    Status::Error,
+                );
+            }
+        }
+    }
+
+    /// Change the layout of the main view, and save it to the config file
+    fn change_layout(&self, change: impl FnOnce(&mut PanelLayout)) {
+        {
+            let mut locked_gui = self.gui_state.lock();
+            let mut layout = locked_gui.get_layout();
+            change(&mut layout);
+            locked_gui.set_layout(layout);
+        }
+        self.save_layout();
+    }
+
+    /// Grow, or shrink, the selected panel
+    fn resize_key(&self, grow: bool) {
+        self.gui_state.lock().resize_selected(grow);
+        self.save_layout();
+    }
+
+    /// Change to the next preset layout
+    fn next_layout_key(&self) {
+        let preset = self.gui_state.lock().get_layout().preset.next();
+        self.change_layout(|layout| *layout = preset.layout());
+        self.gui_state
+            .lock()
+            .set_info_box(&format!("layout: {preset}"));
+    }
+
+    /// Handle key presses whilst the column picker is visible
+    fn columns_key(&self, key_code: KeyCode, action: Option<Action>) {
+        if key_code == KeyCode::Esc || action == Some(Action::Columns) {
+            self.clear_column_picker();
+            return;
+        }
+        if action == Some(Action::ColumnsSave) {
+            self.apply_column_picker();
+            return;
+        }
+        let mut locked_data = self.app_data.lock();
+        if let Some(picker) = locked_data.get_column_picker() {
+            match action {
+                Some(Action::ColumnToggle) => picker.toggle(),
+                Some(Action::ColumnMoveUp) => picker.move_up(),
+                Some(Action::ColumnMoveDown) => picker.move_down(),
+                Some(Action::Up) => picker.columns.previous(),
+                Some(Action::Down) => picker.columns.next(),
+                Some(Action::PageUp) => {
+                    for _ in 0..=6 {
+                        picker.columns.previous();
+                    }
+                }
+                Some(Action::PageDown) => {
+                    for _ in 0..=6 {
+                        picker.columns.next();
+                    }
+                }
+                Some(Action::Start) => picker.columns.start(),
+                Some(Action::End) => picker.columns.end(),
+                _ => (),
+            }
+        }
+    }
+
+    /// Open the file browser, at the root directory of the selected container
+    async fn b_key(&self) {
+        let container = self.app_data.lock().get_selected_container_id_state_name();
+        if let Some((id, _, name)) = container {
+            self.docker_tx
+                .send(DockerMessage::Files {
+                    id,
+                    name,
+                    path: "/".to_owned(),
+                })
+                .await
+                .ok();
+        }
+    }
+
+    /// Close the file browser, and remove the directory listing
+    fn clear_files(&self) {
+        self.app_data.lock().clear_files();
+        self.gui_state.lock().status_del(Status::Files);
+    }
+
+    /// Download the selected file, or directory, into a `[container_name]_[timestamp]_[file_name]` file, or directory, in the same directory as saved logs
+    async fn download_file(&self) {
+        let save_dir = self.app_data.lock().args.save_dir.clone();
+        let selected = self.app_data.lock().get_files().and_then(|files| {
+            files
+                .get_selected()
+                .filter(|(entry, _)| entry.name != "..")
+                .map(|(entry, path)| (files.id.clone(), files.name.clone(), entry.clone(), path))
+        });
+        if let (Some(save_dir), Some((id, name, entry, path))) = (save_dir, selected) {
+            let output = save_dir.join(format!("{name}_{}_{}", unix_timestamp(), entry.name));
+            self.docker_tx
+                .send(DockerMessage::Download { id, path, output })
+                .await
+                .ok();
+        }
+    }
+
+    /// Handle key presses whilst the file browser is visible
+    async fn files_key(&self, key_code: KeyCode, action: Option<Action>) {
+        if key_code == KeyCode::Esc || action == Some(Action::Files) {
+            self.clear_files();
+            return;
+        }
+        if action.is_some_and(Action::is_navigation) {
+            if let Some(files) = self.app_data.lock().get_files() {
+                let entries = &mut files.entries;
+                match action {
+                    Some(Action::Up) => entries.previous(),
+                    Some(Action::Down) => entries.next(),
+                    Some(Action::PageUp) => {
+                        for _ in 0..=6 {
+                            entries.previous();
+                        }
+                    }
+                    Some(Action::PageDown) => {
+                        for _ in 0..=6 {
+                            entries.next();
+                        }
+                    }
+                    Some(Action::Start) => entries.start(),
+                    Some(Action::End) => entries.end(),
+                    _ => (),
+                }
+            }
+            return;
+        }
+        match action {
+            Some(Action::Download) => self.download_file().await,
+            Some(Action::Upload) => {
+                let upload = self
+                    .app_data
+                    .lock()
+                    .get_files()
+                    .map(|i| Form::upload(i.id.clone(), &i.path));
+                if upload.is_some() {
+                    self.gui_state.lock().set_form(upload);
+                }
+            }
+            Some(Action::FileOpen) => {
+                let selected = self.app_data.lock().get_files().and_then(|files| {
+                    files.get_selected().map(|(entry, path)| {
+                        (files.id.clone(), files.name.clone(), entry.is_dir, path)
+                    })
+                });
+                match selected {
+                    Some((id, name, true, path)) => {
+                        self.docker_tx
+                            .send(DockerMessage::Files { id, name, path })
+                            .await
+                            .ok();
+                    }
+                    Some((_, _, false, _)) => self.download_file().await,
+                    None => (),
+                }
+            }
+            _ => (),
+        }
+    }
+
+    /// Handle key presses whilst the inspect panel is visible
+    fn inspect_key(&self, key_code: KeyCode, action: Option<Action>) {
+        if key_code == KeyCode::Esc || action == Some(Action::Inspect) {
+            self.clear_inspect();
+            return;
+        }
+        let mut locked_data = self.app_data.lock();
+        if let Some(inspect) = locked_data.get_inspect() {
+            let tree = &mut inspect.tree;
+            match action {
+                Some(Action::Up) => tree.previous(),
+                Some(Action::Down) => tree.next(),
+                Some(Action::PageUp) => {
+                    for _ in 0..=6 {
+                        tree.previous();
+                    }
+                }
+                Some(Action::PageDown) => {
+                    for _ in 0..=6 {
+                        tree.next();
+                    }
+                }
+                Some(Action::Start) => tree.start(),
+                Some(Action::End) => tree.end(),
+                Some(Action::TreeToggle) => tree.toggle(),
+                Some(Action::TreeExpand) => tree.expand(),
+                Some(Action::TreeCollapse) => tree.collapse(),
+                Some(Action::TreeExpandAll) => tree.expand_all(),
+                _ => (),
+            }
+        }
     }
 
     /// Validate that one can exec into a Docker container
@@ -139,7 +952,9 @@ impl InputHandler {
                         );
                     },
                     |mode| {
-                        self.gui_state.lock().set_exec_mode(mode);
+                        self.gui_state
+                            .lock()
+                            .set_exec_mode(mode, self.mouse_capture);
                     },
                 );
             }
@@ -176,92 +991,50 @@ impl InputHandler {
         self.mouse_capture = !self.mouse_capture;
     }
 
-    /// Save the currently selected containers logs into a `[container_name]_[timestamp].log` file
-    async fn s_key(&mut self) {
-        /// This is the inner workings, *inlined* here to return a Result
-        async fn save_logs(
-            app_data: &Arc<Mutex<AppData>>,
-            gui_state: &Arc<Mutex<GuiState>>,
-            docker_tx: &Sender<DockerMessage>,
-        ) -> Result<(), Box<dyn std::error::Error>> {
-            let args = app_data.lock().args.clone();
-            let container = app_data.lock().get_selected_container_id_state_name();
-            if let Some((id, _, name)) = container {
-                if let Some(log_path) = args.save_dir {
-                    let (sx, rx) = tokio::sync::oneshot::channel::<Arc<Docker>>();
-                    docker_tx.send(DockerMessage::Exec(sx)).await?;
-
-                    let now = SystemTime::now()
-                        .duration_since(SystemTime::UNIX_EPOCH)
-                        .map_or(0, |i| i.as_secs());
-
-                    let path = log_path.join(format!("{name}_{now}.log"));
-
-                    let docker = rx.await?;
-                    let options = Some(LogsOptions::<String> {
-                        stdout: true,
-                        timestamps: args.timestamp,
-                        since: 0,
-                        ..Default::default()
-                    });
-                    let mut logs = docker.logs(id.get(), options);
-                    let mut output = vec![];
-
-                    while let Some(Ok(value)) = logs.next().await {
-                        let data = value.to_string();
-                        if !data.trim().is_empty() {
-                            output.push(
-                                categorise_text(&data)
-                                    .into_iter()
-                                    .map(|i| i.text)
-                                    .collect::<String>(),
-                            );
-                        }
-                    }
-                    if !output.is_empty() {
-                        let mut stream = BufWriter::new(
-                            OpenOptions::new()
-                                .read(true)
-                                .write(true)
-                                .create(true)
-                                .truncate(true)
-                                .open(&path)?,
-                        );
-
-                        for line in &output {
-                            stream.write_all(line.as_bytes())?;
-                        }
-                        stream.flush()?;
-
-                        gui_state
-                            .lock()
-                            .set_info_box(&format!("saved to {}", path.display()));
-                    }
-                }
-            }
-            Ok(())
+    /// Open the log export form, for the selected container, with the file name pre-filled as `[container_name]_[timestamp]`
+    fn s_key(&self) {
+        let container = self.app_data.lock().get_selected_container_id_state_name();
+        if let Some((id, _, name)) = container {
+            self.gui_state.lock().set_form(Some(Form::log_export(
+                id,
+                &format!("{name}_{}", unix_timestamp()),
+            )));
         }
+    }
 
-        let log_status = Status::Logs;
-        let status = self.gui_state.lock().status_contains(&[log_status]);
-        if !status {
-            self.gui_state.lock().status_push(log_status);
+    /// Open the stats export form, with the file name pre-filled as `stats_[timestamp]`
+    fn export_stats_key(&self) {
+        self.gui_state
+            .lock()
+            .set_form(Some(Form::stats_export(&format!(
+                "stats_{}",
+                unix_timestamp()
+            ))));
+    }
 
-            let uuid = Uuid::new_v4();
-            let handle = GuiState::start_loading_animation(&self.gui_state, uuid);
-            if save_logs(&self.app_data, &self.gui_state, &self.docker_tx)
-                .await
-                .is_err()
-            {
-                self.app_data.lock().set_error(
-                    AppError::DockerLogs,
-                    &self.gui_state,
-                    Status::Error,
-                );
-            }
-            self.gui_state.lock().status_del(log_status);
-            self.gui_state.lock().stop_loading_animation(&handle, uuid);
+    /// Write the stats history of the selected, or all, containers into the save directory, and show the path in an info box
+    fn save_stats(&self, form: &Form) -> Result<(), String> {
+        let export = form.stats_export_config().to_export()?;
+        let (save_dir, containers) = {
+            let app_data = self.app_data.lock();
+            (
+                app_data.args.save_dir.clone(),
+                app_data.get_container_stats(export.all),
+            )
+        };
+        let Some(save_dir) = save_dir else {
+            return Err("no directory to save into, set with --save-dir".to_owned());
+        };
+        if containers.is_empty() {
+            return Err("no container selected".to_owned());
         }
+        let path = save_dir.join(export.file_name());
+        std::fs::write(&path, export.output(&containers))
+            .map_err(|e| format!("unable to write {}: {e}", path.display()))?;
+        self.gui_state
+            .lock()
+            .set_info_box(&format!("saved stats to {}", path.display()));
+        Ok(())
     }
 
     /// Send docker command, if the Commands panel is selected
@@ -281,7 +1054,7 @@ impl InputHandler {
                     match command {
                         DockerControls::Delete => self
                             .docker_tx
-                            .send(DockerMessage::ConfirmDelete(id))
+                            .send(DockerMessage::ConfirmDelete(DeleteTarget::Container(id)))
                             .await
                             .ok(),
                         DockerControls::Pause => {
@@ -338,6 +1111,9 @@ impl InputHandler {
         match selected_panel {
             SelectablePanel::Containers => locked_data.containers_start(),
             SelectablePanel::Logs => locked_data.log_start(),
+            SelectablePanel::Images => locked_data.images_start(),
+            SelectablePanel::Volumes => locked_data.volumes_start(),
+            SelectablePanel::Networks => locked_data.networks_start(),
             SelectablePanel::Commands => locked_data.docker_controls_start(),
         }
     }
@@ -349,28 +1125,159 @@ impl InputHandler {
         match selected_panel {
             SelectablePanel::Containers => locked_data.containers_end(),
             SelectablePanel::Logs => locked_data.log_end(),
+            SelectablePanel::Images => locked_data.images_end(),
+            SelectablePanel::Volumes => locked_data.volumes_end(),
+            SelectablePanel::Networks => locked_data.networks_end(),
             SelectablePanel::Commands => locked_data.docker_controls_end(),
         }
     }
 
+    /// Handle actions when no popup, form, or dialog, is visible
+    async fn main_key(&mut self, action: Action) {
+        match action {
+            Action::SortReset => self.app_data.lock().reset_sorted(),
+            Action::SortName => self.sort(Header::Name),
+            Action::SortState => self.sort(Header::State),
+            Action::SortStatus => self.sort(Header::Status),
+            Action::SortCpu => self.sort(Header::Cpu),
+            Action::SortMemory => self.sort(Header::Memory),
+            Action::SortId => self.sort(Header::Id),
+            Action::SortImage => self.sort(Header::Image),
+            Action::SortRx => self.sort(Header::Rx),
+            Action::SortTx => self.sort(Header::Tx),
+            Action::SortHealth => self.sort(Header::Health),
+            Action::SortRxRate => self.sort(Header::RxRate),
+            Action::SortTxRate => self.sort(Header::TxRate),
+            Action::SortCreated => self.sort(Header::Created),
+            Action::SortUptime => self.sort(Header::Uptime),
+            Action::SortPorts => self.sort(Header::Ports),
+            Action::SortCommand => self.sort(Header::Command),
+            Action::SortService => self.sort(Header::Service),
+            Action::SortLabel => self.sort(Header::Label),
+            Action::SortRestarts => self.sort(Header::Restarts),
+            Action::SortIp => self.sort(Header::Ip),
+            Action::Recreate => self.a_key().await,
+            Action::Files => self.b_key().await,
+            Action::Create => self.c_key(),
+            Action::Delete => self.d_key().await,
+            Action::Exec => self.e_key().await,
+            Action::NetworkFilter => self.f_key().await,
+            Action::Changes => self.g_key().await,
+            Action::Help => self.gui_state.lock().status_push(Status::Help),
+            Action::Inspect => self.i_key().await,
+            Action::Limits => self.l_key().await,
+            Action::MouseCapture => self.m_key(),
+            Action::Rename => self.n_key(),
+            Action::Commit => self.o_key(),
+            Action::Prune => self.p_key().await,
+            Action::Run => self.r_key(),
+            Action::SaveLogs => self.s_key(),
+            Action::ExportStats => self.export_stats_key(),
+            Action::Processes => self.t_key().await,
+            Action::Pull => self.u_key(),
+            Action::CyclePanel => self.v_key().await,
+            Action::Overview => self.w_key().await,
+            Action::Columns => self.z_key(),
+            Action::GrowPanel => self.resize_key(true),
+            Action::ShrinkPanel => self.resize_key(false),
+            Action::MaximisePanel => self.change_layout(|layout| {
+                layout.maximised = !layout.maximised;
+            }),
+            Action::ToggleCommands => {
+                self.change_layout(|layout| layout.toggle(Section::Commands));
+            }
+            Action::ToggleCharts => self.change_layout(|layout| layout.toggle(Section::Charts)),
+            Action::NextLayout => self.next_layout_key(),
+            Action::Export => self.x_key().await,
+            Action::RunCopy => self.y_key().await,
+            Action::NextPanel => self.tab_key(),
+            Action::PreviousPanel => self.back_tab_key(),
+            Action::Start => self.home_key(),
+            Action::End => self.end_key(),
+            Action::Up => self.previous(),
+            Action::PageUp => {
+                for _ in 0..=6 {
+                    self.previous();
+                }
+            }
+            Action::Down => self.next(),
+            Action::PageDown => {
+                for _ in 0..=6 {
+                    self.next();
+                }
+            }
+            Action::Select => self.enter_key().await,
+            // Only resolved whilst a panel, or the delete confirm, is shown
+            Action::ChangesClear
+            | Action::ChangesFilter
+            | Action::ChangesKind
+            | Action::ColumnMoveDown
+            | Action::ColumnMoveUp
+            | Action::ColumnToggle
+            | Action::ColumnsSave
+            | Action::ConfirmNo
+            | Action::ConfirmYes
+            | Action::Download
+            | Action::FileOpen
+            | Action::OverviewRefresh
+            | Action::Signal
+            | Action::TreeCollapse
+            | Action::TreeExpand
+            | Action::TreeExpandAll
+            | Action::TreeToggle
+            | Action::Upload
+            | Action::Quit => (),
+        }
+    }
+
     /// Handle keyboard button events
     async fn button_press(&mut self, key_code: KeyCode, key_modififer: KeyModifiers) {
-        let contains_delete = self
-            .gui_state
-            .lock()
-            .status_contains(&[Status::DeleteConfirm]);
-
         let contains = |s: Status| self.gui_state.lock().status_contains(&[s]);
+        let contains_delete = contains(Status::DeleteConfirm);
 
         let contains_error = contains(Status::Error);
         let contains_help = contains(Status::Help);
         let contains_exec = contains(Status::Exec);
+        let contains_inspect = contains(Status::Inspect);
+        let contains_form = contains(Status::Form);
+        let contains_files = contains(Status::Files);
+        let contains_changes = contains(Status::Changes);
+        let contains_processes = contains(Status::Processes);
+        let contains_overview = contains(Status::Overview);
+        let contains_columns = contains(Status::Columns);
 
         if !contains_exec {
-            // Always just quit on Ctrl + c/C or q/Q
+            // Keys typed into a form, or filter, aren't resolved into actions
+            let action = if contains_form || self.changes_editing() {
+                self.chord.clear();
+                None
+            } else {
+                let context = if contains_error || contains_help {
+                    KeyContext::Main
+                } else if contains_files {
+                    KeyContext::Files
+                } else if contains_changes {
+                    KeyContext::Changes
+                } else if contains_processes {
+                    KeyContext::Processes
+                } else if contains_overview {
+                    KeyContext::Overview
+                } else if contains_columns {
+                    KeyContext::Columns
+                } else if contains_inspect {
+                    KeyContext::Inspect
+                } else if contains_delete {
+                    KeyContext::DeleteConfirm
+                } else {
+                    KeyContext::Main
+                };
+                self.keymap
+                    .resolve(&mut self.chord, Key::new(key_code, key_modififer), context)
+            };
+
+            // Always just quit on Ctrl + c/C, or the quit action
             let is_c = || key_code == KeyCode::Char('c') || key_code == KeyCode::Char('C');
-            let is_q = || key_code == KeyCode::Char('q') || key_code == KeyCode::Char('Q');
-            if key_modififer == KeyModifiers::CONTROL && is_c() || is_q() {
+            if key_modififer == KeyModifiers::CONTROL && is_c() || action == Some(Action::Quit) {
                 self.quit().await;
             }
 
@@ -380,52 +1287,33 @@ impl InputHandler {
                     self.gui_state.lock().status_del(Status::Error);
                 }
             } else if contains_help {
-                match key_code {
-                    KeyCode::Char('h' | 'H') => self.gui_state.lock().status_del(Status::Help),
-                    KeyCode::Char('m' | 'M') => self.m_key(),
+                match action {
+                    Some(Action::Help) => self.gui_state.lock().status_del(Status::Help),
+                    Some(Action::MouseCapture) => self.m_key(),
                     _ => (),
                 }
+            } else if contains_form {
+                self.form_key(key_code).await;
+            } else if contains_files {
+                self.files_key(key_code, action).await;
+            } else if contains_changes {
+                self.changes_key(key_code, action);
+            } else if contains_processes {
+                self.processes_key(key_code, action).await;
+            } else if contains_overview {
+                self.overview_key(key_code, action).await;
+            } else if contains_columns {
+                self.columns_key(key_code, action);
+            } else if contains_inspect {
+                self.inspect_key(key_code, action);
             } else if contains_delete {
-                match key_code {
-                    KeyCode::Char('y' | 'Y') => self.confirm_delete().await,
-                    KeyCode::Char('n' | 'N') => self.clear_delete(),
-                    _ => (),
-                }
-            } else {
-                match key_code {
-                    KeyCode::Char('0') => self.app_data.lock().reset_sorted(),
-                    KeyCode::Char('1') => self.sort(Header::Name),
-                    KeyCode::Char('2') => self.sort(Header::State),
-                    KeyCode::Char('3') => self.sort(Header::Status),
-                    KeyCode::Char('4') => self.sort(Header::Cpu),
-                    KeyCode::Char('5') => self.sort(Header::Memory),
-                    KeyCode::Char('6') => self.sort(Header::Id),
-                    KeyCode::Char('7') => self.sort(Header::Image),
-                    KeyCode::Char('8') => self.sort(Header::Rx),
-                    KeyCode::Char('9') => self.sort(Header::Tx),
-                    KeyCode::Char('e' | 'E') => self.e_key().await,
-                    KeyCode::Char('h' | 'H') => self.gui_state.lock().status_push(Status::Help),
-                    KeyCode::Char('m' | 'M') => self.m_key(),
-                    KeyCode::Char('s' | 'S') => self.s_key().await,
-                    KeyCode::Tab => self.tab_key(),
-                    KeyCode::BackTab => self.back_tab_key(),
-                    KeyCode::Home => self.home_key(),
-                    KeyCode::End => self.end_key(),
-                    KeyCode::Up | KeyCode::Char('k' | 'K') => self.previous(),
-                    KeyCode::PageUp => {
-                        for _ in 0..=6 {
-                            self.previous();
-                        }
-                    }
-                    KeyCode::Down | KeyCode::Char('j' | 'J') => self.next(),
-                    KeyCode::PageDown => {
-                        for _ in 0..=6 {
-                            self.next();
-                        }
-                    }
-                    KeyCode::Enter => self.enter_key().await,
+                match action {
+                    Some(Action::ConfirmYes) => self.confirm_delete().await,
+                    Some(Action::ConfirmNo) => self.clear_delete(),
                     _ => (),
                 }
+            } else if let Some(action) = action {
+                self.main_key(action).await;
             }
         }
     }
@@ -455,21 +1343,27 @@ impl InputHandler {
             MouseEventKind::ScrollUp => self.previous(),
             MouseEventKind::ScrollDown => self.next(),
             MouseEventKind::Down(MouseButton::Left) => {
-                if let Some(header) = self.gui_state.lock().header_intersect(Rect::new(
-                    mouse_event.column,
-                    mouse_event.row,
-                    1,
-                    1,
-                )) {
+                let clicked = Rect::new(mouse_event.column, mouse_event.row, 1, 1);
+                if self.gui_state.lock().drag_start(clicked) {
+                    return;
+                }
+                let header = self.gui_state.lock().header_intersect(clicked);
+                if let Some(header) = header {
                     self.sort(header);
                 }
 
-                self.gui_state.lock().panel_intersect(Rect::new(
-                    mouse_event.column,
-                    mouse_event.row,
-                    1,
-                    1,
-                ));
+                self.gui_state.lock().panel_intersect(clicked);
+            }
+            MouseEventKind::Drag(MouseButton::Left) => {
+                self.gui_state
+                    .lock()
+                    .drag_to(mouse_event.column, mouse_event.row);
+            }
+            MouseEventKind::Up(MouseButton::Left) => {
+                let dragged = self.gui_state.lock().drag_end();
+                if dragged {
+                    self.save_layout();
+                }
             }
             _ => (),
         }
@@ -482,6 +1376,9 @@ impl InputHandler {
         match selected_panel {
             SelectablePanel::Containers => locked_data.containers_next(),
             SelectablePanel::Logs => locked_data.log_next(),
+            SelectablePanel::Images => locked_data.images_next(),
+            SelectablePanel::Volumes => locked_data.volumes_next(),
+            SelectablePanel::Networks => locked_data.networks_next(),
             SelectablePanel::Commands => locked_data.docker_controls_next(),
         };
     }
@@ -493,6 +1390,9 @@ impl InputHandler {
         match selected_panel {
             SelectablePanel::Containers => locked_data.containers_previous(),
             SelectablePanel::Logs => locked_data.log_previous(),
+            SelectablePanel::Images => locked_data.images_previous(),
+            SelectablePanel::Volumes => locked_data.volumes_previous(),
+            SelectablePanel::Networks => locked_data.networks_previous(),
             SelectablePanel::Commands => locked_data.docker_controls_previous(),
         }
     }
@@ -8,7 +8,9 @@ use docker_data::DockerData;
 use input_handler::InputMessages;
 use parking_lot::Mutex;
 use parse_args::CliArgs;
+use session::Session;
 use std::{
+    net::SocketAddr,
     process,
     sync::{
         atomic::{AtomicBool, Ordering},
@@ -20,10 +22,14 @@ use tracing::{error, info, Level};
 
 mod app_data;
 mod app_error;
+mod config;
 mod docker_data;
 mod exec;
 mod input_handler;
+mod metrics;
 mod parse_args;
+mod session;
+mod snapshot;
 mod ui;
 
 use ui::{GuiState, Status, Ui};
@@ -90,6 +96,7 @@ fn handler_init(
     gui_state: &Arc<Mutex<GuiState>>,
     input_rx: Receiver<InputMessages>,
     is_running: &Arc<AtomicBool>,
+    mouse_capture: bool,
 ) {
     let app_data = Arc::clone(app_data);
     let gui_state = Arc::clone(gui_state);
@@ -100,14 +107,34 @@ fn handler_init(
         docker_sx.clone(),
         gui_state,
         is_running,
+        mouse_capture,
     ));
 }
 
+/// Bind the metrics address, and spawn a tokio thread to serve the metrics, exit if the address can't be bound, e.g. it's already in use
+async fn metrics_init(app_data: &Arc<Mutex<AppData>>, address: SocketAddr) {
+    match tokio::net::TcpListener::bind(address).await {
+        Ok(listener) => {
+            tokio::spawn(metrics::serve(listener, Arc::clone(app_data)));
+        }
+        Err(e) => {
+            eprintln!("unable to serve metrics on {address}: {e}");
+            process::exit(1)
+        }
+    }
+}
+
 #[tokio::main]
 async fn main() {
     setup_tracing();
 
-    let args = CliArgs::new();
+    let args = match CliArgs::new() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1)
+        }
+    };
 
     // If running via Docker image, need to sleep else program will just quit straight away, no real idea why
     // So just sleep for small while
@@ -116,11 +143,30 @@ async fn main() {
     }
     let host = read_docker_host(&args);
 
-    let app_data = Arc::new(Mutex::new(AppData::default(args.clone())));
-    let gui_state = Arc::new(Mutex::new(GuiState::default()));
+    // A snapshot shows every container, so the network filter, or any other state, of the previous session isn't restored
+    let session = if args.snapshot.is_some() {
+        Session::default()
+    } else {
+        Session::read(args.state_path.as_deref())
+    };
+    let mut app_data = AppData::default(args.clone());
+    app_data.restore_session(&session);
+    let app_data = Arc::new(Mutex::new(app_data));
+    let mut gui_state = GuiState::default();
+    gui_state.set_layout(args.layout);
+    gui_state.restore_session(&session);
+    let mouse_capture = session.mouse_capture.unwrap_or(true);
+    if args.snapshot.is_some() {
+        gui_state.status_push(Status::Init);
+    }
+    let gui_state = Arc::new(Mutex::new(gui_state));
     let is_running = Arc::new(AtomicBool::new(true));
     let (docker_tx, docker_rx) = tokio::sync::mpsc::channel(32);
 
+    if let Some(address) = args.metrics_listen {
+        metrics_init(&app_data, address).await;
+    }
+
     docker_init(
         &app_data,
         docker_rx,
@@ -131,10 +177,22 @@ async fn main() {
     )
     .await;
 
-    if args.gui {
+    if let Some(mode) = args.snapshot {
+        if let Err(e) = snapshot::run(&app_data, &gui_state, mode, args.docker_interval).await {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    } else if args.gui {
         let (input_tx, input_rx) = tokio::sync::mpsc::channel(32);
-        handler_init(&app_data, &docker_tx, &gui_state, input_rx, &is_running);
-        Ui::create(app_data, gui_state, input_tx, is_running).await;
+        handler_init(
+            &app_data,
+            &docker_tx,
+            &gui_state,
+            input_rx,
+            &is_running,
+            mouse_capture,
+        );
+        Ui::create(app_data, gui_state, input_tx, is_running, mouse_capture).await;
     } else {
         info!("in debug mode\n");
         // Debug mode for testing, less pointless now, will display some basic information
@@ -170,20 +228,36 @@ mod tests {
     use bollard::service::{ContainerSummary, Port};
 
     use crate::{
-        app_data::{AppData, ContainerId, ContainerItem, ContainerPorts, State, StatefulList},
+        app_data::{
+            AppData, ContainerId, ContainerItem, ContainerPorts, Header, State, StatefulList,
+        },
+        input_handler::Keymap,
         parse_args::CliArgs,
+        session::Session,
+        ui::{PanelLayout, Theme},
     };
 
-    pub const fn gen_args() -> CliArgs {
+    pub fn gen_args() -> CliArgs {
         CliArgs {
             color: false,
+            column_label: None,
+            columns: Header::DEFAULT_COLUMNS.to_vec(),
+            config_path: None,
             docker_interval: 1000,
             gui: true,
             host: None,
             in_container: false,
+            keymap: Keymap::default(),
+            layout: PanelLayout::default(),
+            metrics_listen: None,
             save_dir: None,
             raw: false,
             show_self: false,
+            snapshot: None,
+            sort: None,
+            state_path: None,
+            stats_history: 300_000,
+            theme: Theme::default(),
             timestamp: false,
             use_cli: false,
         }
@@ -208,9 +282,20 @@ mod tests {
 
     pub fn gen_appdata(containers: &[ContainerItem]) -> AppData {
         AppData {
+            changes: None,
+            column_picker: None,
             containers: StatefulList::new(containers.to_vec()),
             error: None,
+            files: None,
+            host: None,
+            images: StatefulList::new(vec![]),
+            inspect: None,
+            network_filter: None,
+            networks: StatefulList::new(vec![]),
+            processes: None,
+            session: Session::default(),
             sorted_by: None,
+            volumes: StatefulList::new(vec![]),
             args: gen_args(),
         }
     }
@@ -0,0 +1,280 @@
+use std::{sync::Arc, time::Duration};
+
+use parking_lot::Mutex;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+};
+
+use crate::app_data::{round_cpu, AppData, ContainerItem, Health, Stats};
+
+/// Content type of the Prometheus text exposition format
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Only the request line is needed, so anything beyond this is ignored
+const REQUEST_SIZE: usize = 1024;
+
+/// A connection that hasn't sent its request within this time is closed, so idle connections can't build up
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Escape a label value, a backslash, double quote, and line feed each need escaping
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// The labels of every sample of a container
+fn labels(item: &ContainerItem) -> String {
+    format!(
+        "id=\"{}\",name=\"{}\",image=\"{}\"",
+        escape(item.id.get()),
+        escape(item.name.get()),
+        escape(item.image.get())
+    )
+}
+
+/// A metric family, with a line for the labels and value of each sample
+fn family(name: &str, kind: &str, help: &str, samples: Vec<(String, String)>) -> Vec<String> {
+    let mut lines = vec![
+        format!("# HELP {name} {help}"),
+        format!("# TYPE {name} {kind}"),
+    ];
+    lines.extend(
+        samples
+            .into_iter()
+            .map(|(labels, value)| format!("{name}{{{labels}}} {value}")),
+    );
+    lines
+}
+
+/// The metrics of every container, in the Prometheus text exposition format
+/// Cpu & memory are only included for running containers, and the network totals once there's been a stats sample
+pub fn render(containers: &[ContainerItem]) -> String {
+    let samples = |value: fn(&ContainerItem) -> Option<String>| {
+        containers
+            .iter()
+            .filter_map(|item| value(item).map(|value| (labels(item), value)))
+            .collect::<Vec<_>>()
+    };
+    [
+        family(
+            "oxker_container_cpu_percent",
+            "gauge",
+            "Cpu usage of a running container, 100 is a single core",
+            samples(|i| {
+                i.cpu_stats
+                    .back()
+                    .filter(|_| i.state.is_alive())
+                    .map(|cpu| round_cpu(cpu.get_value()).to_string())
+            }),
+        ),
+        family(
+            "oxker_container_memory_bytes",
+            "gauge",
+            "Memory usage of a running container, in bytes",
+            samples(|i| {
+                i.mem_stats
+                    .back()
+                    .filter(|_| i.state.is_alive())
+                    .map(|memory| memory.get().to_string())
+            }),
+        ),
+        family(
+            "oxker_container_memory_limit_bytes",
+            "gauge",
+            "Memory limit of the container, in bytes",
+            samples(|i| Some(i.mem_limit.get().to_string())),
+        ),
+        family(
+            "oxker_container_network_receive_bytes_total",
+            "counter",
+            "Bytes received by the container, across every network interface",
+            samples(|i| i.io_sample.map(|_| i.rx.get().to_string())),
+        ),
+        family(
+            "oxker_container_network_transmit_bytes_total",
+            "counter",
+            "Bytes transmitted by the container, across every network interface",
+            samples(|i| i.io_sample.map(|_| i.tx.get().to_string())),
+        ),
+        family(
+            "oxker_container_state",
+            "gauge",
+            "State of the container, the state label is the current state",
+            containers
+                .iter()
+                .map(|i| {
+                    (
+                        format!("{},state=\"{}\"", labels(i), i.state.name()),
+                        "1".to_owned(),
+                    )
+                })
+                .collect(),
+        ),
+        family(
+            "oxker_container_health",
+            "gauge",
+            "Health check status of the container, the health label is the current status, containers without a health check are excluded",
+            containers
+                .iter()
+                .filter(|i| i.health != Health::Unset)
+                .map(|i| {
+                    (
+                        format!("{},health=\"{}\"", labels(i), i.health.name()),
+                        "1".to_owned(),
+                    )
+                })
+                .collect(),
+        ),
+    ]
+    .concat()
+    .into_iter()
+    .map(|line| line + "\n")
+    .collect()
+}
+
+/// The http response to a request line, only `GET /metrics` is served, and the connection is always closed
+fn response(request_line: &str, metrics: impl FnOnce() -> String) -> String {
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next();
+    let path = parts.next().and_then(|i| i.split('?').next());
+    let (status, content_type, body) = match (method, path) {
+        (Some("GET"), Some("/metrics")) => ("200 OK", CONTENT_TYPE, metrics()),
+        (Some("GET"), _) => (
+            "404 Not Found",
+            "text/plain; charset=utf-8",
+            "not found, the metrics are at /metrics\n".to_owned(),
+        ),
+        _ => (
+            "405 Method Not Allowed",
+            "text/plain; charset=utf-8",
+            "method not allowed\n".to_owned(),
+        ),
+    };
+    format!(
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )
+}
+
+/// Read the request line of a single connection, and write the response, the connection is closed if the request isn't received within `READ_TIMEOUT`
+async fn handle(mut stream: TcpStream, app_data: &Arc<Mutex<AppData>>) -> std::io::Result<()> {
+    let mut buffer = [0; REQUEST_SIZE];
+    let read = tokio::time::timeout(READ_TIMEOUT, stream.read(&mut buffer))
+        .await
+        .map_err(|_| std::io::Error::new(std::io::ErrorKind::TimedOut, "no request received"))??;
+    let request = String::from_utf8_lossy(&buffer[..read]);
+    let output = response(request.lines().next().unwrap_or_default(), || {
+        render(app_data.lock().get_container_items())
+    });
+    stream.write_all(output.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+/// Serve the metrics, each connection is handled in its own tokio thread, a failed connection is ignored
+pub async fn serve(listener: TcpListener, app_data: Arc<Mutex<AppData>>) {
+    loop {
+        if let Ok((stream, _)) = listener.accept().await {
+            let app_data = Arc::clone(&app_data);
+            tokio::spawn(async move {
+                handle(stream, &app_data).await.ok();
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use crate::{
+        app_data::{ByteStats, ContainerName, CpuStats, Health, IoCounters, State},
+        tests::gen_containers,
+    };
+
+    use super::{escape, render, response};
+
+    #[test]
+    /// Label values have backslashes, double quotes, and line feeds escaped
+    fn test_metrics_escape() {
+        assert_eq!(escape("plain"), "plain");
+        assert_eq!(escape(r#"a\b"c"#), r#"a\\b\"c"#);
+        assert_eq!(escape("a\nb"), "a\\nb");
+    }
+
+    #[test]
+    /// Every metric family has a help and type line, cpu & memory are only for running containers, and health only when set
+    fn test_metrics_render() {
+        let (_, mut containers) = gen_containers();
+        containers.truncate(2);
+        let item = &mut containers[0];
+        item.health = Health::Healthy;
+        item.mem_limit = ByteStats::new(4096);
+        item.cpu_stats.push(1000, CpuStats::new(1.234), 60_000);
+        item.mem_stats.push(1000, ByteStats::new(1024), 60_000);
+        item.push_io(
+            IoCounters {
+                rx: 100,
+                tx: 10,
+                ..IoCounters::default()
+            },
+            1000,
+            60_000,
+        );
+        containers[1].state = State::Exited;
+        containers[1].name = ContainerName::from("a\"b");
+        containers[1]
+            .cpu_stats
+            .push(1000, CpuStats::new(5.0), 60_000);
+
+        let result = render(&containers);
+        let first = r#"id="1",name="container_1",image="image_1""#;
+        let second = r#"id="2",name="a\"b",image="image_2""#;
+        assert_eq!(
+            result.lines().collect::<Vec<_>>(),
+            [
+                "# HELP oxker_container_cpu_percent Cpu usage of a running container, 100 is a single core",
+                "# TYPE oxker_container_cpu_percent gauge",
+                &format!("oxker_container_cpu_percent{{{first}}} 1.23"),
+                "# HELP oxker_container_memory_bytes Memory usage of a running container, in bytes",
+                "# TYPE oxker_container_memory_bytes gauge",
+                &format!("oxker_container_memory_bytes{{{first}}} 1024"),
+                "# HELP oxker_container_memory_limit_bytes Memory limit of the container, in bytes",
+                "# TYPE oxker_container_memory_limit_bytes gauge",
+                &format!("oxker_container_memory_limit_bytes{{{first}}} 4096"),
+                &format!("oxker_container_memory_limit_bytes{{{second}}} 0"),
+                "# HELP oxker_container_network_receive_bytes_total Bytes received by the container, across every network interface",
+                "# TYPE oxker_container_network_receive_bytes_total counter",
+                &format!("oxker_container_network_receive_bytes_total{{{first}}} 100"),
+                "# HELP oxker_container_network_transmit_bytes_total Bytes transmitted by the container, across every network interface",
+                "# TYPE oxker_container_network_transmit_bytes_total counter",
+                &format!("oxker_container_network_transmit_bytes_total{{{first}}} 10"),
+                "# HELP oxker_container_state State of the container, the state label is the current state",
+                "# TYPE oxker_container_state gauge",
+                &format!("oxker_container_state{{{first},state=\"running\"}} 1"),
+                &format!("oxker_container_state{{{second},state=\"exited\"}} 1"),
+                "# HELP oxker_container_health Health check status of the container, the health label is the current status, containers without a health check are excluded",
+                "# TYPE oxker_container_health gauge",
+                &format!("oxker_container_health{{{first},health=\"healthy\"}} 1"),
+            ]
+        );
+        assert!(render(&[]).lines().all(|i| i.starts_with('#')));
+    }
+
+    #[test]
+    /// Metrics served for `GET /metrics`, with or without a query, anything else is an error
+    fn test_metrics_response() {
+        let metrics = || "metrics\n".to_owned();
+        let result = response("GET /metrics HTTP/1.1", metrics);
+        assert_eq!(
+            result,
+            "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4; charset=utf-8\r\nContent-Length: 8\r\nConnection: close\r\n\r\nmetrics\n"
+        );
+        assert!(response("GET /metrics?name=1 HTTP/1.1", metrics).starts_with("HTTP/1.1 200 OK"));
+        assert!(response("GET / HTTP/1.1", metrics).starts_with("HTTP/1.1 404 Not Found"));
+        assert!(response("POST /metrics HTTP/1.1", metrics)
+            .starts_with("HTTP/1.1 405 Method Not Allowed"));
+        assert!(response("", metrics).starts_with("HTTP/1.1 405 Method Not Allowed"));
+    }
+}
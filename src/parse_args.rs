@@ -1,17 +1,34 @@
-use std::{path::PathBuf, process};
+use std::{
+    net::SocketAddr,
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+};
 
-use clap::Parser;
-use tracing::error;
+use clap::{Parser, Subcommand};
 
-use crate::{ENV_KEY, ENV_VALUE};
+use crate::{
+    app_data::{Header, SortedOrder},
+    config::{Config, ConfigError, LayoutConfig, LogMode},
+    input_handler::Keymap,
+    session::Session,
+    snapshot::SnapshotMode,
+    ui::{PanelLayout, Theme, CHARTS_RANGE, COMMANDS_RANGE, CONTAINERS_RANGE},
+    ENV_KEY, ENV_VALUE,
+};
+
+/// Default docker update interval in ms
+const DEFAULT_INTERVAL: u32 = 1000;
+
+/// Default length of the stats history, 5 minutes, in ms
+const DEFAULT_HISTORY: u64 = 300_000;
 
 #[derive(Parser, Debug, Clone)]
 #[allow(clippy::struct_excessive_bools)]
 #[command(version, about)]
 pub struct Args {
-    /// Docker update interval in ms, minimum effectively 1000
-    #[clap(short = 'd', value_name = "ms", default_value_t = 1000)]
-    pub docker_interval: u32,
+    /// Docker update interval in ms, minimum effectively 1000, defaults to 1000
+    #[clap(short = 'd', value_name = "ms")]
+    pub docker_interval: Option<u32>,
 
     /// Remove timestamps from Docker logs
     #[clap(short = 't')]
@@ -41,22 +58,87 @@ pub struct Args {
     #[clap(long="use-cli", short = None)]
     pub use_cli: bool,
 
-    /// Directory for saving exported logs, defaults to `$HOME`
+    /// Directory for saving exported logs, stats, and container filesystems, defaults to `$HOME`
     #[clap(long="save-dir", short = None)]
     pub save_dir: Option<String>,
+
+    /// How long to keep cpu, memory, & io stats for the charts, between 1m and 24h, e.g. 90s, 15m, 2h, defaults to 5m
+    #[clap(long="history", short = None, value_name = "duration", value_parser = parse_history)]
+    pub history: Option<u64>,
+
+    /// Path of the config file, defaults to `oxker/config.toml` in the user config directory
+    #[clap(long="config", short = None, value_name = "path")]
+    pub config: Option<PathBuf>,
+
+    /// Color theme, one of dark, light, high_contrast, none, or a theme from the config file, defaults to dark, or none when NO_COLOR is set
+    #[clap(long="theme", short = None, value_name = "name")]
+    pub theme: Option<String>,
+
+    /// Serve container metrics, in the Prometheus text format, at `/metrics` on this address, e.g. 127.0.0.1:9187
+    #[clap(long="metrics-listen", short = None, value_name = "address")]
+    pub metrics_listen: Option<SocketAddr>,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug, Clone, Copy)]
+pub enum Command {
+    /// Print the containers, with their state, health, and cpu, memory, & network stats, as JSON, without drawing the gui
+    Snapshot {
+        /// Output as JSON, currently the only format
+        #[clap(long, required = true)]
+        json: bool,
+
+        /// Keep printing a snapshot, as a single line, every update interval, i.e. newline delimited JSON
+        #[clap(long)]
+        watch: bool,
+    },
+}
+
+/// Parse a duration, with an optional s, m, or h suffix, into ms
+/// Seconds are assumed when there's no suffix
+fn parse_history(value: &str) -> Result<u64, String> {
+    let value = value.trim();
+    let (number, multiplier) = match value.chars().last() {
+        Some('s') => (&value[..value.len() - 1], 1),
+        Some('m') => (&value[..value.len() - 1], 60),
+        Some('h') => (&value[..value.len() - 1], 3600),
+        _ => (value, 1),
+    };
+    let seconds = number
+        .parse::<u64>()
+        .map_err(|_| format!("invalid duration: {value}"))?
+        .saturating_mul(multiplier);
+    if (60..=86400).contains(&seconds) {
+        Ok(seconds * 1000)
+    } else {
+        Err("duration needs to be between 1m and 24h".to_owned())
+    }
 }
 
 #[derive(Debug, Clone)]
 #[allow(clippy::struct_excessive_bools)]
 pub struct CliArgs {
     pub color: bool,
+    pub column_label: Option<String>,
+    pub columns: Vec<Header>,
+    pub config_path: Option<PathBuf>,
     pub docker_interval: u32,
     pub gui: bool,
     pub host: Option<String>,
     pub in_container: bool,
+    pub keymap: Keymap,
+    pub layout: PanelLayout,
+    pub metrics_listen: Option<SocketAddr>,
     pub save_dir: Option<PathBuf>,
     pub raw: bool,
     pub show_self: bool,
+    pub snapshot: Option<SnapshotMode>,
+    pub sort: Option<(Header, SortedOrder)>,
+    pub state_path: Option<PathBuf>,
+    pub stats_history: u64,
+    pub theme: Theme,
     pub timestamp: bool,
     pub use_cli: bool,
 }
@@ -73,32 +155,431 @@ impl CliArgs {
         false
     }
 
-    /// Parse cli arguments
-    pub fn new() -> Self {
+    /// Expand a leading `~` into the home directory, as paths in the config file aren't expanded by a shell
+    fn expand_home(path: &str, home: Option<&Path>) -> PathBuf {
+        match (path.strip_prefix('~'), home) {
+            (Some(rest), Some(home)) if rest.is_empty() || rest.starts_with('/') => {
+                home.join(rest.trim_start_matches('/'))
+            }
+            _ => PathBuf::from(path),
+        }
+    }
+
+    /// The containers columns, each column at most once, and the label column needs the key of the label to show
+    fn check_columns(columns: &[Header], column_label: Option<&str>) -> Result<(), ConfigError> {
+        if columns.is_empty() {
+            return Err(ConfigError::Invalid(
+                "columns - at least one column is needed".to_owned(),
+            ));
+        }
+        for (index, header) in columns.iter().enumerate() {
+            if columns[..index].contains(header) {
+                return Err(ConfigError::Invalid(format!(
+                    "columns - \"{}\" is included more than once",
+                    header.config_name()
+                )));
+            }
+        }
+        if columns.contains(&Header::Label) && column_label.is_none() {
+            return Err(ConfigError::Invalid(
+                "columns - the label column needs column_label to be set".to_owned(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// The layout of the main view, starting from the preset, with each size checked against its range
+    fn check_layout(config: &LayoutConfig) -> Result<PanelLayout, ConfigError> {
+        let layout = config.preset.unwrap_or_default().layout();
+        let size = |name: &str, value: Option<u16>, default: u16, range: RangeInclusive<u16>| {
+            let value = value.unwrap_or(default);
+            if range.contains(&value) {
+                Ok(value)
+            } else {
+                Err(ConfigError::Invalid(format!(
+                    "layout.{name} - needs to be between {} and {}",
+                    range.start(),
+                    range.end()
+                )))
+            }
+        };
+        Ok(PanelLayout {
+            charts: size("charts", config.charts, layout.charts, CHARTS_RANGE)?,
+            commands: size("commands", config.commands, layout.commands, COMMANDS_RANGE)?,
+            containers: size(
+                "containers",
+                config.containers,
+                layout.containers,
+                CONTAINERS_RANGE,
+            )?,
+            maximised: config.maximised.unwrap_or(layout.maximised),
+            preset: layout.preset,
+            show_charts: config.show_charts.unwrap_or(layout.show_charts),
+            show_commands: config.show_commands.unwrap_or(layout.show_commands),
+        })
+    }
+
+    /// NO_COLOR is only honoured when set to a non-empty value, see https://no-color.org
+    fn check_no_color() -> bool {
+        std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+    }
+
+    /// Choose the theme, the cli argument takes priority, then the config file, then NO_COLOR, else the default dark theme
+    fn choose_theme(
+        cli_theme: Option<String>,
+        config: &Config,
+        no_color: bool,
+    ) -> Result<Theme, ConfigError> {
+        let name = cli_theme
+            .or_else(|| config.theme.clone())
+            .unwrap_or_else(|| if no_color { "none" } else { "dark" }.to_owned());
+        Theme::new(&name, &config.themes)
+    }
+
+    /// Parse cli arguments, and the config file
+    pub fn new() -> Result<Self, ConfigError> {
         let args = Args::parse();
+        let config = Config::read(args.config.as_deref())?;
+        Self::merge(args, config)
+    }
 
-        let logs_dir = args.save_dir.map_or_else(
-            || directories::BaseDirs::new().map(|base_dirs| base_dirs.home_dir().to_owned()),
-            |logs_dir| Some(std::path::Path::new(&logs_dir).to_owned()),
-        );
+    /// Combine the cli arguments with the config file, a cli argument takes priority over its matching setting
+    fn merge(args: Args, config: Config) -> Result<Self, ConfigError> {
+        let home_dir =
+            directories::BaseDirs::new().map(|base_dirs| base_dirs.home_dir().to_owned());
 
-        // Quit the program if the docker update argument is 0
+        let docker_interval = args
+            .docker_interval
+            .or(config.docker_interval)
+            .unwrap_or(DEFAULT_INTERVAL);
         // Should maybe change it to check if less than 100
-        if args.docker_interval == 0 {
-            error!("\"-d\" argument needs to be greater than 0");
-            process::exit(1)
+        if docker_interval == 0 {
+            return Err(ConfigError::Invalid(
+                "docker interval needs to be greater than 0".to_owned(),
+            ));
         }
-        Self {
-            color: args.color,
-            docker_interval: args.docker_interval,
-            use_cli: args.use_cli,
+
+        let theme = Self::choose_theme(args.theme, &config, Self::check_no_color())?;
+
+        let columns = config
+            .columns
+            .unwrap_or_else(|| Header::DEFAULT_COLUMNS.to_vec());
+        Self::check_columns(&columns, config.column_label.as_deref())?;
+
+        let layout = Self::check_layout(&config.layout)?;
+
+        let stats_history = match (args.history, config.history) {
+            (Some(history), _) => history,
+            (None, Some(history)) => parse_history(&history)
+                .map_err(|e| ConfigError::Invalid(format!("history - {e}")))?,
+            (None, None) => DEFAULT_HISTORY,
+        };
+
+        let log_mode = if args.color {
+            LogMode::Color
+        } else if args.raw {
+            LogMode::Raw
+        } else {
+            config.log_mode.unwrap_or_default()
+        };
+
+        let metrics_listen = match (args.metrics_listen, config.metrics_listen) {
+            (Some(address), _) => Some(address),
+            (None, Some(address)) => Some(address.parse().map_err(|_| {
+                ConfigError::Invalid(format!("metrics_listen - invalid address: {address}"))
+            })?),
+            (None, None) => None,
+        };
+
+        let save_dir = args.save_dir.or(config.save_dir).map_or_else(
+            || home_dir.clone(),
+            |save_dir| Some(Self::expand_home(&save_dir, home_dir.as_deref())),
+        );
+
+        Ok(Self {
+            color: log_mode == LogMode::Color,
+            column_label: config.column_label,
+            columns,
+            config_path: args.config.or_else(Config::default_path),
+            docker_interval,
+            use_cli: args.use_cli || config.use_cli.unwrap_or_default(),
             gui: !args.gui,
-            host: args.host,
+            host: args.host.or(config.host),
             in_container: Self::check_if_in_container(),
-            save_dir: logs_dir,
-            raw: args.raw,
-            show_self: !args.show_self,
-            timestamp: !args.timestamp,
-        }
+            keymap: Keymap::new(&config.keys)?,
+            layout,
+            metrics_listen,
+            save_dir,
+            raw: log_mode == LogMode::Raw,
+            show_self: !(args.show_self || config.show_self.unwrap_or_default()),
+            snapshot: args.command.map(|Command::Snapshot { watch, .. }| {
+                if watch {
+                    SnapshotMode::Watch
+                } else {
+                    SnapshotMode::Once
+                }
+            }),
+            sort: config
+                .sort
+                .map(|sort| (sort.header, sort.order.unwrap_or(SortedOrder::Asc))),
+            state_path: Session::default_path(),
+            stats_history,
+            theme,
+            timestamp: !args.timestamp && config.show_timestamps.unwrap_or(true),
+        })
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        path::{Path, PathBuf},
+    };
+
+    use clap::Parser;
+
+    use crate::{
+        app_data::{Header, SortedOrder},
+        config::{Config, ConfigError, LayoutConfig, LogMode, SortConfig},
+        snapshot::SnapshotMode,
+        ui::{LayoutPreset, PanelLayout, Theme},
+    };
+
+    use super::{Args, CliArgs};
+
+    #[test]
+    /// Defaults used when neither a cli argument nor a setting is given
+    fn test_parse_args_merge_default() {
+        let result = CliArgs::merge(Args::parse_from(["oxker"]), Config::default()).unwrap();
+        assert_eq!(result.docker_interval, 1000);
+        assert_eq!(result.stats_history, 300_000);
+        assert!(!result.color);
+        assert!(!result.raw);
+        assert!(result.timestamp);
+        assert!(result.show_self);
+        assert!(!result.use_cli);
+        assert!(result.host.is_none());
+        assert!(result.sort.is_none());
+        assert!(result.snapshot.is_none());
+        assert!(result.metrics_listen.is_none());
+    }
+
+    #[test]
+    /// The metrics address from the cli takes priority over the config file, and an invalid address in the config file is an error
+    fn test_parse_args_metrics_listen() {
+        let config = Config {
+            metrics_listen: Some("0.0.0.0:9000".to_owned()),
+            ..Config::default()
+        };
+        let result = CliArgs::merge(Args::parse_from(["oxker"]), config.clone()).unwrap();
+        assert_eq!(result.metrics_listen, Some("0.0.0.0:9000".parse().unwrap()));
+
+        let args = Args::parse_from(["oxker", "--metrics-listen", "127.0.0.1:9187"]);
+        let result = CliArgs::merge(args, config).unwrap();
+        assert_eq!(
+            result.metrics_listen,
+            Some("127.0.0.1:9187".parse().unwrap())
+        );
+
+        assert!(Args::try_parse_from(["oxker", "--metrics-listen", "9187"]).is_err());
+        let config = Config {
+            metrics_listen: Some("localhost".to_owned()),
+            ..Config::default()
+        };
+        assert_eq!(
+            CliArgs::merge(Args::parse_from(["oxker"]), config)
+                .unwrap_err()
+                .to_string(),
+            "Invalid setting: metrics_listen - invalid address: localhost"
+        );
+    }
+
+    #[test]
+    /// The snapshot subcommand requires `--json`, and prints once, unless `--watch` is set
+    fn test_parse_args_snapshot() {
+        let snapshot = |args: &[&str]| {
+            Args::try_parse_from(args)
+                .map(|args| CliArgs::merge(args, Config::default()).unwrap().snapshot)
+        };
+        assert_eq!(
+            snapshot(&["oxker", "snapshot", "--json"]).unwrap(),
+            Some(SnapshotMode::Once)
+        );
+        assert_eq!(
+            snapshot(&["oxker", "-d", "2000", "snapshot", "--json", "--watch"]).unwrap(),
+            Some(SnapshotMode::Watch)
+        );
+        assert!(snapshot(&["oxker", "snapshot"]).is_err());
+    }
+
+    #[test]
+    /// Settings from the config file are used, unless overridden by a cli argument
+    fn test_parse_args_merge_config() {
+        let config = Config {
+            column_label: None,
+            columns: Some(vec![Header::Name, Header::Cpu]),
+            docker_interval: Some(2000),
+            history: Some("1h".to_owned()),
+            host: Some("config_host".to_owned()),
+            keys: HashMap::new(),
+            layout: LayoutConfig::default(),
+            log_mode: Some(LogMode::Raw),
+            metrics_listen: None,
+            save_dir: Some("/tmp/config".to_owned()),
+            show_self: Some(true),
+            show_timestamps: Some(false),
+            sort: Some(SortConfig {
+                header: Header::Cpu,
+                order: None,
+            }),
+            theme: None,
+            themes: HashMap::new(),
+            use_cli: Some(true),
+        };
+        let result = CliArgs::merge(Args::parse_from(["oxker"]), config.clone()).unwrap();
+        assert_eq!(result.columns, vec![Header::Name, Header::Cpu]);
+        assert_eq!(result.docker_interval, 2000);
+        assert_eq!(result.stats_history, 3_600_000);
+        assert_eq!(result.host, Some("config_host".to_owned()));
+        assert_eq!(result.save_dir, Some(PathBuf::from("/tmp/config")));
+        assert!(result.raw);
+        assert!(!result.timestamp);
+        assert!(!result.show_self);
+        assert!(result.use_cli);
+        assert_eq!(result.sort, Some((Header::Cpu, SortedOrder::Asc)));
+
+        let args = Args::parse_from([
+            "oxker",
+            "-d",
+            "500",
+            "-c",
+            "--history",
+            "2m",
+            "--host",
+            "cli_host",
+            "--save-dir",
+            "/tmp/cli",
+        ]);
+        let result = CliArgs::merge(args, config).unwrap();
+        assert_eq!(result.docker_interval, 500);
+        assert_eq!(result.stats_history, 120_000);
+        assert_eq!(result.host, Some("cli_host".to_owned()));
+        assert_eq!(result.save_dir, Some(PathBuf::from("/tmp/cli")));
+        assert!(result.color);
+        assert!(!result.raw);
+    }
+
+    #[test]
+    /// Invalid values, from either the cli or the config file, are errors
+    fn test_parse_args_merge_err() {
+        let result = CliArgs::merge(Args::parse_from(["oxker", "-d", "0"]), Config::default());
+        assert!(matches!(result, Err(ConfigError::Invalid(_))));
+
+        let config = Config {
+            history: Some("25h".to_owned()),
+            ..Config::default()
+        };
+        let result = CliArgs::merge(Args::parse_from(["oxker"]), config);
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Invalid setting: history - duration needs to be between 1m and 24h"
+        );
+    }
+
+    #[test]
+    /// Columns can't be empty, or repeated, and the label column needs a label
+    fn test_parse_args_check_columns() {
+        assert!(CliArgs::check_columns(&Header::DEFAULT_COLUMNS, None).is_ok());
+        assert!(CliArgs::check_columns(&[Header::Label], Some("team")).is_ok());
+        let err = |columns: &[Header], label| {
+            CliArgs::check_columns(columns, label)
+                .unwrap_err()
+                .to_string()
+        };
+        assert_eq!(
+            err(&[], None),
+            "Invalid setting: columns - at least one column is needed"
+        );
+        assert_eq!(
+            err(&[Header::Name, Header::Cpu, Header::Name], None),
+            "Invalid setting: columns - \"name\" is included more than once"
+        );
+        assert_eq!(
+            err(&[Header::Name, Header::Label], None),
+            "Invalid setting: columns - the label column needs column_label to be set"
+        );
+    }
+
+    #[test]
+    /// The layout starts from the preset, with any size, or section, changed, and each size is checked
+    fn test_parse_args_check_layout() {
+        let result = CliArgs::check_layout(&LayoutConfig::default()).unwrap();
+        assert_eq!(result, PanelLayout::default());
+
+        let config = LayoutConfig {
+            containers: Some(30),
+            preset: Some(LayoutPreset::Logs),
+            show_commands: Some(false),
+            ..LayoutConfig::default()
+        };
+        let result = CliArgs::check_layout(&config).unwrap();
+        assert_eq!(
+            result,
+            PanelLayout {
+                containers: 30,
+                show_commands: false,
+                ..LayoutPreset::Logs.layout()
+            }
+        );
+
+        let config = LayoutConfig {
+            commands: Some(60),
+            ..LayoutConfig::default()
+        };
+        assert_eq!(
+            CliArgs::check_layout(&config).unwrap_err().to_string(),
+            "Invalid setting: layout.commands - needs to be between 5 and 50"
+        );
+    }
+
+    #[test]
+    /// The cli theme takes priority over the config theme, and NO_COLOR is only used when neither is set
+    fn test_parse_args_choose_theme() {
+        let config = Config {
+            theme: Some("light".to_owned()),
+            ..Config::default()
+        };
+        let result = CliArgs::choose_theme(Some("high_contrast".to_owned()), &config, true);
+        assert_eq!(result.unwrap(), Theme::HIGH_CONTRAST);
+        let result = CliArgs::choose_theme(None, &config, true);
+        assert_eq!(result.unwrap(), Theme::LIGHT);
+        let result = CliArgs::choose_theme(None, &Config::default(), true);
+        assert_eq!(result.unwrap(), Theme::NONE);
+        let result = CliArgs::choose_theme(None, &Config::default(), false);
+        assert_eq!(result.unwrap(), Theme::DARK);
+        let result = CliArgs::choose_theme(Some("missing".to_owned()), &config, false);
+        assert!(matches!(result, Err(ConfigError::Invalid(_))));
+    }
+
+    #[test]
+    /// A leading `~` is expanded into the home directory, but only when followed by a separator
+    fn test_parse_args_expand_home() {
+        let home = Some(Path::new("/home/oxker"));
+        assert_eq!(
+            CliArgs::expand_home("~/logs", home),
+            PathBuf::from("/home/oxker/logs")
+        );
+        assert_eq!(
+            CliArgs::expand_home("~", home),
+            PathBuf::from("/home/oxker")
+        );
+        assert_eq!(CliArgs::expand_home("~logs", home), PathBuf::from("~logs"));
+        assert_eq!(CliArgs::expand_home("/logs", home), PathBuf::from("/logs"));
+        assert_eq!(
+            CliArgs::expand_home("~/logs", None),
+            PathBuf::from("~/logs")
+        );
     }
 }
@@ -0,0 +1,160 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config::{ConfigError, SortConfig},
+    ui::SelectablePanel,
+};
+
+const STATE_DIR: &str = "oxker";
+const STATE_FILE: &str = "state.toml";
+
+/// A container, or network, saved by both id and name, as the id changes when it's recreated, e.g. by `docker compose up`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionItem {
+    pub id: String,
+    pub name: String,
+}
+
+impl SessionItem {
+    /// Find the position of the matching item in a list, by id, or by name if no item has the same id
+    pub fn position<T>(&self, items: &[T], id_name: impl Fn(&T) -> (&str, &str)) -> Option<usize> {
+        items
+            .iter()
+            .position(|i| id_name(i).0 == self.id)
+            .or_else(|| items.iter().position(|i| id_name(i).1 == self.name))
+    }
+}
+
+/// State of the previous session, saved on quit, and restored on the next launch
+/// Unlike the config file, it's written by oxker, so an unreadable file is ignored, rather than being an error
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Session {
+    pub lower_panel: Option<SelectablePanel>,
+    pub mouse_capture: Option<bool>,
+    pub selected_panel: Option<SelectablePanel>,
+    pub sort: Option<SortConfig>,
+    /// The sort was cleared, so the containers are left unsorted, rather than sorted by the `sort` setting of the config file
+    pub unsorted: Option<bool>,
+    pub container: Option<SessionItem>,
+    pub network_filter: Option<SessionItem>,
+}
+
+impl Session {
+    /// Default location of the state file, `oxker/state.toml` in the users state directory, e.g. `$HOME/.local/state/oxker/state.toml`, or the local data directory if the platform has no state directory
+    pub fn default_path() -> Option<PathBuf> {
+        directories::BaseDirs::new().map(|base_dirs| {
+            base_dirs
+                .state_dir()
+                .unwrap_or_else(|| base_dirs.data_local_dir())
+                .join(STATE_DIR)
+                .join(STATE_FILE)
+        })
+    }
+
+    /// Parse the contents of a state file, an invalid file is treated as an empty session
+    pub fn parse(contents: &str) -> Self {
+        toml::from_str(contents).unwrap_or_default()
+    }
+
+    /// Read the state file, a missing, or unreadable, file is treated as an empty session
+    pub fn read(path: Option<&Path>) -> Self {
+        path.and_then(|path| std::fs::read_to_string(path).ok())
+            .map_or_else(Self::default, |contents| Self::parse(&contents))
+    }
+
+    /// Write the state file, creating its parent directories if they don't exist
+    pub fn write(&self, path: &Path) -> Result<(), ConfigError> {
+        let write_err = |e| ConfigError::Write(path.to_owned(), e);
+        let output = toml::to_string(self)
+            .map_err(|e| write_err(std::io::Error::new(std::io::ErrorKind::InvalidData, e)))?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(write_err)?;
+        }
+        std::fs::write(path, output).map_err(write_err)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use crate::{
+        app_data::{Header, SortedOrder},
+        config::SortConfig,
+        ui::SelectablePanel,
+    };
+
+    use super::{Session, SessionItem};
+
+    #[test]
+    /// A session is written, and read back, unchanged, and an empty session writes an empty file
+    fn test_session_round_trip() {
+        let session = Session {
+            container: Some(SessionItem {
+                id: "abc".to_owned(),
+                name: "web".to_owned(),
+            }),
+            lower_panel: Some(SelectablePanel::Networks),
+            mouse_capture: Some(false),
+            network_filter: Some(SessionItem {
+                id: "def".to_owned(),
+                name: "backend".to_owned(),
+            }),
+            selected_panel: Some(SelectablePanel::Containers),
+            sort: Some(SortConfig {
+                header: Header::Cpu,
+                order: Some(SortedOrder::Desc),
+            }),
+            unsorted: None,
+        };
+        let contents = toml::to_string(&session).unwrap();
+        assert_eq!(
+            contents,
+            "lower_panel = \"networks\"\nmouse_capture = false\nselected_panel = \"containers\"\n\n[sort]\nheader = \"cpu\"\norder = \"desc\"\n\n[container]\nid = \"abc\"\nname = \"web\"\n\n[network_filter]\nid = \"def\"\nname = \"backend\"\n"
+        );
+        assert_eq!(Session::parse(&contents), session);
+
+        assert_eq!(toml::to_string(&Session::default()).unwrap(), "");
+
+        let session = Session {
+            unsorted: Some(true),
+            ..Session::default()
+        };
+        let contents = toml::to_string(&session).unwrap();
+        assert_eq!(contents, "unsorted = true\n");
+        assert_eq!(Session::parse(&contents), session);
+    }
+
+    #[test]
+    /// An invalid, or unknown, state file is an empty session, and unknown settings are ignored
+    fn test_session_parse_invalid() {
+        assert_eq!(Session::parse("lower_panel = 1"), Session::default());
+        assert_eq!(Session::parse("not toml"), Session::default());
+        assert_eq!(
+            Session::parse("mouse_capture = true\nunknown = 1"),
+            Session {
+                mouse_capture: Some(true),
+                ..Session::default()
+            }
+        );
+        assert_eq!(
+            Session::read(Some(std::path::Path::new("/oxker/missing.toml"))),
+            Session::default()
+        );
+    }
+
+    #[test]
+    /// Items are matched by id, then by name
+    fn test_session_item_position() {
+        let items = [("1", "a"), ("2", "b"), ("3", "c")];
+        let item = |id: &str, name: &str| SessionItem {
+            id: id.to_owned(),
+            name: name.to_owned(),
+        };
+        assert_eq!(item("2", "c").position(&items, |i| (i.0, i.1)), Some(1));
+        assert_eq!(item("4", "c").position(&items, |i| (i.0, i.1)), Some(2));
+        assert_eq!(item("4", "d").position(&items, |i| (i.0, i.1)), None);
+    }
+}
@@ -0,0 +1,237 @@
+use std::{io::Write, sync::Arc, time::SystemTime};
+
+use parking_lot::Mutex;
+use serde::Serialize;
+
+use crate::{
+    app_data::{format_timestamp, round_cpu, AppData, ContainerItem, Health, State, Stats},
+    app_error::AppError,
+    ui::{GuiState, Status},
+};
+
+/// How often to check if the initial container data has loaded, in ms
+const INIT_POLL: u64 = 100;
+
+/// Print a single snapshot, or keep printing a snapshot every update interval
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapshotMode {
+    Once,
+    Watch,
+}
+
+/// The current state, and stats, of a single container
+/// The rates are None until there are two io samples, and cpu & memory are None when the container isn't running
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ContainerSnapshot {
+    pub id: String,
+    pub name: String,
+    pub image: String,
+    pub state: State,
+    pub status: String,
+    pub health: Option<Health>,
+    pub cpu_percent: Option<f64>,
+    pub memory_bytes: Option<u64>,
+    pub memory_limit_bytes: u64,
+    pub rx_bytes: u64,
+    pub tx_bytes: u64,
+    pub rx_bytes_per_second: Option<u64>,
+    pub tx_bytes_per_second: Option<u64>,
+}
+
+impl From<&ContainerItem> for ContainerSnapshot {
+    fn from(item: &ContainerItem) -> Self {
+        let alive = item.state.is_alive();
+        Self {
+            id: item.id.get().to_owned(),
+            name: item.name.get().to_owned(),
+            image: item.image.get().to_owned(),
+            state: item.state,
+            status: item.status.clone(),
+            health: (item.health != Health::Unset).then_some(item.health),
+            cpu_percent: item
+                .cpu_stats
+                .back()
+                .filter(|_| alive)
+                .map(|i| round_cpu(i.get_value())),
+            memory_bytes: item.mem_stats.back().filter(|_| alive).map(|i| i.get()),
+            memory_limit_bytes: item.mem_limit.get(),
+            rx_bytes: item.rx.get(),
+            tx_bytes: item.tx.get(),
+            rx_bytes_per_second: item.rx_stats.back().map(|i| i.get()),
+            tx_bytes_per_second: item.tx_stats.back().map(|i| i.get()),
+        }
+    }
+}
+
+/// Every container, at a single point in time
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Snapshot {
+    pub timestamp: String,
+    pub containers: Vec<ContainerSnapshot>,
+}
+
+impl Snapshot {
+    pub fn new(app_data: &AppData, timestamp: u64) -> Self {
+        Self {
+            timestamp: format_timestamp(timestamp),
+            containers: app_data
+                .get_container_items()
+                .iter()
+                .map(ContainerSnapshot::from)
+                .collect(),
+        }
+    }
+
+    /// A single snapshot is pretty printed, but in watch mode each snapshot is a single line, so the output is newline delimited json
+    pub fn to_json(&self, mode: SnapshotMode) -> String {
+        match mode {
+            SnapshotMode::Once => serde_json::to_string_pretty(self),
+            SnapshotMode::Watch => serde_json::to_string(self),
+        }
+        .unwrap_or_default()
+    }
+}
+
+/// Current time in ms
+fn unix_timestamp_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map_or(0, |i| u64::try_from(i.as_millis()).unwrap_or_default())
+}
+
+/// An error, e.g. failing to connect to docker, ends the snapshot
+fn check_error(app_data: &Arc<Mutex<AppData>>) -> Result<(), AppError> {
+    app_data.lock().get_error().map_or(Ok(()), Err)
+}
+
+/// Wait for the initial container data to load, then print a snapshot to stdout, and, in watch mode, another every update interval
+/// The gui_state Init status needs to be set before the docker data handler is spawned
+/// Returns early, without an error, if stdout is closed, e.g. when piped into `head`
+pub async fn run(
+    app_data: &Arc<Mutex<AppData>>,
+    gui_state: &Arc<Mutex<GuiState>>,
+    mode: SnapshotMode,
+    docker_interval: u32,
+) -> Result<(), AppError> {
+    loop {
+        check_error(app_data)?;
+        if !gui_state.lock().status_contains(&[Status::Init]) {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(INIT_POLL)).await;
+    }
+
+    loop {
+        let snapshot = Snapshot::new(&app_data.lock(), unix_timestamp_ms());
+        if writeln!(std::io::stdout().lock(), "{}", snapshot.to_json(mode)).is_err()
+            || mode == SnapshotMode::Once
+        {
+            return Ok(());
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(u64::from(docker_interval))).await;
+        check_error(app_data)?;
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use crate::{
+        app_data::{ByteStats, ContainerId, CpuStats, Health, IoCounters, State},
+        tests::{gen_appdata, gen_containers},
+    };
+
+    use super::{ContainerSnapshot, Snapshot, SnapshotMode};
+
+    /// 2023-01-14T19:13:00Z, in ms
+    const NOW: u64 = 1_673_723_580_000;
+
+    #[test]
+    /// A container without any stats has no cpu, memory, or rates, and an unset health is None
+    fn test_snapshot_container_no_stats() {
+        let (_, containers) = gen_containers();
+        let result = ContainerSnapshot::from(&containers[0]);
+        assert_eq!(
+            result,
+            ContainerSnapshot {
+                id: "1".to_owned(),
+                name: "container_1".to_owned(),
+                image: "image_1".to_owned(),
+                state: State::Running,
+                status: "Up 1 hour".to_owned(),
+                health: None,
+                cpu_percent: None,
+                memory_bytes: None,
+                memory_limit_bytes: 0,
+                rx_bytes: 0,
+                tx_bytes: 0,
+                rx_bytes_per_second: None,
+                tx_bytes_per_second: None,
+            }
+        );
+    }
+
+    #[test]
+    /// The most recent stats are used, the cpu is rounded to two decimal places, and a stopped container has no cpu or memory
+    fn test_snapshot_container_stats() {
+        let (_, mut containers) = gen_containers();
+        let item = &mut containers[0];
+        item.health = Health::Healthy;
+        item.mem_limit = ByteStats::new(4096);
+        for (index, cpu) in [1.234, 5.678].into_iter().enumerate() {
+            let index = index as u64;
+            let timestamp = NOW + index * 1000;
+            item.cpu_stats.push(timestamp, CpuStats::new(cpu), 60_000);
+            item.mem_stats
+                .push(timestamp, ByteStats::new(1024 * (index + 1)), 60_000);
+            item.push_io(
+                IoCounters {
+                    rx: 100 * index,
+                    tx: 10 * index,
+                    ..IoCounters::default()
+                },
+                timestamp,
+                60_000,
+            );
+        }
+        let result = ContainerSnapshot::from(&*item);
+        assert_eq!(result.health, Some(Health::Healthy));
+        assert_eq!(result.cpu_percent, Some(5.68));
+        assert_eq!(result.memory_bytes, Some(2048));
+        assert_eq!(result.memory_limit_bytes, 4096);
+        assert_eq!(result.rx_bytes, 100);
+        assert_eq!(result.tx_bytes, 10);
+        assert_eq!(result.rx_bytes_per_second, Some(100));
+        assert_eq!(result.tx_bytes_per_second, Some(10));
+
+        item.state = State::Exited;
+        let result = ContainerSnapshot::from(&*item);
+        assert_eq!(result.cpu_percent, None);
+        assert_eq!(result.memory_bytes, None);
+    }
+
+    #[test]
+    /// Every container is in the snapshot, a single snapshot is pretty printed, and a watch snapshot is a single line
+    fn test_snapshot_json() {
+        let (_, containers) = gen_containers();
+        let mut app_data = gen_appdata(&containers);
+        app_data.containers.items[1].id = ContainerId::from("abc");
+        let snapshot = Snapshot::new(&app_data, NOW);
+        assert_eq!(snapshot.timestamp, "2023-01-14T19:13:00.000Z");
+        assert_eq!(snapshot.containers.len(), 3);
+
+        let watch = snapshot.to_json(SnapshotMode::Watch);
+        assert_eq!(watch.lines().count(), 1);
+        let value = serde_json::from_str::<serde_json::Value>(&watch).unwrap();
+        assert_eq!(value["containers"][1]["id"], "abc");
+        assert_eq!(value["containers"][0]["state"], "running");
+        assert!(value["containers"][0]["health"].is_null());
+
+        let once = snapshot.to_json(SnapshotMode::Once);
+        assert!(once.lines().count() > 1);
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&once).unwrap(),
+            value
+        );
+    }
+}
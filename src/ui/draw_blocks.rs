@@ -5,23 +5,32 @@ use ratatui::{
     symbols,
     text::{Line, Span},
     widgets::{
-        Axis, Block, BorderType, Borders, Chart, Clear, Dataset, GraphType, List, ListItem,
-        Paragraph,
+        block::{Position, Title},
+        Axis, Block, BorderType, Borders, Chart, Clear, Dataset, GraphType, LineGauge, List,
+        ListItem, ListState, Padding, Paragraph,
     },
     Frame,
 };
-use std::{default::Default, time::Instant};
+use std::{
+    default::Default,
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
 use std::{fmt::Display, sync::Arc};
 
-use crate::app_data::{ContainerItem, ContainerName, Header, SortedOrder};
+use crate::app_data::{ContainerItem, Header, SortedOrder};
 use crate::{
-    app_data::{AppData, ByteStats, Columns, CpuStats, State, Stats},
+    app_data::{
+        time_since, AppData, ByteStats, ChangeKind, ContainerTotals, CpuStats, DiskUsage, HostInfo,
+        ImageItem, IoTuple, Segments, State, Stats, TimeAxis,
+    },
     app_error::AppError,
+    input_handler::{Action, Keymap},
 };
 
 use super::{
+    form::Form,
     gui_state::{BoxLocation, DeleteButton, Region},
-    FrameData,
+    FrameData, Theme,
 };
 use super::{GuiState, SelectablePanel};
 
@@ -39,12 +48,15 @@ const NAME: &str = env!("CARGO_PKG_NAME");
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 const REPO: &str = env!("CARGO_PKG_REPOSITORY");
 const DESCRIPTION: &str = env!("CARGO_PKG_DESCRIPTION");
-const ORANGE: Color = Color::Rgb(255, 178, 36);
 const MARGIN: &str = "   ";
 const RIGHT_ARROW: &str = "▶ ";
 const CIRCLE: &str = "⚪ ";
+const EXPANDED: &str = "▾ ";
+const COLLAPSED: &str = "▸ ";
 
 const CONSTRAINT_50_50: [Constraint; 2] = [Constraint::Percentage(50), Constraint::Percentage(50)];
+const CONSTRAINT_25_25_25_25: [Constraint; 4] = [Constraint::Percentage(25); 4];
+const IO_CHARTS_MIN_WIDTH: u16 = 120;
 const CONSTRAINT_100: [Constraint; 1] = [Constraint::Percentage(100)];
 const CONSTRAINT_POPUP: [Constraint; 5] = [
     Constraint::Min(2),
@@ -89,6 +101,15 @@ fn generate_block<'a>(
         SelectablePanel::Logs => {
             format!("{}{}", panel.title(), app_data.lock().get_log_title())
         }
+        SelectablePanel::Images => {
+            format!("{}{}", panel.title(), app_data.lock().image_title())
+        }
+        SelectablePanel::Volumes => {
+            format!("{}{}", panel.title(), app_data.lock().volume_title())
+        }
+        SelectablePanel::Networks => {
+            format!("{}{}", panel.title(), app_data.lock().network_title())
+        }
         SelectablePanel::Commands => String::new(),
     };
     if !title.is_empty() {
@@ -99,7 +120,7 @@ fn generate_block<'a>(
         .border_type(BorderType::Rounded)
         .title(title);
     if fd.selected_panel == panel {
-        block = block.border_style(Style::default().fg(Color::LightCyan));
+        block = block.border_style(Style::default().fg(fd.theme.border));
     }
     block
 }
@@ -118,7 +139,7 @@ pub fn commands(
             .map(|c| {
                 let lines = Line::from(vec![Span::styled(
                     c.to_string(),
-                    Style::default().fg(c.get_color()),
+                    Style::default().fg(c.get_color(&fd.theme)),
                 )]);
                 ListItem::new(lines)
             })
@@ -137,82 +158,67 @@ pub fn commands(
     }
 }
 
-/// Format the container data to display nicely on the screen
-fn format_containers<'a>(i: &ContainerItem, widths: &Columns) -> Line<'a> {
-    let state_style = Style::default().fg(i.state.get_color());
-    let blue = Style::default().fg(Color::Blue);
+/// Format the container data to display nicely on the screen, with the columns in the chosen order
+/// The first column doesn't have a margin, as it follows the highlight symbol
+fn format_containers<'a>(i: &ContainerItem, fd: &FrameData, now: i64) -> Line<'a> {
+    let theme = &fd.theme;
+    let widths = &fd.columns;
+    let state_style = Style::default().fg(i.state.get_color(theme));
+    let blue = Style::default().fg(theme.name);
 
-    // Truncate?
-    Line::from(vec![
-        Span::styled(
-            format!(
-                "{:>width$}",
-                i.name.to_string(),
-                width = widths.name.1.into()
-            ),
-            blue,
-        ),
-        Span::styled(
-            format!(
-                "{MARGIN}{:<width$}",
-                i.state.to_string(),
-                width = widths.state.1.into()
-            ),
-            state_style,
-        ),
-        Span::styled(
-            format!(
-                "{MARGIN}{:>width$}",
-                i.status,
-                width = &widths.status.1.into()
-            ),
-            state_style,
-        ),
-        Span::styled(
-            format!(
-                "{}{:>width$}",
-                MARGIN,
-                i.cpu_stats.back().unwrap_or(&CpuStats::default()),
-                width = &widths.cpu.1.into()
-            ),
-            state_style,
-        ),
-        Span::styled(
-            format!(
-                "{MARGIN}{:>width_current$} / {:>width_limit$}",
-                i.mem_stats.back().unwrap_or(&ByteStats::default()),
-                i.mem_limit,
-                width_current = &widths.mem.1.into(),
-                width_limit = &widths.mem.2.into()
-            ),
-            state_style,
-        ),
-        Span::styled(
-            format!(
-                "{}{:>width$}",
-                MARGIN,
-                i.id.get_short(),
-                width = &widths.id.1.into()
-            ),
-            blue,
-        ),
-        Span::styled(
-            format!(
-                "{MARGIN}{:>width$}",
-                i.image.to_string(),
-                width = widths.image.1.into()
-            ),
-            blue,
-        ),
-        Span::styled(
-            format!("{MARGIN}{:>width$}", i.rx, width = widths.net_rx.1.into()),
-            Style::default().fg(Color::Rgb(255, 233, 193)),
-        ),
-        Span::styled(
-            format!("{MARGIN}{:>width$}", i.tx, width = widths.net_tx.1.into()),
-            Style::default().fg(Color::Rgb(205, 140, 140)),
-        ),
-    ])
+    let spans = fd
+        .column_order
+        .iter()
+        .enumerate()
+        .map(|(index, header)| {
+            let margin = if index == 0 { "" } else { MARGIN };
+            let width = usize::from(widths.width(*header));
+            let (text, style) = match header {
+                Header::Name => (i.name.to_string(), blue),
+                Header::State => (format!("{:<width$}", i.state.to_string()), state_style),
+                Header::Status => (i.status.clone(), state_style),
+                Header::Health => (
+                    format!("{:<width$}", i.health.to_string()),
+                    Style::default().fg(i.health.get_color(theme)),
+                ),
+                Header::Cpu => (
+                    i.cpu_stats
+                        .back()
+                        .map_or_else(|| CpuStats::default().to_string(), ToString::to_string),
+                    state_style,
+                ),
+                Header::Memory => (
+                    format!(
+                        "{:>width_current$} / {:>width_limit$}",
+                        i.mem_stats.back().unwrap_or(&ByteStats::default()),
+                        i.mem_limit,
+                        width_current = usize::from(widths.mem.1),
+                        width_limit = usize::from(widths.mem.2)
+                    ),
+                    state_style,
+                ),
+                Header::Id => (i.id.get_short(), blue),
+                Header::Image => (i.image.to_string(), blue),
+                Header::Rx => (i.rx.to_string(), Style::default().fg(theme.rx)),
+                Header::Tx => (i.tx.to_string(), Style::default().fg(theme.tx)),
+                Header::RxRate => (
+                    i.rx_stats.back().copied().unwrap_or_default().to_string(),
+                    Style::default().fg(theme.rx),
+                ),
+                Header::TxRate => (
+                    i.tx_stats.back().copied().unwrap_or_default().to_string(),
+                    Style::default().fg(theme.tx),
+                ),
+                _ => (
+                    i.column_text(*header, fd.column_label.as_deref(), now)
+                        .unwrap_or_default(),
+                    state_style,
+                ),
+            };
+            Span::styled(format!("{margin}{text:>width$}"), style)
+        })
+        .collect::<Vec<_>>();
+    Line::from(spans)
 }
 
 /// Draw the containers panel
@@ -224,13 +230,19 @@ pub fn containers(
     gui_state: &Arc<Mutex<GuiState>>,
 ) {
     let block = generate_block(app_data, area, fd, gui_state, SelectablePanel::Containers);
-
-    let items = app_data
-        .lock()
-        .get_container_items()
-        .iter()
-        .map(|i| ListItem::new(format_containers(i, &fd.columns)))
-        .collect::<Vec<_>>();
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |i| i64::try_from(i.as_secs()).unwrap_or_default());
+
+    let (items, mut state) = {
+        let data = app_data.lock();
+        let items = data
+            .get_visible_container_items()
+            .into_iter()
+            .map(|i| ListItem::new(format_containers(i, fd, now)))
+            .collect::<Vec<_>>();
+        (items, data.get_visible_container_state())
+    };
 
     if items.is_empty() {
         let paragraph = Paragraph::new("no containers running")
@@ -242,7 +254,8 @@ pub fn containers(
             .block(block)
             .highlight_style(Style::default().add_modifier(Modifier::BOLD))
             .highlight_symbol(CIRCLE);
-        f.render_stateful_widget(items, area, app_data.lock().get_container_state());
+        f.render_stateful_widget(items, area, &mut state);
+        app_data.lock().set_container_offset(state.offset());
     }
 }
 
@@ -282,6 +295,214 @@ pub fn logs(
     }
 }
 
+/// The containers using an image, volume, or network, or a dimmed "unused" if there are none
+fn attached_containers<'a>(containers: &[String], theme: &Theme) -> Span<'a> {
+    if containers.is_empty() {
+        Span::styled("unused", Style::default().fg(theme.dim))
+    } else {
+        Span::styled(containers.join(", "), Style::default().fg(theme.attached))
+    }
+}
+
+/// The max char count of a given column, so that each column can be padded to the same width
+fn column_width<T>(items: &[T], column: impl Fn(&T) -> String) -> usize {
+    items
+        .iter()
+        .map(|i| column(i).chars().count())
+        .max()
+        .unwrap_or_default()
+}
+
+/// Draw a list of images, volumes, or networks, or the empty text if there are no items
+fn resource_list(
+    area: Rect,
+    block: Block,
+    empty_text: &str,
+    f: &mut Frame,
+    items: Vec<ListItem>,
+    state: &mut ListState,
+) {
+    if items.is_empty() {
+        let paragraph = Paragraph::new(empty_text)
+            .block(block)
+            .alignment(Alignment::Center);
+        f.render_widget(paragraph, area);
+    } else {
+        let items = List::new(items)
+            .block(block)
+            .highlight_style(Style::default().add_modifier(Modifier::BOLD))
+            .highlight_symbol(RIGHT_ARROW);
+        f.render_stateful_widget(items, area, state);
+    }
+}
+
+/// Format a single image, with each column padded to the given widths
+fn format_image<'a>(
+    image: &ImageItem,
+    widths: (usize, usize, usize),
+    now: i64,
+    theme: &Theme,
+) -> Line<'a> {
+    let (name_width, size_width, created_width) = widths;
+    let name_style = if image.dangling {
+        Style::default().fg(theme.dim)
+    } else {
+        Style::default().fg(theme.name)
+    };
+    Line::from(vec![
+        Span::styled(format!("{:<name_width$}", image.name), name_style),
+        Span::styled(
+            format!("{MARGIN}{}", image.get_short_id()),
+            Style::default().fg(theme.name),
+        ),
+        Span::raw(format!("{MARGIN}{:>size_width$}", image.size.to_string())),
+        Span::raw(format!(
+            "{MARGIN}{:>created_width$}",
+            time_since(image.created, now)
+        )),
+        Span::raw(MARGIN),
+        attached_containers(&image.containers, theme),
+    ])
+}
+
+/// Draw the images panel, a list of all local images, and the containers that use each image
+pub fn images(
+    app_data: &Arc<Mutex<AppData>>,
+    area: Rect,
+    f: &mut Frame,
+    fd: &FrameData,
+    gui_state: &Arc<Mutex<GuiState>>,
+) {
+    let block = generate_block(app_data, area, fd, gui_state, SelectablePanel::Images).title(
+        Title::from(" ( d ) delete ( p ) prune dangling ( u ) pull ")
+            .position(Position::Bottom)
+            .alignment(Alignment::Right),
+    );
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |i| i64::try_from(i.as_secs()).unwrap_or_default());
+
+    let mut locked_data = app_data.lock();
+    let images = locked_data.get_image_items();
+    let widths = (
+        column_width(images, |i| i.name.clone()),
+        column_width(images, |i| i.size.to_string()),
+        column_width(images, |i| time_since(i.created, now)),
+    );
+    let items = images
+        .iter()
+        .map(|i| ListItem::new(format_image(i, widths, now, &fd.theme)))
+        .collect::<Vec<_>>();
+    resource_list(
+        area,
+        block,
+        "no images found",
+        f,
+        items,
+        locked_data.get_image_state(),
+    );
+}
+
+/// Draw the volumes panel, a list of all volumes, and the containers that mount each volume
+pub fn volumes(
+    app_data: &Arc<Mutex<AppData>>,
+    area: Rect,
+    f: &mut Frame,
+    fd: &FrameData,
+    gui_state: &Arc<Mutex<GuiState>>,
+) {
+    let block = generate_block(app_data, area, fd, gui_state, SelectablePanel::Volumes).title(
+        Title::from(" ( c ) create ( d ) delete ( p ) prune ")
+            .position(Position::Bottom)
+            .alignment(Alignment::Right),
+    );
+
+    let mut locked_data = app_data.lock();
+    let volumes = locked_data.get_volume_items();
+    let name_width = column_width(volumes, |i| i.name.clone());
+    let driver_width = column_width(volumes, |i| i.driver.clone());
+    let mountpoint_width = column_width(volumes, |i| i.mountpoint.clone());
+    let items = volumes
+        .iter()
+        .map(|i| {
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("{:<name_width$}", i.name),
+                    Style::default().fg(fd.theme.name),
+                ),
+                Span::raw(format!("{MARGIN}{:<driver_width$}", i.driver)),
+                Span::raw(format!(
+                    "{MARGIN}{:<mountpoint_width$}{MARGIN}",
+                    i.mountpoint
+                )),
+                attached_containers(&i.containers, &fd.theme),
+            ]))
+        })
+        .collect::<Vec<_>>();
+    resource_list(
+        area,
+        block,
+        "no volumes found",
+        f,
+        items,
+        locked_data.get_volume_state(),
+    );
+}
+
+/// Draw the networks panel, a list of all networks, and the containers attached to each network
+/// The network currently used to filter the containers panel is highlighted
+pub fn networks(
+    app_data: &Arc<Mutex<AppData>>,
+    area: Rect,
+    f: &mut Frame,
+    fd: &FrameData,
+    gui_state: &Arc<Mutex<GuiState>>,
+) {
+    let block = generate_block(app_data, area, fd, gui_state, SelectablePanel::Networks).title(
+        Title::from(" ( c ) create ( d ) delete ( p ) prune ( f ) filter containers ")
+            .position(Position::Bottom)
+            .alignment(Alignment::Right),
+    );
+
+    let mut locked_data = app_data.lock();
+    let filter = locked_data.get_network_filter();
+    let networks = locked_data.get_network_items();
+    let name_width = column_width(networks, |i| i.name.clone());
+    let driver_width = column_width(networks, |i| i.driver.clone());
+    let subnet_width = column_width(networks, |i| i.subnet.clone());
+    let items = networks
+        .iter()
+        .map(|i| {
+            let name_color = if filter.as_ref() == Some(&i.id) {
+                fd.theme.filter
+            } else {
+                fd.theme.name
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("{:<name_width$}", i.name),
+                    Style::default().fg(name_color),
+                ),
+                Span::styled(
+                    format!("{MARGIN}{}", i.get_short_id()),
+                    Style::default().fg(fd.theme.name),
+                ),
+                Span::raw(format!("{MARGIN}{:<driver_width$}", i.driver)),
+                Span::raw(format!("{MARGIN}{:<subnet_width$}{MARGIN}", i.subnet)),
+                attached_containers(&i.containers, &fd.theme),
+            ]))
+        })
+        .collect::<Vec<_>>();
+    resource_list(
+        area,
+        block,
+        "no networks found",
+        f,
+        items,
+        locked_data.get_network_state(),
+    );
+}
+
 // Display the ports in a formatted list
 pub fn ports(
     f: &mut Frame,
@@ -289,6 +510,7 @@ pub fn ports(
     app_data: &Arc<Mutex<AppData>>,
     max_lens: (usize, usize, usize),
 ) {
+    let theme = app_data.lock().args.theme;
     if let Some(ports) = app_data.lock().get_selected_ports() {
         let block = Block::default()
             .borders(Borders::ALL)
@@ -297,7 +519,7 @@ pub fn ports(
             .title(Span::styled(
                 " ports ",
                 Style::default()
-                    .fg(ports.1.get_color())
+                    .fg(ports.1.get_color(&theme))
                     .add_modifier(Modifier::BOLD),
             ));
 
@@ -318,10 +540,10 @@ pub fn ports(
                     "{:>ip$}{:>private$}{:>public$}",
                     "ip", "private", "public"
                 ))
-                .fg(Color::Yellow),
+                .fg(theme.heading),
             )];
             for item in &ports.0 {
-                let fg = Color::White;
+                let fg = theme.text;
                 let strings = item.print();
 
                 let line = vec![
@@ -339,64 +561,145 @@ pub fn ports(
 
 /// Draw the cpu + mem charts
 pub fn chart(f: &mut Frame, area: Rect, app_data: &Arc<Mutex<AppData>>) {
-    if let Some((cpu, mem)) = app_data.lock().get_chart_data() {
+    // Only show the network and block io charts if there's enough space for all four charts to be readable
+    let show_io = area.width >= IO_CHARTS_MIN_WIDTH;
+    // The dot marker draws a single point per cell, so no point in having more points than the width of each chart
+    let points = area.width / if show_io { 4 } else { 2 };
+    let (chart_data, io, theme) = {
+        let data = app_data.lock();
+        (
+            data.get_chart_data(points),
+            data.get_io_chart_data(points).filter(|_| show_io),
+            data.args.theme,
+        )
+    };
+    if let Some((axis, cpu, mem)) = chart_data {
+        let constraints = if io.is_some() {
+            CONSTRAINT_25_25_25_25.to_vec()
+        } else {
+            CONSTRAINT_50_50.to_vec()
+        };
         let area = Layout::default()
             .direction(Direction::Horizontal)
-            .constraints(CONSTRAINT_50_50)
+            .constraints(constraints)
             .split(area);
 
-        let cpu_dataset = vec![Dataset::default()
-            .marker(symbols::Marker::Dot)
-            .style(Style::default().fg(Color::Magenta))
-            .graph_type(GraphType::Line)
-            .data(&cpu.0)];
-        let mem_dataset = vec![Dataset::default()
-            .marker(symbols::Marker::Dot)
-            .style(Style::default().fg(Color::Cyan))
-            .graph_type(GraphType::Line)
-            .data(&mem.0)];
-
-        let cpu_stats = CpuStats::new(cpu.0.last().map_or(0.00, |f| f.1));
+        let cpu_dataset = segment_datasets(&cpu.0, theme.chart_cpu);
+        let mem_dataset = segment_datasets(&mem.0, theme.chart_memory);
+
+        let cpu_stats = CpuStats::new(last_value(&cpu.0));
         #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
-        let mem_stats = ByteStats::new(mem.0.last().map_or(0, |f| f.1 as u64));
-        let cpu_chart = make_chart(cpu.2, "cpu", cpu_dataset, &cpu_stats, &cpu.1);
-        let mem_chart = make_chart(mem.2, "memory", mem_dataset, &mem_stats, &mem.1);
+        let mem_stats = ByteStats::new(last_value(&mem.0) as u64);
+        let cpu_chart = make_chart(cpu.2, "cpu", cpu_dataset, &cpu_stats, &cpu.1, axis, &theme);
+        let mem_chart = make_chart(
+            mem.2,
+            "memory",
+            mem_dataset,
+            &mem_stats,
+            &mem.1,
+            axis,
+            &theme,
+        );
 
         f.render_widget(cpu_chart, area[0]);
         f.render_widget(mem_chart, area[1]);
+
+        if let Some((net, blk)) = io {
+            io_chart(f, area[2], ("net", ("↓", "↑")), &net, axis, &theme);
+            io_chart(f, area[3], ("disk", ("r", "w")), &blk, axis, &theme);
+        }
     }
 }
 
-/// Create charts
+/// Create a dataset for each segment, so that a gap in the samples isn't drawn as a line
+fn segment_datasets(segments: &Segments, color: Color) -> Vec<Dataset<'_>> {
+    segments
+        .iter()
+        .map(|i| {
+            Dataset::default()
+                .marker(symbols::Marker::Dot)
+                .style(Style::default().fg(color))
+                .graph_type(GraphType::Line)
+                .data(i)
+        })
+        .collect()
+}
+
+/// The value of the newest point in a chart
+fn last_value(segments: &Segments) -> f64 {
+    segments.last().and_then(|i| i.last()).map_or(0.00, |i| i.1)
+}
+
+/// Draw a chart with two io rate datasets, received or read first, then sent or written, titled with the latest value of each
+fn io_chart(
+    f: &mut Frame,
+    area: Rect,
+    (name, labels): (&str, (&str, &str)),
+    data: &IoTuple,
+    axis: Option<TimeAxis>,
+    theme: &Theme,
+) {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let last = |data: &Segments| ByteStats::new(last_value(data) as u64);
+    let current = format!(
+        "{} {}/s {} {}/s",
+        labels.0,
+        last(&data.0),
+        labels.1,
+        last(&data.1)
+    );
+    let mut datasets = segment_datasets(&data.0, theme.chart_in);
+    datasets.extend(segment_datasets(&data.1, theme.chart_out));
+    f.render_widget(
+        make_chart(data.3, name, datasets, &current, &data.2, axis, theme),
+        area,
+    );
+}
+
+/// Create charts, the x-axis is time, with the start and end times shown on the bottom border
 fn make_chart<'a, T: Stats + Display>(
     state: State,
     name: &'a str,
     dataset: Vec<Dataset<'a>>,
-    current: &'a T,
+    current: &impl Display,
     max: &'a T,
+    axis: Option<TimeAxis>,
+    theme: &Theme,
 ) -> Chart<'a> {
-    let title_color = state.get_color();
+    let title_color = state.get_color(theme);
     let label_color = match state {
-        State::Running => ORANGE,
-        _ => state.get_color(),
+        State::Running => theme.chart_max,
+        _ => title_color,
     };
+    let time_label = |timestamp: u64, alignment: Alignment| {
+        Title::from(Span::styled(
+            format!(" {} ", TimeAxis::label(timestamp)),
+            Style::default().fg(title_color),
+        ))
+        .position(Position::Bottom)
+        .alignment(alignment)
+    };
+    let mut block = Block::default()
+        .title_alignment(Alignment::Center)
+        .title(Span::styled(
+            format!(" {name} {current} "),
+            Style::default()
+                .fg(title_color)
+                .add_modifier(Modifier::BOLD),
+        ))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded);
+    if let Some(axis) = axis {
+        block = block
+            .title(time_label(axis.start, Alignment::Left))
+            .title(time_label(axis.end, Alignment::Right));
+    }
     Chart::new(dataset)
-        .block(
-            Block::default()
-                .title_alignment(Alignment::Center)
-                .title(Span::styled(
-                    format!(" {name} {current} "),
-                    Style::default()
-                        .fg(title_color)
-                        .add_modifier(Modifier::BOLD),
-                ))
-                .borders(Borders::ALL)
-                .border_type(BorderType::Rounded),
-        )
+        .block(block)
         .x_axis(
             Axis::default()
                 .style(Style::default().fg(title_color))
-                .bounds([0.00, 60.0]),
+                .bounds([0.00, axis.unwrap_or_default().width()]),
         )
         .y_axis(
             Axis::default()
@@ -423,13 +726,14 @@ pub fn heading_bar(
     data: &FrameData,
     gui_state: &Arc<Mutex<GuiState>>,
 ) {
-    let block = |fg: Color| Block::default().style(Style::default().bg(Color::Magenta).fg(fg));
+    let theme = data.theme;
+    let block = |fg: Color| Block::default().style(Style::default().bg(theme.bar_bg).fg(fg));
 
-    frame.render_widget(block(Color::Black), area);
+    frame.render_widget(block(theme.bar_fg), area);
 
     // Generate a block for the header, if the header is currently being used to sort a column, then highlight it white
     let header_block = |x: &Header| {
-        let mut color = Color::Black;
+        let mut color = theme.bar_fg;
         let mut prefix = "";
         let mut prefix_margin = 0;
         if let Some((a, b)) = &data.sorted_by {
@@ -439,44 +743,25 @@ pub fn heading_bar(
                     SortedOrder::Desc => prefix = "▼ ",
                 }
                 prefix_margin = 2;
-                color = Color::White;
+                color = theme.bar_highlight;
             };
         };
         (
-            Block::default().style(Style::default().bg(Color::Magenta).fg(color)),
+            Block::default().style(Style::default().bg(theme.bar_bg).fg(color)),
             prefix,
             prefix_margin,
         )
     };
 
-    // Generate block for the headers, state and status has a specific layout, others all equal
-    // width is dependant on it that column is selected to sort - or not
-    let gen_header = |header: &Header, width: usize| {
+    // Generate block for the headers, aligned the same as the column text, state & health are left aligned, others right aligned
+    // The first header is offset by the width of the highlight symbol, minus the width of the loading icon, in place of a margin
+    let gen_header = |index: usize, header: &Header, width: usize| {
         let block = header_block(header);
-        // Yes this is a mess, needs documenting correctly
+        let margin = if index == 0 { "  " } else { MARGIN };
+        let x = format!("{ic}{header}", ic = block.1);
         let text = match header {
-            Header::State => format!(
-                " {x:>width$}",
-                x = format!("{ic}{header}", ic = block.1),
-                width = width
-            ),
-            Header::Name => format!(
-                "  {x:>width$}",
-                x = format!("{ic}{header}", ic = block.1),
-                width = width
-            ),
-            Header::Status => format!(
-                "{}  {x:>width$}",
-                MARGIN,
-                x = format!("{ic}{header}", ic = block.1),
-                width = width
-            ),
-            _ => format!(
-                "{}{x:>width$}",
-                MARGIN,
-                x = format!("{ic}{header}", ic = block.1),
-                width = width
-            ),
+            Header::State | Header::Health => format!("{margin}{x:<width$}"),
+            _ => format!("{margin}{x:>width$}"),
         };
         let count = u16::try_from(text.chars().count()).unwrap_or_default();
         let status = Paragraph::new(text)
@@ -486,32 +771,37 @@ pub fn heading_bar(
     };
 
     // Meta data to iterate over to create blocks with correct widths
-    let header_meta = [
-        (Header::Name, data.columns.name.1),
-        (Header::State, data.columns.state.1),
-        (Header::Status, data.columns.status.1),
-        (Header::Cpu, data.columns.cpu.1),
-        (Header::Memory, data.columns.mem.1 + data.columns.mem.2 + 3),
-        (Header::Id, data.columns.id.1),
-        (Header::Image, data.columns.image.1),
-        (Header::Rx, data.columns.net_rx.1),
-        (Header::Tx, data.columns.net_tx.1),
-    ];
-
-    let header_data = header_meta
+    let header_meta = data
+        .column_order
         .iter()
-        .map(|i| {
-            let header_block = gen_header(&i.0, i.1.into());
-            (header_block.0, i.0, Constraint::Max(header_block.1))
-        })
+        .map(|header| (*header, data.columns.width(*header)))
         .collect::<Vec<_>>();
 
     let suffix = if data.help_visible { "exit" } else { "show" };
-    let info_text = format!("( h ) {suffix} help {MARGIN}",);
+    let info_text = data.help_key.as_ref().map_or_else(String::new, |key| {
+        format!("( {key} ) {suffix} help {MARGIN}")
+    });
     let info_width = info_text.chars().count();
 
     let column_width = usize::from(area.width).saturating_sub(info_width);
     let column_width = if column_width > 0 { column_width } else { 1 };
+
+    // Headers that don't fit are dropped from the right, matching how the container rows are clipped
+    let mut used_width = 2;
+    let header_data = header_meta
+        .iter()
+        .enumerate()
+        .map(|(index, i)| {
+            let header_block = gen_header(index, &i.0, i.1.into());
+            (header_block.0, i.0, Constraint::Max(header_block.1))
+        })
+        .take_while(|(_, _, constraint)| {
+            if let Constraint::Max(width) = constraint {
+                used_width += usize::from(*width);
+            }
+            used_width <= column_width
+        })
+        .collect::<Vec<_>>();
     let splits = if data.has_containers {
         vec![
             Constraint::Max(2),
@@ -529,7 +819,7 @@ pub fn heading_bar(
     if data.has_containers {
         // Draw loading icon, or not, and a prefix with a single space
         let loading_paragraph = Paragraph::new(format!("{:>2}", data.loading_icon))
-            .block(block(Color::White))
+            .block(block(theme.bar_highlight))
             .alignment(Alignment::Center);
         frame.render_widget(loading_paragraph, split_bar[0]);
 
@@ -551,9 +841,9 @@ pub fn heading_bar(
 
     // show/hide help
     let color = if data.help_visible {
-        Color::Black
+        theme.bar_fg
     } else {
-        Color::White
+        theme.bar_highlight
     };
     let help_paragraph = Paragraph::new(info_text)
         .block(block(color))
@@ -564,6 +854,168 @@ pub fn heading_bar(
     frame.render_widget(help_paragraph, split_bar[help_index]);
 }
 
+/// The lines of the help box, each a list of groups of actions, and a description
+const HELP_BUTTONS: [(&[&[Action]], &str); 38] = [
+    (
+        &[&[Action::NextPanel], &[Action::PreviousPanel]],
+        "change panels",
+    ),
+    (
+        &[
+            &[Action::Up, Action::Down],
+            &[Action::PageUp, Action::PageDown],
+            &[Action::Start, Action::End],
+        ],
+        "change selected line",
+    ),
+    (&[&[Action::Select]], "send docker container command"),
+    (&[&[Action::Exec]], "exec into a container"),
+    (&[&[Action::Help]], "toggle this help information"),
+    (&[&[Action::Inspect]], "inspect container - esc to close"),
+    (
+        &[&[Action::TreeToggle]],
+        "inspect & changes - toggle the selected line",
+    ),
+    (
+        &[
+            &[Action::TreeExpand, Action::TreeCollapse],
+            &[Action::TreeExpandAll],
+        ],
+        "inspect & changes - expand, collapse, or expand all",
+    ),
+    (
+        &[&[Action::Limits]],
+        "update memory, cpu, and restart limits of the selected container",
+    ),
+    (
+        &[&[Action::Run], &[Action::RunCopy]],
+        "run a new container, or a copy of the selected container",
+    ),
+    (
+        &[&[Action::Recreate]],
+        "recreate the selected container with modified settings",
+    ),
+    (&[&[Action::Rename]], "rename the selected container"),
+    (
+        &[&[Action::Commit], &[Action::Export]],
+        "commit container to an image, or export filesystem to file",
+    ),
+    (
+        &[&[Action::Changes]],
+        "show filesystem changes of the selected container, relative to its image",
+    ),
+    (
+        &[
+            &[Action::ChangesFilter],
+            &[Action::ChangesKind],
+            &[Action::ChangesClear],
+        ],
+        "changes - filter path, filter kind, or clear",
+    ),
+    (
+        &[&[Action::Processes]],
+        "show processes of the selected container",
+    ),
+    (
+        &[&[Action::Signal]],
+        "processes - send a signal to the selected process",
+    ),
+    (
+        &[&[Action::Overview]],
+        "host overview - total container usage against host capacity, and disk usage",
+    ),
+    (&[&[Action::OverviewRefresh]], "overview - refresh"),
+    (
+        &[&[Action::Columns]],
+        "choose, and order, the container columns, saved to the config file",
+    ),
+    (
+        &[
+            &[Action::ColumnToggle],
+            &[Action::ColumnMoveUp, Action::ColumnMoveDown],
+            &[Action::ColumnsSave],
+        ],
+        "columns - show/hide, move, or save",
+    ),
+    (
+        &[&[Action::GrowPanel], &[Action::ShrinkPanel]],
+        "grow or shrink the selected panel - or drag a border",
+    ),
+    (
+        &[&[Action::MaximisePanel]],
+        "maximise the selected panel, or restore the layout",
+    ),
+    (
+        &[&[Action::ToggleCommands], &[Action::ToggleCharts]],
+        "show or hide the commands, or the charts",
+    ),
+    (
+        &[&[Action::NextLayout]],
+        "next preset layout - default, logs, charts, compact",
+    ),
+    (&[&[Action::Files]], "browse the files of a container"),
+    (
+        &[&[Action::FileOpen], &[Action::Download], &[Action::Upload]],
+        "files - open, download, or upload a file",
+    ),
+    (
+        &[&[Action::SaveLogs]],
+        "save logs - time range, filter, plain, jsonl, or csv, and gzip",
+    ),
+    (
+        &[&[Action::ExportStats]],
+        "export stats history of the selected, or all, containers - csv or json",
+    ),
+    (
+        &[&[Action::CyclePanel]],
+        "cycle lower panel - logs, images, volumes, networks",
+    ),
+    (
+        &[&[Action::Create], &[Action::Delete], &[Action::Prune]],
+        "create, delete, or prune - in the lower panels",
+    ),
+    (
+        &[&[Action::ConfirmYes], &[Action::ConfirmNo]],
+        "confirm, or cancel, a delete",
+    ),
+    (&[&[Action::Pull]], "pull image - images panel"),
+    (
+        &[&[Action::NetworkFilter]],
+        "show containers on the selected network - networks panel",
+    ),
+    (
+        &[&[Action::MouseCapture]],
+        "toggle mouse capture - if disabled, text on screen can be selected & copied",
+    ),
+    (&[&[Action::SortReset]], "stop sort"),
+    (
+        &[&[
+            Action::SortName,
+            Action::SortState,
+            Action::SortStatus,
+            Action::SortCpu,
+            Action::SortMemory,
+            Action::SortId,
+            Action::SortImage,
+            Action::SortRx,
+            Action::SortTx,
+            Action::SortHealth,
+            Action::SortRxRate,
+            Action::SortTxRate,
+            Action::SortCreated,
+            Action::SortUptime,
+            Action::SortPorts,
+            Action::SortCommand,
+            Action::SortService,
+            Action::SortLabel,
+            Action::SortRestarts,
+            Action::SortIp,
+        ]],
+        "sort by header - or click header",
+    ),
+    (&[&[Action::Quit]], "quit at any time"),
+];
+
 /// Help popup box needs these three pieces of information
 struct HelpInfo {
     lines: Vec<Line<'static>>,
@@ -592,21 +1044,21 @@ impl HelpInfo {
         Span::styled(input.to_owned(), Style::default().fg(color))
     }
 
-    /// &str to black text span
-    fn black_span<'a>(input: &str) -> Span<'a> {
-        Self::span(input, Color::Black)
+    /// &str to a text span, in the foreground color of the bar
+    fn text_span<'a>(input: &str, theme: &Theme) -> Span<'a> {
+        Self::span(input, theme.bar_fg)
     }
 
-    /// &str to white text span
-    fn white_span<'a>(input: &str) -> Span<'a> {
-        Self::span(input, Color::White)
+    /// &str to a highlighted text span
+    fn highlight_span<'a>(input: &str, theme: &Theme) -> Span<'a> {
+        Self::span(input, theme.bar_highlight)
     }
 
     /// Generate the `oxker` name span + metadata
-    fn gen_name() -> Self {
+    fn gen_name(theme: &Theme) -> Self {
         let mut lines = NAME_TEXT
             .lines()
-            .map(|i| Line::from(Self::white_span(i)))
+            .map(|i| Line::from(Self::highlight_span(i, theme)))
             .collect::<Vec<_>>();
         lines.insert(0, Self::empty_span());
         let width = Self::calc_width(&lines);
@@ -619,10 +1071,10 @@ impl HelpInfo {
     }
 
     /// Generate the description span + metadata
-    fn gen_description() -> Self {
+    fn gen_description(theme: &Theme) -> Self {
         let lines = [
             Self::empty_span(),
-            Line::from(Self::white_span(DESCRIPTION)),
+            Line::from(Self::highlight_span(DESCRIPTION, theme)),
             Self::empty_span(),
         ];
         let width = Self::calc_width(&lines);
@@ -634,92 +1086,67 @@ impl HelpInfo {
         }
     }
 
-    /// Generate the button information span + metadata
-    fn gen_button() -> Self {
-        let button_item = |x: &str| Self::white_span(&format!(" ( {x} ) "));
-        let button_desc = |x: &str| Self::black_span(x);
+    /// Generate the button information span + metadata, from the keys bound in the keymap
+    /// The keys of each group of actions are zipped together, so ↑ & ↓ are shown as ( ↑ ↓ ), a line with no bound keys is skipped
+    fn gen_button(keymap: &Keymap, theme: &Theme) -> Self {
+        let button_item = |x: &str| Self::highlight_span(&format!(" ( {x} ) "), theme);
+        let button_desc = |x: &str| Self::text_span(x, theme);
         let or = || button_desc("or");
         let space = || button_desc(" ");
 
-        let lines = [
-            Line::from(vec![
-                space(),
-                button_item("tab"),
-                or(),
-                button_item("shift+tab"),
-                button_desc("change panels"),
-            ]),
-            Line::from(vec![
-                space(),
-                button_item("↑ ↓"),
-                or(),
-                button_item("j k"),
-                or(),
-                button_item("PgUp PgDown"),
-                or(),
-                button_item("Home End"),
-                button_desc("change selected line"),
-            ]),
-            Line::from(vec![
-                space(),
-                button_item("enter"),
-                button_desc("send docker container command"),
-            ]),
-            Line::from(vec![
-                space(),
-                button_item("e"),
-                button_desc("exec into a container"),
-            ]),
-            Line::from(vec![
-                space(),
-                button_item("h"),
-                button_desc("toggle this help information"),
-            ]),
-            Line::from(vec![
-                space(),
-                button_item("s"),
-                button_desc("save logs to file"),
-            ]),
-            Line::from(vec![
-                space(),
-                button_item("m"),
-                button_desc(
-                    "toggle mouse capture - if disabled, text on screen can be selected & copied",
-                ),
-            ]),
-            Line::from(vec![space(), button_item("0"), button_desc("stop sort")]),
-            Line::from(vec![
-                space(),
-                button_item("1 - 9"),
-                button_desc("sort by header - or click header"),
-            ]),
-            Line::from(vec![
-                space(),
-                button_item("q"),
-                button_desc("quit at any time"),
-            ]),
-        ];
+        let lines = HELP_BUTTONS
+            .iter()
+            .filter_map(|(groups, desc)| {
+                let items = groups
+                    .iter()
+                    .flat_map(|group| {
+                        let labels = group.iter().map(|i| keymap.labels(*i)).collect::<Vec<_>>();
+                        let count = labels.iter().map(Vec::len).max().unwrap_or_default();
+                        (0..count).map(move |index| {
+                            labels
+                                .iter()
+                                .filter_map(|i| i.get(index).cloned())
+                                .collect::<Vec<_>>()
+                                .join(" ")
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                if items.is_empty() {
+                    return None;
+                }
+                let mut spans = vec![space()];
+                for (index, item) in items.iter().enumerate() {
+                    if index > 0 {
+                        spans.push(or());
+                    }
+                    spans.push(button_item(item));
+                }
+                spans.push(button_desc(desc));
+                Some(Line::from(spans))
+            })
+            .collect::<Vec<_>>();
 
         let height = lines.len();
         let width = Self::calc_width(&lines);
         Self {
-            lines: lines.to_vec(),
+            lines,
             width,
             height,
         }
     }
 
     /// Generate the final lines, GitHub link etc, + metadata
-    fn gen_final() -> Self {
+    fn gen_final(theme: &Theme) -> Self {
         let lines = [
             Self::empty_span(),
-            Line::from(vec![Self::black_span(
+            Line::from(vec![Self::text_span(
                 "currently an early work in progress, all and any input appreciated",
+                theme,
             )]),
             Line::from(vec![Span::styled(
                 REPO.to_owned(),
                 Style::default()
-                    .fg(Color::White)
+                    .fg(theme.bar_highlight)
                     .add_modifier(Modifier::UNDERLINED),
             )]),
         ];
@@ -734,13 +1161,13 @@ impl HelpInfo {
 }
 
 /// Draw the help box in the centre of the screen
-pub fn help_box(f: &mut Frame) {
+pub fn help_box(f: &mut Frame, keymap: &Keymap, theme: &Theme) {
     let title = format!(" {VERSION} ");
 
-    let name_info = HelpInfo::gen_name();
-    let description_info = HelpInfo::gen_description();
-    let button_info = HelpInfo::gen_button();
-    let final_info = HelpInfo::gen_final();
+    let name_info = HelpInfo::gen_name(theme);
+    let description_info = HelpInfo::gen_description(theme);
+    let button_info = HelpInfo::gen_button(keymap, theme);
+    let final_info = HelpInfo::gen_final(theme);
 
     // have to add 10, but shouldn't need to, is an error somewhere
     let max_line_width = [
@@ -774,22 +1201,22 @@ pub fn help_box(f: &mut Frame) {
         .split(area);
 
     let name_paragraph = Paragraph::new(name_info.lines)
-        .style(Style::default().bg(Color::Magenta).fg(Color::White))
+        .style(Style::default().bg(theme.bar_bg).fg(theme.bar_highlight))
         .block(Block::default())
         .alignment(Alignment::Center);
 
     let description_paragraph = Paragraph::new(description_info.lines)
-        .style(Style::default().bg(Color::Magenta).fg(Color::Black))
+        .style(Style::default().bg(theme.bar_bg).fg(theme.bar_fg))
         .block(Block::default())
         .alignment(Alignment::Center);
 
     let help_paragraph = Paragraph::new(button_info.lines)
-        .style(Style::default().bg(Color::Magenta).fg(Color::Black))
+        .style(Style::default().bg(theme.bar_bg).fg(theme.bar_fg))
         .block(Block::default())
         .alignment(Alignment::Left);
 
     let final_paragraph = Paragraph::new(final_info.lines)
-        .style(Style::default().bg(Color::Magenta).fg(Color::Black))
+        .style(Style::default().bg(theme.bar_bg).fg(theme.bar_fg))
         .block(Block::default())
         .alignment(Alignment::Center);
 
@@ -797,7 +1224,7 @@ pub fn help_box(f: &mut Frame) {
         .title(title)
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
-        .border_style(Style::default().fg(Color::Black).bg(Color::Magenta));
+        .border_style(Style::default().fg(theme.bar_fg).bg(theme.bar_bg));
 
     // Order is important here
     f.render_widget(Clear, area);
@@ -808,23 +1235,466 @@ pub fn help_box(f: &mut Frame) {
     f.render_widget(block, area);
 }
 
+/// Draw the inspect panel, a collapsible tree, over the majority of the screen
+pub fn inspect(f: &mut Frame, app_data: &Arc<Mutex<AppData>>) {
+    let mut locked_data = app_data.lock();
+    let theme = locked_data.args.theme;
+    if let Some(inspect) = locked_data.get_inspect() {
+        let items = inspect
+            .tree
+            .visible()
+            .into_iter()
+            .map(|i| {
+                let marker = if !i.has_children {
+                    "  "
+                } else if i.expanded {
+                    EXPANDED
+                } else {
+                    COLLAPSED
+                };
+                let indent = "  ".repeat(i.depth);
+                let label_style = if i.depth == 0 {
+                    Style::default().fg(theme.heading)
+                } else {
+                    Style::default()
+                };
+                let line = match i.value.as_ref() {
+                    Some(value) if !i.label.is_empty() => Line::from(vec![
+                        Span::raw(format!("{indent}{marker}")),
+                        Span::styled(format!("{}: ", i.label), label_style),
+                        Span::raw(value.clone()),
+                    ]),
+                    _ => Line::from(vec![
+                        Span::raw(format!("{indent}{marker}")),
+                        Span::styled(i.text(), label_style),
+                    ]),
+                };
+                ListItem::new(line)
+            })
+            .collect::<Vec<_>>();
+
+        let block = Block::default()
+            .title(format!(" Inspect - {} ", inspect.name))
+            .title(
+                Title::from(" ( enter ) toggle ( a ) expand all ( esc ) close ")
+                    .position(Position::Bottom)
+                    .alignment(Alignment::Center),
+            )
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.border));
+
+        let size = f.size();
+        let area = popup(
+            usize::from(size.height.saturating_sub(4)),
+            usize::from(size.width.saturating_sub(8)),
+            size,
+            BoxLocation::MiddleCentre,
+        );
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(Style::default().add_modifier(Modifier::BOLD))
+            .highlight_symbol(RIGHT_ARROW);
+        f.render_widget(Clear, area);
+        f.render_stateful_widget(list, area, &mut inspect.tree.state);
+    }
+}
+
+/// Draw the filesystem changes of a container, a collapsible tree of paths, over the majority of the screen
+/// Each changed path is prefixed with the kind of change, as shown by `docker diff`
+pub fn changes(f: &mut Frame, app_data: &Arc<Mutex<AppData>>) {
+    let mut locked_data = app_data.lock();
+    let theme = locked_data.args.theme;
+    if let Some(changes) = locked_data.get_changes() {
+        let items = changes
+            .tree
+            .visible()
+            .into_iter()
+            .map(|i| {
+                let marker = if !i.has_children {
+                    "  "
+                } else if i.expanded {
+                    EXPANDED
+                } else {
+                    COLLAPSED
+                };
+                let indent = "  ".repeat(i.depth);
+                let kind = i.value.as_deref().and_then(ChangeKind::from_symbol);
+                let (symbol, color) = match kind {
+                    Some(ChangeKind::Added) => ("A ", theme.change_added),
+                    Some(ChangeKind::Changed) => ("C ", theme.change_changed),
+                    Some(ChangeKind::Deleted) => ("D ", theme.change_deleted),
+                    None => ("  ", Color::Reset),
+                };
+                ListItem::new(Line::from(vec![
+                    Span::raw(format!("{indent}{marker}")),
+                    Span::styled(symbol, Style::default().fg(color)),
+                    Span::raw(i.label.clone()),
+                ]))
+            })
+            .collect::<Vec<_>>();
+
+        let mut title = vec![
+            format!("Changes - {}", changes.name),
+            format!("{}/{} paths", changes.matching(), changes.len()),
+        ];
+        if let Some(size) = changes.size_rw.and_then(|i| u64::try_from(i).ok()) {
+            title.push(format!("writable layer {}", ByteStats::new(size)));
+        }
+        if changes.editing || !changes.filter.is_empty() {
+            let cursor = if changes.editing { "_" } else { "" };
+            title.push(format!("filter: {}{cursor}", changes.filter));
+        }
+        if let Some(kind) = changes.kind_filter {
+            title.push(format!("kind: {}", kind.symbol()));
+        }
+        let help = if changes.editing {
+            " ( enter ) or ( esc ) finish filter "
+        } else {
+            " ( / ) filter path ( f ) filter kind ( enter ) toggle ( a ) expand all ( esc ) close "
+        };
+
+        let block = Block::default()
+            .title(format!(" {} ", title.join(" - ")))
+            .title(
+                Title::from(help)
+                    .position(Position::Bottom)
+                    .alignment(Alignment::Center),
+            )
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.border));
+
+        let size = f.size();
+        let area = popup(
+            usize::from(size.height.saturating_sub(4)),
+            usize::from(size.width.saturating_sub(8)),
+            size,
+            BoxLocation::MiddleCentre,
+        );
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(Style::default().add_modifier(Modifier::BOLD))
+            .highlight_symbol(RIGHT_ARROW);
+        f.render_widget(Clear, area);
+        f.render_stateful_widget(list, area, &mut changes.tree.state);
+    }
+}
+
+/// Draw the processes of a container, with a header row, over the majority of the screen
+pub fn processes(f: &mut Frame, app_data: &Arc<Mutex<AppData>>) {
+    let mut locked_data = app_data.lock();
+    let theme = locked_data.args.theme;
+    if let Some(processes) = locked_data.get_processes() {
+        let row = |pid: &str, user: &str, cpu: &str, memory: &str, command: &str| {
+            format!("{pid:<8} {user:<12} {cpu:>6} {memory:>10}  {command}")
+        };
+        let items = processes
+            .processes
+            .items
+            .iter()
+            .map(|i| ListItem::new(row(&i.pid, &i.user, &i.cpu, &i.memory, &i.command)))
+            .collect::<Vec<_>>();
+
+        let block = Block::default()
+            .title(format!(
+                " Processes - {}{} ",
+                processes.name,
+                processes.processes.get_state_title()
+            ))
+            .title(
+                Title::from(" ( x ) send signal ( esc ) close ")
+                    .position(Position::Bottom)
+                    .alignment(Alignment::Center),
+            )
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.border));
+
+        let size = f.size();
+        let area = popup(
+            usize::from(size.height.saturating_sub(4)),
+            usize::from(size.width.saturating_sub(8)),
+            size,
+            BoxLocation::MiddleCentre,
+        );
+        let split = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(1)])
+            .split(block.inner(area));
+
+        // Pad the header by the width of the highlight symbol, so that it lines up with the items
+        let header = Paragraph::new(format!(
+            "{}{}",
+            " ".repeat(RIGHT_ARROW.chars().count()),
+            row("PID", "USER", "CPU", "MEMORY", "COMMAND")
+        ))
+        .style(
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(theme.heading),
+        );
+        let list = List::new(items)
+            .highlight_style(Style::default().add_modifier(Modifier::BOLD))
+            .highlight_symbol(RIGHT_ARROW);
+        f.render_widget(Clear, area);
+        f.render_widget(block, area);
+        f.render_widget(header, split[0]);
+        f.render_stateful_widget(list, split[1], &mut processes.processes.state);
+    }
+}
+
+/// Draw the column picker, visible columns are checked, and listed in the order they are shown
+pub fn column_picker(f: &mut Frame, app_data: &Arc<Mutex<AppData>>) {
+    let mut locked_data = app_data.lock();
+    let theme = locked_data.args.theme;
+    if let Some(picker) = locked_data.get_column_picker() {
+        let items = picker
+            .columns
+            .items
+            .iter()
+            .map(|(header, visible)| {
+                let check = if *visible { "[x]" } else { "[ ]" };
+                let style = if *visible {
+                    Style::default().fg(theme.text)
+                } else {
+                    Style::default().fg(theme.dim)
+                };
+                ListItem::new(Span::styled(
+                    format!("{check} {}", header.config_name()),
+                    style,
+                ))
+            })
+            .collect::<Vec<_>>();
+
+        let block = Block::default()
+            .title(" Columns ")
+            .title(
+                Title::from(" ( space ) show/hide ( [ ] ) move ( enter ) save ( esc ) close ")
+                    .position(Position::Bottom)
+                    .alignment(Alignment::Center),
+            )
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.border));
+
+        let size = f.size();
+        let area = popup(items.len() + 2, 66, size, BoxLocation::MiddleCentre);
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(Style::default().add_modifier(Modifier::BOLD))
+            .highlight_symbol(RIGHT_ARROW);
+        f.render_widget(Clear, area);
+        f.render_stateful_widget(list, area, &mut picker.columns.state);
+    }
+}
+
+/// Color of a usage gauge, high when the host is close to capacity
+const fn usage_color(ratio: f64, theme: &Theme) -> Color {
+    if ratio >= 0.9 {
+        theme.usage_high
+    } else if ratio >= 0.7 {
+        theme.usage_mid
+    } else {
+        theme.usage_low
+    }
+}
+
+/// Fixed width label, so that the values in the host overview line up
+fn overview_label(text: &str) -> Span<'static> {
+    Span::styled(format!("{text:<13}"), Style::default().bold())
+}
+
+/// Containers by state, disk usage, and daemon & host information, for the host overview
+fn overview_lines(host: &HostInfo, totals: &ContainerTotals, theme: &Theme) -> Vec<Line<'static>> {
+    let mut states = vec![overview_label("containers")];
+    for (index, (state, count)) in totals.states.iter().enumerate() {
+        if index > 0 {
+            states.push(Span::raw(", "));
+        }
+        states.push(Span::styled(
+            format!("{count} {state}"),
+            Style::default().fg(state.get_color(theme)),
+        ));
+    }
+    let line = |name: &str, value: String| Line::from(vec![overview_label(name), Span::raw(value)]);
+    let usage = |usage: &DiskUsage| format!("{} - {}", usage.count, usage.size);
+    let join = |parts: &[&str]| {
+        parts
+            .iter()
+            .filter(|i| !i.is_empty())
+            .copied()
+            .collect::<Vec<_>>()
+            .join(" - ")
+    };
+    vec![
+        Line::from(states),
+        line("images", usage(&host.images)),
+        line("volumes", usage(&host.volumes)),
+        line("writable", usage(&host.containers)),
+        line("build cache", usage(&host.build_cache)),
+        line(
+            "docker",
+            join(&[
+                &host.version,
+                &format!("api {}", host.api_version),
+                &host.storage_driver,
+            ]),
+        ),
+        line("host", join(&[&host.os, &host.kernel, &host.architecture])),
+    ]
+}
+
+/// Draw the host overview, total container usage against the capacity of the host, disk usage, and daemon information
+pub fn overview(f: &mut Frame, app_data: &Arc<Mutex<AppData>>) {
+    let (host, totals, theme) = {
+        let locked_data = app_data.lock();
+        (
+            locked_data.get_host().cloned(),
+            locked_data.get_container_totals(),
+            locked_data.args.theme,
+        )
+    };
+    if let Some(host) = host {
+        let host_cpu = totals.host_cpu(host.cpus);
+        let cpu_ratio = (host_cpu.get_value() / 100.0).clamp(0.0, 1.0);
+        let mem_ratio = totals.mem_ratio(host.mem_total);
+
+        let gauge = |name: &str, value: String, ratio: f64| {
+            LineGauge::default()
+                .label(Line::from(vec![
+                    overview_label(name),
+                    Span::raw(format!("{value:<28}")),
+                ]))
+                .gauge_style(
+                    Style::default()
+                        .fg(usage_color(ratio, &theme))
+                        .bg(theme.dim),
+                )
+                .line_set(symbols::line::THICK)
+                .ratio(ratio)
+        };
+        let cpu_gauge = gauge(
+            "cpu",
+            format!("{host_cpu} of {} cpus", host.cpus),
+            cpu_ratio,
+        );
+        let mem_gauge = gauge(
+            "memory",
+            format!("{} / {}", totals.mem, host.mem_total),
+            mem_ratio,
+        );
+
+        let lines = overview_lines(&host, &totals, &theme);
+
+        let block = Block::default()
+            .title(format!(" Host Overview - {} ", host.name))
+            .padding(Padding::horizontal(1))
+            .title(
+                Title::from(" ( r ) refresh ( esc ) close ")
+                    .position(Position::Bottom)
+                    .alignment(Alignment::Center),
+            )
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.border));
+
+        let size = f.size();
+        let area = popup(
+            lines.len() + 5,
+            usize::from(size.width.saturating_sub(8)).min(80),
+            size,
+            BoxLocation::MiddleCentre,
+        );
+        let split = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Min(1),
+            ])
+            .split(block.inner(area));
+
+        f.render_widget(Clear, area);
+        f.render_widget(block, area);
+        f.render_widget(cpu_gauge, split[0]);
+        f.render_widget(mem_gauge, split[1]);
+        f.render_widget(Paragraph::new(lines), split[3]);
+    }
+}
+
+/// Draw the file browser, a directory listing of a container, over the majority of the screen
+pub fn files(f: &mut Frame, app_data: &Arc<Mutex<AppData>>) {
+    let mut locked_data = app_data.lock();
+    let theme = locked_data.args.theme;
+    if let Some(files) = locked_data.get_files() {
+        let items = files
+            .entries
+            .items
+            .iter()
+            .map(|i| {
+                if i.is_dir {
+                    ListItem::new(Span::styled(
+                        format!("{}/", i.name),
+                        Style::default().fg(theme.name),
+                    ))
+                } else {
+                    ListItem::new(Span::raw(i.name.clone()))
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let block = Block::default()
+            .title(format!(" Files - {} - {} ", files.name, files.path))
+            .title(
+                Title::from(" ( enter ) open ( d ) download ( u ) upload ( esc ) close ")
+                    .position(Position::Bottom)
+                    .alignment(Alignment::Center),
+            )
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.border));
+
+        let size = f.size();
+        let area = popup(
+            usize::from(size.height.saturating_sub(4)),
+            usize::from(size.width.saturating_sub(8)),
+            size,
+            BoxLocation::MiddleCentre,
+        );
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(Style::default().add_modifier(Modifier::BOLD))
+            .highlight_symbol(RIGHT_ARROW);
+        f.render_widget(Clear, area);
+        f.render_stateful_widget(list, area, &mut files.entries.state);
+    }
+}
+
 /// Draw the delete confirm box in the centre of the screen
 /// take in container id and container name here?
-pub fn delete_confirm(f: &mut Frame, gui_state: &Arc<Mutex<GuiState>>, name: &ContainerName) {
+pub fn delete_confirm(
+    f: &mut Frame,
+    gui_state: &Arc<Mutex<GuiState>>,
+    action: &str,
+    name: &str,
+    theme: &Theme,
+) {
     let block = Block::default()
         .title(" Confirm Delete ")
         .border_type(BorderType::Rounded)
-        .style(Style::default().bg(Color::White).fg(Color::Black))
+        .style(Style::default().bg(theme.dialog_bg).fg(theme.dialog_fg))
         .title_alignment(Alignment::Center)
         .borders(Borders::ALL);
 
     let confirm = Line::from(vec![
-        Span::from("Are you sure you want to delete container: "),
+        Span::from(format!("Are you sure you want to {action}: ")),
         Span::styled(
-            name.get(),
+            name.to_owned(),
             Style::default()
-                .fg(Color::Red)
-                .bg(Color::White)
+                .fg(theme.dialog_highlight)
+                .bg(theme.dialog_bg)
                 .add_modifier(Modifier::BOLD),
         ),
     ]);
@@ -842,7 +1712,7 @@ pub fn delete_confirm(f: &mut Frame, gui_state: &Arc<Mutex<GuiState>>, name: &Co
         Block::default()
             .border_type(BorderType::Rounded)
             .borders(Borders::ALL)
-            .style(Style::default().bg(Color::White))
+            .style(Style::default().bg(theme.dialog_bg))
     };
 
     let yes_para = Paragraph::new(yes_text)
@@ -888,8 +1758,92 @@ pub fn delete_confirm(f: &mut Frame, gui_state: &Arc<Mutex<GuiState>>, name: &Co
         .update_region_map(Region::Delete(DeleteButton::Yes), yes_area);
 }
 
+/// Draw a form popup, a label and a text input for each field, with the cursor placed in the selected field
+pub fn form(f: &mut Frame, form: &Form, theme: &Theme) {
+    let label_width = form
+        .fields
+        .iter()
+        .map(|i| i.label.chars().count())
+        .max()
+        .unwrap_or_default();
+    let value_width = form
+        .fields
+        .iter()
+        .map(|i| i.value.chars().count().max(i.hint.chars().count()))
+        .max()
+        .unwrap_or_default();
+
+    let size = f.size();
+    let width = (label_width + value_width + 8)
+        .max(60)
+        .min(usize::from(size.width));
+    // Space available for each value, values longer than this scroll to keep the cursor visible
+    let available = width.saturating_sub(label_width + 6).max(1);
+    let offset = |cursor: usize| cursor.saturating_sub(available - 1);
+
+    let mut lines = form
+        .fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| {
+            let label_style = if index == form.selected {
+                Style::default()
+                    .fg(theme.border)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            let value = if field.value.is_empty() {
+                Span::styled(field.hint, Style::default().fg(theme.dim))
+            } else {
+                let skip = if index == form.selected {
+                    offset(field.cursor())
+                } else {
+                    0
+                };
+                Span::raw(field.value.chars().skip(skip).collect::<String>())
+            };
+            Line::from(vec![
+                Span::styled(format!(" {:>label_width$}: ", field.label), label_style),
+                value,
+            ])
+        })
+        .collect::<Vec<_>>();
+
+    if let Some(error) = form.error.as_ref() {
+        lines.push(Line::from(Span::styled(
+            format!(" {error}"),
+            Style::default().fg(theme.form_error),
+        )));
+    }
+
+    let block = Block::default()
+        .title(format!(" {} ", form.kind.title()))
+        .title(
+            Title::from(" ( tab ) next field ( enter ) submit ( esc ) cancel ")
+                .position(Position::Bottom)
+                .alignment(Alignment::Center),
+        )
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(theme.border));
+
+    let area = popup(lines.len() + 2, width, size, BoxLocation::MiddleCentre);
+
+    f.render_widget(Clear, area);
+    f.render_widget(Paragraph::new(lines).block(block), area);
+
+    if let Some(field) = form.fields.get(form.selected) {
+        let cursor = field.cursor() - offset(field.cursor());
+        let x = area.x + u16::try_from(label_width + cursor + 4).unwrap_or_default();
+        let y = area.y + 1 + u16::try_from(form.selected).unwrap_or_default();
+        f.set_cursor(x.min(area.right().saturating_sub(2)), y);
+    }
+}
+
 /// Draw an error popup over whole screen
-pub fn error(f: &mut Frame, error: AppError, seconds: Option<u8>) {
+pub fn error(f: &mut Frame, error: AppError, seconds: Option<u8>, theme: &Theme) {
     let block = Block::default()
         .title(" Error ")
         .border_type(BorderType::Rounded)
@@ -921,7 +1875,7 @@ pub fn error(f: &mut Frame, error: AppError, seconds: Option<u8>) {
     lines += 3;
 
     let paragraph = Paragraph::new(text)
-        .style(Style::default().bg(Color::Red).fg(Color::White))
+        .style(Style::default().bg(theme.error_bg).fg(theme.error_fg))
         .block(block)
         .alignment(Alignment::Center);
 
@@ -934,7 +1888,13 @@ pub fn error(f: &mut Frame, error: AppError, seconds: Option<u8>) {
 
 /// Draw info box in one of the 9 BoxLocations
 // TODO is this broken?
-pub fn info(f: &mut Frame, text: &str, instant: Instant, gui_state: &Arc<Mutex<GuiState>>) {
+pub fn info(
+    f: &mut Frame,
+    text: &str,
+    instant: Instant,
+    gui_state: &Arc<Mutex<GuiState>>,
+    theme: &Theme,
+) {
     let block = Block::default()
         .title("")
         .title_alignment(Alignment::Center)
@@ -948,7 +1908,7 @@ pub fn info(f: &mut Frame, text: &str, instant: Instant, gui_state: &Arc<Mutex<G
     lines += 2;
 
     let paragraph = Paragraph::new(text)
-        .style(Style::default().bg(Color::Blue).fg(Color::White))
+        .style(Style::default().bg(theme.info_bg).fg(theme.info_fg))
         .block(block)
         .alignment(Alignment::Center);
 
@@ -994,6 +1954,7 @@ mod tests {
 
     use std::{ops::RangeInclusive, sync::Arc};
 
+    use bollard::service::{ImageSummary, Ipam, IpamConfig, Network};
     use parking_lot::Mutex;
     use ratatui::{
         backend::TestBackend,
@@ -1006,14 +1967,15 @@ mod tests {
     use crate::{
         app_data::{
             AppData, ContainerId, ContainerImage, ContainerName, ContainerPorts, Header,
-            SortedOrder, State, StatefulList,
+            IoCounters, SortedOrder, State, StatefulList,
         },
         app_error::AppError,
+        input_handler::Keymap,
         tests::{gen_appdata, gen_container_summary, gen_containers},
-        ui::{draw_frame, GuiState},
+        ui::{draw_frame, Form, FormField, FormKind, GuiState},
     };
 
-    use super::{FrameData, ORANGE, VERSION};
+    use super::{FrameData, Theme, VERSION};
 
     struct TuiTestSetup {
         app_data: Arc<Mutex<AppData>>,
@@ -1300,7 +2262,10 @@ mod tests {
                 let result_cell = &result[index];
 
                 assert_eq!(result_cell.symbol(), expected_char.to_string());
-                if (145..=207).contains(&index) {
+                // state & status, then cpu & memory, either side of the health column
+                if row_index == 1
+                    && ((15..=47).contains(&char_index) || (60..=88).contains(&char_index))
+                {
                     assert_eq!(result_cell.fg, color);
                     assert_eq!(result_cell.modifier, Modifier::BOLD);
                 }
@@ -1372,16 +2337,16 @@ mod tests {
     #[test]
     /// Containers panel drawn, selected line is bold, border is blue
     fn test_draw_blocks_containers_some() {
-        let (w, h) = (130, 6);
+        let (w, h) = (131, 6);
         let mut setup = test_setup(w, h, true, true);
 
         let expected = [
-        "╭ Containers 1/3 ────────────────────────────────────────────────────────────────────────────────────────────────────────────────╮",
-        "│⚪  container_1   ✓ running            Up 1 hour    00.00%   0.00 kB / 0.00 kB          1   image_1   0.00 kB   0.00 kB          │",
-        "│   container_2   ✓ running            Up 2 hour    00.00%   0.00 kB / 0.00 kB          2   image_2   0.00 kB   0.00 kB          │",
-        "│   container_3   ✓ running            Up 3 hour    00.00%   0.00 kB / 0.00 kB          3   image_3   0.00 kB   0.00 kB          │",
-        "│                                                                                                                                │",
-        "╰────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────╯",
+        "╭ Containers 1/3 ─────────────────────────────────────────────────────────────────────────────────────────────────────────────────╮",
+        "│⚪  container_1   ✓ running            Up 1 hour               00.00%   0.00 kB / 0.00 kB          1   image_1   0.00 kB   0.00 kB│",
+        "│   container_2   ✓ running            Up 2 hour               00.00%   0.00 kB / 0.00 kB          2   image_2   0.00 kB   0.00 kB│",
+        "│   container_3   ✓ running            Up 3 hour               00.00%   0.00 kB / 0.00 kB          3   image_3   0.00 kB   0.00 kB│",
+        "│                                                                                                                                 │",
+        "╰─────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────╯",
     ];
 
         setup
@@ -1402,7 +2367,7 @@ mod tests {
 
                 // Selected container is bold
                 match index {
-                    131 | 133..=258 => assert_eq!(result_cell.modifier, Modifier::BOLD),
+                    132 | 134..=260 => assert_eq!(result_cell.modifier, Modifier::BOLD),
                     _ => {
                         assert!(result_cell.modifier.is_empty());
                     }
@@ -1444,16 +2409,16 @@ mod tests {
     #[test]
     /// ALl columns on all rows are coloured correctly
     fn test_draw_blocks_containers_colors() {
-        let (w, h) = (130, 6);
+        let (w, h) = (131, 6);
         let mut setup = test_setup(w, h, true, true);
 
         let expected = [
-            "╭ Containers 1/3 ────────────────────────────────────────────────────────────────────────────────────────────────────────────────╮",
-            "│⚪  container_1   ✓ running            Up 1 hour    00.00%   0.00 kB / 0.00 kB          1   image_1   0.00 kB   0.00 kB          │",
-            "│   container_2   ✓ running            Up 2 hour    00.00%   0.00 kB / 0.00 kB          2   image_2   0.00 kB   0.00 kB          │",
-            "│   container_3   ✓ running            Up 3 hour    00.00%   0.00 kB / 0.00 kB          3   image_3   0.00 kB   0.00 kB          │",
-            "│                                                                                                                                │",
-            "╰────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────╯",
+            "╭ Containers 1/3 ─────────────────────────────────────────────────────────────────────────────────────────────────────────────────╮",
+            "│⚪  container_1   ✓ running            Up 1 hour               00.00%   0.00 kB / 0.00 kB          1   image_1   0.00 kB   0.00 kB│",
+            "│   container_2   ✓ running            Up 2 hour               00.00%   0.00 kB / 0.00 kB          2   image_2   0.00 kB   0.00 kB│",
+            "│   container_3   ✓ running            Up 3 hour               00.00%   0.00 kB / 0.00 kB          3   image_3   0.00 kB   0.00 kB│",
+            "│                                                                                                                                 │",
+            "╰─────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────╯",
         ];
         let fd = FrameData::from((setup.app_data.lock(), setup.gui_state.lock()));
 
@@ -1465,34 +2430,40 @@ mod tests {
             .unwrap();
 
         let index_blue = [
-            134, 135, 136, 137, 138, 139, 140, 141, 142, 143, 144, 208, 209, 210, 211, 212, 213,
-            214, 215, 216, 217, 218, 219, 220, 221, 222, 223, 224, 225, 226, 227, 228,
+            135, 136, 137, 138, 139, 140, 141, 142, 143, 144, 145, 220, 221, 222, 223, 224, 225,
+            226, 227, 228, 229, 230, 231, 232, 233, 234, 235, 236, 237, 238, 239, 240,
         ];
         let index_blue = index_blue
             .iter()
-            .flat_map(|&x| vec![x, x + 130, x + 260])
+            .flat_map(|&x| vec![x, x + 131, x + 262])
             .collect::<Vec<_>>();
         let index_green = [
-            145, 146, 147, 148, 149, 150, 151, 152, 153, 154, 155, 156, 157, 158, 159, 160, 161,
-            162, 163, 164, 165, 166, 167, 168, 169, 170, 171, 172, 173, 174, 175, 176, 177, 178,
-            179, 180, 181, 182, 183, 184, 185, 186, 187, 188, 189, 190, 191, 192, 193, 194, 195,
-            196, 197, 198, 199, 200, 201, 202, 203, 204, 205, 206, 207,
+            146, 147, 148, 149, 150, 151, 152, 153, 154, 155, 156, 157, 158, 159, 160, 161, 162,
+            163, 164, 165, 166, 167, 168, 169, 170, 171, 172, 173, 174, 175, 176, 177, 178, 190,
+            191, 192, 193, 194, 195, 196, 197, 198, 199, 200, 201, 202, 203, 204, 205, 206, 207,
+            208, 209, 210, 211, 212, 213, 214, 215, 216, 217, 218, 219,
         ];
         let index_green = index_green
             .iter()
-            .flat_map(|&x| vec![x, x + 130, x + 260])
+            .flat_map(|&x| vec![x, x + 131, x + 262])
+            .collect::<Vec<_>>();
+
+        let index_gray = [179, 180, 181, 182, 183, 184, 185, 186, 187, 188, 189];
+        let index_gray = index_gray
+            .iter()
+            .flat_map(|&x| vec![x, x + 131, x + 262])
             .collect::<Vec<_>>();
 
-        let index_rx = [229, 230, 231, 232, 233, 234, 235, 236, 237, 238];
+        let index_rx = [241, 242, 243, 244, 245, 246, 247, 248, 249, 250];
         let index_rx = index_rx
             .iter()
-            .flat_map(|&x| vec![x, x + 130, x + 260])
+            .flat_map(|&x| vec![x, x + 131, x + 262])
             .collect::<Vec<_>>();
 
-        let index_tx = [239, 240, 241, 242, 243, 244, 245, 246, 247, 248];
+        let index_tx = [251, 252, 253, 254, 255, 256, 257, 258, 259, 260];
         let index_tx = index_tx
             .iter()
-            .flat_map(|&x| vec![x, x + 130, x + 260])
+            .flat_map(|&x| vec![x, x + 131, x + 262])
             .collect::<Vec<_>>();
 
         let result = &setup.terminal.backend().buffer().content;
@@ -1510,13 +2481,16 @@ mod tests {
                     _x if index_green.contains(&index) => {
                         assert_eq!(result_cell.fg, Color::Green);
                     }
+                    _x if index_gray.contains(&index) => {
+                        assert_eq!(result_cell.fg, Color::Gray);
+                    }
                     _x if index_rx.contains(&index) => {
                         assert_eq!(result_cell.fg, Color::Rgb(255, 233, 193));
                     }
                     _x if index_tx.contains(&index) => {
                         assert_eq!(result_cell.fg, Color::Rgb(205, 140, 140));
                     }
-                    (0..=130) | (259..=260) | (389..=390) | (519..=520) | (649..=779) => {
+                    (0..=131) | (261..=262) | (392..=393) | (523..=524) | (654..=785) => {
                         assert_eq!(result_cell.fg, Color::LightCyan);
                     }
                     _ => {
@@ -1530,7 +2504,7 @@ mod tests {
     #[test]
     /// When long container/image name, it is truncated correctly
     fn test_draw_blocks_containers_long_name_image() {
-        let (w, h) = (170, 6);
+        let (w, h) = (173, 6);
         let mut setup = test_setup(w, h, true, true);
         setup.app_data.lock().containers.items[0].name =
             ContainerName::from("a_long_container_name_for_the_purposes_of_this_test");
@@ -1538,12 +2512,12 @@ mod tests {
             ContainerImage::from("a_long_image_name_for_the_purposes_of_this_test");
 
         let expected = [
-        "╭ Containers 1/3 ────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────╮",
-        "│⚪  a_long_container_name_for_the…   ॥ paused             Up 1 hour    00.00%   0.00 kB / 0.00 kB          1   a_long_image_name_for_the_pur…   0.00 kB   0.00 kB        │",
-        "│                      container_2   ✓ running            Up 2 hour    00.00%   0.00 kB / 0.00 kB          2                          image_2   0.00 kB   0.00 kB        │",
-        "│                      container_3   ✓ running            Up 3 hour    00.00%   0.00 kB / 0.00 kB          3                          image_3   0.00 kB   0.00 kB        │",
-        "│                                                                                                                                                                        │",
-        "╰────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────╯",
+        "╭ Containers 1/3 ───────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────╮",
+        "│⚪  a_long_container_name_for_the…   ॥ paused             Up 1 hour               00.00%   0.00 kB / 0.00 kB          1   a_long_image_name_for_the_pur…   0.00 kB   0.00 kB│",
+        "│                      container_2   ✓ running            Up 2 hour               00.00%   0.00 kB / 0.00 kB          2                          image_2   0.00 kB   0.00 kB│",
+        "│                      container_3   ✓ running            Up 3 hour               00.00%   0.00 kB / 0.00 kB          3                          image_3   0.00 kB   0.00 kB│",
+        "│                                                                                                                                                                           │",
+        "╰───────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────╯",
         ];
         let fd = FrameData::from((setup.app_data.lock(), setup.gui_state.lock()));
         setup.app_data.lock().containers.items[0].state = State::Paused;
@@ -1571,16 +2545,16 @@ mod tests {
     #[test]
     /// When container is paused, correct colors displayed
     fn test_draw_blocks_containers_paused() {
-        let (w, h) = (130, 6);
+        let (w, h) = (131, 6);
         let mut setup = test_setup(w, h, true, true);
 
         let expected = [
-            "╭ Containers 1/3 ────────────────────────────────────────────────────────────────────────────────────────────────────────────────╮",
-        "│⚪  container_1   ॥ paused             Up 1 hour    00.00%   0.00 kB / 0.00 kB          1   image_1   0.00 kB   0.00 kB          │",
-        "│   container_2   ✓ running            Up 2 hour    00.00%   0.00 kB / 0.00 kB          2   image_2   0.00 kB   0.00 kB          │",
-        "│   container_3   ✓ running            Up 3 hour    00.00%   0.00 kB / 0.00 kB          3   image_3   0.00 kB   0.00 kB          │",
-        "│                                                                                                                                │",
-        "╰────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────╯",
+            "╭ Containers 1/3 ─────────────────────────────────────────────────────────────────────────────────────────────────────────────────╮",
+        "│⚪  container_1   ॥ paused             Up 1 hour               00.00%   0.00 kB / 0.00 kB          1   image_1   0.00 kB   0.00 kB│",
+        "│   container_2   ✓ running            Up 2 hour               00.00%   0.00 kB / 0.00 kB          2   image_2   0.00 kB   0.00 kB│",
+        "│   container_3   ✓ running            Up 3 hour               00.00%   0.00 kB / 0.00 kB          3   image_3   0.00 kB   0.00 kB│",
+        "│                                                                                                                                 │",
+        "╰─────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────╯",
         ];
         let fd = FrameData::from((setup.app_data.lock(), setup.gui_state.lock()));
         setup.app_data.lock().containers.items[0].state = State::Paused;
@@ -1598,16 +2572,16 @@ mod tests {
     #[test]
     /// When container is dead, correct colors displayed
     fn test_draw_blocks_containers_dead() {
-        let (w, h) = (130, 6);
+        let (w, h) = (131, 6);
         let mut setup = test_setup(w, h, true, true);
 
         let expected = [
-            "╭ Containers 1/3 ────────────────────────────────────────────────────────────────────────────────────────────────────────────────╮",
-            "│⚪  container_1   ✖ dead               Up 1 hour    00.00%   0.00 kB / 0.00 kB          1   image_1   0.00 kB   0.00 kB          │",
-            "│   container_2   ✓ running            Up 2 hour    00.00%   0.00 kB / 0.00 kB          2   image_2   0.00 kB   0.00 kB          │",
-            "│   container_3   ✓ running            Up 3 hour    00.00%   0.00 kB / 0.00 kB          3   image_3   0.00 kB   0.00 kB          │",
-            "│                                                                                                                                │",
-            "╰────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────╯",
+            "╭ Containers 1/3 ─────────────────────────────────────────────────────────────────────────────────────────────────────────────────╮",
+            "│⚪  container_1   ✖ dead               Up 1 hour               00.00%   0.00 kB / 0.00 kB          1   image_1   0.00 kB   0.00 kB│",
+            "│   container_2   ✓ running            Up 2 hour               00.00%   0.00 kB / 0.00 kB          2   image_2   0.00 kB   0.00 kB│",
+            "│   container_3   ✓ running            Up 3 hour               00.00%   0.00 kB / 0.00 kB          3   image_3   0.00 kB   0.00 kB│",
+            "│                                                                                                                                 │",
+            "╰─────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────╯",
         ];
         setup.app_data.lock().containers.items[0].state = State::Dead;
         let fd = FrameData::from((setup.app_data.lock(), setup.gui_state.lock()));
@@ -1624,16 +2598,16 @@ mod tests {
     #[test]
     /// When container is exited, correct colors displayed
     fn test_draw_blocks_containers_exited() {
-        let (w, h) = (130, 6);
+        let (w, h) = (131, 6);
         let mut setup = test_setup(w, h, true, true);
 
         let expected = [
-            "╭ Containers 1/3 ────────────────────────────────────────────────────────────────────────────────────────────────────────────────╮",
-            "│⚪  container_1   ✖ exited             Up 1 hour    00.00%   0.00 kB / 0.00 kB          1   image_1   0.00 kB   0.00 kB          │",
-            "│   container_2   ✓ running            Up 2 hour    00.00%   0.00 kB / 0.00 kB          2   image_2   0.00 kB   0.00 kB          │",
-            "│   container_3   ✓ running            Up 3 hour    00.00%   0.00 kB / 0.00 kB          3   image_3   0.00 kB   0.00 kB          │",
-            "│                                                                                                                                │",
-            "╰────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────╯",
+            "╭ Containers 1/3 ─────────────────────────────────────────────────────────────────────────────────────────────────────────────────╮",
+            "│⚪  container_1   ✖ exited             Up 1 hour               00.00%   0.00 kB / 0.00 kB          1   image_1   0.00 kB   0.00 kB│",
+            "│   container_2   ✓ running            Up 2 hour               00.00%   0.00 kB / 0.00 kB          2   image_2   0.00 kB   0.00 kB│",
+            "│   container_3   ✓ running            Up 3 hour               00.00%   0.00 kB / 0.00 kB          3   image_3   0.00 kB   0.00 kB│",
+            "│                                                                                                                                 │",
+            "╰─────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────╯",
         ];
         setup.app_data.lock().containers.items[0].state = State::Exited;
         let fd = FrameData::from((setup.app_data.lock(), setup.gui_state.lock()));
@@ -1650,16 +2624,16 @@ mod tests {
     #[test]
     /// When container is paused, correct colors displayed
     fn test_draw_blocks_containers_removing() {
-        let (w, h) = (130, 6);
+        let (w, h) = (131, 6);
         let mut setup = test_setup(w, h, true, true);
 
         let expected = [
-            "╭ Containers 1/3 ────────────────────────────────────────────────────────────────────────────────────────────────────────────────╮",
-            "│⚪  container_1   removing             Up 1 hour    00.00%   0.00 kB / 0.00 kB          1   image_1   0.00 kB   0.00 kB          │",
-            "│   container_2   ✓ running            Up 2 hour    00.00%   0.00 kB / 0.00 kB          2   image_2   0.00 kB   0.00 kB          │",
-            "│   container_3   ✓ running            Up 3 hour    00.00%   0.00 kB / 0.00 kB          3   image_3   0.00 kB   0.00 kB          │",
-            "│                                                                                                                                │",
-            "╰────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────╯",
+            "╭ Containers 1/3 ─────────────────────────────────────────────────────────────────────────────────────────────────────────────────╮",
+            "│⚪  container_1   removing             Up 1 hour               00.00%   0.00 kB / 0.00 kB          1   image_1   0.00 kB   0.00 kB│",
+            "│   container_2   ✓ running            Up 2 hour               00.00%   0.00 kB / 0.00 kB          2   image_2   0.00 kB   0.00 kB│",
+            "│   container_3   ✓ running            Up 3 hour               00.00%   0.00 kB / 0.00 kB          3   image_3   0.00 kB   0.00 kB│",
+            "│                                                                                                                                 │",
+            "╰─────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────╯",
         ];
         setup.app_data.lock().containers.items[0].state = State::Removing;
         let fd = FrameData::from((setup.app_data.lock(), setup.gui_state.lock()));
@@ -1676,16 +2650,16 @@ mod tests {
     #[test]
     /// When container state is restarting, correct colors displayed
     fn test_draw_blocks_containers_restarting() {
-        let (w, h) = (130, 6);
+        let (w, h) = (132, 6);
         let mut setup = test_setup(w, h, true, true);
 
         let expected = [
-            "╭ Containers 1/3 ────────────────────────────────────────────────────────────────────────────────────────────────────────────────╮",
-            "│⚪  container_1   ↻ restarting          Up 1 hour    00.00%   0.00 kB / 0.00 kB          1   image_1   0.00 kB   0.00 kB         │",
-            "│   container_2   ✓ running             Up 2 hour    00.00%   0.00 kB / 0.00 kB          2   image_2   0.00 kB   0.00 kB         │",
-            "│   container_3   ✓ running             Up 3 hour    00.00%   0.00 kB / 0.00 kB          3   image_3   0.00 kB   0.00 kB         │",
-            "│                                                                                                                                │",
-            "╰────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────╯",
+            "╭ Containers 1/3 ──────────────────────────────────────────────────────────────────────────────────────────────────────────────────╮",
+            "│⚪  container_1   ↻ restarting          Up 1 hour               00.00%   0.00 kB / 0.00 kB          1   image_1   0.00 kB   0.00 kB│",
+            "│   container_2   ✓ running             Up 2 hour               00.00%   0.00 kB / 0.00 kB          2   image_2   0.00 kB   0.00 kB│",
+            "│   container_3   ✓ running             Up 3 hour               00.00%   0.00 kB / 0.00 kB          3   image_3   0.00 kB   0.00 kB│",
+            "│                                                                                                                                  │",
+            "╰──────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────╯",
         ];
         setup.app_data.lock().containers.items[0].state = State::Restarting;
         let fd = FrameData::from((setup.app_data.lock(), setup.gui_state.lock()));
@@ -1702,16 +2676,16 @@ mod tests {
     #[test]
     /// When container state is unknown, correct colors displayed
     fn test_draw_blocks_containers_unknown() {
-        let (w, h) = (130, 6);
+        let (w, h) = (131, 6);
         let mut setup = test_setup(w, h, true, true);
 
         let expected = [
-            "╭ Containers 1/3 ────────────────────────────────────────────────────────────────────────────────────────────────────────────────╮",
-            "│⚪  container_1   ? unknown            Up 1 hour    00.00%   0.00 kB / 0.00 kB          1   image_1   0.00 kB   0.00 kB          │",
-            "│   container_2   ✓ running            Up 2 hour    00.00%   0.00 kB / 0.00 kB          2   image_2   0.00 kB   0.00 kB          │",
-            "│   container_3   ✓ running            Up 3 hour    00.00%   0.00 kB / 0.00 kB          3   image_3   0.00 kB   0.00 kB          │",
-            "│                                                                                                                                │",
-            "╰────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────╯",
+            "╭ Containers 1/3 ─────────────────────────────────────────────────────────────────────────────────────────────────────────────────╮",
+            "│⚪  container_1   ? unknown            Up 1 hour               00.00%   0.00 kB / 0.00 kB          1   image_1   0.00 kB   0.00 kB│",
+            "│   container_2   ✓ running            Up 2 hour               00.00%   0.00 kB / 0.00 kB          2   image_2   0.00 kB   0.00 kB│",
+            "│   container_3   ✓ running            Up 3 hour               00.00%   0.00 kB / 0.00 kB          3   image_3   0.00 kB   0.00 kB│",
+            "│                                                                                                                                 │",
+            "╰─────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────╯",
         ];
         setup.app_data.lock().containers.items[0].state = State::Unknown;
         let fd = FrameData::from((setup.app_data.lock(), setup.gui_state.lock()));
@@ -1790,6 +2764,213 @@ mod tests {
         }
     }
 
+    #[test]
+    /// No images, "no images found" displayed in the images panel
+    fn test_draw_blocks_images_none() {
+        let (w, h) = (50, 5);
+        let mut setup = test_setup(w, h, true, true);
+
+        let expected = [
+            "╭ Images ────────────────────────────────────────╮",
+            "│                 no images found                │",
+            "│                                                │",
+            "│                                                │",
+            "╰── ( d ) delete ( p ) prune dangling ( u ) pull ╯",
+        ];
+
+        setup
+            .terminal
+            .draw(|f| {
+                super::images(&setup.app_data, setup.area, f, &setup.fd, &setup.gui_state);
+            })
+            .unwrap();
+
+        let result = &setup.terminal.backend().buffer().content;
+        for (row_index, row) in expected.iter().enumerate() {
+            for (char_index, expected_char) in row.chars().enumerate() {
+                let index = row_index * usize::from(w) + char_index;
+                let result_cell = &result[index];
+                assert_eq!(result_cell.symbol(), expected_char.to_string());
+            }
+        }
+    }
+
+    #[test]
+    /// Images listed, newest first, with dangling images in gray, and the containers using each image in green
+    fn test_draw_blocks_images_some() {
+        let (w, h) = (80, 5);
+        let mut setup = test_setup(w, h, true, true);
+        let now = i64::try_from(
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_secs(),
+        )
+        .unwrap();
+        let gen_summary = |id: &str, tags: Vec<String>, created: i64, size: i64| ImageSummary {
+            id: id.to_owned(),
+            repo_tags: tags,
+            created,
+            size,
+            ..Default::default()
+        };
+        setup.app_data.lock().containers.items[0].image_id = "sha256:aaaaaaaaaaaaaaaa".to_owned();
+        setup.app_data.lock().update_images(&[
+            gen_summary(
+                "sha256:bbbbbbbbbbbbbbbb",
+                vec![],
+                now - 604_800 * 3,
+                500_000,
+            ),
+            gen_summary(
+                "sha256:aaaaaaaaaaaaaaaa",
+                vec!["image_1:latest".to_owned()],
+                now - 86_400 * 2,
+                1_500_000,
+            ),
+        ]);
+        let fd = FrameData::from((setup.app_data.lock(), setup.gui_state.lock()));
+
+        let expected = [
+            "╭ Images 1/2 ──────────────────────────────────────────────────────────────────╮",
+            "│▶ image_1:latest   aaaaaaaaaaaa     1.50 MB    2 days ago   container_1       │",
+            "│  <none>:<none>    bbbbbbbbbbbb   500.00 kB   3 weeks ago   unused            │",
+            "│                                                                              │",
+            "╰──────────────────────────────── ( d ) delete ( p ) prune dangling ( u ) pull ╯",
+        ];
+
+        setup
+            .terminal
+            .draw(|f| {
+                super::images(&setup.app_data, setup.area, f, &fd, &setup.gui_state);
+            })
+            .unwrap();
+
+        let result = &setup.terminal.backend().buffer().content;
+        for (row_index, row) in expected.iter().enumerate() {
+            for (char_index, expected_char) in row.chars().enumerate() {
+                let index = row_index * usize::from(w) + char_index;
+                let result_cell = &result[index];
+                assert_eq!(result_cell.symbol(), expected_char.to_string());
+                match (row_index, char_index) {
+                    (1, 3..=16) | (1 | 2, 20..=31) => assert_eq!(result_cell.fg, Color::Blue),
+                    (2, 3..=15 | 61..=66) => assert_eq!(result_cell.fg, Color::DarkGray),
+                    (1, 61..=71) => assert_eq!(result_cell.fg, Color::Green),
+                    _ => (),
+                }
+            }
+        }
+    }
+
+    #[test]
+    /// Networks listed by name, the network used to filter the containers is magenta
+    fn test_draw_blocks_networks() {
+        let (w, h) = (90, 5);
+        let mut setup = test_setup(w, h, true, true);
+        let gen_network = |id: &str, name: &str, subnet: &str| Network {
+            id: Some(id.to_owned()),
+            name: Some(name.to_owned()),
+            driver: Some("bridge".to_owned()),
+            ipam: Some(Ipam {
+                config: Some(vec![IpamConfig {
+                    subnet: Some(subnet.to_owned()),
+                    ..Default::default()
+                }]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        setup.app_data.lock().update_networks(
+            &[
+                gen_network("aaaaaaaaaaaaaaaa", "bridge", "172.17.0.0/16"),
+                gen_network("bbbbbbbbbbbbbbbb", "backend", "172.20.0.0/16"),
+            ],
+            &[],
+        );
+        setup.app_data.lock().networks_end();
+        setup.app_data.lock().toggle_network_filter();
+
+        let expected = [
+            "╭ Networks 2/2 ──────────────────────────────────────────────────────────────────────────╮",
+            "│  backend   bbbbbbbbbbbb   bridge   172.20.0.0/16   unused                              │",
+            "│▶ bridge    aaaaaaaaaaaa   bridge   172.17.0.0/16   unused                              │",
+            "│                                                                                        │",
+            "╰───────────────────────── ( c ) create ( d ) delete ( p ) prune ( f ) filter containers ╯",
+        ];
+
+        setup
+            .terminal
+            .draw(|f| {
+                super::networks(&setup.app_data, setup.area, f, &setup.fd, &setup.gui_state);
+            })
+            .unwrap();
+
+        let result = &setup.terminal.backend().buffer().content;
+        for (row_index, row) in expected.iter().enumerate() {
+            for (char_index, expected_char) in row.chars().enumerate() {
+                let index = row_index * usize::from(w) + char_index;
+                let result_cell = &result[index];
+                assert_eq!(result_cell.symbol(), expected_char.to_string());
+                match (row_index, char_index) {
+                    (1, 3..=9) | (1 | 2, 13..=24) => assert_eq!(result_cell.fg, Color::Blue),
+                    (2, 3..=9) => assert_eq!(result_cell.fg, Color::Magenta),
+                    (1 | 2, 53..=58) => assert_eq!(result_cell.fg, Color::DarkGray),
+                    _ => (),
+                }
+            }
+        }
+    }
+
+    #[test]
+    /// Form drawn with hints for empty fields, the error line, and the selected field scrolled to keep the cursor visible
+    fn test_draw_blocks_form() {
+        let (w, h) = (64, 8);
+        let mut setup = test_setup(w, h, true, true);
+        let mut form = Form::new(
+            FormKind::Run,
+            vec![
+                FormField::new("image", "a".repeat(60)),
+                FormField::new("name", "").hint("optional"),
+            ],
+        );
+        form.error = Some("an image is required".to_owned());
+
+        setup
+            .terminal
+            .draw(|f| {
+                super::form(f, &form, &Theme::default());
+            })
+            .unwrap();
+
+        let expected = [
+            "                                                                ",
+            "╭─────────────────────── Run Container ────────────────────────╮",
+            "│ image: aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa  │",
+            "│  name: optional                                              │",
+            "│ an image is required                                         │",
+            "│                                                              │",
+            "╰───── ( tab ) next field ( enter ) submit ( esc ) cancel ─────╯",
+            "                                                                ",
+        ];
+
+        let result = &setup.terminal.backend().buffer().content;
+        for (row_index, row) in expected.iter().enumerate() {
+            for (char_index, expected_char) in row.chars().enumerate() {
+                let index = row_index * usize::from(w) + char_index;
+                let result_cell = &result[index];
+                assert_eq!(result_cell.symbol(), expected_char.to_string());
+                match (row_index, char_index) {
+                    (2, 2..=6) => assert_eq!(result_cell.fg, Color::LightCyan),
+                    (3, 9..=16) => assert_eq!(result_cell.fg, Color::DarkGray),
+                    (4, 2..=21) => assert_eq!(result_cell.fg, Color::Red),
+                    _ => (),
+                }
+            }
+        }
+        // Cursor is directly after the last visible char of the selected field
+        assert_eq!(setup.terminal.get_cursor().unwrap(), (61, 2));
+    }
+
     #[test]
     /// Parsing logs, spinner visible, and then animates by one frame
     fn test_draw_blocks_logs_parsing() {
@@ -1972,22 +3153,22 @@ mod tests {
 
     const EXPECTED: [&str; 10] = [
         "╭───────────── cpu 03.00% ─────────────╮╭────────── memory 30.00 kB ───────────╮",
-        "│10.00%│    •                          ││100.00 kB│   ••                       │",
-        "│      │   ••                          ││         │   ••                       │",
-        "│      │  •••                          ││         │  • •                       │",
-        "│      │  • •                          ││         │ •  •                       │",
-        "│      │ •   ••                        ││         │••  ••                      │",
-        "│      │•    •                         ││         │•   •                       │",
+        "│10.00%│     •                         ││100.00 kB│    •                       │",
+        "│      │    ••                         ││         │   ••                       │",
+        "│      │   • •                         ││         │   ••                       │",
+        "│      │  •  •                         ││         │  • •                       │",
+        "│      │  •  ••                        ││         │ •  ••                      │",
+        "│      │ •   ••                        ││         │•   ••                      │",
         "│      │•    •                         ││         │•   •                       │",
         "│      │                               ││         │                            │",
-        "╰──────────────────────────────────────╯╰──────────────────────────────────────╯",
+        "╰ 00:00:00 ────────────────── 00:01:00 ╯╰ 00:00:00 ────────────────── 00:01:00 ╯",
     ];
-    const MEMORY_INDEX: [usize; 16] = [
-        134, 135, 214, 215, 293, 295, 372, 375, 451, 452, 455, 456, 531, 535, 611, 615,
+    const MEMORY_INDEX: [usize; 15] = [
+        135, 214, 215, 294, 295, 373, 375, 452, 455, 456, 531, 535, 536, 611, 615,
     ];
 
     const CPU_INDEX: [usize; 15] = [
-        92, 171, 172, 250, 251, 252, 330, 332, 409, 413, 414, 488, 493, 568, 573,
+        93, 172, 173, 251, 253, 330, 333, 410, 413, 414, 489, 493, 494, 568, 573,
     ];
 
     #[allow(clippy::cast_precision_loss)]
@@ -1999,8 +3180,8 @@ mod tests {
                 Some(i as f64),
                 Some(i * 10000),
                 i * 10000,
-                i,
-                i,
+                IoCounters::default(),
+                i * 1000,
             );
         }
         for i in 1..=3 {
@@ -2009,8 +3190,8 @@ mod tests {
                 Some(i as f64),
                 Some(i * 10000),
                 i * 10000,
-                i,
-                i,
+                IoCounters::default(),
+                (i + 10) * 1000,
             );
         }
     }
@@ -2056,7 +3237,7 @@ mod tests {
                     }
                     // Cpu & Memory max are orange and bold
                     81..=86 | 121..=127 => {
-                        assert_eq!(result_cell.fg, ORANGE);
+                        assert_eq!(result_cell.fg, Theme::DARK.chart_max);
                         assert_eq!(result_cell.modifier, Modifier::BOLD);
                     }
                     // All others
@@ -2099,7 +3280,7 @@ mod tests {
                     }
                     // Cpu & Memory max are orange and bold
                     81..=86 | 121..=129 => {
-                        assert_eq!(result_cell.fg, ORANGE);
+                        assert_eq!(result_cell.fg, Theme::DARK.chart_max);
                         assert_eq!(result_cell.modifier, Modifier::BOLD);
                     }
                     // cpu dots are magenta
@@ -2112,6 +3293,11 @@ mod tests {
                         assert_eq!(result_cell.fg, Color::Cyan);
                         assert!(result_cell.modifier.is_empty());
                     }
+                    // Start and end times of the x-axis
+                    721..=730 | 749..=758 | 761..=770 | 789..=798 => {
+                        assert_eq!(result_cell.fg, Color::Green);
+                        assert!(result_cell.modifier.is_empty());
+                    }
                     // All others
                     _ => {
                         assert_eq!(result_cell.fg, Color::Reset);
@@ -2160,6 +3346,11 @@ mod tests {
                         assert_eq!(result_cell.fg, Color::Cyan);
                         assert!(result_cell.modifier.is_empty());
                     }
+                    // Start and end times of the x-axis
+                    721..=730 | 749..=758 | 761..=770 | 789..=798 => {
+                        assert_eq!(result_cell.fg, Color::Yellow);
+                        assert!(result_cell.modifier.is_empty());
+                    }
                     // All others
                     _ => {
                         assert_eq!(result_cell.fg, Color::Reset);
@@ -2208,6 +3399,11 @@ mod tests {
                         assert_eq!(result_cell.fg, Color::Cyan);
                         assert!(result_cell.modifier.is_empty());
                     }
+                    // Start and end times of the x-axis
+                    721..=730 | 749..=758 | 761..=770 | 789..=798 => {
+                        assert_eq!(result_cell.fg, Color::Red);
+                        assert!(result_cell.modifier.is_empty());
+                    }
                     // All others
                     _ => {
                         assert_eq!(result_cell.fg, Color::Reset);
@@ -2271,11 +3467,11 @@ mod tests {
     #[test]
     /// Show all headings when containers present, colors valid
     fn test_draw_blocks_headers_some_containers() {
-        let (w, h) = (140, 1);
+        let (w, h) = (173, 1);
         let mut setup = test_setup(w, h, true, true);
         let fd = FrameData::from((setup.app_data.lock(), setup.gui_state.lock()));
 
-        let expected =   "           name       state               status       cpu        memory/limit         id     image      ↓ rx      ↑ tx    ( h ) show help  ";
+        let expected =   "           name   state                   status   health         cpu        memory/limit         id     image      ↓ rx      ↑ tx     ↓ rx/s     ↑ tx/s    ( h ) show help  ";
         setup
             .terminal
             .draw(|f| {
@@ -2292,7 +3488,7 @@ mod tests {
             assert_eq!(
                 result_cell.fg,
                 match index {
-                    (2..=122) => Color::Black,
+                    (2..=155) => Color::Black,
                     _ => Color::White,
                 }
             );
@@ -2302,7 +3498,7 @@ mod tests {
     #[test]
     /// Test all combination of headers & sort by
     fn test_draw_blocks_headers_sort_containers() {
-        let (w, h) = (140, 1);
+        let (w, h) = (173, 1);
         let mut setup = test_setup(w, h, true, true);
         let mut fd = FrameData::from((setup.app_data.lock(), setup.gui_state.lock()));
         let mut test = |expected: &str, range: RangeInclusive<usize>, x: (Header, SortedOrder)| {
@@ -2325,7 +3521,7 @@ mod tests {
                     match index {
                         0 | 1 => Color::White,
                         // given range | help section
-                        x if range.contains(&x) || (123..=139).contains(&x) => Color::White,
+                        x if range.contains(&x) || (156..=172).contains(&x) => Color::White,
                         _ => Color::Black,
                     }
                 );
@@ -2333,46 +3529,58 @@ mod tests {
         };
 
         // Name
-        test("         ▲ name       state               status       cpu        memory/limit         id     image      ↓ rx      ↑ tx    ( h ) show help  ", 1..=14, (Header::Name, SortedOrder::Asc));
-        test("         ▼ name       state               status       cpu        memory/limit         id     image      ↓ rx      ↑ tx    ( h ) show help  ", 1..=14, (Header::Name, SortedOrder::Desc));
+        test("         ▲ name   state                   status   health         cpu        memory/limit         id     image      ↓ rx      ↑ tx     ↓ rx/s     ↑ tx/s    ( h ) show help  ", 1..=14, (Header::Name, SortedOrder::Asc));
+        test("         ▼ name   state                   status   health         cpu        memory/limit         id     image      ↓ rx      ↑ tx     ↓ rx/s     ↑ tx/s    ( h ) show help  ", 1..=14, (Header::Name, SortedOrder::Desc));
 
         // state
-        test("           name     ▲ state               status       cpu        memory/limit         id     image      ↓ rx      ↑ tx    ( h ) show help  ", 15..=26, (Header::State, SortedOrder::Asc));
-        test("           name     ▼ state               status       cpu        memory/limit         id     image      ↓ rx      ↑ tx    ( h ) show help  ", 15..=26, (Header::State, SortedOrder::Desc));
+        test("           name   ▲ state                 status   health         cpu        memory/limit         id     image      ↓ rx      ↑ tx     ↓ rx/s     ↑ tx/s    ( h ) show help  ", 15..=28, (Header::State, SortedOrder::Asc));
+        test("           name   ▼ state                 status   health         cpu        memory/limit         id     image      ↓ rx      ↑ tx     ↓ rx/s     ↑ tx/s    ( h ) show help  ", 15..=28, (Header::State, SortedOrder::Desc));
 
         // status
-        test("           name       state             ▲ status       cpu        memory/limit         id     image      ↓ rx      ↑ tx    ( h ) show help  ", 27..=47, (Header::Status, SortedOrder::Asc));
-        test("           name       state             ▼ status       cpu        memory/limit         id     image      ↓ rx      ↑ tx    ( h ) show help  ", 27..=47, (Header::Status, SortedOrder::Desc));
+        test("           name   state                 ▲ status   health         cpu        memory/limit         id     image      ↓ rx      ↑ tx     ↓ rx/s     ↑ tx/s    ( h ) show help  ", 29..=47, (Header::Status, SortedOrder::Asc));
+        test("           name   state                 ▼ status   health         cpu        memory/limit         id     image      ↓ rx      ↑ tx     ↓ rx/s     ↑ tx/s    ( h ) show help  ", 29..=47, (Header::Status, SortedOrder::Desc));
+
+        // health
+        test("           name   state                   status   ▲ health       cpu        memory/limit         id     image      ↓ rx      ↑ tx     ↓ rx/s     ↑ tx/s    ( h ) show help  ", 48..=58, (Header::Health, SortedOrder::Asc));
+        test("           name   state                   status   ▼ health       cpu        memory/limit         id     image      ↓ rx      ↑ tx     ↓ rx/s     ↑ tx/s    ( h ) show help  ", 48..=58, (Header::Health, SortedOrder::Desc));
 
         // cpu
-        test("           name       state               status     ▲ cpu        memory/limit         id     image      ↓ rx      ↑ tx    ( h ) show help  ", 48..=57, (Header::Cpu, SortedOrder::Asc));
-        test("           name       state               status     ▼ cpu        memory/limit         id     image      ↓ rx      ↑ tx    ( h ) show help  ", 48..=57, (Header::Cpu, SortedOrder::Desc));
+        test("           name   state                   status   health       ▲ cpu        memory/limit         id     image      ↓ rx      ↑ tx     ↓ rx/s     ↑ tx/s    ( h ) show help  ", 59..=68, (Header::Cpu, SortedOrder::Asc));
+        test("           name   state                   status   health       ▼ cpu        memory/limit         id     image      ↓ rx      ↑ tx     ↓ rx/s     ↑ tx/s    ( h ) show help  ", 59..=68, (Header::Cpu, SortedOrder::Desc));
 
         // mem
-        test("           name       state               status       cpu      ▲ memory/limit         id     image      ↓ rx      ↑ tx    ( h ) show help  ", 58..=77, (Header::Memory, SortedOrder::Asc));
-        test("           name       state               status       cpu      ▼ memory/limit         id     image      ↓ rx      ↑ tx    ( h ) show help  ", 58..=77, (Header::Memory, SortedOrder::Desc));
+        test("           name   state                   status   health         cpu      ▲ memory/limit         id     image      ↓ rx      ↑ tx     ↓ rx/s     ↑ tx/s    ( h ) show help  ", 69..=88, (Header::Memory, SortedOrder::Asc));
+        test("           name   state                   status   health         cpu      ▼ memory/limit         id     image      ↓ rx      ↑ tx     ↓ rx/s     ↑ tx/s    ( h ) show help  ", 69..=88, (Header::Memory, SortedOrder::Desc));
 
         // id
-        test("           name       state               status       cpu        memory/limit       ▲ id     image      ↓ rx      ↑ tx    ( h ) show help  ", 78..=88, (Header::Id, SortedOrder::Asc));
-        test("           name       state               status       cpu        memory/limit       ▼ id     image      ↓ rx      ↑ tx    ( h ) show help  ", 78..=88, (Header::Id, SortedOrder::Desc));
+        test("           name   state                   status   health         cpu        memory/limit       ▲ id     image      ↓ rx      ↑ tx     ↓ rx/s     ↑ tx/s    ( h ) show help  ", 89..=99, (Header::Id, SortedOrder::Asc));
+        test("           name   state                   status   health         cpu        memory/limit       ▼ id     image      ↓ rx      ↑ tx     ↓ rx/s     ↑ tx/s    ( h ) show help  ", 89..=99, (Header::Id, SortedOrder::Desc));
 
         // image
-        test("           name       state               status       cpu        memory/limit         id   ▲ image      ↓ rx      ↑ tx    ( h ) show help  ", 89..=98, (Header::Image, SortedOrder::Asc));
-        test("           name       state               status       cpu        memory/limit         id   ▼ image      ↓ rx      ↑ tx    ( h ) show help  ", 89..=98, (Header::Image, SortedOrder::Desc));
+        test("           name   state                   status   health         cpu        memory/limit         id   ▲ image      ↓ rx      ↑ tx     ↓ rx/s     ↑ tx/s    ( h ) show help  ", 100..=109, (Header::Image, SortedOrder::Asc));
+        test("           name   state                   status   health         cpu        memory/limit         id   ▼ image      ↓ rx      ↑ tx     ↓ rx/s     ↑ tx/s    ( h ) show help  ", 100..=109, (Header::Image, SortedOrder::Desc));
 
         // rx
-        test("           name       state               status       cpu        memory/limit         id     image    ▲ ↓ rx      ↑ tx    ( h ) show help  ", 99..=108, (Header::Rx, SortedOrder::Asc));
-        test("           name       state               status       cpu        memory/limit         id     image    ▼ ↓ rx      ↑ tx    ( h ) show help  ", 99..=108, (Header::Rx, SortedOrder::Desc));
+        test("           name   state                   status   health         cpu        memory/limit         id     image    ▲ ↓ rx      ↑ tx     ↓ rx/s     ↑ tx/s    ( h ) show help  ", 110..=119, (Header::Rx, SortedOrder::Asc));
+        test("           name   state                   status   health         cpu        memory/limit         id     image    ▼ ↓ rx      ↑ tx     ↓ rx/s     ↑ tx/s    ( h ) show help  ", 110..=119, (Header::Rx, SortedOrder::Desc));
 
         // tx
-        test("           name       state               status       cpu        memory/limit         id     image      ↓ rx    ▲ ↑ tx    ( h ) show help  ", 109..=118, (Header::Tx, SortedOrder::Asc));
-        test("           name       state               status       cpu        memory/limit         id     image      ↓ rx    ▼ ↑ tx    ( h ) show help  ", 109..=118, (Header::Tx, SortedOrder::Desc));
+        test("           name   state                   status   health         cpu        memory/limit         id     image      ↓ rx    ▲ ↑ tx     ↓ rx/s     ↑ tx/s    ( h ) show help  ", 120..=129, (Header::Tx, SortedOrder::Asc));
+        test("           name   state                   status   health         cpu        memory/limit         id     image      ↓ rx    ▼ ↑ tx     ↓ rx/s     ↑ tx/s    ( h ) show help  ", 120..=129, (Header::Tx, SortedOrder::Desc));
+
+        // rx rate
+        test("           name   state                   status   health         cpu        memory/limit         id     image      ↓ rx      ↑ tx   ▲ ↓ rx/s     ↑ tx/s    ( h ) show help  ", 130..=140, (Header::RxRate, SortedOrder::Asc));
+        test("           name   state                   status   health         cpu        memory/limit         id     image      ↓ rx      ↑ tx   ▼ ↓ rx/s     ↑ tx/s    ( h ) show help  ", 130..=140, (Header::RxRate, SortedOrder::Desc));
+
+        // tx rate
+        test("           name   state                   status   health         cpu        memory/limit         id     image      ↓ rx      ↑ tx     ↓ rx/s   ▲ ↑ tx/s    ( h ) show help  ", 141..=151, (Header::TxRate, SortedOrder::Asc));
+        test("           name   state                   status   health         cpu        memory/limit         id     image      ↓ rx      ↑ tx     ↓ rx/s   ▼ ↑ tx/s    ( h ) show help  ", 141..=151, (Header::TxRate, SortedOrder::Desc));
     }
 
     #[test]
     /// Show animation
     fn test_draw_blocks_headers_animation() {
-        let (w, h) = (140, 1);
+        let (w, h) = (173, 1);
         let mut setup = test_setup(w, h, true, true);
         let uuid = Uuid::new_v4();
         setup.gui_state.lock().next_loading(uuid);
@@ -2385,7 +3593,7 @@ mod tests {
             })
             .unwrap();
 
-        let expected =   " ⠙         name       state               status       cpu        memory/limit         id     image      ↓ rx      ↑ tx    ( h ) show help  ";
+        let expected =   " ⠙         name   state                   status   health         cpu        memory/limit         id     image      ↓ rx      ↑ tx     ↓ rx/s     ↑ tx/s    ( h ) show help  ";
 
         let result = &setup.terminal.backend().buffer().content;
         for (index, expected_char) in expected.chars().enumerate() {
@@ -2396,7 +3604,7 @@ mod tests {
             assert_eq!(
                 result_cell.fg,
                 match index {
-                    (2..=122) => Color::Black,
+                    (2..=155) => Color::Black,
                     _ => Color::White,
                 }
             );
@@ -2410,13 +3618,13 @@ mod tests {
     /// This will cause issues once the version has more than the current 5 chars (0.5.0)
     // Help  popup is drawn correctly
     fn test_draw_blocks_help() {
-        let (w, h) = (87, 30);
+        let (w, h) = (87, 58);
         let mut setup = test_setup(w, h, true, true);
 
         setup
             .terminal
             .draw(|f| {
-                super::help_box(f);
+                super::help_box(f, &Keymap::default(), &Theme::default());
             })
             .unwrap();
 
@@ -2436,14 +3644,42 @@ mod tests {
                 " │                 A simple tui to view & control docker containers                  │ ".to_owned(),
                 " │                                                                                   │ ".to_owned(),
                 " │ ( tab ) or ( shift+tab ) change panels                                            │ ".to_owned(),
-                " │ ( ↑ ↓ ) or ( j k ) or ( PgUp PgDown ) or ( Home End ) change selected line        │ ".to_owned(),
+                " │ ( ↑ ↓ ) or ( k j ) or ( PgUp PgDown ) or ( Home End ) change selected line        │ ".to_owned(),
                 " │ ( enter ) send docker container command                                           │ ".to_owned(),
                 " │ ( e ) exec into a container                                                       │ ".to_owned(),
                 " │ ( h ) toggle this help information                                                │ ".to_owned(),
-                " │ ( s ) save logs to file                                                           │ ".to_owned(),
+                " │ ( i ) inspect container - esc to close                                            │ ".to_owned(),
+                " │ ( enter ) or ( space ) inspect & changes - toggle the selected line               │ ".to_owned(),
+                " │ ( → ← ) or ( l ) or ( a ) inspect & changes - expand, collapse, or expand all     │ ".to_owned(),
+                " │ ( l ) update memory, cpu, and restart limits of the selected container            │ ".to_owned(),
+                " │ ( r ) or ( y ) run a new container, or a copy of the selected container           │ ".to_owned(),
+                " │ ( a ) recreate the selected container with modified settings                      │ ".to_owned(),
+                " │ ( n ) rename the selected container                                               │ ".to_owned(),
+                " │ ( o ) or ( x ) commit container to an image, or export filesystem to file         │ ".to_owned(),
+                " │ ( g ) show filesystem changes of the selected container, relative to its image    │ ".to_owned(),
+                " │ ( / ) or ( f ) or ( backspace ) changes - filter path, filter kind, or clear      │ ".to_owned(),
+                " │ ( t ) show processes of the selected container                                    │ ".to_owned(),
+                " │ ( x ) processes - send a signal to the selected process                           │ ".to_owned(),
+                " │ ( w ) host overview - total container usage against host capacity, and disk usage │ ".to_owned(),
+                " │ ( r ) overview - refresh                                                          │ ".to_owned(),
+                " │ ( z ) choose, and order, the container columns, saved to the config file          │ ".to_owned(),
+                " │ ( space ) or ( [ ] ) or ( enter ) columns - show/hide, move, or save              │ ".to_owned(),
+                " │ ( + ) or ( = ) or ( - ) grow or shrink the selected panel - or drag a border      │ ".to_owned(),
+                " │ ( . ) maximise the selected panel, or restore the layout                          │ ".to_owned(),
+                " │ ( [ ) or ( ] ) show or hide the commands, or the charts                           │ ".to_owned(),
+                " │ ( , ) next preset layout - default, logs, charts, compact                         │ ".to_owned(),
+                " │ ( b ) browse the files of a container                                             │ ".to_owned(),
+                " │ ( enter ) or ( d ) or ( u ) files - open, download, or upload a file              │ ".to_owned(),
+                " │ ( s ) save logs - time range, filter, plain, jsonl, or csv, and gzip              │ ".to_owned(),
+                " │ ( ctrl+s ) export stats history of the selected, or all, containers - csv or json │ ".to_owned(),
+                " │ ( v ) cycle lower panel - logs, images, volumes, networks                         │ ".to_owned(),
+                " │ ( c ) or ( d ) or ( p ) create, delete, or prune - in the lower panels            │ ".to_owned(),
+                " │ ( y ) or ( n ) confirm, or cancel, a delete                                       │ ".to_owned(),
+                " │ ( u ) pull image - images panel                                                   │ ".to_owned(),
+                " │ ( f ) show containers on the selected network - networks panel                    │ ".to_owned(),
                 " │ ( m ) toggle mouse capture - if disabled, text on screen can be selected & copied │ ".to_owned(),
                 " │ ( 0 ) stop sort                                                                   │ ".to_owned(),
-                " │ ( 1 - 9 ) sort by header - or click header                                        │ ".to_owned(),
+                " │ ( 1 2 3 4 5 6 7 8 9 ) sort by header - or click header                            │ ".to_owned(),
                 " │ ( q ) quit at any time                                                            │ ".to_owned(),
                 " │                                                                                   │ ".to_owned(),
                 " │        currently an early work in progress, all and any input appreciated         │ ".to_owned(),
@@ -2528,7 +3764,13 @@ mod tests {
         setup
             .terminal
             .draw(|f| {
-                super::delete_confirm(f, &setup.gui_state, &ContainerName::from("container_1"));
+                super::delete_confirm(
+                    f,
+                    &setup.gui_state,
+                    "delete container",
+                    "container_1",
+                    &Theme::default(),
+                );
             })
             .unwrap();
 
@@ -2593,7 +3835,13 @@ mod tests {
         setup
             .terminal
             .draw(|f| {
-                super::delete_confirm(f, &setup.gui_state, &name);
+                super::delete_confirm(
+                    f,
+                    &setup.gui_state,
+                    "delete container",
+                    name.get(),
+                    &Theme::default(),
+                );
             })
             .unwrap();
 
@@ -2658,7 +3906,13 @@ mod tests {
         setup
             .terminal
             .draw(|f| {
-                super::info(f, "test", std::time::Instant::now(), &setup.gui_state);
+                super::info(
+                    f,
+                    "test",
+                    std::time::Instant::now(),
+                    &setup.gui_state,
+                    &Theme::default(),
+                );
             })
             .unwrap();
 
@@ -2695,7 +3949,7 @@ mod tests {
         setup
             .terminal
             .draw(|f| {
-                super::error(f, AppError::DockerConnect, Some(4));
+                super::error(f, AppError::DockerConnect, Some(4), &Theme::default());
             })
             .unwrap();
 
@@ -2746,7 +4000,7 @@ mod tests {
         setup
             .terminal
             .draw(|f| {
-                super::error(f, AppError::DockerExec, Some(4));
+                super::error(f, AppError::DockerExec, Some(4), &Theme::default());
             })
             .unwrap();
 
@@ -3048,11 +4302,11 @@ mod tests {
             });
 
         let expected = [
-            "           name       state               status       cpu          memory/limit         id     image      ↓ rx      ↑ tx                      ( h ) show help  ",
+        "           name   state                   status   health         cpu          memory/limit         id     image      ↓ rx      ↑ tx           ( h ) show help  ",
         "╭ Containers 1/3 ──────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────╮╭──────────────╮",
-        "│⚪  container_1   ✓ running            Up 1 hour    03.00%   30.00 kB / 30.00 kB          1   image_1   0.00 kB   0.00 kB                      ││▶ pause       │",
-        "│   container_2   ✓ running            Up 2 hour    00.00%    0.00 kB /  0.00 kB          2   image_2   0.00 kB   0.00 kB                      ││  restart     │",
-        "│   container_3   ✓ running            Up 3 hour    00.00%    0.00 kB /  0.00 kB          3   image_3   0.00 kB   0.00 kB                      ││  stop        │",
+        "│⚪  container_1   ✓ running            Up 1 hour               03.00%   30.00 kB / 30.00 kB          1   image_1   0.00 kB   0.00 kB    0.00 kB││▶ pause       │",
+        "│   container_2   ✓ running            Up 2 hour               00.00%    0.00 kB /  0.00 kB          2   image_2   0.00 kB   0.00 kB    0.00 kB││  restart     │",
+        "│   container_3   ✓ running            Up 3 hour               00.00%    0.00 kB /  0.00 kB          3   image_3   0.00 kB   0.00 kB    0.00 kB││  stop        │",
         "│                                                                                                                                              ││  delete      │",
         "│                                                                                                                                              ││              │",
         "│                                                                                                                                              ││              │",
@@ -3072,12 +4326,12 @@ mod tests {
         "│                                                                                                                                                              │",
         "│                                                                                                                                                              │",
         "╰──────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────╯",
-        "╭───────────────────────── cpu 03.00% ──────────────────────────╮╭─────────────────────── memory 30.00 kB ───────────────────────╮╭────────── ports ───────────╮",
-        "│10.00%│     ••••                                               ││100.00 kB│     •••                                             ││       ip   private   public│",
-        "│      │  •••   •                                               ││         │  •••  •                                             ││               8001         │",
-        "│      │••       •••                                            ││         │••      •••                                          ││127.0.0.1      8003     8003│",
-        "│      │                                                        ││         │                                                     ││                            │",
-        "╰───────────────────────────────────────────────────────────────╯╰───────────────────────────────────────────────────────────────╯╰────────────────────────────╯",
+        "╭───────── cpu 03.00% ──────────╮╭────── memory 30.00 kB ───────╮╭─ net ↓ 0.00 kB/s ↑ 0.00 kB/s ─╮╭ disk r 0.00 kB/s w 0.00 kB/s ╮╭────────── ports ───────────╮",
+        "│10.00%│  ••                    ││100.00 kB│  ••                ││0.00 kB│                       ││0.00 kB│                      ││       ip   private   public│",
+        "│      │ • •                    ││         │ • •                ││       │                       ││       │                      ││               8001         │",
+        "│      │••  •                   ││         │•  ••               ││       │                       ││       │                      ││127.0.0.1      8003     8003│",
+        "│      │                        ││         │                    ││       │•••••                  ││       │•••••                 ││                            │",
+        "╰ 00:00:00 ─────────── 00:01:00 ╯╰ 00:00:00 ────────── 00:01:00 ╯╰ 00:00:00 ─────────── 00:01:00 ╯╰ 00:00:00 ────────── 00:01:00 ╯╰────────────────────────────╯",
         ];
         setup
             .terminal
@@ -3100,7 +4354,7 @@ mod tests {
     #[test]
     /// Check that the whole layout is drawn correctly when have long container name and long image name
     fn test_draw_blocks_whole_layout_long_name() {
-        let (w, h) = (190, 30);
+        let (w, h) = (200, 30);
         let mut setup = test_setup(w, h, true, true);
 
         insert_chart_data(&setup);
@@ -3119,36 +4373,36 @@ mod tests {
             ContainerImage::from("a_long_image_name_for_the_purposes_of_this_test");
 
         let expected = [
-        "                              name       state               status       cpu          memory/limit         id                            image      ↓ rx      ↑ tx          ( h ) show help  ",
-        "╭ Containers 1/3 ─────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────╮╭─────────────────╮",
-        "│⚪  a_long_container_name_for_the…   ✓ running            Up 1 hour    03.00%   30.00 kB / 30.00 kB          1   a_long_image_name_for_the_pur…   0.00 kB   0.00 kB       ││▶ pause          │",
-        "│                      container_2   ✓ running            Up 2 hour    00.00%    0.00 kB /  0.00 kB          2                          image_2   0.00 kB   0.00 kB       ││  restart        │",
-        "│                      container_3   ✓ running            Up 3 hour    00.00%    0.00 kB /  0.00 kB          3                          image_3   0.00 kB   0.00 kB       ││  stop           │",
-        "│                                                                                                                                                                         ││  delete         │",
-        "│                                                                                                                                                                         ││                 │",
-        "│                                                                                                                                                                         ││                 │",
-        "╰─────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────╯╰─────────────────╯",
-        "╭ Logs 3/3 - a_long_container_name_for_the_purposes_of_this_test ────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────╮",
-        "│  line 1                                                                                                                                                                                    │",
-        "│  line 2                                                                                                                                                                                    │",
-        "│▶ line 3                                                                                                                                                                                    │",
-        "│                                                                                                                                                                                            │",
-        "│                                                                                                                                                                                            │",
-        "│                                                                                                                                                                                            │",
-        "│                                                                                                                                                                                            │",
-        "│                                                                                                                                                                                            │",
-        "│                                                                                                                                                                                            │",
-        "│                                                                                                                                                                                            │",
-        "│                                                                                                                                                                                            │",
-        "│                                                                                                                                                                                            │",
-        "│                                                                                                                                                                                            │",
-        "╰────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────╯",
-        "╭───────────────────────────────── cpu 03.00% ─────────────────────────────────╮╭────────────────────────────── memory 30.00 kB ───────────────────────────────╮╭────────── ports ───────────╮",
-        "│10.00%│       ••••                                                            ││100.00 kB│      •••••                                                         ││       ip   private   public│",
-        "│      │   ••••   •                                                            ││         │   •••    •                                                         ││               8001         │",
-        "│      │•••        ••••                                                        ││         │•••        •••                                                      ││127.0.0.1      8003     8003│",
-        "│      │                                                                       ││         │                                                                    ││                            │",
-        "╰──────────────────────────────────────────────────────────────────────────────╯╰──────────────────────────────────────────────────────────────────────────────╯╰────────────────────────────╯",
+        "                              name   state                   status   health         cpu          memory/limit         id                            image      ↓ rx      ↑ tx         ( h ) show help  ",
+        "╭ Containers 1/3 ──────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────╮╭──────────────────╮",
+        "│⚪  a_long_container_name_for_the…   ✓ running            Up 1 hour               03.00%   30.00 kB / 30.00 kB          1   a_long_image_name_for_the_pur…   0.00 kB   0.00 kB    0││▶ pause           │",
+        "│                      container_2   ✓ running            Up 2 hour               00.00%    0.00 kB /  0.00 kB          2                          image_2   0.00 kB   0.00 kB    0││  restart         │",
+        "│                      container_3   ✓ running            Up 3 hour               00.00%    0.00 kB /  0.00 kB          3                          image_3   0.00 kB   0.00 kB    0││  stop            │",
+        "│                                                                                                                                                                                  ││  delete          │",
+        "│                                                                                                                                                                                  ││                  │",
+        "│                                                                                                                                                                                  ││                  │",
+        "╰──────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────╯╰──────────────────╯",
+        "╭ Logs 3/3 - a_long_container_name_for_the_purposes_of_this_test ──────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────╮",
+        "│  line 1                                                                                                                                                                                              │",
+        "│  line 2                                                                                                                                                                                              │",
+        "│▶ line 3                                                                                                                                                                                              │",
+        "│                                                                                                                                                                                                      │",
+        "│                                                                                                                                                                                                      │",
+        "│                                                                                                                                                                                                      │",
+        "│                                                                                                                                                                                                      │",
+        "│                                                                                                                                                                                                      │",
+        "│                                                                                                                                                                                                      │",
+        "│                                                                                                                                                                                                      │",
+        "│                                                                                                                                                                                                      │",
+        "│                                                                                                                                                                                                      │",
+        "│                                                                                                                                                                                                      │",
+        "╰──────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────────╯",
+        "╭────────────── cpu 03.00% ───────────────╮╭─────────── memory 30.00 kB ────────────╮╭────── net ↓ 0.00 kB/s ↑ 0.00 kB/s ──────╮╭───── disk r 0.00 kB/s w 0.00 kB/s ─────╮╭────────── ports ───────────╮",
+        "│10.00%│   •••                            ││100.00 kB│   ••                         ││0.00 kB│                                 ││0.00 kB│                                ││       ip   private   public│",
+        "│      │  •  •                            ││         │ •• •                         ││       │                                 ││       │                                ││               8001         │",
+        "│      │••    ••                          ││         │•    ••                       ││       │                                 ││       │                                ││127.0.0.1      8003     8003│",
+        "│      │                                  ││         │                              ││       │ ••••••                          ││       │ ••••••                         ││                            │",
+        "╰ 00:00:00 ───────────────────── 00:01:00 ╯╰ 00:00:00 ──────────────────── 00:01:00 ╯╰ 00:00:00 ───────────────────── 00:01:00 ╯╰ 00:00:00 ──────────────────── 00:01:00 ╯╰────────────────────────────╯",
         ];
         setup
             .terminal
@@ -0,0 +1,566 @@
+use crate::app_data::{
+    ContainerId, CurrentLimits, LimitsConfig, LogExportConfig, RunConfig, StatsExportConfig,
+};
+
+/// What the form is used for, so that the correct action can be taken when it's submitted
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FormKind {
+    Commit(ContainerId),
+    ImagePull,
+    Limits(ContainerId, CurrentLimits),
+    LogExport(ContainerId),
+    NetworkCreate,
+    Recreate(ContainerId),
+    Rename(ContainerId),
+    Run,
+    Signal(ContainerId),
+    StatsExport,
+    Upload(ContainerId, String),
+    VolumeCreate,
+}
+
+impl FormKind {
+    pub const fn title(&self) -> &'static str {
+        match self {
+            Self::Commit(_) => "Commit Container",
+            Self::ImagePull => "Pull Image",
+            Self::Limits(..) => "Update Limits",
+            Self::LogExport(_) => "Save Logs",
+            Self::NetworkCreate => "Create Network",
+            Self::Recreate(_) => "Recreate Container",
+            Self::Rename(_) => "Rename Container",
+            Self::Run => "Run Container",
+            Self::Signal(_) => "Signal Process",
+            Self::StatsExport => "Export Stats",
+            Self::Upload(..) => "Upload File",
+            Self::VolumeCreate => "Create Volume",
+        }
+    }
+}
+
+/// A single line text input, with a label
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormField {
+    pub hint: &'static str,
+    pub label: &'static str,
+    pub value: String,
+    cursor: usize,
+}
+
+impl FormField {
+    pub fn new(label: &'static str, value: impl Into<String>) -> Self {
+        let value = value.into();
+        Self {
+            hint: "",
+            label,
+            cursor: value.chars().count(),
+            value,
+        }
+    }
+
+    /// Text to display, in place of the value, when the value is empty
+    pub const fn hint(mut self, hint: &'static str) -> Self {
+        self.hint = hint;
+        self
+    }
+
+    /// Byte index of the cursor, as the cursor itself is measured in chars
+    fn byte_index(&self) -> usize {
+        self.value
+            .char_indices()
+            .nth(self.cursor)
+            .map_or(self.value.len(), |(i, _)| i)
+    }
+
+    /// Cursor position, in chars, from the start of the value
+    pub const fn cursor(&self) -> usize {
+        self.cursor
+    }
+}
+
+/// A collection of text inputs, drawn as a popup, only one field is selected at a time
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Form {
+    pub error: Option<String>,
+    pub kind: FormKind,
+    pub fields: Vec<FormField>,
+    pub selected: usize,
+}
+
+impl Form {
+    pub const fn new(kind: FormKind, fields: Vec<FormField>) -> Self {
+        Self {
+            error: None,
+            kind,
+            fields,
+            selected: 0,
+        }
+    }
+
+    /// The run container form, pre-filled with the given settings
+    pub fn run(config: &RunConfig) -> Self {
+        Self::new(
+            FormKind::Run,
+            vec![
+                FormField::new("image", &config.image).hint("e.g. nicolaka/netshoot"),
+                FormField::new("name", &config.name).hint("optional"),
+                FormField::new("ports", &config.ports).hint("e.g. 8080:80, 53:53/udp"),
+                FormField::new("env", &config.env).hint(r"e.g. KEY=value, OTHER=a\,b"),
+                FormField::new("volumes", &config.volumes)
+                    .hint("e.g. volume_name:/data, /host/path:/data:ro"),
+                FormField::new("restart", &config.restart)
+                    .hint("no, always, unless-stopped, or on-failure[:max-retries]"),
+                FormField::new("command", &config.command).hint("optional, e.g. sleep infinity"),
+                FormField::new("memory", &config.memory).hint("optional, e.g. 512m"),
+                FormField::new("cpus", &config.cpus).hint("optional, e.g. 1.5"),
+            ],
+        )
+    }
+
+    /// The recreate container form, pre-filled with the settings of the given container
+    /// There's no name field, as the recreated container keeps its current name
+    pub fn recreate(id: ContainerId, config: &RunConfig) -> Self {
+        let mut form = Self::run(config);
+        form.kind = FormKind::Recreate(id);
+        form.fields.retain(|i| i.label != "name");
+        form
+    }
+
+    /// The settings entered into the run container form
+    pub fn run_config(&self) -> RunConfig {
+        RunConfig {
+            command: self.value("command"),
+            cpus: self.value("cpus"),
+            env: self.value("env"),
+            image: self.value("image"),
+            memory: self.value("memory"),
+            name: self.value("name"),
+            ports: self.value("ports"),
+            restart: self.value("restart"),
+            volumes: self.value("volumes"),
+        }
+    }
+
+    /// The commit container form, to save a container as a new image
+    pub fn commit(id: ContainerId) -> Self {
+        Self::new(
+            FormKind::Commit(id),
+            vec![
+                FormField::new("image", "").hint("e.g. debug/redis:broken"),
+                FormField::new("message", "").hint("optional"),
+            ],
+        )
+    }
+
+    /// The rename container form, pre-filled with the current name
+    pub fn rename(id: ContainerId, name: &str) -> Self {
+        Self::new(
+            FormKind::Rename(id),
+            vec![FormField::new("name", name).hint("letters, numbers, _ . or -")],
+        )
+    }
+
+    /// The signal process form, pre-filled with the pid, as seen inside the container, and TERM
+    /// The pid is empty when it can't be found, so the user has to enter it
+    pub fn signal(id: ContainerId, pid: &str) -> Self {
+        Self::new(
+            FormKind::Signal(id),
+            vec![
+                FormField::new("pid", pid).hint("pid inside the container"),
+                FormField::new("signal", "TERM").hint("e.g. TERM, KILL, HUP, or 9"),
+            ],
+        )
+    }
+
+    /// The upload file form, to upload a local file into the given directory of a container
+    pub fn upload(id: ContainerId, dir: &str) -> Self {
+        Self::new(
+            FormKind::Upload(id, dir.to_owned()),
+            vec![FormField::new("file", "").hint("local path, e.g. /home/user/config.toml")],
+        )
+    }
+
+    /// The limits form, pre-filled with the current limits of the given container
+    pub fn limits(id: ContainerId, config: &LimitsConfig) -> Self {
+        Self::new(
+            FormKind::Limits(id, config.current),
+            vec![
+                FormField::new("memory", &config.memory).hint("e.g. 512m, empty is unchanged"),
+                FormField::new("cpus", &config.cpus).hint("e.g. 1.5, empty is unchanged"),
+                FormField::new("cpu shares", &config.cpu_shares)
+                    .hint("e.g. 512, empty is unchanged"),
+                FormField::new("restart", &config.restart)
+                    .hint("no, always, unless-stopped, or on-failure[:max-retries]"),
+            ],
+        )
+    }
+
+    /// The limits entered into the limits form
+    pub fn limits_config(&self) -> LimitsConfig {
+        LimitsConfig {
+            cpu_shares: self.value("cpu shares"),
+            cpus: self.value("cpus"),
+            current: match self.kind {
+                FormKind::Limits(_, current) => current,
+                _ => CurrentLimits::default(),
+            },
+            memory: self.value("memory"),
+            restart: self.value("restart"),
+        }
+    }
+
+    /// The log export form, the file name is pre-filled with the name of the container, and a timestamp, without an extension
+    pub fn log_export(id: ContainerId, file: &str) -> Self {
+        Self::new(
+            FormKind::LogExport(id),
+            vec![
+                FormField::new("file", file)
+                    .hint("file name, not a path, the extension is added from the format"),
+                FormField::new("format", "plain").hint("plain, jsonl, or csv"),
+                FormField::new("since", "").hint("optional, e.g. 2h ago, empty for all"),
+                FormField::new("until", "").hint("optional, e.g. 15m ago, empty for now"),
+                FormField::new("filter", "").hint("optional, only lines containing this text"),
+                FormField::new("gzip", "no").hint("yes or no"),
+            ],
+        )
+    }
+
+    /// The settings entered into the log export form
+    pub fn log_export_config(&self) -> LogExportConfig {
+        LogExportConfig {
+            file: self.value("file"),
+            filter: self.value("filter"),
+            format: self.value("format"),
+            gzip: self.value("gzip"),
+            since: self.value("since"),
+            until: self.value("until"),
+        }
+    }
+
+    /// The stats export form, the file name is pre-filled with a timestamp, without an extension
+    pub fn stats_export(file: &str) -> Self {
+        Self::new(
+            FormKind::StatsExport,
+            vec![
+                FormField::new("containers", "selected").hint("selected or all"),
+                FormField::new("format", "csv").hint("csv or json"),
+                FormField::new("file", file)
+                    .hint("file name, not a path, the extension is added from the format"),
+            ],
+        )
+    }
+
+    /// The settings entered into the stats export form
+    pub fn stats_export_config(&self) -> StatsExportConfig {
+        StatsExportConfig {
+            containers: self.value("containers"),
+            file: self.value("file"),
+            format: self.value("format"),
+        }
+    }
+
+    fn field_mut(&mut self) -> Option<&mut FormField> {
+        self.fields.get_mut(self.selected)
+    }
+
+    /// Get the trimmed value of a field, by label
+    pub fn value(&self, label: &str) -> String {
+        self.fields
+            .iter()
+            .find(|i| i.label == label)
+            .map_or_else(String::new, |i| i.value.trim().to_owned())
+    }
+
+    /// Insert a char at the cursor position of the selected field
+    pub fn insert(&mut self, c: char) {
+        if let Some(field) = self.field_mut() {
+            let index = field.byte_index();
+            field.value.insert(index, c);
+            field.cursor += 1;
+        }
+    }
+
+    /// Remove the char before the cursor of the selected field
+    pub fn backspace(&mut self) {
+        if let Some(field) = self.field_mut() {
+            if field.cursor > 0 {
+                field.cursor -= 1;
+                let index = field.byte_index();
+                field.value.remove(index);
+            }
+        }
+    }
+
+    /// Remove the char at the cursor of the selected field
+    pub fn delete(&mut self) {
+        if let Some(field) = self.field_mut() {
+            if field.cursor < field.value.chars().count() {
+                let index = field.byte_index();
+                field.value.remove(index);
+            }
+        }
+    }
+
+    pub fn cursor_left(&mut self) {
+        if let Some(field) = self.field_mut() {
+            field.cursor = field.cursor.saturating_sub(1);
+        }
+    }
+
+    pub fn cursor_right(&mut self) {
+        if let Some(field) = self.field_mut() {
+            field.cursor = (field.cursor + 1).min(field.value.chars().count());
+        }
+    }
+
+    pub fn cursor_start(&mut self) {
+        if let Some(field) = self.field_mut() {
+            field.cursor = 0;
+        }
+    }
+
+    pub fn cursor_end(&mut self) {
+        if let Some(field) = self.field_mut() {
+            field.cursor = field.value.chars().count();
+        }
+    }
+
+    /// Select the next field, wrapping round to the first
+    pub const fn next_field(&mut self) {
+        if !self.fields.is_empty() {
+            self.selected = (self.selected + 1) % self.fields.len();
+        }
+    }
+
+    /// Select the previous field, wrapping round to the last
+    pub fn previous_field(&mut self) {
+        if !self.fields.is_empty() {
+            self.selected = self
+                .selected
+                .checked_sub(1)
+                .unwrap_or(self.fields.len() - 1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Form, FormField, FormKind};
+    use crate::app_data::{
+        ContainerId, CurrentLimits, LimitsConfig, LogExportConfig, RunConfig, StatsExportConfig,
+    };
+
+    fn gen_form() -> Form {
+        Form::new(
+            FormKind::ImagePull,
+            vec![FormField::new("image", "redis"), FormField::new("tag", "")],
+        )
+    }
+
+    #[test]
+    /// Chars inserted and removed at the cursor position, cursor starts at end of value
+    fn test_form_insert_remove() {
+        let mut form = gen_form();
+        form.insert(':');
+        form.insert('7');
+        assert_eq!(form.value("image"), "redis:7");
+
+        form.cursor_start();
+        form.insert('é');
+        assert_eq!(form.value("image"), "éredis:7");
+        form.backspace();
+        form.backspace();
+        assert_eq!(form.value("image"), "redis:7");
+        form.delete();
+        assert_eq!(form.value("image"), "edis:7");
+
+        form.cursor_end();
+        form.delete();
+        form.cursor_left();
+        form.backspace();
+        assert_eq!(form.value("image"), "edis7");
+        assert_eq!(form.fields[0].cursor(), 4);
+        form.cursor_right();
+        form.cursor_right();
+        assert_eq!(form.fields[0].cursor(), 5);
+    }
+
+    #[test]
+    /// Run form pre-filled from settings, and the entered settings read back
+    fn test_form_run() {
+        let config = RunConfig {
+            image: "redis".to_owned(),
+            ports: "6379:6379".to_owned(),
+            ..Default::default()
+        };
+        let mut form = Form::run(&config);
+        assert_eq!(form.kind, FormKind::Run);
+        assert_eq!(form.fields.len(), 9);
+        assert_eq!(form.run_config(), config);
+
+        form.next_field();
+        for c in "cache ".chars() {
+            form.insert(c);
+        }
+        assert_eq!(
+            form.run_config(),
+            RunConfig {
+                name: "cache".to_owned(),
+                ..config
+            }
+        );
+    }
+
+    #[test]
+    /// Commit form starts empty, with image and message fields
+    fn test_form_commit() {
+        let id = ContainerId::from("abc");
+        let form = Form::commit(id.clone());
+        assert_eq!(form.kind, FormKind::Commit(id));
+        assert_eq!(form.kind.title(), "Commit Container");
+        assert_eq!(
+            form.fields.iter().map(|i| i.label).collect::<Vec<_>>(),
+            ["image", "message"]
+        );
+        assert_eq!(form.value("image"), "");
+    }
+
+    #[test]
+    /// Signal form pre-filled with the pid, and TERM
+    fn test_form_signal() {
+        let id = ContainerId::from("abc");
+        let form = Form::signal(id.clone(), "42");
+        assert_eq!(form.kind, FormKind::Signal(id));
+        assert_eq!(form.value("pid"), "42");
+        assert_eq!(form.value("signal"), "TERM");
+    }
+
+    #[test]
+    /// Upload form keeps the target directory in the kind, with a single file field
+    fn test_form_upload() {
+        let id = ContainerId::from("abc");
+        let mut form = Form::upload(id.clone(), "/etc");
+        assert_eq!(form.kind, FormKind::Upload(id, "/etc".to_owned()));
+        assert_eq!(form.kind.title(), "Upload File");
+        for c in " /tmp/a.txt ".chars() {
+            form.insert(c);
+        }
+        assert_eq!(form.value("file"), "/tmp/a.txt");
+    }
+
+    #[test]
+    /// Recreate form has no name field, and the container name isn't part of the entered settings
+    fn test_form_recreate() {
+        let id = ContainerId::from("abc");
+        let config = RunConfig {
+            image: "redis".to_owned(),
+            memory: "1g".to_owned(),
+            name: "redis_copy".to_owned(),
+            ..Default::default()
+        };
+        let form = Form::recreate(id.clone(), &config);
+        assert_eq!(form.kind, FormKind::Recreate(id));
+        assert_eq!(form.fields.len(), 8);
+        assert!(form.fields.iter().all(|i| i.label != "name"));
+        assert_eq!(
+            form.run_config(),
+            RunConfig {
+                name: String::new(),
+                ..config
+            }
+        );
+    }
+
+    #[test]
+    /// Limits form pre-filled from the current limits, and the entered limits read back
+    fn test_form_limits() {
+        let id = ContainerId::from("abc");
+        let config = LimitsConfig {
+            cpus: "2".to_owned(),
+            current: CurrentLimits {
+                nano_cpus: 2_000_000_000,
+                uses_nano_cpus: true,
+                ..Default::default()
+            },
+            restart: "always".to_owned(),
+            ..Default::default()
+        };
+        let mut form = Form::limits(id.clone(), &config);
+        assert_eq!(form.kind, FormKind::Limits(id, config.current));
+        assert_eq!(form.limits_config(), config);
+
+        for c in "1g".chars() {
+            form.insert(c);
+        }
+        assert_eq!(
+            form.limits_config(),
+            LimitsConfig {
+                memory: "1g".to_owned(),
+                ..config
+            }
+        );
+    }
+
+    #[test]
+    /// Log export form pre-filled with the file name, and plain format, and the entered settings read back
+    fn test_form_log_export() {
+        let id = ContainerId::from("abc");
+        let mut form = Form::log_export(id.clone(), "redis_1700000000");
+        assert_eq!(form.kind, FormKind::LogExport(id));
+        assert_eq!(form.kind.title(), "Save Logs");
+        form.next_field();
+        form.backspace();
+        form.backspace();
+        form.backspace();
+        form.backspace();
+        form.backspace();
+        for c in "csv".chars() {
+            form.insert(c);
+        }
+        assert_eq!(
+            form.log_export_config(),
+            LogExportConfig {
+                file: "redis_1700000000".to_owned(),
+                format: "csv".to_owned(),
+                gzip: "no".to_owned(),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    /// Stats export form pre-filled with the selected container, csv format, and the file name, and the entered settings read back
+    fn test_form_stats_export() {
+        let mut form = Form::stats_export("stats_1700000000");
+        assert_eq!(form.kind, FormKind::StatsExport);
+        assert_eq!(form.kind.title(), "Export Stats");
+        for _ in 0.."selected".len() {
+            form.backspace();
+        }
+        for c in "all".chars() {
+            form.insert(c);
+        }
+        assert_eq!(
+            form.stats_export_config(),
+            StatsExportConfig {
+                containers: "all".to_owned(),
+                file: "stats_1700000000".to_owned(),
+                format: "csv".to_owned(),
+            }
+        );
+    }
+
+    #[test]
+    /// Field selection wraps in both directions, and inputs only effect the selected field
+    fn test_form_field_selection() {
+        let mut form = gen_form();
+        form.previous_field();
+        assert_eq!(form.selected, 1);
+        form.insert('7');
+        assert_eq!(form.value("tag"), "7");
+        assert_eq!(form.value("image"), "redis");
+        form.next_field();
+        assert_eq!(form.selected, 0);
+        assert_eq!(form.value("missing"), "");
+    }
+}
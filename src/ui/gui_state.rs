@@ -1,5 +1,6 @@
 use parking_lot::Mutex;
 use ratatui::layout::{Constraint, Rect};
+use serde::{Deserialize, Serialize};
 use std::{
     collections::{HashMap, HashSet},
     sync::Arc,
@@ -8,17 +9,26 @@ use std::{
 use tokio::task::JoinHandle;
 use uuid::Uuid;
 
+use super::{
+    form::Form,
+    panel_layout::{PanelLayout, Section},
+};
 use crate::{
     app_data::{ContainerId, Header},
     exec::ExecMode,
+    session::Session,
 };
 
-#[derive(Debug, Default, Clone, Copy, Eq, Hash, PartialEq)]
+#[derive(Debug, Default, Clone, Copy, Eq, Hash, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum SelectablePanel {
     #[default]
     Containers,
     Commands,
     Logs,
+    Images,
+    Volumes,
+    Networks,
 }
 
 impl SelectablePanel {
@@ -26,25 +36,62 @@ impl SelectablePanel {
         match self {
             Self::Containers => "Containers",
             Self::Logs => "Logs",
+            Self::Images => "Images",
+            Self::Volumes => "Volumes",
+            Self::Networks => "Networks",
             Self::Commands => "",
         }
     }
-    pub const fn next(self) -> Self {
+
+    /// Panels that share the lower section of the screen, only one of which is visible at a time
+    pub const fn is_lower(self) -> bool {
+        matches!(
+            self,
+            Self::Logs | Self::Images | Self::Volumes | Self::Networks
+        )
+    }
+
+    /// The next panel to display in the lower section of the screen
+    pub const fn next_lower(self) -> Self {
+        match self {
+            Self::Logs => Self::Images,
+            Self::Images => Self::Volumes,
+            Self::Volumes => Self::Networks,
+            _ => Self::Logs,
+        }
+    }
+
+    /// Next selectable panel, the lower panel is whichever is currently visible in the lower section
+    pub const fn next(self, lower: Self) -> Self {
         match self {
             Self::Containers => Self::Commands,
-            Self::Commands => Self::Logs,
-            Self::Logs => Self::Containers,
+            Self::Commands => lower,
+            Self::Logs | Self::Images | Self::Volumes | Self::Networks => Self::Containers,
         }
     }
-    pub const fn prev(self) -> Self {
+
+    /// Previous selectable panel, the lower panel is whichever is currently visible in the lower section
+    pub const fn prev(self, lower: Self) -> Self {
         match self {
-            Self::Containers => Self::Logs,
+            Self::Containers => lower,
             Self::Commands => Self::Containers,
-            Self::Logs => Self::Commands,
+            Self::Logs | Self::Images | Self::Volumes | Self::Networks => Self::Commands,
         }
     }
 }
 
+/// Items that can be removed, or pruned, after confirmation via the delete confirm dialog
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeleteTarget {
+    Container(ContainerId),
+    Image(String),
+    Network(String),
+    PruneImages,
+    PruneNetworks,
+    PruneVolumes,
+    Volume(String),
+}
+
 #[derive(Debug, Copy, Clone)]
 pub enum Region {
     Panel(SelectablePanel),
@@ -159,33 +206,151 @@ const FRAMES_LEN: u8 = 9;
 /// Various functions (e.g input handler), operate differently depending upon current Status
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
 pub enum Status {
+    Changes,
+    Columns,
     DeleteConfirm,
     DockerConnect,
     Error,
     Exec,
+    Files,
+    Form,
     Help,
     Init,
-    Logs,
+    Inspect,
+    Overview,
+    Processes,
 }
 
 /// Global gui_state, stored in an Arc<Mutex>
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Clone)]
 pub struct GuiState {
-    delete_container: Option<ContainerId>,
     delete_map: HashMap<DeleteButton, Rect>,
+    delete_target: Option<DeleteTarget>,
+    dragging: Option<Section>,
+    form: Option<Form>,
     heading_map: HashMap<Header, Rect>,
     is_loading: HashSet<Uuid>,
+    layout: PanelLayout,
     loading_index: u8,
+    lower_panel: SelectablePanel,
     panel_map: HashMap<SelectablePanel, Rect>,
     selected_panel: SelectablePanel,
+    split_map: HashMap<Section, (Rect, Rect)>,
     status: HashSet<Status>,
-    exec_mode: Option<ExecMode>,
+    exec_mode: Option<(ExecMode, bool)>,
     pub info_box_text: Option<(String, Instant)>,
 }
+
+impl Default for GuiState {
+    fn default() -> Self {
+        Self {
+            delete_map: HashMap::new(),
+            delete_target: None,
+            dragging: None,
+            form: None,
+            heading_map: HashMap::new(),
+            is_loading: HashSet::new(),
+            layout: PanelLayout::default(),
+            loading_index: 0,
+            lower_panel: SelectablePanel::Logs,
+            panel_map: HashMap::new(),
+            selected_panel: SelectablePanel::default(),
+            split_map: HashMap::new(),
+            status: HashSet::new(),
+            exec_mode: None,
+            info_box_text: None,
+        }
+    }
+}
+
 impl GuiState {
     /// Clear panels hash map, so on resize can fix the sizes for mouse clicks
     pub fn clear_area_map(&mut self) {
         self.panel_map.clear();
+        self.split_map.clear();
+    }
+
+    /// Get the current layout of the main view
+    pub const fn get_layout(&self) -> PanelLayout {
+        self.layout
+    }
+
+    /// Set the layout of the main view, if the commands panel is hidden whilst selected, then select the containers panel
+    /// The area maps are cleared when a section is shown, or hidden, as hidden panels shouldn't be clickable
+    pub fn set_layout(&mut self, layout: PanelLayout) {
+        if !layout.show_commands && self.selected_panel == SelectablePanel::Commands {
+            self.selected_panel = SelectablePanel::Containers;
+        }
+        if (layout.show_commands, layout.show_charts, layout.maximised)
+            != (
+                self.layout.show_commands,
+                self.layout.show_charts,
+                self.layout.maximised,
+            )
+        {
+            self.clear_area_map();
+        }
+        self.layout = layout;
+    }
+
+    /// Restore the selected, and lower, panels of the previous session, a panel that isn't visible isn't selected
+    pub fn restore_session(&mut self, session: &Session) {
+        if let Some(lower_panel) = session.lower_panel.filter(|i| i.is_lower()) {
+            self.lower_panel = lower_panel;
+        }
+        if let Some(selected_panel) = session.selected_panel {
+            let visible = match selected_panel {
+                SelectablePanel::Containers => true,
+                SelectablePanel::Commands => self.layout.show_commands,
+                _ => selected_panel == self.lower_panel,
+            };
+            if visible {
+                self.selected_panel = selected_panel;
+            }
+        }
+    }
+
+    /// Grow, or shrink, the selected panel, a lower panel is resized by changing the size of the charts
+    pub fn resize_selected(&mut self, grow: bool) {
+        let mut layout = self.layout;
+        match self.selected_panel {
+            SelectablePanel::Containers => layout.resize(Section::Containers, grow),
+            SelectablePanel::Commands => layout.resize(Section::Commands, grow),
+            _ => layout.resize(Section::Charts, !grow),
+        }
+        self.set_layout(layout);
+    }
+
+    /// Insert, or update, the border of a resizable section, and the area which it splits
+    pub fn update_split_map(&mut self, section: Section, border: Rect, area: Rect) {
+        self.split_map.insert(section, (border, area));
+    }
+
+    /// Start dragging a border, if a given Rect (a clicked area of 1x1) is on the border of a resizable section
+    pub fn drag_start(&mut self, rect: Rect) -> bool {
+        self.dragging = self
+            .split_map
+            .iter()
+            .find(|i| i.1 .0.intersects(rect))
+            .map(|i| *i.0);
+        self.dragging.is_some()
+    }
+
+    /// Move the border currently being dragged to a given column & row
+    pub fn drag_to(&mut self, column: u16, row: u16) {
+        if let Some((section, (_, area))) = self
+            .dragging
+            .and_then(|section| self.split_map.get(&section).map(|i| (section, *i)))
+        {
+            let mut layout = self.layout;
+            layout.drag(section, area, column, row);
+            self.set_layout(layout);
+        }
+    }
+
+    /// Stop dragging, returns true if a border was being dragged
+    pub const fn drag_end(&mut self) -> bool {
+        self.dragging.take().is_some()
     }
 
     /// Get the currently selected panel
@@ -193,11 +358,27 @@ impl GuiState {
         self.selected_panel
     }
 
+    /// Get the panel currently visible in the lower section of the screen
+    pub const fn get_lower_panel(&self) -> SelectablePanel {
+        self.lower_panel
+    }
+
+    /// Cycle the panel visible in the lower section, if the previous lower panel was selected, then select the new one
+    pub fn next_lower_panel(&mut self) {
+        let next = self.lower_panel.next_lower();
+        if self.selected_panel == self.lower_panel {
+            self.selected_panel = next;
+        }
+        self.lower_panel = next;
+    }
+
     /// Check if a given Rect (a clicked area of 1x1), interacts with any known panels
+    /// Hidden lower panels share an area with the visible lower panel, so are ignored
     pub fn panel_intersect(&mut self, rect: Rect) {
         if let Some(data) = self
             .panel_map
             .iter()
+            .filter(|i| !i.0.is_lower() || *i.0 == self.lower_panel)
             .filter(|i| i.1.intersects(rect))
             .collect::<Vec<_>>()
             .first()
@@ -247,21 +428,37 @@ impl GuiState {
         };
     }
 
-    /// Check if an ContainerId is set in the delete_container field
-    pub fn get_delete_container(&self) -> Option<ContainerId> {
-        self.delete_container.clone()
+    /// Check if a DeleteTarget is set in the delete_target field
+    pub fn get_delete_target(&self) -> Option<DeleteTarget> {
+        self.delete_target.clone()
     }
 
-    /// Set either a ContainerId, or None, to the delete_container field
+    /// Set either a DeleteTarget, or None, to the delete_target field
     /// If Some, will also insert the DeleteConfirm status into self.status
-    pub fn set_delete_container(&mut self, id: Option<ContainerId>) {
-        if id.is_some() {
+    pub fn set_delete_target(&mut self, target: Option<DeleteTarget>) {
+        if target.is_some() {
             self.status.insert(Status::DeleteConfirm);
         } else {
             self.delete_map.clear();
             self.status.remove(&Status::DeleteConfirm);
         }
-        self.delete_container = id;
+        self.delete_target = target;
+    }
+
+    /// Get a mutable reference to the currently open form
+    pub const fn get_form(&mut self) -> Option<&mut Form> {
+        self.form.as_mut()
+    }
+
+    /// Set either a Form, or None, to the form field
+    /// If Some, will also insert the Form status into self.status
+    pub fn set_form(&mut self, form: Option<Form>) {
+        if form.is_some() {
+            self.status.insert(Status::Form);
+        } else {
+            self.status.remove(&Status::Form);
+        }
+        self.form = form;
     }
 
     /// Check if the current gui_status contains any of the given status'
@@ -286,14 +483,15 @@ impl GuiState {
     }
 
     /// Inset the ExecMode into self, and set the Status as exec
+    /// The mouse capture is kept, so that it can be restored when the exec ends
     /// Using StatusPush with Status::Exec won't insert into the hash map
     /// To force self.exec_mode to be set
-    pub fn set_exec_mode(&mut self, mode: ExecMode) {
-        self.exec_mode = Some(mode);
+    pub fn set_exec_mode(&mut self, mode: ExecMode, mouse_capture: bool) {
+        self.exec_mode = Some((mode, mouse_capture));
         self.status.insert(Status::Exec);
     }
 
-    pub fn get_exec_mode(&mut self) -> Option<ExecMode> {
+    pub fn get_exec_mode(&mut self) -> Option<(ExecMode, bool)> {
         self.exec_mode.clone()
     }
 
@@ -308,14 +506,26 @@ impl GuiState {
         }
     }
 
-    /// Change to next selectable panel
+    /// Change to next selectable panel, skipping the commands panel if hidden
     pub fn next_panel(&mut self) {
-        self.selected_panel = self.selected_panel.next();
+        self.selected_panel = self.selected_panel.next(self.lower_panel);
+        if !self.layout.show_commands && self.selected_panel == SelectablePanel::Commands {
+            self.selected_panel = self.selected_panel.next(self.lower_panel);
+        }
+        if self.layout.maximised {
+            self.clear_area_map();
+        }
     }
 
-    /// Change to previous selectable panel
+    /// Change to previous selectable panel, skipping the commands panel if hidden
     pub fn previous_panel(&mut self) {
-        self.selected_panel = self.selected_panel.prev();
+        self.selected_panel = self.selected_panel.prev(self.lower_panel);
+        if !self.layout.show_commands && self.selected_panel == SelectablePanel::Commands {
+            self.selected_panel = self.selected_panel.prev(self.lower_panel);
+        }
+        if self.layout.maximised {
+            self.clear_area_map();
+        }
     }
 
     /// Insert a new loading_uuid into HashSet, and advance the loading_index by one frame, or reset to 0 if at end of array
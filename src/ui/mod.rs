@@ -7,7 +7,7 @@ use crossterm::{
 use parking_lot::{Mutex, MutexGuard};
 use ratatui::{
     backend::CrosstermBackend,
-    layout::{Constraint, Direction, Layout},
+    layout::{Constraint, Direction, Layout, Rect},
     Frame, Terminal,
 };
 use std::{
@@ -21,15 +21,23 @@ use tracing::error;
 
 mod color_match;
 mod draw_blocks;
+mod form;
 mod gui_state;
+mod panel_layout;
+mod theme;
 
 pub use self::color_match::*;
-pub use self::gui_state::{DeleteButton, GuiState, SelectablePanel, Status};
+pub use self::form::{Form, FormField, FormKind};
+pub use self::gui_state::{DeleteButton, DeleteTarget, GuiState, SelectablePanel, Status};
+pub use self::panel_layout::{
+    LayoutPreset, PanelLayout, Section, CHARTS_RANGE, COMMANDS_RANGE, CONTAINERS_RANGE,
+};
+pub use self::theme::Theme;
 use crate::{
-    app_data::{AppData, Columns, ContainerId, Header, SortedOrder},
+    app_data::{AppData, Columns, Header, SortedOrder},
     app_error::AppError,
     exec::TerminalSize,
-    input_handler::InputMessages,
+    input_handler::{Action, InputMessages},
 };
 
 pub struct Ui {
@@ -49,6 +57,7 @@ impl Ui {
         Ok(io::stdout().write_all(
             concat!(
                 crossterm::csi!("?1000h"),
+                crossterm::csi!("?1002h"),
                 crossterm::csi!("?1015h"),
                 crossterm::csi!("?1006h"),
             )
@@ -62,8 +71,9 @@ impl Ui {
         gui_state: Arc<Mutex<GuiState>>,
         input_tx: Sender<InputMessages>,
         is_running: Arc<AtomicBool>,
+        mouse_capture: bool,
     ) {
-        if let Ok(mut terminal) = Self::setup_terminal() {
+        if let Ok(mut terminal) = Self::setup_terminal(mouse_capture) {
             // let args = app_data.lock().args.clone();
             let cursor_position = terminal.get_cursor().unwrap_or_default();
             let mut ui = Self {
@@ -87,18 +97,20 @@ impl Ui {
         }
     }
 
-    /// Setup the terminal for full-screen drawing mode, with mouse capture
-    fn setup_terminal() -> Result<Terminal<CrosstermBackend<Stdout>>> {
-        let stdout = Self::init_terminal()?;
+    /// Setup the terminal for full-screen drawing mode, with mouse capture unless disabled in the previous session
+    fn setup_terminal(mouse_capture: bool) -> Result<Terminal<CrosstermBackend<Stdout>>> {
+        let stdout = Self::init_terminal(mouse_capture)?;
         let backend = CrosstermBackend::new(stdout);
         Ok(Terminal::new(backend)?)
     }
 
-    fn init_terminal() -> Result<Stdout> {
+    fn init_terminal(mouse_capture: bool) -> Result<Stdout> {
         enable_raw_mode()?;
         let mut stdout = io::stdout();
         execute!(stdout, EnterAlternateScreen)?;
-        Self::enable_mouse_capture()?;
+        if mouse_capture {
+            Self::enable_mouse_capture()?;
+        }
         Ok(stdout)
     }
 
@@ -121,6 +133,7 @@ impl Ui {
     /// Draw the the error message ui, for 5 seconds, with a countdown
     fn err_loop(&mut self) -> Result<(), AppError> {
         let mut seconds = 5;
+        let theme = self.app_data.lock().args.theme;
         loop {
             if self.now.elapsed() >= std::time::Duration::from_secs(1) {
                 seconds -= 1;
@@ -132,7 +145,7 @@ impl Ui {
 
             if self
                 .terminal
-                .draw(|f| draw_blocks::error(f, AppError::DockerConnect, Some(seconds)))
+                .draw(|f| draw_blocks::error(f, AppError::DockerConnect, Some(seconds), &theme))
                 .is_err()
             {
                 return Err(AppError::Terminal);
@@ -142,10 +155,14 @@ impl Ui {
     }
 
     /// Use exeternal docker cli to exec into a container
+    /// Once finished, the terminal is setup again, with the mouse capture as it was before the exec
     async fn exec(&mut self) {
         let exec_mode = self.gui_state.lock().get_exec_mode();
+        let mouse_capture = exec_mode
+            .as_ref()
+            .is_none_or(|(_, mouse_capture)| *mouse_capture);
 
-        if let Some(mode) = exec_mode {
+        if let Some((mode, _)) = exec_mode {
             self.reset_terminal().ok();
             self.terminal.clear().ok();
             if let Err(e) = mode.run(TerminalSize::new(&self.terminal)).await {
@@ -156,7 +173,7 @@ impl Ui {
         }
         self.terminal.clear().ok();
         self.reset_terminal().ok();
-        Self::init_terminal().ok();
+        Self::init_terminal(mouse_capture).ok();
         self.gui_state.lock().status_del(Status::Exec);
     }
 
@@ -186,6 +203,8 @@ impl Ui {
                     } else if let Event::Mouse(m) = event {
                         match m.kind {
                             event::MouseEventKind::Down(_)
+                            | event::MouseEventKind::Drag(_)
+                            | event::MouseEventKind::Up(_)
                             | event::MouseEventKind::ScrollDown
                             | event::MouseEventKind::ScrollUp => {
                                 self.input_tx.send(InputMessages::MouseEvent(m)).await.ok();
@@ -219,64 +238,153 @@ impl Ui {
 
 /// Frequent data required by multiple framde drawing functions, can reduce mutex reads by placing it all in here
 #[derive(Debug)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct FrameData {
+    changes_visible: bool,
+    column_label: Option<String>,
+    column_order: Vec<Header>,
+    column_picker_visible: bool,
     columns: Columns,
-    delete_confirm: Option<ContainerId>,
+    delete_confirm: Option<DeleteTarget>,
+    files_visible: bool,
+    form: Option<Form>,
     has_containers: bool,
     has_error: Option<AppError>,
     height: u16,
+    help_key: Option<String>,
     help_visible: bool,
     init: bool,
+    inspect_visible: bool,
     info_text: Option<(String, Instant)>,
+    layout: PanelLayout,
     loading_icon: String,
+    lower_panel: SelectablePanel,
+    overview_visible: bool,
+    processes_visible: bool,
     selected_panel: SelectablePanel,
     sorted_by: Option<(Header, SortedOrder)>,
+    theme: Theme,
 }
 
 impl From<(MutexGuard<'_, AppData>, MutexGuard<'_, GuiState>)> for FrameData {
-    fn from(data: (MutexGuard<'_, AppData>, MutexGuard<'_, GuiState>)) -> Self {
+    fn from(mut data: (MutexGuard<'_, AppData>, MutexGuard<'_, GuiState>)) -> Self {
         // set max height for container section, needs +5 to deal with docker commands list and borders
-        let height = data.0.get_container_len();
-        let height = if height < 12 {
-            u16::try_from(height + 5).unwrap_or_default()
-        } else {
-            12
-        };
+        let layout = data.1.get_layout();
+        let height = layout.containers_height(data.0.get_container_len());
 
         Self {
+            changes_visible: data.1.status_contains(&[Status::Changes]),
+            column_label: data.0.args.column_label.clone(),
+            column_order: data.0.args.columns.clone(),
+            column_picker_visible: data.1.status_contains(&[Status::Columns]),
             columns: data.0.get_width(),
-            delete_confirm: data.1.get_delete_container(),
+            delete_confirm: data.1.get_delete_target(),
+            files_visible: data.1.status_contains(&[Status::Files]),
+            form: data.1.get_form().cloned(),
             has_containers: data.0.get_container_len() > 0,
             has_error: data.0.get_error(),
             height,
+            help_key: data.0.args.keymap.label(Action::Help),
             help_visible: data.1.status_contains(&[Status::Help]),
             init: data.1.status_contains(&[Status::Init]),
+            inspect_visible: data.1.status_contains(&[Status::Inspect]),
             info_text: data.1.info_box_text.clone(),
+            layout,
             loading_icon: data.1.get_loading().to_string(),
+            lower_panel: data.1.get_lower_panel(),
+            overview_visible: data.1.status_contains(&[Status::Overview]),
+            processes_visible: data.1.status_contains(&[Status::Processes]),
             selected_panel: data.1.get_selected_panel(),
             sorted_by: data.0.get_sorted(),
+            theme: data.0.args.theme,
         }
     }
 }
 
-/// Draw the main ui to a frame of the terminal
-fn draw_frame(f: &mut Frame, app_data: &Arc<Mutex<AppData>>, gui_state: &Arc<Mutex<GuiState>>) {
-    let fd = FrameData::from((app_data.lock(), gui_state.lock()));
+/// Draw the delete confirm box, for the name of the item that is to be deleted
+fn draw_delete_confirm(
+    f: &mut Frame,
+    app_data: &Arc<Mutex<AppData>>,
+    gui_state: &Arc<Mutex<GuiState>>,
+    target: &DeleteTarget,
+    theme: &Theme,
+) {
+    let confirm = match target {
+        DeleteTarget::Container(id) => app_data
+            .lock()
+            .get_container_name_by_id(id)
+            .map(|name| ("delete container", name.get().to_owned())),
+        DeleteTarget::Image(id) => app_data
+            .lock()
+            .get_image_name_by_id(id)
+            .map(|name| ("delete image", name)),
+        DeleteTarget::Network(id) => app_data
+            .lock()
+            .get_network_name_by_id(id)
+            .map(|name| ("delete network", name)),
+        DeleteTarget::Volume(name) => Some(("delete volume", name.clone())),
+        DeleteTarget::PruneImages => Some(("prune", "all dangling images".to_owned())),
+        DeleteTarget::PruneNetworks => Some(("prune", "all unused networks".to_owned())),
+        DeleteTarget::PruneVolumes => Some(("prune", "all unused volumes".to_owned())),
+    };
+    confirm.map_or_else(
+        || {
+            // If an item is deleted outside of oxker but whilst the Delete Confirm dialog is open, it can get caught in kind of a dead lock situation
+            // so if in that unique situation, just clear the delete_target
+            gui_state.lock().set_delete_target(None);
+        },
+        |(action, name)| {
+            draw_blocks::delete_confirm(f, gui_state, action, &name, theme);
+        },
+    );
+}
 
-    let whole_layout = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([Constraint::Max(1), Constraint::Min(1)].as_ref())
-        .split(f.size());
+/// Draw whichever panel is visible in the lower section
+fn draw_lower_panel(
+    app_data: &Arc<Mutex<AppData>>,
+    area: Rect,
+    f: &mut Frame,
+    fd: &FrameData,
+    gui_state: &Arc<Mutex<GuiState>>,
+) {
+    match fd.lower_panel {
+        SelectablePanel::Images => draw_blocks::images(app_data, area, f, fd, gui_state),
+        SelectablePanel::Volumes => draw_blocks::volumes(app_data, area, f, fd, gui_state),
+        SelectablePanel::Networks => draw_blocks::networks(app_data, area, f, fd, gui_state),
+        _ => draw_blocks::logs(app_data, area, f, fd, gui_state),
+    }
+}
+
+/// Draw the containers, commands, lower panel, and charts, sized by the current layout
+/// The border between each section is stored, so that it can be dragged with the mouse
+fn draw_sections(
+    app_data: &Arc<Mutex<AppData>>,
+    area: Rect,
+    f: &mut Frame,
+    fd: &FrameData,
+    gui_state: &Arc<Mutex<GuiState>>,
+) {
+    let layout = fd.layout;
+    let top_row = |r: Rect| Rect::new(r.x, r.y, r.width, 1);
 
     // Split into 3, containers+controls, logs, then graphs
-    // This one is the issue!
     let upper_main = Layout::default()
         .direction(Direction::Vertical)
         .constraints([Constraint::Max(fd.height), Constraint::Min(1)].as_ref())
-        .split(whole_layout[1]);
-
-    let top_split = if fd.has_containers {
-        vec![Constraint::Percentage(90), Constraint::Percentage(10)]
+        .split(area);
+    let upper = upper_main[0];
+    gui_state.lock().update_split_map(
+        Section::Containers,
+        Rect::new(upper.x, upper.bottom().saturating_sub(1), upper.width, 1),
+        area,
+    );
+
+    let show_commands = fd.has_containers && layout.show_commands;
+    let top_split = if show_commands {
+        vec![
+            Constraint::Percentage(100 - layout.commands),
+            Constraint::Percentage(layout.commands),
+        ]
     } else {
         vec![Constraint::Percentage(100)]
     };
@@ -284,10 +392,14 @@ fn draw_frame(f: &mut Frame, app_data: &Arc<Mutex<AppData>>, gui_state: &Arc<Mut
     let top_panel = Layout::default()
         .direction(Direction::Horizontal)
         .constraints(top_split)
-        .split(upper_main[0]);
-
-    let lower_split = if fd.has_containers {
-        vec![Constraint::Percentage(70), Constraint::Percentage(30)]
+        .split(upper);
+
+    let show_charts = fd.has_containers && layout.show_charts;
+    let lower_split = if show_charts {
+        vec![
+            Constraint::Percentage(100 - layout.charts),
+            Constraint::Percentage(layout.charts),
+        ]
     } else {
         vec![Constraint::Percentage(100)]
     };
@@ -298,28 +410,23 @@ fn draw_frame(f: &mut Frame, app_data: &Arc<Mutex<AppData>>, gui_state: &Arc<Mut
         .constraints(lower_split)
         .split(upper_main[1]);
 
-    draw_blocks::containers(app_data, top_panel[0], f, &fd, gui_state);
-
-    draw_blocks::logs(app_data, lower_main[0], f, &fd, gui_state);
-
-    draw_blocks::heading_bar(whole_layout[0], f, &fd, gui_state);
+    draw_blocks::containers(app_data, top_panel[0], f, fd, gui_state);
+    draw_lower_panel(app_data, lower_main[0], f, fd, gui_state);
 
-    if let Some(id) = fd.delete_confirm.as_ref() {
-        app_data.lock().get_container_name_by_id(id).map_or_else(
-            || {
-                // If a container is deleted outside of oxker but whilst the Delete Confirm dialog is open, it can get caught in kind of a dead lock situation
-                // so if in that unique situation, just clear the delete_container id
-                gui_state.lock().set_delete_container(None);
-            },
-            |name| {
-                draw_blocks::delete_confirm(f, gui_state, &name);
-            },
+    if show_commands {
+        let commands = top_panel[1];
+        gui_state.lock().update_split_map(
+            Section::Commands,
+            Rect::new(commands.x, commands.y, 1, commands.height),
+            upper,
         );
+        draw_blocks::commands(app_data, commands, f, fd, gui_state);
     }
 
-    // only draw commands + charts if there are containers
-    if fd.has_containers {
-        draw_blocks::commands(app_data, top_panel[1], f, &fd, gui_state);
+    if show_charts {
+        gui_state
+            .lock()
+            .update_split_map(Section::Charts, top_row(lower_main[1]), upper_main[1]);
 
         // Can calculate the max string length here, and then use that to keep the ports section as small as possible (+4 for some padding + border)
         let max_lens = app_data.lock().get_longest_port();
@@ -333,17 +440,77 @@ fn draw_frame(f: &mut Frame, app_data: &Arc<Mutex<AppData>>, gui_state: &Arc<Mut
         draw_blocks::chart(f, lower[0], app_data);
         draw_blocks::ports(f, lower[1], app_data, max_lens);
     }
+}
+
+/// Draw the main ui to a frame of the terminal
+fn draw_frame(f: &mut Frame, app_data: &Arc<Mutex<AppData>>, gui_state: &Arc<Mutex<GuiState>>) {
+    let fd = FrameData::from((app_data.lock(), gui_state.lock()));
+
+    let whole_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Max(1), Constraint::Min(1)].as_ref())
+        .split(f.size());
+
+    // A maximised layout draws only the selected panel, below the heading bar
+    if fd.layout.maximised {
+        match fd.selected_panel {
+            SelectablePanel::Containers => {
+                draw_blocks::containers(app_data, whole_layout[1], f, &fd, gui_state);
+            }
+            SelectablePanel::Commands if fd.has_containers => {
+                draw_blocks::commands(app_data, whole_layout[1], f, &fd, gui_state);
+            }
+            _ => draw_lower_panel(app_data, whole_layout[1], f, &fd, gui_state),
+        }
+    } else {
+        draw_sections(app_data, whole_layout[1], f, &fd, gui_state);
+    }
+
+    draw_blocks::heading_bar(whole_layout[0], f, &fd, gui_state);
+
+    if let Some(target) = fd.delete_confirm.as_ref() {
+        draw_delete_confirm(f, app_data, gui_state, target, &fd.theme);
+    }
+
+    if fd.inspect_visible {
+        draw_blocks::inspect(f, app_data);
+    }
+
+    if fd.processes_visible {
+        draw_blocks::processes(f, app_data);
+    }
+
+    if fd.overview_visible {
+        draw_blocks::overview(f, app_data);
+    }
+
+    if fd.column_picker_visible {
+        draw_blocks::column_picker(f, app_data);
+    }
+
+    if fd.changes_visible {
+        draw_blocks::changes(f, app_data);
+    }
+
+    if fd.files_visible {
+        draw_blocks::files(f, app_data);
+    }
+
+    if let Some(form) = fd.form.as_ref() {
+        draw_blocks::form(f, form, &fd.theme);
+    }
 
     if let Some((text, instant)) = fd.info_text {
-        draw_blocks::info(f, &text, instant, gui_state);
+        draw_blocks::info(f, &text, instant, gui_state, &fd.theme);
     }
 
     // Check if error, and show popup if so
     if fd.help_visible {
-        draw_blocks::help_box(f);
+        let keymap = app_data.lock().args.keymap.clone();
+        draw_blocks::help_box(f, &keymap, &fd.theme);
     }
 
     if let Some(error) = fd.has_error {
-        draw_blocks::error(f, error, None);
+        draw_blocks::error(f, error, None, &fd.theme);
     }
 }
@@ -0,0 +1,262 @@
+use std::{fmt, ops::RangeInclusive};
+
+use ratatui::layout::Rect;
+use serde::Deserialize;
+
+/// Maximum height of the containers section, in rows, including the borders
+pub const CONTAINERS_RANGE: RangeInclusive<u16> = 3..=100;
+/// Width of the commands panel, as a percentage of the containers section
+pub const COMMANDS_RANGE: RangeInclusive<u16> = 5..=50;
+/// Height of the charts, as a percentage of the lower section
+pub const CHARTS_RANGE: RangeInclusive<u16> = 10..=80;
+
+/// Change in the containers height, in rows, for each grow or shrink
+const CONTAINERS_STEP: u16 = 1;
+/// Change in the commands width, and charts height, in percent, for each grow or shrink
+const PERCENT_STEP: u16 = 5;
+
+/// The parts of the main view that can be resized, or hidden
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Section {
+    Containers,
+    Commands,
+    Charts,
+}
+
+/// Preset layouts, which can be cycled through, and adjusted afterwards
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LayoutPreset {
+    #[default]
+    Default,
+    Logs,
+    Charts,
+    Compact,
+}
+
+impl fmt::Display for LayoutPreset {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let disp = match self {
+            Self::Default => "default",
+            Self::Logs => "logs",
+            Self::Charts => "charts",
+            Self::Compact => "compact",
+        };
+        write!(f, "{disp}")
+    }
+}
+
+impl LayoutPreset {
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Default => Self::Logs,
+            Self::Logs => Self::Charts,
+            Self::Charts => Self::Compact,
+            Self::Compact => Self::Default,
+        }
+    }
+
+    /// The layout of each preset, default is the original fixed layout, logs gives the most rows to the logs, charts doubles the height of the charts, and compact hides everything but the containers and logs
+    pub const fn layout(self) -> PanelLayout {
+        let default = PanelLayout {
+            charts: 30,
+            commands: 10,
+            containers: 12,
+            maximised: false,
+            preset: self,
+            show_charts: true,
+            show_commands: true,
+        };
+        match self {
+            Self::Default => default,
+            Self::Logs => PanelLayout {
+                containers: 8,
+                show_charts: false,
+                ..default
+            },
+            Self::Charts => PanelLayout {
+                charts: 50,
+                ..default
+            },
+            Self::Compact => PanelLayout {
+                containers: 8,
+                show_charts: false,
+                show_commands: false,
+                ..default
+            },
+        }
+    }
+}
+
+/// Sizes, and visibility, of the sections of the main view
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PanelLayout {
+    pub charts: u16,
+    pub commands: u16,
+    pub containers: u16,
+    pub maximised: bool,
+    pub preset: LayoutPreset,
+    pub show_charts: bool,
+    pub show_commands: bool,
+}
+
+impl Default for PanelLayout {
+    fn default() -> Self {
+        LayoutPreset::Default.layout()
+    }
+}
+
+/// Clamp a value into a range
+fn clamp(value: u16, range: &RangeInclusive<u16>) -> u16 {
+    value.clamp(*range.start(), *range.end())
+}
+
+impl PanelLayout {
+    /// Height of the containers section, five rows larger than the number of containers, to fit the commands and borders, up to the maximum height
+    pub fn containers_height(&self, container_len: usize) -> u16 {
+        u16::try_from(container_len.saturating_add(5))
+            .unwrap_or(u16::MAX)
+            .min(self.containers)
+    }
+
+    /// Increase, or decrease, the size of a section
+    pub fn resize(&mut self, section: Section, grow: bool) {
+        let change = |value: u16, step: u16, range: &RangeInclusive<u16>| {
+            let value = if grow {
+                value.saturating_add(step)
+            } else {
+                value.saturating_sub(step)
+            };
+            clamp(value, range)
+        };
+        match section {
+            Section::Containers => {
+                self.containers = change(self.containers, CONTAINERS_STEP, &CONTAINERS_RANGE);
+            }
+            Section::Commands => {
+                self.commands = change(self.commands, PERCENT_STEP, &COMMANDS_RANGE);
+            }
+            Section::Charts => self.charts = change(self.charts, PERCENT_STEP, &CHARTS_RANGE),
+        }
+    }
+
+    /// Resize a section by dragging its border to a given column & row, within the area that the section splits
+    pub fn drag(&mut self, section: Section, area: Rect, column: u16, row: u16) {
+        let percent = |value: u16, total: u16| {
+            u16::try_from(u32::from(value) * 100 / u32::from(total.max(1))).unwrap_or_default()
+        };
+        match section {
+            Section::Containers => {
+                self.containers = clamp(
+                    row.saturating_sub(area.y).saturating_add(1),
+                    &CONTAINERS_RANGE,
+                );
+            }
+            Section::Commands => {
+                self.commands = clamp(
+                    percent(area.right().saturating_sub(column), area.width),
+                    &COMMANDS_RANGE,
+                );
+            }
+            Section::Charts => {
+                self.charts = clamp(
+                    percent(area.bottom().saturating_sub(row), area.height),
+                    &CHARTS_RANGE,
+                );
+            }
+        }
+    }
+
+    /// Show, or hide, a section, the containers section can't be hidden
+    pub const fn toggle(&mut self, section: Section) {
+        match section {
+            Section::Containers => (),
+            Section::Commands => self.show_commands = !self.show_commands,
+            Section::Charts => self.show_charts = !self.show_charts,
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use ratatui::layout::Rect;
+
+    use super::{LayoutPreset, PanelLayout, Section};
+
+    #[test]
+    /// The default preset is the original layout, and presets cycle back to the default
+    fn test_panel_layout_preset() {
+        let layout = PanelLayout::default();
+        assert_eq!(layout.containers, 12);
+        assert_eq!(layout.commands, 10);
+        assert_eq!(layout.charts, 30);
+        assert!(layout.show_charts && layout.show_commands && !layout.maximised);
+
+        let mut preset = LayoutPreset::Default;
+        for _ in 0..4 {
+            preset = preset.next();
+        }
+        assert_eq!(preset, LayoutPreset::Default);
+        assert!(!LayoutPreset::Logs.layout().show_charts);
+        assert_eq!(LayoutPreset::Logs.layout().preset, LayoutPreset::Logs);
+    }
+
+    #[test]
+    /// Containers height fits the containers, up to the maximum
+    fn test_panel_layout_containers_height() {
+        let layout = PanelLayout::default();
+        assert_eq!(layout.containers_height(0), 5);
+        assert_eq!(layout.containers_height(3), 8);
+        assert_eq!(layout.containers_height(9), 12);
+        assert_eq!(layout.containers_height(40), 12);
+    }
+
+    #[test]
+    /// Resizing is in steps, and kept within the range of each section
+    fn test_panel_layout_resize() {
+        let mut layout = PanelLayout::default();
+        layout.resize(Section::Containers, true);
+        assert_eq!(layout.containers, 13);
+        layout.resize(Section::Commands, false);
+        assert_eq!(layout.commands, 5);
+        layout.resize(Section::Commands, false);
+        assert_eq!(layout.commands, 5);
+        layout.resize(Section::Charts, true);
+        assert_eq!(layout.charts, 35);
+        for _ in 0..20 {
+            layout.resize(Section::Charts, true);
+        }
+        assert_eq!(layout.charts, 80);
+    }
+
+    #[test]
+    /// Dragging a border sets the size from the position of the mouse
+    fn test_panel_layout_drag() {
+        let mut layout = PanelLayout::default();
+        let area = Rect::new(0, 1, 100, 40);
+        layout.drag(Section::Containers, area, 10, 20);
+        assert_eq!(layout.containers, 20);
+        layout.drag(Section::Commands, area, 80, 2);
+        assert_eq!(layout.commands, 20);
+        layout.drag(Section::Commands, area, 0, 2);
+        assert_eq!(layout.commands, 50);
+        layout.drag(Section::Charts, area, 10, 21);
+        assert_eq!(layout.charts, 50);
+        layout.drag(Section::Charts, area, 10, 40);
+        assert_eq!(layout.charts, 10);
+    }
+
+    #[test]
+    /// Commands & charts can be hidden, but the containers can't
+    fn test_panel_layout_toggle() {
+        let mut layout = PanelLayout::default();
+        layout.toggle(Section::Commands);
+        layout.toggle(Section::Charts);
+        layout.toggle(Section::Containers);
+        assert!(!layout.show_commands);
+        assert!(!layout.show_charts);
+        layout.toggle(Section::Charts);
+        assert!(layout.show_charts);
+    }
+}
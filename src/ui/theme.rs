@@ -0,0 +1,416 @@
+use std::{collections::HashMap, str::FromStr};
+
+use ratatui::style::Color;
+
+use crate::config::ConfigError;
+
+/// Built in theme names, a user defined theme uses one of these as its base
+pub const THEMES: [&str; 4] = ["dark", "light", "high_contrast", "none"];
+
+/// Okabe-Ito colors, distinguishable with the common forms of color blindness
+const OI_ORANGE: Color = Color::Rgb(230, 159, 0);
+const OI_SKY: Color = Color::Rgb(86, 180, 233);
+const OI_GREEN: Color = Color::Rgb(0, 158, 115);
+const OI_YELLOW: Color = Color::Rgb(240, 228, 66);
+const OI_BLUE: Color = Color::Rgb(0, 114, 178);
+const OI_VERMILLION: Color = Color::Rgb(213, 94, 0);
+const OI_PURPLE: Color = Color::Rgb(204, 121, 167);
+
+/// A darker yellow, readable on a light background
+const AMBER: Color = Color::Rgb(175, 95, 0);
+
+/// Every color used to draw the ui
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    pub attached: Color,
+    pub bar_bg: Color,
+    pub bar_fg: Color,
+    pub bar_highlight: Color,
+    pub border: Color,
+    pub change_added: Color,
+    pub change_changed: Color,
+    pub change_deleted: Color,
+    pub chart_cpu: Color,
+    pub chart_in: Color,
+    pub chart_max: Color,
+    pub chart_memory: Color,
+    pub chart_out: Color,
+    pub control_delete: Color,
+    pub control_pause: Color,
+    pub control_restart: Color,
+    pub control_resume: Color,
+    pub control_start: Color,
+    pub control_stop: Color,
+    pub dialog_bg: Color,
+    pub dialog_fg: Color,
+    pub dialog_highlight: Color,
+    pub dim: Color,
+    pub error_bg: Color,
+    pub error_fg: Color,
+    pub filter: Color,
+    pub form_error: Color,
+    pub heading: Color,
+    pub health_healthy: Color,
+    pub health_starting: Color,
+    pub health_unhealthy: Color,
+    pub health_unset: Color,
+    pub info_bg: Color,
+    pub info_fg: Color,
+    pub name: Color,
+    pub rx: Color,
+    pub state_paused: Color,
+    pub state_removing: Color,
+    pub state_restarting: Color,
+    pub state_running: Color,
+    pub state_stopped: Color,
+    pub text: Color,
+    pub tx: Color,
+    pub usage_high: Color,
+    pub usage_low: Color,
+    pub usage_mid: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::DARK
+    }
+}
+
+impl Theme {
+    /// The original palette, for a dark terminal background
+    pub const DARK: Self = Self {
+        attached: Color::Green,
+        bar_bg: Color::Magenta,
+        bar_fg: Color::Black,
+        bar_highlight: Color::White,
+        border: Color::LightCyan,
+        change_added: Color::Green,
+        change_changed: Color::Yellow,
+        change_deleted: Color::Red,
+        chart_cpu: Color::Magenta,
+        chart_in: Color::Cyan,
+        chart_max: Color::Rgb(255, 178, 36),
+        chart_memory: Color::Cyan,
+        chart_out: Color::Magenta,
+        control_delete: Color::Gray,
+        control_pause: Color::Yellow,
+        control_restart: Color::Magenta,
+        control_resume: Color::Blue,
+        control_start: Color::Green,
+        control_stop: Color::Red,
+        dialog_bg: Color::White,
+        dialog_fg: Color::Black,
+        dialog_highlight: Color::Red,
+        dim: Color::DarkGray,
+        error_bg: Color::Red,
+        error_fg: Color::White,
+        filter: Color::Magenta,
+        form_error: Color::Red,
+        heading: Color::Yellow,
+        health_healthy: Color::Green,
+        health_starting: Color::Yellow,
+        health_unhealthy: Color::Red,
+        health_unset: Color::Gray,
+        info_bg: Color::Blue,
+        info_fg: Color::White,
+        name: Color::Blue,
+        rx: Color::Rgb(255, 233, 193),
+        state_paused: Color::Yellow,
+        state_removing: Color::LightRed,
+        state_restarting: Color::LightGreen,
+        state_running: Color::Green,
+        state_stopped: Color::Red,
+        text: Color::White,
+        tx: Color::Rgb(205, 140, 140),
+        usage_high: Color::Red,
+        usage_low: Color::Green,
+        usage_mid: Color::Yellow,
+    };
+
+    /// Darker text colors, for a light terminal background
+    pub const LIGHT: Self = Self {
+        border: Color::Blue,
+        change_changed: AMBER,
+        chart_in: Color::Blue,
+        chart_max: AMBER,
+        chart_memory: Color::Blue,
+        control_delete: Color::DarkGray,
+        control_pause: AMBER,
+        heading: AMBER,
+        health_starting: AMBER,
+        health_unset: Color::DarkGray,
+        rx: Color::Rgb(0, 95, 135),
+        state_paused: AMBER,
+        state_removing: Color::Red,
+        state_restarting: Color::Rgb(0, 135, 95),
+        text: Color::Black,
+        tx: Color::Rgb(135, 0, 0),
+        usage_mid: AMBER,
+        ..Self::DARK
+    };
+
+    /// Bright colors on a dark background, without relying on telling red from green
+    pub const HIGH_CONTRAST: Self = Self {
+        attached: Color::White,
+        bar_bg: OI_BLUE,
+        bar_fg: Color::White,
+        bar_highlight: OI_YELLOW,
+        border: OI_YELLOW,
+        change_added: OI_SKY,
+        change_changed: OI_YELLOW,
+        change_deleted: OI_VERMILLION,
+        chart_cpu: OI_ORANGE,
+        chart_in: OI_SKY,
+        chart_max: Color::White,
+        chart_memory: OI_SKY,
+        chart_out: OI_ORANGE,
+        control_pause: OI_YELLOW,
+        control_restart: OI_PURPLE,
+        control_resume: OI_SKY,
+        control_start: OI_SKY,
+        control_stop: OI_VERMILLION,
+        dialog_highlight: OI_BLUE,
+        dim: Color::Gray,
+        error_bg: OI_VERMILLION,
+        error_fg: Color::Black,
+        filter: OI_YELLOW,
+        form_error: OI_VERMILLION,
+        heading: OI_YELLOW,
+        health_healthy: OI_SKY,
+        health_starting: OI_YELLOW,
+        health_unhealthy: OI_VERMILLION,
+        info_bg: OI_BLUE,
+        name: OI_SKY,
+        rx: OI_SKY,
+        state_paused: OI_YELLOW,
+        state_removing: OI_PURPLE,
+        state_restarting: OI_GREEN,
+        state_running: OI_SKY,
+        state_stopped: OI_VERMILLION,
+        tx: OI_ORANGE,
+        usage_high: OI_VERMILLION,
+        usage_low: OI_SKY,
+        usage_mid: OI_YELLOW,
+        ..Self::DARK
+    };
+
+    /// The default terminal colors everywhere, used when the NO_COLOR env is set
+    pub const NONE: Self = Self {
+        attached: Color::Reset,
+        bar_bg: Color::Reset,
+        bar_fg: Color::Reset,
+        bar_highlight: Color::Reset,
+        border: Color::Reset,
+        change_added: Color::Reset,
+        change_changed: Color::Reset,
+        change_deleted: Color::Reset,
+        chart_cpu: Color::Reset,
+        chart_in: Color::Reset,
+        chart_max: Color::Reset,
+        chart_memory: Color::Reset,
+        chart_out: Color::Reset,
+        control_delete: Color::Reset,
+        control_pause: Color::Reset,
+        control_restart: Color::Reset,
+        control_resume: Color::Reset,
+        control_start: Color::Reset,
+        control_stop: Color::Reset,
+        dialog_bg: Color::Reset,
+        dialog_fg: Color::Reset,
+        dialog_highlight: Color::Reset,
+        dim: Color::Reset,
+        error_bg: Color::Reset,
+        error_fg: Color::Reset,
+        filter: Color::Reset,
+        form_error: Color::Reset,
+        heading: Color::Reset,
+        health_healthy: Color::Reset,
+        health_starting: Color::Reset,
+        health_unhealthy: Color::Reset,
+        health_unset: Color::Reset,
+        info_bg: Color::Reset,
+        info_fg: Color::Reset,
+        name: Color::Reset,
+        rx: Color::Reset,
+        state_paused: Color::Reset,
+        state_removing: Color::Reset,
+        state_restarting: Color::Reset,
+        state_running: Color::Reset,
+        state_stopped: Color::Reset,
+        text: Color::Reset,
+        tx: Color::Reset,
+        usage_high: Color::Reset,
+        usage_low: Color::Reset,
+        usage_mid: Color::Reset,
+    };
+
+    /// Get a built in theme by name
+    fn built_in(name: &str) -> Option<Self> {
+        match name {
+            "dark" => Some(Self::DARK),
+            "light" => Some(Self::LIGHT),
+            "high_contrast" => Some(Self::HIGH_CONTRAST),
+            "none" => Some(Self::NONE),
+            _ => None,
+        }
+    }
+
+    /// Get a mutable reference to a color by its name in the config file
+    fn color_mut(&mut self, name: &str) -> Option<&mut Color> {
+        let color = match name {
+            "attached" => &mut self.attached,
+            "bar_bg" => &mut self.bar_bg,
+            "bar_fg" => &mut self.bar_fg,
+            "bar_highlight" => &mut self.bar_highlight,
+            "border" => &mut self.border,
+            "change_added" => &mut self.change_added,
+            "change_changed" => &mut self.change_changed,
+            "change_deleted" => &mut self.change_deleted,
+            "chart_cpu" => &mut self.chart_cpu,
+            "chart_in" => &mut self.chart_in,
+            "chart_max" => &mut self.chart_max,
+            "chart_memory" => &mut self.chart_memory,
+            "chart_out" => &mut self.chart_out,
+            "control_delete" => &mut self.control_delete,
+            "control_pause" => &mut self.control_pause,
+            "control_restart" => &mut self.control_restart,
+            "control_resume" => &mut self.control_resume,
+            "control_start" => &mut self.control_start,
+            "control_stop" => &mut self.control_stop,
+            "dialog_bg" => &mut self.dialog_bg,
+            "dialog_fg" => &mut self.dialog_fg,
+            "dialog_highlight" => &mut self.dialog_highlight,
+            "dim" => &mut self.dim,
+            "error_bg" => &mut self.error_bg,
+            "error_fg" => &mut self.error_fg,
+            "filter" => &mut self.filter,
+            "form_error" => &mut self.form_error,
+            "heading" => &mut self.heading,
+            "health_healthy" => &mut self.health_healthy,
+            "health_starting" => &mut self.health_starting,
+            "health_unhealthy" => &mut self.health_unhealthy,
+            "health_unset" => &mut self.health_unset,
+            "info_bg" => &mut self.info_bg,
+            "info_fg" => &mut self.info_fg,
+            "name" => &mut self.name,
+            "rx" => &mut self.rx,
+            "state_paused" => &mut self.state_paused,
+            "state_removing" => &mut self.state_removing,
+            "state_restarting" => &mut self.state_restarting,
+            "state_running" => &mut self.state_running,
+            "state_stopped" => &mut self.state_stopped,
+            "text" => &mut self.text,
+            "tx" => &mut self.tx,
+            "usage_high" => &mut self.usage_high,
+            "usage_low" => &mut self.usage_low,
+            "usage_mid" => &mut self.usage_mid,
+            _ => return None,
+        };
+        Some(color)
+    }
+
+    /// Find a theme by name, a user defined theme takes priority over a built in theme of the same name
+    /// A user defined theme starts from its `base` theme, dark if not set, with each color replaced by the colors it sets
+    pub fn new(
+        name: &str,
+        themes: &HashMap<String, HashMap<String, String>>,
+    ) -> Result<Self, ConfigError> {
+        let Some(colors) = themes.get(name) else {
+            return Self::built_in(name).ok_or_else(|| {
+                ConfigError::Invalid(format!(
+                    "theme - unknown theme \"{name}\", expected one of {}, or a theme in [themes]",
+                    THEMES.join(", ")
+                ))
+            });
+        };
+        let base = colors.get("base").map_or("dark", String::as_str);
+        let mut theme = Self::built_in(base).ok_or_else(|| {
+            ConfigError::Invalid(format!(
+                "themes.{name}.base - unknown theme \"{base}\", expected one of {}",
+                THEMES.join(", ")
+            ))
+        })?;
+        for (key, value) in colors.iter().filter(|(key, _)| *key != "base") {
+            let color = theme.color_mut(key).ok_or_else(|| {
+                ConfigError::Invalid(format!("themes.{name}.{key} - unknown color name"))
+            })?;
+            *color = Color::from_str(value).map_err(|_| {
+                ConfigError::Invalid(format!(
+                    "themes.{name}.{key} - invalid color \"{value}\", expected a color name, a #rrggbb hex value, or a 0-255 index"
+                ))
+            })?;
+        }
+        Ok(theme)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::collections::HashMap;
+
+    use ratatui::style::Color;
+
+    use super::{Theme, THEMES};
+
+    #[test]
+    /// Every built in theme found by name, and unknown names are an error
+    fn test_theme_built_in() {
+        for name in THEMES {
+            assert!(Theme::new(name, &HashMap::new()).is_ok());
+        }
+        assert_eq!(
+            Theme::new("dark", &HashMap::new()).unwrap(),
+            Theme::default()
+        );
+        assert_eq!(
+            Theme::new("solarized", &HashMap::new())
+                .unwrap_err()
+                .to_string(),
+            "Invalid setting: theme - unknown theme \"solarized\", expected one of dark, light, high_contrast, none, or a theme in [themes]"
+        );
+    }
+
+    #[test]
+    /// A user defined theme replaces the colors of its base theme
+    fn test_theme_user_defined() {
+        let gen_themes = |colors: &[(&str, &str)]| {
+            HashMap::from([(
+                "custom".to_owned(),
+                colors
+                    .iter()
+                    .map(|(k, v)| ((*k).to_owned(), (*v).to_owned()))
+                    .collect::<HashMap<_, _>>(),
+            )])
+        };
+
+        let themes = gen_themes(&[("base", "light"), ("border", "#ff0000"), ("name", "green")]);
+        let result = Theme::new("custom", &themes).unwrap();
+        assert_eq!(result.border, Color::Rgb(255, 0, 0));
+        assert_eq!(result.name, Color::Green);
+        assert_eq!(result.text, Theme::LIGHT.text);
+
+        let themes = gen_themes(&[("dim", "8")]);
+        let result = Theme::new("custom", &themes).unwrap();
+        assert_eq!(result.dim, Color::Indexed(8));
+        assert_eq!(result.text, Theme::DARK.text);
+
+        for (colors, err) in [
+            (
+                [("base", "custom")],
+                "Invalid setting: themes.custom.base - unknown theme \"custom\", expected one of dark, light, high_contrast, none",
+            ),
+            (
+                [("colour", "red")],
+                "Invalid setting: themes.custom.colour - unknown color name",
+            ),
+            (
+                [("border", "#ff00")],
+                "Invalid setting: themes.custom.border - invalid color \"#ff00\", expected a color name, a #rrggbb hex value, or a 0-255 index",
+            ),
+        ] {
+            let result = Theme::new("custom", &gen_themes(&colors));
+            assert_eq!(result.unwrap_err().to_string(), err);
+        }
+    }
+}